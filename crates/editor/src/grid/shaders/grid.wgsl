@@ -11,7 +11,7 @@ struct CameraRaw {
     position: vec4<f32>,  // .xyz = position, .w = unused
     frame_count_and_padding: vec4<u32>,  // .x = frame_count, .yzw = padding
     frustum_rays: array<vec4<f32>, 4>,
-    _padding_end: array<vec4<f32>, 2>,  // Total: 512 bytes
+    _padding_end: array<vec4<f32>, 7>,  // Total: 592 bytes
 };
 
 // Friendly camera structure (no padding, easier to work with)
@@ -71,7 +71,7 @@ impl AppCanvas {
                             .with_device_request_limits(DeviceRequestLimits::typical());
                             //.with_device_request_limits(DeviceRequestLimits::max_all());
 
-                        let renderer = match AwsmRendererBuilder::new(gpu_builder)
+                        let mut renderer = match AwsmRendererBuilder::new(gpu_builder)
                             .with_logging(AwsmRendererLogging { render_timings: true })
                             .with_clear_color(Color::MID_GREY)
                             .build()
@@ -84,6 +84,11 @@ impl AppCanvas {
                                 }
                             };
 
+                        // the editor selects glass and decals too, just not their see-through parts
+                        if let Err(err) = renderer.set_transparent_picking(Some(0.1)).await {
+                            tracing::error!("Error enabling transparent picking: {:?}", err);
+                        }
+
                         state.ctx.loading_status.lock_mut().renderer = Ok(false);
                         let scene = AppScene::new(state.ctx.clone(), renderer).await.unwrap();
 
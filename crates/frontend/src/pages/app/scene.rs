@@ -52,6 +52,7 @@ pub struct AppScene {
     pub last_request_animation_frame: Cell<Option<f64>>,
     pub event_listeners: Mutex<Vec<EventListener>>,
     lights: Mutex<Option<Vec<LightKey>>>,
+    gltf_has_lights: Cell<bool>,
     move_action: Cell<Option<MoveAction>>,
     last_size: Cell<(f64, f64)>,
     last_camera_id: Cell<CameraId>,
@@ -66,7 +67,12 @@ enum MoveAction {
 
 impl AppScene {
     pub async fn new(ctx: AppContext, renderer: AwsmRenderer) -> Result<Arc<Self>> {
-        let canvas = renderer.gpu.canvas();
+        let canvas = renderer
+            .gpu
+            .canvas()
+            .html()
+            .cloned()
+            .expect("the editor renders on the main thread");
 
         let state = Arc::new(Self {
             ctx,
@@ -86,6 +92,7 @@ impl AppScene {
             editor: Mutex::new(None),
             move_action: Cell::new(None),
             lights: Mutex::new(None),
+            gltf_has_lights: Cell::new(false),
         });
 
         let resize_observer = ResizeObserver::new(
@@ -120,7 +127,8 @@ impl AppScene {
                                 tracing::error!("Pick error: {:?}", err);
                             }
                             Ok(res) => {
-                                if let PickResult::Hit(mesh_key) = res {
+                                if let PickResult::Hit(hit) = res {
+                                    let mesh_key = hit.mesh_key;
                                     if let Some(editor) = state.editor.lock().unwrap().as_ref() {
                                         if let Some(transform_controller) = editor.transform_controller.lock().unwrap().as_mut() {
                                             match transform_controller.start_pick(&mut renderer, mesh_key, x, y) {
@@ -236,9 +244,51 @@ impl AppScene {
             }))).await;
         }));
 
+        spawn_local(clone!(state => async move {
+            loop {
+                // don't hold the renderer lock while waiting
+                let device_lost = state.renderer.lock().await.device_lost();
+                let lost = device_lost.await;
+                tracing::warn!("GPU device lost ({:?}): {}", lost.reason, lost.message);
+
+                if let Err(err) = state.recover_from_device_loss().await {
+                    tracing::error!("Failed to recover from GPU device loss: {:?}", err);
+                    state.ctx.loading_status.lock_mut().renderer = Err(err.to_string());
+                    break;
+                }
+            }
+        }));
+
         Ok(state)
     }
 
+    async fn recover_from_device_loss(self: &Arc<Self>) -> Result<()> {
+        let editor = self.editor.lock().unwrap().clone();
+
+        {
+            let renderer = &mut *self.renderer.lock().await;
+            // every cubemap here is inserted with its source, so none come back empty
+            renderer.recover_from_device_loss().await?;
+
+            for ibl in self.ibl_cache.lock().unwrap().values_mut() {
+                ibl.prefiltered_env
+                    .restore_gpu(&renderer.gpu, &mut renderer.textures)?;
+                ibl.irradiance
+                    .restore_gpu(&renderer.gpu, &mut renderer.textures)?;
+            }
+
+            for skybox in self.skybox_by_ibl_cache.lock().unwrap().values_mut() {
+                skybox.restore_gpu(&renderer.gpu, &mut renderer.textures)?;
+            }
+
+            if let Some(editor) = editor {
+                editor.restore_gpu(renderer).await?;
+            }
+        }
+
+        self.render().await
+    }
+
     fn on_viewport_change(self: &Arc<Self>) {
         let state = self;
 
@@ -386,7 +436,10 @@ impl AppScene {
 
                             {
                                 let renderer = &mut *scene.renderer.lock().await;
-                                let key = renderer.textures.insert_cubemap(texture);
+                                // kept so it can be recreated after a device loss
+                                let key = renderer
+                                    .textures
+                                    .insert_cubemap_with_source(texture, skybox_cubemap);
 
                                 let sampler_key = renderer
                                     .textures
@@ -462,7 +515,9 @@ impl AppScene {
                     .create_texture_and_view(&renderer.gpu, Some("IBL Cubemap"))
                     .await?;
 
-                let texture_key = renderer.textures.insert_cubemap(texture);
+                let texture_key = renderer
+                    .textures
+                    .insert_cubemap_with_source(texture, cubemap_image);
 
                 let sampler_key = renderer
                     .textures
@@ -568,7 +623,14 @@ impl AppScene {
 
                 let mut renderer = scene.renderer.lock().await;
 
-                renderer.populate_gltf(data, None).await?;
+                let ctx = renderer.populate_gltf(data, None).await?;
+                scene.gltf_has_lights.set(
+                    !ctx.key_lookups
+                        .lock()
+                        .unwrap()
+                        .node_index_to_light
+                        .is_empty(),
+                );
 
                 let editor_gizmo_gltf_data = {
                     let editor_guard = scene.editor.lock().unwrap();
@@ -649,7 +711,8 @@ impl AppScene {
             }
         }
 
-        if !self.ctx.punctual_lights.get() {
+        // the model's own KHR_lights_punctual lights take the place of the default rig
+        if !self.ctx.punctual_lights.get() || self.gltf_has_lights.get() {
             return Ok(());
         }
 
@@ -18,7 +18,7 @@ use awsm_renderer_editor::{
 
 #[derive(Clone)]
 pub struct AppSceneEditor {
-    pub pipelines: Arc<EditorPipelines>,
+    pub pipelines: Arc<std::sync::RwLock<EditorPipelines>>,
     pub render_hooks: Arc<std::sync::RwLock<Option<Arc<RenderHooks>>>>,
     pub gizmo_gltf_data: Arc<GltfData>,
     pub transform_controller: Arc<std::sync::Mutex<Option<TransformController>>>,
@@ -47,7 +47,9 @@ impl AppSceneEditor {
                 ))?,
         );
 
-        let pipelines = Arc::new(EditorPipelines::load(&mut *renderer.lock().await).await?);
+        let pipelines = Arc::new(std::sync::RwLock::new(
+            EditorPipelines::load(&mut *renderer.lock().await).await?,
+        ));
 
         let render_hooks = Arc::new(std::sync::RwLock::new(None));
 
@@ -85,23 +87,21 @@ impl AppSceneEditor {
                             Ok(())
                         }))),
                         before_transparent_pass: if grid_enabled {
-                            let grid_bind_group = pipelines.grid_bind_group.clone();
-                            let grid_pipeline_msaa_4_key = pipelines.grid_pipeline_msaa_4_key;
-                            let grid_pipeline_singlesampled_key = pipelines.grid_pipeline_singlesampled_key;
-
-                            Some(Box::new(move |ctx| {
+                            // read at render time, the pipelines are reloaded after a device loss
+                            Some(Box::new(clone!(pipelines => move |ctx| {
+                                let pipelines = pipelines.read().unwrap();
                                 let grid_pipeline_key = match ctx.anti_aliasing.msaa_sample_count {
-                                    Some(4) => grid_pipeline_msaa_4_key,
-                                    None => grid_pipeline_singlesampled_key,
+                                    Some(4) => pipelines.grid_pipeline_msaa_4_key,
+                                    None => pipelines.grid_pipeline_singlesampled_key,
                                     _ => panic!("Unsupported MSAA sample count for grid pipeline"),
                                 };
 
                                 render_grid(
                                     ctx,
-                                    &grid_bind_group,
+                                    &pipelines.grid_bind_group,
                                     grid_pipeline_key,
                                 )
-                            }))
+                            })))
                         } else {
                             None
                         },
@@ -137,4 +137,11 @@ impl AppSceneEditor {
             transform_controller,
         })
     }
+
+    /// Reloads the grid pipelines after the renderer recovered from a device loss.
+    pub async fn restore_gpu(&self, renderer: &mut AwsmRenderer) -> Result<()> {
+        *self.pipelines.write().unwrap() = EditorPipelines::load(renderer).await?;
+
+        Ok(())
+    }
 }
@@ -59,6 +59,7 @@ impl SidebarMaterial {
                     ("Occlusion".to_string(), PbrMaterialDebug::Occlusion),
                     ("Emissive".to_string(), PbrMaterialDebug::Emissive),
                     ("Specular".to_string(), PbrMaterialDebug::Specular),
+                    ("Anisotropy".to_string(), PbrMaterialDebug::Anisotropy),
                 ])
                 .render(),
         )
@@ -1,4 +1,4 @@
-use awsm_renderer::post_process::ToneMapping;
+use awsm_renderer::post_process::{Ssao, ToneMapping};
 use wasm_bindgen_futures::spawn_local;
 
 use crate::{
@@ -33,8 +33,10 @@ impl SidebarProcessing {
             .child(state.render_tonemapping_selector())
             .child(state.render_bloom_selector())
             .child(state.render_dof_selector())
+            .child(state.render_ssao_selector())
             .child(state.render_msaa_selector())
             .child(state.render_smaa_selector())
+            .child(state.render_taa_selector())
         })
     }
 
@@ -102,6 +104,31 @@ impl SidebarProcessing {
             .render()
     }
 
+    fn render_taa_selector(self: &Arc<Self>) -> Dom {
+        let state = self;
+
+        Checkbox::new(CheckboxStyle::Dark)
+            .with_content_after(html!("span", {
+                .text("TAA Anti-Aliasing")
+            }))
+            .with_selected_signal(state.ctx.anti_alias.signal_ref(|anti_alias| anti_alias.taa))
+            .with_on_click(clone!(state => move || {
+                {
+                    let mut lock = state.ctx.anti_alias.lock_mut();
+                    lock.taa = !lock.taa;
+                }
+
+                spawn_local(clone!(state => async move {
+                    if let Some(scene) = state.ctx.scene.get_cloned() {
+                        if let Err(err) = scene.reset_anti_aliasing().await {
+                            tracing::error!("Error resetting anti_aliasing: {}", err);
+                        }
+                    }
+                }));
+            }))
+            .render()
+    }
+
     fn render_bloom_selector(self: &Arc<Self>) -> Dom {
         let state = self;
 
@@ -162,6 +189,40 @@ impl SidebarProcessing {
             .render()
     }
 
+    fn render_ssao_selector(self: &Arc<Self>) -> Dom {
+        let state = self;
+
+        Checkbox::new(CheckboxStyle::Dark)
+            .with_content_after(html!("span", {
+                .text("SSAO")
+            }))
+            .with_selected_signal(
+                state
+                    .ctx
+                    .post_processing
+                    .signal_ref(|post_processing| post_processing.ssao.is_some()),
+            )
+            .with_on_click(clone!(state => move || {
+                {
+                    let mut lock = state.ctx.post_processing.lock_mut();
+                    lock.ssao = if lock.ssao.is_some() {
+                        None
+                    } else {
+                        Some(Ssao::default())
+                    };
+                }
+
+                spawn_local(clone!(state => async move {
+                    if let Some(scene) = state.ctx.scene.get_cloned() {
+                        if let Err(err) = scene.reset_post_processing().await {
+                            tracing::error!("Error resetting post processing: {}", err);
+                        }
+                    }
+                }));
+            }))
+            .render()
+    }
+
     fn render_tonemapping_selector(self: &Arc<Self>) -> Dom {
         let state = self;
 
@@ -20,6 +20,12 @@ thread_local! {
     static BRDF_SAMPLER: RefCell<Option<web_sys::GpuSampler>> = const { RefCell::new(None) };
 }
 
+// cached objects belong to the device that made them
+pub(crate) fn clear_cache() {
+    BRDF_LUT_PIPELINE.with(|pipeline_cell| *pipeline_cell.borrow_mut() = None);
+    BRDF_SAMPLER.with(|sampler_cell| *sampler_cell.borrow_mut() = None);
+}
+
 /// Generated BRDF lookup texture and sampler.
 pub struct BrdfLut {
     pub texture: web_sys::GpuTexture,
@@ -0,0 +1,101 @@
+//! Canvas targets for the WebGPU context.
+
+use wasm_bindgen::JsCast;
+
+use crate::error::{AwsmCoreError, Result};
+
+/// The canvas the renderer draws into.
+///
+/// An `HtmlCanvasElement` lives on the main thread, an `OffscreenCanvas` can be transferred
+/// to a Web Worker (see [`crate::worker`]).
+#[derive(Clone, Debug)]
+pub enum RenderCanvas {
+    Html(web_sys::HtmlCanvasElement),
+    Offscreen(web_sys::OffscreenCanvas),
+}
+
+impl RenderCanvas {
+    /// Wraps the canvas a context was created from.
+    pub fn from_context(context: &web_sys::GpuCanvasContext) -> Self {
+        let canvas = context.canvas();
+
+        // checked first since it exists in every scope, HtmlCanvasElement doesn't exist in workers
+        if canvas.is_instance_of::<web_sys::OffscreenCanvas>() {
+            Self::Offscreen(canvas.unchecked_into())
+        } else {
+            Self::Html(canvas.unchecked_into())
+        }
+    }
+
+    /// Creates the WebGPU context for this canvas.
+    pub fn get_context(&self) -> Result<web_sys::GpuCanvasContext> {
+        let context = match self {
+            Self::Html(canvas) => canvas.get_context("webgpu"),
+            Self::Offscreen(canvas) => canvas.get_context("webgpu"),
+        };
+
+        match context {
+            Ok(Some(ctx)) => Ok(ctx.unchecked_into()),
+            Err(err) => Err(AwsmCoreError::canvas_context(err)),
+            Ok(None) => Err(AwsmCoreError::CanvasContext("No context found".to_string())),
+        }
+    }
+
+    /// Returns the backing buffer size.
+    pub fn size(&self) -> (u32, u32) {
+        match self {
+            Self::Html(canvas) => (canvas.width(), canvas.height()),
+            Self::Offscreen(canvas) => (canvas.width(), canvas.height()),
+        }
+    }
+
+    /// Sets the backing buffer size.
+    ///
+    /// Returns true if the size changed.
+    pub fn set_size(&self, width: u32, height: u32) -> bool {
+        if self.size() == (width, height) {
+            return false;
+        }
+
+        match self {
+            Self::Html(canvas) => {
+                canvas.set_width(width);
+                canvas.set_height(height);
+            }
+            Self::Offscreen(canvas) => {
+                canvas.set_width(width);
+                canvas.set_height(height);
+            }
+        }
+
+        true
+    }
+
+    /// Returns the element, if this is a main-thread canvas.
+    pub fn html(&self) -> Option<&web_sys::HtmlCanvasElement> {
+        match self {
+            Self::Html(canvas) => Some(canvas),
+            Self::Offscreen(_) => None,
+        }
+    }
+
+    /// Returns the offscreen canvas, if this is one.
+    pub fn offscreen(&self) -> Option<&web_sys::OffscreenCanvas> {
+        match self {
+            Self::Html(_) => None,
+            Self::Offscreen(canvas) => Some(canvas),
+        }
+    }
+}
+
+impl From<web_sys::HtmlCanvasElement> for RenderCanvas {
+    fn from(canvas: web_sys::HtmlCanvasElement) -> Self {
+        Self::Html(canvas)
+    }
+}
+
+impl From<web_sys::OffscreenCanvas> for RenderCanvas {
+    fn from(canvas: web_sys::OffscreenCanvas) -> Self {
+        Self::Offscreen(canvas)
+    }
+}
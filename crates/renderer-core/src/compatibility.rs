@@ -3,6 +3,7 @@
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
 
+use crate::global::GlobalScope;
 use crate::renderer::DeviceRequestLimits;
 
 /// Summary of WebGPU compatibility or failure reason.
@@ -73,7 +74,10 @@ impl Compatibility {
     pub async fn check(requirements: Option<CompatibilityRequirements>) -> Self {
         let requirements = requirements.unwrap_or_default();
 
-        let gpu = web_sys::window().unwrap().navigator().gpu();
+        let gpu = match GlobalScope::current() {
+            Ok(global) => global.gpu(),
+            Err(_) => return Self::MissingGpu,
+        };
 
         if gpu.is_null() || gpu.is_undefined() {
             return Self::MissingGpu;
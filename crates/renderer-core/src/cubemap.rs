@@ -249,7 +249,35 @@ pub async fn regenerate_texture_mipmaps(
     .await
 }
 
-fn create_texture_view(
+/// Creates an empty cubemap texture with the same size, format, mip count and usage as another,
+/// e.g. to stand in for one whose device was lost and that has no source to recreate it from.
+pub fn create_texture_like(
+    gpu: &AwsmRendererWebGpu,
+    texture: &web_sys::GpuTexture,
+    label: Option<&str>,
+) -> Result<web_sys::GpuTexture> {
+    // these attributes stay readable after the texture's device is gone
+    let descriptor = web_sys::GpuTextureDescriptor::new(
+        texture.format(),
+        &Extent3d::new(
+            texture.width(),
+            Some(texture.height()),
+            Some(texture.depth_or_array_layers()),
+        )
+        .into(),
+        texture.usage(),
+    );
+    descriptor.set_dimension(texture.dimension());
+    descriptor.set_mip_level_count(texture.mip_level_count());
+    if let Some(label) = label {
+        descriptor.set_label(label);
+    }
+
+    gpu.create_texture(&descriptor)
+}
+
+/// Creates a cube view over a cubemap texture.
+pub fn create_texture_view(
     texture: &web_sys::GpuTexture,
     label: Option<&str>,
 ) -> Result<web_sys::GpuTextureView> {
@@ -21,6 +21,15 @@ pub enum AwsmCoreError {
     #[error("[gpu] Failed to create Canvas Context: {0}")]
     CanvasContext(String),
 
+    #[error("[gpu] Global scope is neither a Window nor a WorkerGlobalScope")]
+    UnsupportedGlobalScope,
+
+    #[error("[gpu] Invalid canvas message: {0}")]
+    CanvasMessage(String),
+
+    #[error("[gpu] Failed to post canvas message: {0}")]
+    CanvasMessagePost(String),
+
     #[error("[gpu] Failed to configure Context: {0}")]
     ContextConfiguration(String),
 
@@ -205,6 +214,9 @@ pub enum AwsmCoreError {
 
     #[error("[gpu] mipmap generation unsupported format: {0:?}")]
     MipmapUnsupportedFormat(TextureFormat),
+
+    #[error("[gpu] mipmap pipeline not prepared for format: {0:?}")]
+    MipmapPipelineNotReady(TextureFormat),
 }
 
 impl AwsmCoreError {
@@ -223,6 +235,11 @@ impl AwsmCoreError {
         Self::CanvasContext(format_err(err))
     }
 
+    /// Creates a canvas message post error from a JS value.
+    pub fn canvas_message_post(err: JsValue) -> Self {
+        Self::CanvasMessagePost(format_err(err))
+    }
+
     /// Creates a context configuration error from a JS value.
     pub fn context_configuration(err: JsValue) -> Self {
         Self::ContextConfiguration(format_err(err))
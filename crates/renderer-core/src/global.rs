@@ -0,0 +1,83 @@
+//! The JS global scope, which is a `Window` on the main thread and a `WorkerGlobalScope` in workers.
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::error::{AwsmCoreError, Result};
+
+/// The global scope the renderer is running in.
+#[derive(Clone, Debug)]
+pub enum GlobalScope {
+    Window(web_sys::Window),
+    Worker(web_sys::WorkerGlobalScope),
+}
+
+impl GlobalScope {
+    /// Returns the current global scope.
+    pub fn current() -> Result<Self> {
+        let global = js_sys::global();
+
+        if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+            Ok(Self::Window(window.clone()))
+        } else if let Some(worker) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+            Ok(Self::Worker(worker.clone()))
+        } else {
+            Err(AwsmCoreError::UnsupportedGlobalScope)
+        }
+    }
+
+    /// Returns `navigator.gpu`, which may be undefined if WebGPU isn't supported.
+    pub fn gpu(&self) -> web_sys::Gpu {
+        match self {
+            Self::Window(window) => window.navigator().gpu(),
+            Self::Worker(worker) => worker.navigator().gpu(),
+        }
+    }
+
+    /// Returns true if this is a worker scope.
+    pub fn is_worker(&self) -> bool {
+        matches!(self, Self::Worker(_))
+    }
+
+    /// Starts decoding a blob into an `ImageBitmap`.
+    pub fn create_image_bitmap_with_blob(
+        &self,
+        blob: &web_sys::Blob,
+        options: Option<&web_sys::ImageBitmapOptions>,
+    ) -> std::result::Result<js_sys::Promise, JsValue> {
+        match (self, options) {
+            (Self::Window(window), Some(options)) => {
+                window.create_image_bitmap_with_blob_and_image_bitmap_options(blob, options)
+            }
+            (Self::Window(window), None) => window.create_image_bitmap_with_blob(blob),
+            (Self::Worker(worker), Some(options)) => {
+                worker.create_image_bitmap_with_blob_and_image_bitmap_options(blob, options)
+            }
+            (Self::Worker(worker), None) => worker.create_image_bitmap_with_blob(blob),
+        }
+    }
+
+    /// Starts creating an `ImageBitmap` from pixel data.
+    pub fn create_image_bitmap_with_image_data(
+        &self,
+        image_data: &web_sys::ImageData,
+        options: Option<&web_sys::ImageBitmapOptions>,
+    ) -> std::result::Result<js_sys::Promise, JsValue> {
+        match (self, options) {
+            (Self::Window(window), Some(options)) => window
+                .create_image_bitmap_with_image_data_and_image_bitmap_options(image_data, options),
+            (Self::Window(window), None) => window.create_image_bitmap_with_image_data(image_data),
+            (Self::Worker(worker), Some(options)) => worker
+                .create_image_bitmap_with_image_data_and_image_bitmap_options(image_data, options),
+            (Self::Worker(worker), None) => worker.create_image_bitmap_with_image_data(image_data),
+        }
+    }
+
+    /// Returns the origin of the page, or of the worker script.
+    pub fn location_origin(&self) -> std::result::Result<String, JsValue> {
+        match self {
+            Self::Window(window) => window.location().origin(),
+            Self::Worker(worker) => Ok(worker.location().origin()),
+        }
+    }
+}
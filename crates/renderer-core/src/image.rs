@@ -55,12 +55,33 @@ impl ImageData {
                     Ok(Self::Bitmap{image, options})
                 }
             }
+
+            /// Decodes an image or EXR from its encoded bytes.
+            ///
+            /// EXR is detected from the bytes, `mime_type` is only a hint for the browser decoder.
+            pub async fn load_bytes(bytes: Vec<u8>, mime_type: &str, options: Option<ImageBitmapOptions>) -> anyhow::Result<Self> {
+                if exr::ExrImage::is_exr(&bytes) {
+                    let exr_image = exr::ExrImage::from_bytes(bytes)?;
+                    Ok(Self::Exr(Arc::new(exr_image)))
+                } else {
+                    let image = bitmap::load_u8(&bytes, mime_type, options.clone()).await?;
+                    Ok(Self::Bitmap{image, options})
+                }
+            }
         } else if #[cfg(feature = "image")] {
             /// Loads an image from a URL.
             pub async fn load_url(url:&str, options: Option<ImageBitmapOptions>) -> Result<Self> {
                 let image = bitmap::load(url.to_string(), options.clone()).await?;
                 Ok(Self::Bitmap{image, options})
             }
+
+            /// Decodes an image from its encoded bytes.
+            ///
+            /// `mime_type` is only a hint for the browser decoder.
+            pub async fn load_bytes(bytes: Vec<u8>, mime_type: &str, options: Option<ImageBitmapOptions>) -> Result<Self> {
+                let image = bitmap::load_u8(&bytes, mime_type, options.clone()).await?;
+                Ok(Self::Bitmap{image, options})
+            }
         }
     }
 
@@ -6,11 +6,13 @@ use web_sys::{Blob, BlobPropertyBag, ImageBitmap};
 
 use crate::command::color::Color;
 use crate::error::{AwsmCoreError, Result};
+use crate::global::GlobalScope;
 
 use super::ImageBitmapOptions;
 
 thread_local! {
-    static WINDOW: LazyLock<web_sys::Window> = LazyLock::new(|| web_sys::window().unwrap_throw());
+    // a Window on the main thread, a WorkerGlobalScope when loading inside a worker
+    static GLOBAL: LazyLock<GlobalScope> = LazyLock::new(|| GlobalScope::current().unwrap_throw());
 }
 
 // let options = web_sys::ImageBitmapOptions::new();
@@ -37,13 +39,9 @@ pub async fn load_blob(
     blob: &Blob,
     options: Option<ImageBitmapOptions>,
 ) -> Result<web_sys::ImageBitmap> {
-    let promise = WINDOW
-        .with(|window| match options {
-            Some(options) => {
-                window.create_image_bitmap_with_blob_and_image_bitmap_options(blob, &options.into())
-            }
-            None => window.create_image_bitmap_with_blob(blob),
-        })
+    let options: Option<web_sys::ImageBitmapOptions> = options.map(Into::into);
+    let promise = GLOBAL
+        .with(|global| global.create_image_bitmap_with_blob(blob, options.as_ref()))
         .map_err(AwsmCoreError::create_image_bitmap)?;
     let js_value = JsFuture::from(promise)
         .await
@@ -209,14 +207,9 @@ pub async fn create_color(
         .map_err(AwsmCoreError::create_image_bitmap)?;
 
     // Create ImageBitmap from ImageData
-    let promise = WINDOW
-        .with(|window| match options {
-            Some(options) => window.create_image_bitmap_with_image_data_and_image_bitmap_options(
-                &image_data,
-                &options.into(),
-            ),
-            None => window.create_image_bitmap_with_image_data(&image_data),
-        })
+    let options: Option<web_sys::ImageBitmapOptions> = options.map(Into::into);
+    let promise = GLOBAL
+        .with(|global| global.create_image_bitmap_with_image_data(&image_data, options.as_ref()))
         .map_err(AwsmCoreError::create_image_bitmap)?;
 
     let js_value = JsFuture::from(promise)
@@ -270,14 +263,9 @@ pub async fn create_vertical_gradient(
     let image_data = web_sys::ImageData::new_with_js_u8_clamped_array(&uint8_array, width)
         .map_err(AwsmCoreError::create_image_bitmap)?;
 
-    let promise = WINDOW
-        .with(|window| match options {
-            Some(options) => window.create_image_bitmap_with_image_data_and_image_bitmap_options(
-                &image_data,
-                &options.into(),
-            ),
-            None => window.create_image_bitmap_with_image_data(&image_data),
-        })
+    let options: Option<web_sys::ImageBitmapOptions> = options.map(Into::into);
+    let promise = GLOBAL
+        .with(|global| global.create_image_bitmap_with_image_data(&image_data, options.as_ref()))
         .map_err(AwsmCoreError::create_image_bitmap)?;
 
     let js_value = JsFuture::from(promise)
@@ -290,8 +278,8 @@ pub async fn create_vertical_gradient(
 #[cfg(feature = "image")]
 fn _same_origin(url: &str) -> Result<bool> {
     if url.starts_with("http://") || url.starts_with("https://") {
-        let location_origin = WINDOW
-            .with(|window| window.location().origin())
+        let location_origin = GLOBAL
+            .with(|global| global.location_origin())
             .map_err(AwsmCoreError::location_origin)?;
         let url_origin = web_sys::Url::new(url)
             .map_err(AwsmCoreError::url_parse)?
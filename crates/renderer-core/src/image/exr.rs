@@ -28,6 +28,11 @@ impl ExrImage {
             .binary()
             .await?;
 
+        Self::from_bytes(bytes)
+    }
+
+    /// Decodes an EXR image from its encoded bytes.
+    pub fn from_bytes(bytes: Vec<u8>) -> anyhow::Result<Self> {
         let cursor = Cursor::new(bytes);
 
         // https://github.com/johannesvollmer/exrs/blob/master/GUIDE.md
@@ -67,6 +72,11 @@ impl ExrImage {
         Ok(result.layer_data.channel_data.pixels)
     }
 
+    /// Returns true if the bytes start with the EXR magic number.
+    pub fn is_exr(bytes: &[u8]) -> bool {
+        bytes.starts_with(&[0x76, 0x2f, 0x31, 0x01])
+    }
+
     /// Returns a JS object for external image copies if supported.
     pub fn js_obj(&self) -> Result<Cow<'_, js_sys::Object>> {
         Err(AwsmCoreError::ExrImageToJsValue(
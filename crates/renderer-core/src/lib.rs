@@ -4,6 +4,7 @@ pub mod alignment;
 pub mod bind_groups;
 pub mod brdf_lut;
 pub mod buffers;
+pub mod canvas;
 pub mod command;
 pub mod compare;
 pub mod compatibility;
@@ -11,6 +12,7 @@ pub mod configuration;
 pub mod cubemap;
 pub mod data;
 pub mod error;
+pub mod global;
 pub mod image;
 pub mod methods;
 pub mod pipeline;
@@ -18,3 +20,4 @@ pub mod renderer;
 pub mod sampler;
 pub mod shaders;
 pub mod texture;
+pub mod worker;
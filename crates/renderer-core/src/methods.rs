@@ -2,6 +2,7 @@
 
 use crate::{
     buffers::{extract_buffer_vec, BufferDescriptor, BufferUsage},
+    canvas::RenderCanvas,
     configuration::CanvasConfiguration,
     data::JsData,
 };
@@ -13,12 +14,13 @@ use crate::{
     error::{AwsmCoreError, Result},
     renderer::AwsmRendererWebGpu,
     texture::TextureFormat,
+    worker::CanvasPointerMessage,
 };
 
 impl AwsmRendererWebGpu {
-    /// Returns the underlying canvas element.
-    pub fn canvas(&self) -> web_sys::HtmlCanvasElement {
-        self.context.canvas().unchecked_into()
+    /// Returns the underlying canvas, an element or an `OffscreenCanvas`.
+    pub fn canvas(&self) -> RenderCanvas {
+        RenderCanvas::from_context(&self.context)
     }
 
     /// Returns the canvas size.
@@ -33,6 +35,9 @@ impl AwsmRendererWebGpu {
     /// - Use `canvas_size(false)` (default) for rendering, transforms, and coordinate conversions
     ///   where you need the actual buffer dimensions
     ///
+    /// An `OffscreenCanvas` has no CSS size, so both return the backing buffer size. In a worker,
+    /// the CSS size comes in with [`CanvasMessage::Resize`](crate::worker::CanvasMessage::Resize).
+    ///
     /// # Examples
     /// ```ignore
     /// // Get backing buffer size for rendering
@@ -42,27 +47,40 @@ impl AwsmRendererWebGpu {
     /// let (css_width, css_height) = renderer.canvas_size(true);
     /// ```
     pub fn canvas_size(&self, css_pixels: bool) -> (f64, f64) {
-        let canvas = self.canvas();
-
-        if css_pixels {
-            // Return CSS display size
-            let rect = canvas.get_bounding_client_rect();
-            (rect.width(), rect.height())
-        } else {
-            // Return backing buffer size (default behavior)
-            (canvas.width() as f64, canvas.height() as f64)
+        match self.canvas() {
+            RenderCanvas::Html(canvas) if css_pixels => {
+                // Return CSS display size
+                let rect = canvas.get_bounding_client_rect();
+                (rect.width(), rect.height())
+            }
+            canvas => {
+                // Return backing buffer size (default behavior)
+                let (width, height) = canvas.size();
+                (width as f64, height as f64)
+            }
         }
     }
 
+    /// Sets the canvas backing buffer size.
+    ///
+    /// This is how a worker applies [`CanvasMessage::Resize`](crate::worker::CanvasMessage::Resize),
+    /// since it can't see the page layout. Returns true if the size changed.
+    pub fn resize_canvas(&self, width: u32, height: u32) -> bool {
+        self.canvas().set_size(width.max(1), height.max(1))
+    }
+
     /// Syncs the canvas backing buffer size with the CSS display size.
     ///
     /// This ensures the canvas buffer dimensions match what's displayed,
     /// preventing rendering artifacts from mismatched sizes.
     ///
     /// Returns true if the size was updated, false if it was already in sync
-    /// or the CSS size is invalid (zero or negative).
+    /// or the CSS size is invalid (zero or negative). Always false for an `OffscreenCanvas`,
+    /// use [`Self::resize_canvas`] there.
     pub fn sync_canvas_buffer_with_css(&self) -> bool {
-        let canvas = self.canvas();
+        let RenderCanvas::Html(canvas) = self.canvas() else {
+            return false;
+        };
         let rect = canvas.get_bounding_client_rect();
         let css_width = rect.width();
         let css_height = rect.height();
@@ -514,29 +532,19 @@ impl AwsmRendererWebGpu {
     /// and converts them to backing buffer pixel coordinates, accounting for the canvas's position
     /// and the scaling between CSS pixels and backing buffer pixels.
     pub fn pointer_event_to_canvas_coords_f64(&self, evt: &web_sys::PointerEvent) -> (f64, f64) {
-        let canvas = self.canvas();
-        let rect = canvas.get_bounding_client_rect();
+        match self.canvas() {
+            RenderCanvas::Html(canvas) => {
+                let rect = canvas.get_bounding_client_rect();
 
-        // CSS pixels relative to the canvas' top-left
-        let css_x = evt.client_x() as f64 - rect.left();
-        let css_y = evt.client_y() as f64 - rect.top();
+                // CSS pixels relative to the canvas' top-left
+                let css_x = evt.client_x() as f64 - rect.left();
+                let css_y = evt.client_y() as f64 - rect.top();
 
-        // Get CSS and backing buffer sizes
-        let (css_w, css_h) = self.canvas_size(true);
-        let (buffer_w, buffer_h) = self.canvas_size(false);
-
-        // Avoid division by zero if the element is not laid out (display:none etc.)
-        let css_w = css_w.max(1.0);
-        let css_h = css_h.max(1.0);
-
-        // Convert CSS pixels -> backing buffer pixels
-        let scale_x = buffer_w / css_w;
-        let scale_y = buffer_h / css_h;
-
-        let x = css_x * scale_x;
-        let y = css_y * scale_y;
-
-        (x, y)
+                self.css_to_canvas_coords_f64(css_x, css_y, (rect.width(), rect.height()))
+            }
+            // no layout to measure, the event can only be relative to the canvas itself
+            RenderCanvas::Offscreen(_) => (evt.offset_x() as f64, evt.offset_y() as f64),
+        }
     }
 
     /// Converts a pointer event to canvas coordinates in backing buffer pixels (i32).
@@ -546,28 +554,53 @@ impl AwsmRendererWebGpu {
     /// or texel access.
     pub fn pointer_event_to_canvas_coords_i32(&self, evt: &web_sys::PointerEvent) -> (i32, i32) {
         let (x, y) = self.pointer_event_to_canvas_coords_f64(evt);
+        self.clamp_canvas_coords(x, y)
+    }
+
+    /// Converts a forwarded pointer message to canvas coordinates in backing buffer pixels (f64).
+    ///
+    /// The worker-side counterpart of `pointer_event_to_canvas_coords_f64`.
+    pub fn pointer_message_to_canvas_coords_f64(&self, msg: &CanvasPointerMessage) -> (f64, f64) {
+        self.css_to_canvas_coords_f64(msg.x, msg.y, (msg.css_width, msg.css_height))
+    }
+
+    /// Converts a forwarded pointer message to canvas coordinates in backing buffer pixels (i32),
+    /// clamped to the canvas bounds.
+    pub fn pointer_message_to_canvas_coords_i32(&self, msg: &CanvasPointerMessage) -> (i32, i32) {
+        let (x, y) = self.pointer_message_to_canvas_coords_f64(msg);
+        self.clamp_canvas_coords(x, y)
+    }
+
+    /// Converts CSS pixels relative to the canvas' top-left to backing buffer pixels,
+    /// given the canvas' CSS display size.
+    pub fn css_to_canvas_coords_f64(
+        &self,
+        css_x: f64,
+        css_y: f64,
+        css_size: (f64, f64),
+    ) -> (f64, f64) {
+        let (buffer_w, buffer_h) = self.canvas_size(false);
+
+        // Avoid division by zero if the element is not laid out (display:none etc.)
+        let css_w = css_size.0.max(1.0);
+        let css_h = css_size.1.max(1.0);
 
+        // Convert CSS pixels -> backing buffer pixels
+        let scale_x = buffer_w / css_w;
+        let scale_y = buffer_h / css_h;
+
+        (css_x * scale_x, css_y * scale_y)
+    }
+
+    fn clamp_canvas_coords(&self, x: f64, y: f64) -> (i32, i32) {
         // Get backing buffer size for clamping bounds
         let (w, h) = self.canvas_size(false);
         let w = w.max(1.0) as i64;
         let h = h.max(1.0) as i64;
 
         // Floor and clamp to canvas bounds
-        let mut ix = x.floor() as i64;
-        let mut iy = y.floor() as i64;
-
-        if ix < 0 {
-            ix = 0;
-        }
-        if iy < 0 {
-            iy = 0;
-        }
-        if ix >= w {
-            ix = w - 1;
-        }
-        if iy >= h {
-            iy = h - 1;
-        }
+        let ix = (x.floor() as i64).clamp(0, w - 1);
+        let iy = (y.floor() as i64).clamp(0, h - 1);
 
         (ix as i32, iy as i32)
     }
@@ -1,11 +1,14 @@
 //! WebGPU context and builder.
 
+use std::future::Future;
+
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::GpuSupportedLimits;
 
 use crate::{
+    canvas::RenderCanvas,
     configuration::CanvasConfiguration,
     error::{AwsmCoreError, Result},
 };
@@ -18,12 +21,14 @@ pub struct AwsmRendererWebGpu {
     pub adapter: web_sys::GpuAdapter,
     pub device: web_sys::GpuDevice,
     pub context: web_sys::GpuCanvasContext,
+    /// The limits the device was requested with, reused by [`Self::recreate_device`].
+    pub device_req_limits: Option<DeviceRequestLimits>,
 }
 
 /// Builder for creating an `AwsmRendererWebGpu`.
 pub struct AwsmRendererWebGpuBuilder {
     pub gpu: web_sys::Gpu,
-    pub canvas: web_sys::HtmlCanvasElement,
+    pub canvas: RenderCanvas,
     pub configuration: Option<CanvasConfiguration>,
     pub adapter: Option<web_sys::GpuAdapter>,
     pub device: Option<web_sys::GpuDevice>,
@@ -33,10 +38,13 @@ pub struct AwsmRendererWebGpuBuilder {
 
 impl AwsmRendererWebGpuBuilder {
     /// Creates a builder for a given GPU and canvas.
-    pub fn new(gpu: web_sys::Gpu, canvas: web_sys::HtmlCanvasElement) -> Self {
+    ///
+    /// The canvas can be an `HtmlCanvasElement` or, inside a worker, an `OffscreenCanvas`.
+    /// In a worker the GPU comes from [`GlobalScope::gpu`](crate::global::GlobalScope::gpu).
+    pub fn new(gpu: web_sys::Gpu, canvas: impl Into<RenderCanvas>) -> Self {
         Self {
             gpu,
-            canvas,
+            canvas: canvas.into(),
             configuration: None,
             adapter: None,
             device: None,
@@ -73,26 +81,17 @@ impl AwsmRendererWebGpuBuilder {
     pub async fn build(self) -> Result<AwsmRendererWebGpu> {
         tracing::info!("Building WebGPU Context");
 
-        let context: web_sys::GpuCanvasContext = match self.canvas.get_context("webgpu") {
-            Ok(Some(ctx)) => Ok(ctx.unchecked_into()),
-            Err(err) => Err(AwsmCoreError::canvas_context(err)),
-            Ok(None) => Err(AwsmCoreError::CanvasContext("No context found".to_string())),
-        }?;
+        let context = self.canvas.get_context()?;
 
         let mut adapter: web_sys::GpuAdapter = match self.adapter {
             Some(adapter) => adapter,
-            None => JsFuture::from(self.gpu.request_adapter())
-                .await
-                .map_err(AwsmCoreError::gpu_adapter)?
-                .unchecked_into(),
+            None => request_adapter(&self.gpu).await?,
         };
 
         if adapter.is_null() || adapter.is_undefined() {
-            // try one more time... maybe necessary for "lost context" scenarios?
-            adapter = JsFuture::from(self.gpu.request_adapter())
-                .await
-                .map_err(AwsmCoreError::gpu_adapter)?
-                .unchecked_into();
+            // try one more time, the first request can come back empty while a lost device is
+            // still being torn down (see AwsmRendererWebGpu::recreate_device)
+            adapter = request_adapter(&self.gpu).await?;
 
             if adapter.is_null() || adapter.is_undefined() {
                 return Err(AwsmCoreError::GpuAdapter("is null".to_string()));
@@ -101,30 +100,7 @@ impl AwsmRendererWebGpuBuilder {
 
         let device: web_sys::GpuDevice = match self.device {
             Some(device) => device,
-            None => {
-                if let Some(limits) = self.device_req_limits {
-                    let adapter_limits = adapter.limits();
-                    if adapter_limits.is_null() || adapter_limits.is_undefined() {
-                        tracing::warn!("adapter limits are null or undefined");
-                        JsFuture::from(adapter.request_device())
-                            .await
-                            .map_err(AwsmCoreError::gpu_device)?
-                            .unchecked_into()
-                    } else {
-                        let descriptor = web_sys::GpuDeviceDescriptor::new();
-                        descriptor.set_required_limits(&limits.into_js(&adapter.limits()));
-                        JsFuture::from(adapter.request_device_with_descriptor(&descriptor))
-                            .await
-                            .map_err(AwsmCoreError::gpu_device)?
-                            .unchecked_into()
-                    }
-                } else {
-                    JsFuture::from(adapter.request_device())
-                        .await
-                        .map_err(AwsmCoreError::gpu_device)?
-                        .unchecked_into()
-                }
-            }
+            None => request_device(&adapter, self.device_req_limits.clone()).await?,
         };
 
         if device.is_null() || device.is_undefined() {
@@ -145,10 +121,137 @@ impl AwsmRendererWebGpuBuilder {
             adapter,
             device,
             context,
+            device_req_limits: self.device_req_limits,
         })
     }
 }
 
+impl AwsmRendererWebGpu {
+    /// Resolves when the device is lost, e.g. after a driver reset or when a mobile browser
+    /// reclaims the GPU from a backgrounded tab.
+    ///
+    /// Every GPU object created from the device is unusable afterwards, and a device is never
+    /// restored, so the usual response is [`Self::recreate_device`] and rebuilding from there.
+    /// Calling `destroy()` on the device resolves this too, with [`DeviceLostReason::Destroyed`].
+    pub fn device_lost(&self) -> impl Future<Output = DeviceLost> + 'static {
+        let promise = self.device.lost();
+
+        async move {
+            match JsFuture::from(promise).await {
+                Ok(info) => {
+                    let info: web_sys::GpuDeviceLostInfo = info.unchecked_into();
+                    DeviceLost {
+                        reason: info.reason(),
+                        message: info.message(),
+                    }
+                }
+                // the promise never rejects per spec, treat it as an unexplained loss anyway
+                Err(err) => DeviceLost {
+                    reason: DeviceLostReason::Unknown,
+                    message: err.as_string().unwrap_or_else(|| format!("{err:#?}")),
+                },
+            }
+        }
+    }
+
+    /// Requests a new adapter and device, and reconfigures the canvas context to use it.
+    ///
+    /// The same limits are requested and the canvas keeps its configuration. Pipelines cached
+    /// by helpers in this crate (mipmaps, blits, BRDF LUT, etc.) are dropped since they belong
+    /// to the old device; anything else the caller created must be rebuilt.
+    pub async fn recreate_device(&self) -> Result<Self> {
+        let mut adapter = request_adapter(&self.gpu).await?;
+
+        if adapter.is_null() || adapter.is_undefined() {
+            adapter = request_adapter(&self.gpu).await?;
+
+            if adapter.is_null() || adapter.is_undefined() {
+                return Err(AwsmCoreError::GpuAdapter("is null".to_string()));
+            }
+        }
+
+        let device = request_device(&adapter, self.device_req_limits.clone()).await?;
+
+        if device.is_null() || device.is_undefined() {
+            return Err(AwsmCoreError::GpuDevice("is null".to_string()));
+        }
+
+        let configuration = match self.context.get_configuration() {
+            Some(configuration) => {
+                configuration.set_device(&device);
+                configuration
+            }
+            None => CanvasConfiguration::default().into_js(&self.gpu, &device),
+        };
+
+        self.context
+            .configure(&configuration)
+            .map_err(AwsmCoreError::context_configuration)?;
+
+        clear_device_caches();
+
+        Ok(Self {
+            gpu: self.gpu.clone(),
+            adapter,
+            device,
+            context: self.context.clone(),
+            device_req_limits: self.device_req_limits.clone(),
+        })
+    }
+}
+
+/// Why a device was lost, see [`AwsmRendererWebGpu::device_lost`].
+#[derive(Clone, Debug)]
+pub struct DeviceLost {
+    pub reason: DeviceLostReason,
+    /// Human readable, for logging only.
+    pub message: String,
+}
+
+/// WebGPU device lost reason.
+// https://docs.rs/web-sys/latest/web_sys/enum.GpuDeviceLostReason.html
+pub type DeviceLostReason = web_sys::GpuDeviceLostReason;
+
+async fn request_adapter(gpu: &web_sys::Gpu) -> Result<web_sys::GpuAdapter> {
+    Ok(JsFuture::from(gpu.request_adapter())
+        .await
+        .map_err(AwsmCoreError::gpu_adapter)?
+        .unchecked_into())
+}
+
+async fn request_device(
+    adapter: &web_sys::GpuAdapter,
+    limits: Option<DeviceRequestLimits>,
+) -> Result<web_sys::GpuDevice> {
+    let promise = match limits {
+        Some(limits) => {
+            let adapter_limits = adapter.limits();
+            if adapter_limits.is_null() || adapter_limits.is_undefined() {
+                tracing::warn!("adapter limits are null or undefined");
+                adapter.request_device()
+            } else {
+                let descriptor = web_sys::GpuDeviceDescriptor::new();
+                descriptor.set_required_limits(&limits.into_js(&adapter_limits));
+                adapter.request_device_with_descriptor(&descriptor)
+            }
+        }
+        None => adapter.request_device(),
+    };
+
+    Ok(JsFuture::from(promise)
+        .await
+        .map_err(AwsmCoreError::gpu_device)?
+        .unchecked_into())
+}
+
+// thread-local caches hold objects from whichever device filled them
+fn clear_device_caches() {
+    crate::brdf_lut::generate::clear_cache();
+    crate::texture::blit::clear_cache();
+    crate::texture::convert_srgb::clear_cache();
+    crate::texture::mipmap::clear_cache();
+}
+
 /// Requested device limits to increase WebGPU caps.
 #[derive(Debug, Clone, Default)]
 pub struct DeviceRequestLimits {
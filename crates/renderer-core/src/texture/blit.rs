@@ -44,6 +44,11 @@ thread_local! {
     static BLIT_PIPELINE: RefCell<HashMap<BlitPipelineCacheKey, BlitPipeline>> = RefCell::new(HashMap::new());
 }
 
+// cached pipelines belong to the device that made them
+pub(crate) fn clear_cache() {
+    BLIT_PIPELINE.with(|pipeline_cell| pipeline_cell.borrow_mut().clear());
+}
+
 static SHADER_SOURCE: &str = r#"
     @group(0) @binding(0) var src_tex: texture_2d<f32>;
 
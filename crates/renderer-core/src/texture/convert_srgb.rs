@@ -32,6 +32,11 @@ thread_local! {
     static CONVERT_SRGB_PIPELINE: RefCell<HashMap<TextureFormatKey, ConvertSrgbPipeline>> = RefCell::new(HashMap::new());
 }
 
+// cached pipelines belong to the device that made them
+pub(crate) fn clear_cache() {
+    CONVERT_SRGB_PIPELINE.with(|pipeline_cell| pipeline_cell.borrow_mut().clear());
+}
+
 // sRGB to linear conversion shader
 // Converts a single layer of a 2D texture from sRGB to linear color space
 fn shader_source(format: TextureFormat) -> Result<String> {
@@ -8,7 +8,7 @@ use crate::{
         BindGroupLayoutResource, BindGroupResource, StorageTextureAccess,
         StorageTextureBindingLayout, TextureBindingLayout,
     },
-    command::compute_pass::ComputePassDescriptor,
+    command::{compute_pass::ComputePassDescriptor, CommandEncoder},
     error::{AwsmCoreError, Result},
     pipeline::{
         layout::{PipelineLayoutDescriptor, PipelineLayoutKind},
@@ -86,6 +86,11 @@ thread_local! {
     static MIPMAP_PIPELINE: RefCell<HashMap<LookupKey, MipmapPipeline>> = RefCell::new(HashMap::new());
 }
 
+// cached pipelines belong to the device that made them
+pub(crate) fn clear_cache() {
+    MIPMAP_PIPELINE.with(|pipeline_cell| pipeline_cell.borrow_mut().clear());
+}
+
 #[derive(Hash, Debug, Eq, PartialEq)]
 struct LookupKey {
     pub texture_format: TextureFormatKey,
@@ -241,28 +246,110 @@ pub async fn generate_mipmaps(
     texture_kinds_per_layer: &[MipmapTextureKind],
     mip_levels: u32,
 ) -> Result<()> {
-    use crate::buffers::{BufferDescriptor, BufferUsage};
-
     if mip_levels < 2 {
         return Ok(());
     }
 
     let is_array = texture_kinds_per_layer.len() > 1;
-    let array_layer_count = if is_array {
-        Some(texture_kinds_per_layer.len() as u32)
-    } else {
-        None
-    };
 
     // Get pipeline
+    let pipeline = get_pipeline(gpu, texture.format(), is_array).await?;
+
+    let command_encoder = gpu.create_command_encoder(Some("Generate Mipmaps"));
+
+    let layers: Vec<(u32, MipmapTextureKind)> = texture_kinds_per_layer
+        .iter()
+        .enumerate()
+        .map(|(index, kind)| (index as u32, *kind))
+        .collect();
+
+    encode_mipmaps(
+        gpu,
+        &command_encoder,
+        &pipeline,
+        texture,
+        is_array.then_some(texture_kinds_per_layer.len() as u32),
+        &layers,
+        mip_levels,
+    )?;
+
+    // Submit all commands
+    let command_buffer = command_encoder.finish();
+    gpu.submit_commands(&command_buffer);
+
+    Ok(())
+}
+
+/// Compiles and caches the mipmap pipeline for a format ahead of time.
+///
+/// Needed before [`encode_layer_mipmaps`], which can't wait for a compile.
+pub async fn prepare_mipmaps(
+    gpu: &AwsmRendererWebGpu,
+    format: TextureFormat,
+    is_array: bool,
+) -> Result<()> {
+    get_pipeline(gpu, format, is_array).await.map(|_| ())
+}
+
+/// Regenerates the mipmaps of a single layer into an existing command encoder.
+///
+/// For textures whose level 0 is rewritten on the GPU every frame, e.g. render targets
+/// sampled as material textures. The pipeline must already be cached by [`prepare_mipmaps`]
+/// or an earlier [`generate_mipmaps`] with the same format.
+pub fn encode_layer_mipmaps(
+    gpu: &AwsmRendererWebGpu,
+    command_encoder: &CommandEncoder,
+    texture: &web_sys::GpuTexture,
+    texture_kind: MipmapTextureKind,
+    layer: u32,
+    mip_levels: u32,
+) -> Result<()> {
+    if mip_levels < 2 {
+        return Ok(());
+    }
+
+    let layer_count = texture.depth_or_array_layers();
+    // matches how `generate_mipmaps` treats a single layer
+    let is_array = layer_count > 1;
+
+    let key = LookupKey {
+        texture_format: texture.format().into(),
+        is_array,
+    };
+
+    let pipeline = MIPMAP_PIPELINE
+        .with(|pipeline_cell| pipeline_cell.borrow().get(&key).cloned())
+        .ok_or(AwsmCoreError::MipmapPipelineNotReady(texture.format()))?;
+
+    encode_mipmaps(
+        gpu,
+        command_encoder,
+        &pipeline,
+        texture,
+        is_array.then_some(layer_count),
+        &[(layer, texture_kind)],
+        mip_levels,
+    )
+}
+
+fn encode_mipmaps(
+    gpu: &AwsmRendererWebGpu,
+    command_encoder: &CommandEncoder,
+    pipeline: &MipmapPipeline,
+    texture: &web_sys::GpuTexture,
+    array_layer_count: Option<u32>,
+    // the layers to generate, with the kind of texture in each
+    layers: &[(u32, MipmapTextureKind)],
+    mip_levels: u32,
+) -> Result<()> {
+    use crate::buffers::{BufferDescriptor, BufferUsage};
+
     let MipmapPipeline {
         compute_pipeline: mipmap_pipeline,
         bind_group_layout: mipmap_bind_group_layout,
-    } = get_pipeline(gpu, texture.format(), is_array).await?;
+    } = pipeline;
 
-    let command_encoder = gpu.create_command_encoder(Some("Generate Mipmaps"));
-
-    let view_dimension = if is_array {
+    let view_dimension = if array_layer_count.is_some() {
         TextureViewDimension::N2dArray
     } else {
         TextureViewDimension::N2d
@@ -293,13 +380,13 @@ pub async fn generate_mipmaps(
             .create_view_with_descriptor(&dst_view_descriptor.into())
             .map_err(AwsmCoreError::create_texture_view)?;
 
-        for target_layer_index in 0..array_layer_count.unwrap_or(1) {
+        for (target_layer_index, texture_kind) in layers.iter().copied() {
             let (dst_width, dst_height) =
                 get_mipmap_size_for_level(texture.width(), texture.height(), mip_level);
 
             // Build uniform buffer data
             let params_data: Vec<u32> = vec![
-                texture_kinds_per_layer[target_layer_index as usize] as u32,
+                texture_kind as u32,
                 target_layer_index,
                 dst_width,
                 dst_height,
@@ -322,7 +409,7 @@ pub async fn generate_mipmaps(
             let buffer_binding = crate::buffers::BufferBinding::new(&params_buffer);
             let layer_bind_group = gpu.create_bind_group(&<web_sys::GpuBindGroupDescriptor>::from(
                 BindGroupDescriptor::new(
-                    &mipmap_bind_group_layout,
+                    mipmap_bind_group_layout,
                     Some("Mipmap Bind Group"),
                     vec![
                         BindGroupEntry::new(
@@ -345,17 +432,13 @@ pub async fn generate_mipmaps(
             let compute_pass = command_encoder.begin_compute_pass(Some(
                 &ComputePassDescriptor::new(Some("Mipmap Pass")).into(),
             ));
-            compute_pass.set_pipeline(&mipmap_pipeline);
+            compute_pass.set_pipeline(mipmap_pipeline);
             compute_pass.set_bind_group(0, &layer_bind_group, None)?;
             compute_pass.dispatch_workgroups(workgroup_size_x, Some(workgroup_size_y), None);
             compute_pass.end();
         }
     }
 
-    // Submit all commands
-    let command_buffer = command_encoder.finish();
-    gpu.submit_commands(&command_buffer);
-
     Ok(())
 }
 
@@ -26,6 +26,8 @@ use crate::{
 pub struct TexturePool<ID> {
     arrays: IndexMap<TexturePoolArrayKey, TexturePoolArray<ID>>,
     id_to_array_key: HashMap<ID, TexturePoolArrayKey>,
+    // set when layers move or arrays go away, so bindings must be recreated
+    layout_dirty: bool,
 }
 
 /// Single texture array in the pool.
@@ -77,6 +79,7 @@ impl<ID: Eq + Hash + Clone> TexturePool<ID> {
         Self {
             arrays: IndexMap::new(),
             id_to_array_key: HashMap::new(),
+            layout_dirty: false,
         }
     }
 
@@ -104,6 +107,37 @@ impl<ID: Eq + Hash + Clone> TexturePool<ID> {
         self.id_to_array_key.insert(id, array_key);
     }
 
+    /// Removes an image from the pool. Returns true if it was present.
+    ///
+    /// Later layers in its array shift down and an emptied array is dropped (shifting later
+    /// arrays down), so entries looked up before this are stale afterwards.
+    pub fn remove_image(&mut self, id: ID) -> bool {
+        let Some(array_key) = self.id_to_array_key.remove(&id) else {
+            return false;
+        };
+
+        let Some(array) = self.arrays.get_mut(&array_key) else {
+            return false;
+        };
+
+        array.remove(&id);
+
+        if array.images.is_empty() {
+            if let Some(array) = self.arrays.shift_remove(&array_key) {
+                array.destroy_gpu();
+            }
+        }
+
+        self.layout_dirty = true;
+
+        true
+    }
+
+    /// Returns true if the pool contains the image.
+    pub fn contains(&self, id: &ID) -> bool {
+        self.id_to_array_key.contains_key(id)
+    }
+
     /// Returns a texture array by index.
     pub fn array_by_index(&self, index: usize) -> Option<&TexturePoolArray<ID>> {
         self.arrays.values().nth(index)
@@ -136,9 +170,9 @@ impl<ID: Eq + Hash + Clone> TexturePool<ID> {
         None
     }
 
-    /// Uploads dirty arrays to the GPU. Returns true if any array was dirty.
+    /// Uploads dirty arrays to the GPU. Returns true if any array was dirty or removed.
     pub async fn write_gpu(&mut self, gpu: &AwsmRendererWebGpu) -> Result<bool> {
-        let mut any_dirty = false;
+        let mut any_dirty = std::mem::take(&mut self.layout_dirty);
         for array in self.arrays.values_mut() {
             any_dirty |= array.gpu_dirty;
             array.write_gpu(gpu).await?;
@@ -147,6 +181,16 @@ impl<ID: Eq + Hash + Clone> TexturePool<ID> {
         Ok(any_dirty)
     }
 
+    /// Forgets the uploaded textures after their device was lost, every array is uploaded
+    /// again from its retained images on the next [`Self::write_gpu`].
+    pub fn mark_gpu_lost(&mut self) {
+        for array in self.arrays.values_mut() {
+            array.gpu_dirty = true;
+            array.gpu_texture = None;
+            array.gpu_texture_view = None;
+        }
+    }
+
     /// Iterates over GPU textures for uploaded arrays.
     pub fn textures(&self) -> impl Iterator<Item = &web_sys::GpuTexture> {
         self.arrays
@@ -184,6 +228,25 @@ impl<ID> TexturePoolArray<ID> {
         self.gpu_dirty = true;
     }
 
+    /// Removes an image from the array, later layers shift down.
+    pub fn remove(&mut self, id: &ID)
+    where
+        ID: PartialEq,
+    {
+        let len = self.images.len();
+        self.images.retain(|(layer_id, _, _)| layer_id != id);
+        if self.images.len() != len {
+            self.gpu_dirty = true;
+        }
+    }
+
+    /// Destroys the uploaded GPU texture, if any.
+    pub fn destroy_gpu(&self) {
+        if let Some(texture) = &self.gpu_texture {
+            texture.destroy();
+        }
+    }
+
     /// Returns the mip level count for this array.
     pub fn mipmap_levels(&self) -> u32 {
         if self.mipmap {
@@ -329,7 +392,10 @@ impl<ID> TexturePoolArray<ID> {
             .map_err(AwsmCoreError::create_texture_view)?;
 
         self.gpu_dirty = false;
-        self.gpu_texture = Some(dest_tex);
+        // bind groups over the old texture get recreated since the pool reports it was dirty
+        if let Some(old_texture) = self.gpu_texture.replace(dest_tex) {
+            old_texture.destroy();
+        }
         self.gpu_texture_view = Some(dest_view);
 
         Ok(())
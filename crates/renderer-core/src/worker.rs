@@ -0,0 +1,287 @@
+//! Messages for running the renderer inside a Web Worker.
+//!
+//! The main thread keeps the canvas element for layout and input, hands the drawing over to the
+//! worker as an `OffscreenCanvas`, then forwards resizes and pointer input:
+//!
+//! ```ignore
+//! // main thread
+//! let offscreen = canvas.transfer_control_to_offscreen()?;
+//! CanvasMessage::Canvas(offscreen).post(&worker)?;
+//! CanvasMessage::resize(&canvas).post(&worker)?;
+//! // in a pointer listener
+//! CanvasMessage::pointer(CanvasPointerKind::Down, &event, &canvas).post(&worker)?;
+//!
+//! // worker, in its message handler
+//! match CanvasMessage::from_js(&event.data())? {
+//!     Some(CanvasMessage::Canvas(canvas)) => {
+//!         let builder = AwsmRendererWebGpuBuilder::new(GlobalScope::current()?.gpu(), canvas);
+//!     }
+//!     Some(CanvasMessage::Resize { width, height }) => {
+//!         renderer.gpu.resize_canvas(width, height);
+//!     }
+//!     Some(CanvasMessage::Pointer(pointer)) => {
+//!         let (x, y) = renderer.gpu.pointer_message_to_canvas_coords_i32(&pointer);
+//!     }
+//!     Some(CanvasMessage::Wheel { delta_y, .. }) => {}
+//!     // one of the app's own messages
+//!     None => {}
+//! }
+//! ```
+
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::error::{AwsmCoreError, Result};
+
+// the property that marks a message as ours, and holds its kind
+const MESSAGE_KIND_KEY: &str = "awsmCanvasMessage";
+
+/// A message from the main thread to a worker that owns the canvas.
+#[derive(Clone, Debug)]
+pub enum CanvasMessage {
+    /// The canvas to render into, sent once after `transferControlToOffscreen()`.
+    Canvas(web_sys::OffscreenCanvas),
+    /// The new backing buffer size, in pixels.
+    Resize { width: u32, height: u32 },
+    /// Pointer input over the canvas.
+    Pointer(CanvasPointerMessage),
+    /// Wheel input over the canvas, deltas as reported by the event.
+    Wheel { delta_x: f64, delta_y: f64 },
+}
+
+/// A pointer event, with coordinates relative to the canvas.
+#[derive(Clone, Debug)]
+pub struct CanvasPointerMessage {
+    pub kind: CanvasPointerKind,
+    pub pointer_id: i32,
+    /// CSS pixels from the canvas' top-left, see
+    /// [`pointer_message_to_canvas_coords_i32`](crate::renderer::AwsmRendererWebGpu::pointer_message_to_canvas_coords_i32)
+    /// for backing buffer pixels.
+    pub x: f64,
+    pub y: f64,
+    /// The canvas' CSS size when the event happened.
+    pub css_width: f64,
+    pub css_height: f64,
+    pub movement_x: i32,
+    pub movement_y: i32,
+    pub button: i16,
+    pub buttons: u16,
+    pub shift_key: bool,
+    pub ctrl_key: bool,
+    pub alt_key: bool,
+    pub meta_key: bool,
+}
+
+/// Which pointer event was forwarded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CanvasPointerKind {
+    Down,
+    Move,
+    Up,
+    Cancel,
+    Leave,
+}
+
+impl CanvasPointerKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Down => "down",
+            Self::Move => "move",
+            Self::Up => "up",
+            Self::Cancel => "cancel",
+            Self::Leave => "leave",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "down" => Some(Self::Down),
+            "move" => Some(Self::Move),
+            "up" => Some(Self::Up),
+            "cancel" => Some(Self::Cancel),
+            "leave" => Some(Self::Leave),
+            _ => None,
+        }
+    }
+}
+
+impl CanvasMessage {
+    /// A resize to the canvas' current CSS size, the same size
+    /// [`sync_canvas_buffer_with_css`](crate::renderer::AwsmRendererWebGpu::sync_canvas_buffer_with_css)
+    /// would pick on the main thread.
+    pub fn resize(canvas: &web_sys::HtmlCanvasElement) -> Self {
+        let rect = canvas.get_bounding_client_rect();
+
+        Self::Resize {
+            width: rect.width().max(1.0) as u32,
+            height: rect.height().max(1.0) as u32,
+        }
+    }
+
+    /// Captures a pointer event over the canvas.
+    pub fn pointer(
+        kind: CanvasPointerKind,
+        evt: &web_sys::PointerEvent,
+        canvas: &web_sys::HtmlCanvasElement,
+    ) -> Self {
+        let rect = canvas.get_bounding_client_rect();
+
+        Self::Pointer(CanvasPointerMessage {
+            kind,
+            pointer_id: evt.pointer_id(),
+            x: evt.client_x() as f64 - rect.left(),
+            y: evt.client_y() as f64 - rect.top(),
+            css_width: rect.width(),
+            css_height: rect.height(),
+            movement_x: evt.movement_x(),
+            movement_y: evt.movement_y(),
+            button: evt.button(),
+            buttons: evt.buttons(),
+            shift_key: evt.shift_key(),
+            ctrl_key: evt.ctrl_key(),
+            alt_key: evt.alt_key(),
+            meta_key: evt.meta_key(),
+        })
+    }
+
+    /// Captures a wheel event over the canvas.
+    pub fn wheel(evt: &web_sys::WheelEvent) -> Self {
+        Self::Wheel {
+            delta_x: evt.delta_x(),
+            delta_y: evt.delta_y(),
+        }
+    }
+
+    /// Posts this message to a worker, transferring the canvas if it's a [`Self::Canvas`].
+    pub fn post(&self, worker: &web_sys::Worker) -> Result<()> {
+        let message = self.to_js();
+
+        match self {
+            Self::Canvas(canvas) => {
+                worker.post_message_with_transfer(&message, &js_sys::Array::of1(canvas))
+            }
+            _ => worker.post_message(&message),
+        }
+        .map_err(AwsmCoreError::canvas_message_post)
+    }
+
+    /// Converts to a plain JS object, for sending through your own channel.
+    ///
+    /// A [`Self::Canvas`] message must list the canvas in the transfer list.
+    pub fn to_js(&self) -> JsValue {
+        let obj = js_sys::Object::new();
+
+        let kind = match self {
+            Self::Canvas(canvas) => {
+                set(&obj, "canvas", canvas);
+                "canvas"
+            }
+            Self::Resize { width, height } => {
+                set(&obj, "width", &(*width).into());
+                set(&obj, "height", &(*height).into());
+                "resize"
+            }
+            Self::Pointer(pointer) => {
+                set(&obj, "pointerKind", &pointer.kind.as_str().into());
+                set(&obj, "pointerId", &pointer.pointer_id.into());
+                set(&obj, "x", &pointer.x.into());
+                set(&obj, "y", &pointer.y.into());
+                set(&obj, "cssWidth", &pointer.css_width.into());
+                set(&obj, "cssHeight", &pointer.css_height.into());
+                set(&obj, "movementX", &pointer.movement_x.into());
+                set(&obj, "movementY", &pointer.movement_y.into());
+                set(&obj, "button", &pointer.button.into());
+                set(&obj, "buttons", &pointer.buttons.into());
+                set(&obj, "shiftKey", &pointer.shift_key.into());
+                set(&obj, "ctrlKey", &pointer.ctrl_key.into());
+                set(&obj, "altKey", &pointer.alt_key.into());
+                set(&obj, "metaKey", &pointer.meta_key.into());
+                "pointer"
+            }
+            Self::Wheel { delta_x, delta_y } => {
+                set(&obj, "deltaX", &(*delta_x).into());
+                set(&obj, "deltaY", &(*delta_y).into());
+                "wheel"
+            }
+        };
+
+        set(&obj, MESSAGE_KIND_KEY, &kind.into());
+
+        obj.into()
+    }
+
+    /// Reads a message sent with [`Self::post`] or [`Self::to_js`].
+    ///
+    /// Returns `None` for anything else, so a worker can share its message channel with the app.
+    pub fn from_js(value: &JsValue) -> Result<Option<Self>> {
+        if !value.is_object() {
+            return Ok(None);
+        }
+
+        let Some(kind) = get(value, MESSAGE_KIND_KEY).as_string() else {
+            return Ok(None);
+        };
+
+        let message = match kind.as_str() {
+            "canvas" => Self::Canvas(get(value, "canvas").dyn_into().map_err(|_| {
+                AwsmCoreError::CanvasMessage("canvas is not an OffscreenCanvas".to_string())
+            })?),
+            "resize" => Self::Resize {
+                width: get_f64(value, "width")? as u32,
+                height: get_f64(value, "height")? as u32,
+            },
+            "pointer" => {
+                let pointer_kind = get(value, "pointerKind")
+                    .as_string()
+                    .and_then(|kind| CanvasPointerKind::parse(&kind))
+                    .ok_or_else(|| {
+                        AwsmCoreError::CanvasMessage("unknown pointer kind".to_string())
+                    })?;
+
+                Self::Pointer(CanvasPointerMessage {
+                    kind: pointer_kind,
+                    pointer_id: get_f64(value, "pointerId")? as i32,
+                    x: get_f64(value, "x")?,
+                    y: get_f64(value, "y")?,
+                    css_width: get_f64(value, "cssWidth")?,
+                    css_height: get_f64(value, "cssHeight")?,
+                    movement_x: get_f64(value, "movementX")? as i32,
+                    movement_y: get_f64(value, "movementY")? as i32,
+                    button: get_f64(value, "button")? as i16,
+                    buttons: get_f64(value, "buttons")? as u16,
+                    shift_key: get(value, "shiftKey").is_truthy(),
+                    ctrl_key: get(value, "ctrlKey").is_truthy(),
+                    alt_key: get(value, "altKey").is_truthy(),
+                    meta_key: get(value, "metaKey").is_truthy(),
+                })
+            }
+            "wheel" => Self::Wheel {
+                delta_x: get_f64(value, "deltaX")?,
+                delta_y: get_f64(value, "deltaY")?,
+            },
+            other => {
+                return Err(AwsmCoreError::CanvasMessage(format!(
+                    "unknown message kind {other}"
+                )))
+            }
+        };
+
+        Ok(Some(message))
+    }
+}
+
+fn set(obj: &js_sys::Object, key: &str, value: &JsValue) {
+    // can't fail on a plain object
+    let _ = Reflect::set(obj, &key.into(), value);
+}
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &key.into()).unwrap_or(JsValue::UNDEFINED)
+}
+
+fn get_f64(value: &JsValue, key: &str) -> Result<f64> {
+    get(value, key)
+        .as_f64()
+        .ok_or_else(|| AwsmCoreError::CanvasMessage(format!("missing {key}")))
+}
@@ -1,20 +1,43 @@
 //! Animation storage and per-frame updates.
 
-use slotmap::{new_key_type, DenseSlotMap, SecondaryMap};
+use std::collections::{HashMap, HashSet};
+
+use slotmap::{new_key_type, DenseSlotMap, SecondaryMap, SlotMap};
 
 use crate::{
     meshes::morphs::{GeometryMorphKey, MaterialMorphKey},
-    transforms::TransformKey,
+    transforms::{Transform, TransformKey},
     AwsmRenderer,
 };
 
-use super::{data::AnimationData, error::Result, player::AnimationPlayer, AwsmAnimationError};
+use super::{
+    blend::{AnimationBlendMode, TransformBlend, VertexBlend},
+    clip::AnimationEvent,
+    data::AnimationData,
+    error::Result,
+    player::AnimationPlayer,
+    AnimationState, AwsmAnimationError,
+};
 
 new_key_type! {
     /// SlotMap key for animation players.
     pub struct AnimationKey;
 }
 
+new_key_type! {
+    /// SlotMap key for animation groups.
+    pub struct AnimationGroupKey;
+}
+
+/// Players that are controlled together as one named clip.
+///
+/// e.g. a glTF animation, with one player per animated node property.
+#[derive(Debug, Clone, Default)]
+pub struct AnimationGroup {
+    pub name: Option<String>,
+    pub players: Vec<AnimationKey>,
+}
+
 /// Morph targets that can be animated.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AnimationMorphKey {
@@ -34,6 +57,15 @@ impl From<MaterialMorphKey> for AnimationMorphKey {
     }
 }
 
+/// An event crossed by a player during `update_animations`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiredAnimationEvent {
+    pub animation_key: AnimationKey,
+    /// The first group the player belongs to, if any.
+    pub group_key: Option<AnimationGroupKey>,
+    pub event: AnimationEvent,
+}
+
 /// Container for animation players and their targets.
 #[derive(Debug, Clone, Default)]
 pub struct Animations {
@@ -41,6 +73,12 @@ pub struct Animations {
     // Different kinds of animations:
     transforms: SecondaryMap<AnimationKey, TransformKey>,
     morphs: SecondaryMap<AnimationKey, AnimationMorphKey>,
+    groups: SlotMap<AnimationGroupKey, AnimationGroup>,
+    // captured the first time a target is animated, blending starts from these
+    rest_transforms: SecondaryMap<TransformKey, Transform>,
+    rest_morph_weights: HashMap<AnimationMorphKey, Vec<f32>>,
+    // fired during the latest `update_animations`
+    events: Vec<FiredAnimationEvent>,
 }
 
 impl Animations {
@@ -50,10 +88,239 @@ impl Animations {
     }
 
     /// Removes an animation player and its associations.
+    ///
+    /// The rest pose of its target is forgotten once no other player animates it.
     pub fn remove(&mut self, key: AnimationKey) {
         self.players.remove(key);
-        self.transforms.remove(key);
-        self.morphs.remove(key);
+        if let Some(transform_key) = self.transforms.remove(key) {
+            if !self
+                .transforms
+                .values()
+                .any(|other| *other == transform_key)
+            {
+                self.rest_transforms.remove(transform_key);
+            }
+        }
+        if let Some(morph_key) = self.morphs.remove(key) {
+            if !self.morphs.values().any(|other| *other == morph_key) {
+                self.rest_morph_weights.remove(&morph_key);
+            }
+        }
+        for group in self.groups.values_mut() {
+            group.players.retain(|player_key| *player_key != key);
+        }
+    }
+
+    /// Removes the players on these transforms and morphs, along with their rest poses.
+    ///
+    /// For targets that are about to be removed, which the players would otherwise fail on.
+    pub fn remove_targets(
+        &mut self,
+        transform_keys: &HashSet<TransformKey>,
+        morph_keys: &HashSet<AnimationMorphKey>,
+    ) {
+        let player_keys: Vec<AnimationKey> = self
+            .transforms
+            .iter()
+            .filter(|(_, transform_key)| transform_keys.contains(transform_key))
+            .map(|(key, _)| key)
+            .chain(
+                self.morphs
+                    .iter()
+                    .filter(|(_, morph_key)| morph_keys.contains(morph_key))
+                    .map(|(key, _)| key),
+            )
+            .collect();
+
+        for key in player_keys {
+            self.remove(key);
+        }
+
+        // set directly, without a player
+        self.rest_transforms
+            .retain(|transform_key, _| !transform_keys.contains(&transform_key));
+        self.rest_morph_weights
+            .retain(|morph_key, _| !morph_keys.contains(morph_key));
+    }
+
+    /// Returns an animation player.
+    pub fn get(&self, key: AnimationKey) -> Result<&AnimationPlayer> {
+        self.players
+            .get(key)
+            .ok_or(AwsmAnimationError::MissingKey(key))
+    }
+
+    /// Returns an animation player for changing its playback.
+    pub fn get_mut(&mut self, key: AnimationKey) -> Result<&mut AnimationPlayer> {
+        self.players
+            .get_mut(key)
+            .ok_or(AwsmAnimationError::MissingKey(key))
+    }
+
+    /// Groups players so they can be controlled together.
+    pub fn insert_group(&mut self, group: AnimationGroup) -> AnimationGroupKey {
+        self.groups.insert(group)
+    }
+
+    /// Removes a group along with its players.
+    pub fn remove_group(&mut self, key: AnimationGroupKey) {
+        if let Some(group) = self.groups.remove(key) {
+            for player_key in group.players {
+                self.remove(player_key);
+            }
+        }
+    }
+
+    /// Returns an animation group.
+    pub fn group(&self, key: AnimationGroupKey) -> Result<&AnimationGroup> {
+        self.groups
+            .get(key)
+            .ok_or(AwsmAnimationError::MissingGroupKey(key))
+    }
+
+    /// Finds the first group with the given name.
+    ///
+    /// Names aren't unique across assets, prefer a lookup scoped to one asset
+    /// (e.g. `GltfKeyLookups::animation_groups`) where possible.
+    pub fn group_by_name(&self, name: &str) -> Option<AnimationGroupKey> {
+        self.groups
+            .iter()
+            .find(|(_, group)| group.name.as_deref() == Some(name))
+            .map(|(key, _)| key)
+    }
+
+    /// Iterates all animation groups.
+    pub fn groups(&self) -> impl Iterator<Item = (AnimationGroupKey, &AnimationGroup)> {
+        self.groups.iter()
+    }
+
+    /// Runs a function on every player of a group.
+    pub fn update_group(
+        &mut self,
+        key: AnimationGroupKey,
+        mut f: impl FnMut(&mut AnimationPlayer),
+    ) -> Result<()> {
+        let group = self
+            .groups
+            .get(key)
+            .ok_or(AwsmAnimationError::MissingGroupKey(key))?;
+
+        for player_key in group.players.iter() {
+            if let Some(player) = self.players.get_mut(*player_key) {
+                f(player);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Starts or resumes every player of a group.
+    pub fn play_group(&mut self, key: AnimationGroupKey) -> Result<()> {
+        self.update_group(key, |player| player.play())
+    }
+
+    /// Pauses every player of a group.
+    pub fn pause_group(&mut self, key: AnimationGroupKey) -> Result<()> {
+        self.update_group(key, |player| player.pause())
+    }
+
+    /// Stops and rewinds every player of a group.
+    pub fn stop_group(&mut self, key: AnimationGroupKey) -> Result<()> {
+        self.update_group(key, |player| player.stop())
+    }
+
+    /// Sets the blend weight of every player of a group.
+    pub fn set_group_weight(&mut self, key: AnimationGroupKey, weight: f64) -> Result<()> {
+        self.update_group(key, |player| player.weight = weight)
+    }
+
+    /// Sets how every player of a group blends with other players on the same targets.
+    pub fn set_group_blend_mode(
+        &mut self,
+        key: AnimationGroupKey,
+        blend_mode: AnimationBlendMode,
+    ) -> Result<()> {
+        self.update_group(key, |player| player.blend_mode = blend_mode)
+    }
+
+    /// Fades the weight of every player of a group, see [`AnimationPlayer::fade_to`].
+    pub fn fade_group(&mut self, key: AnimationGroupKey, weight: f64, duration: f64) -> Result<()> {
+        self.update_group(key, |player| player.fade_to(weight, duration))
+    }
+
+    /// Plays a group with its weight fading in from 0, unless it's already playing.
+    pub fn fade_in_group(&mut self, key: AnimationGroupKey, duration: f64) -> Result<()> {
+        self.update_group(key, |player| {
+            if *player.state() != AnimationState::Playing {
+                player.stop();
+                player.weight = 0.0;
+                player.play();
+            }
+            player.fade_to(1.0, duration);
+        })
+    }
+
+    /// Fades a group out and then stops it, see [`AnimationPlayer::fade_out`].
+    pub fn fade_out_group(&mut self, key: AnimationGroupKey, duration: f64) -> Result<()> {
+        self.update_group(key, |player| player.fade_out(duration))
+    }
+
+    /// Fades from one group to another over `duration` (in `update_animations` time units).
+    ///
+    /// The `to` group starts from the beginning unless it's already playing,
+    /// and the `from` group stops once it's faded out.
+    pub fn crossfade_group(
+        &mut self,
+        from: AnimationGroupKey,
+        to: AnimationGroupKey,
+        duration: f64,
+    ) -> Result<()> {
+        self.fade_out_group(from, duration)?;
+        self.fade_in_group(to, duration)
+    }
+
+    /// Adds an event to a group.
+    ///
+    /// Events are per player, so this goes on the group's first player
+    /// (glTF players of one animation share its duration).
+    pub fn add_group_event(&mut self, key: AnimationGroupKey, event: AnimationEvent) -> Result<()> {
+        let group = self
+            .groups
+            .get(key)
+            .ok_or(AwsmAnimationError::MissingGroupKey(key))?;
+        let player_key = *group
+            .players
+            .first()
+            .ok_or(AwsmAnimationError::EmptyGroup(key))?;
+
+        self.get_mut(player_key)?.clip_mut().add_event(event);
+
+        Ok(())
+    }
+
+    /// Takes the events fired during the latest `update_animations`, in the order they fired.
+    ///
+    /// Each update replaces the queue, so take them before updating again.
+    pub fn take_events(&mut self) -> Vec<FiredAnimationEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Sets the pose a transform blends from when its players' weights sum to less than 1.
+    ///
+    /// By default this is the local transform from before it was first animated.
+    /// It's forgotten along with the last player on the transform, see [`Self::remove`].
+    pub fn set_rest_transform(&mut self, transform_key: TransformKey, transform: Transform) {
+        self.rest_transforms.insert(transform_key, transform);
+    }
+
+    /// Stops the `from` group and plays the `to` group from the start.
+    ///
+    /// Node properties animated by both switch over right away.
+    pub fn swap_group(&mut self, from: AnimationGroupKey, to: AnimationGroupKey) -> Result<()> {
+        self.stop_group(from)?;
+        // playing a stopped group restarts it
+        self.stop_group(to)?;
+        self.play_group(to)
     }
 
     /// Inserts a transform animation player.
@@ -81,23 +348,58 @@ impl Animations {
 
 impl AwsmRenderer {
     /// Advances animation players and applies their results.
+    ///
+    /// Players on the same target are blended by weight (see [`AnimationBlendMode`]),
+    /// stopped players are skipped.
+    ///
+    /// Clip events crossed along the way are queued, see [`Animations::take_events`].
     pub fn update_animations(&mut self, global_time_delta: f64) -> Result<()> {
-        for player in self.animations.players.values_mut() {
-            player.update(global_time_delta)
+        self.animations.events.clear();
+
+        for (animation_key, player) in self.animations.players.iter_mut() {
+            player.update(global_time_delta);
+            let events = player.take_fired_events();
+            if events.is_empty() {
+                continue;
+            }
+
+            let group_key = self
+                .animations
+                .groups
+                .iter()
+                .find(|(_, group)| group.players.contains(&animation_key))
+                .map(|(group_key, _)| group_key);
+
+            self.animations
+                .events
+                .extend(events.into_iter().map(|event| FiredAnimationEvent {
+                    animation_key,
+                    group_key,
+                    event,
+                }));
         }
 
+        let mut transform_blends: HashMap<TransformKey, TransformBlend> = HashMap::new();
+
         for (animation_key, transform_key) in self.animations.transforms.iter() {
             let player = self
                 .animations
                 .players
                 .get(animation_key)
                 .ok_or(AwsmAnimationError::MissingKey(animation_key))?;
-            let transform = self.transforms.get_local(*transform_key)?;
-            match player.sample() {
-                AnimationData::Transform(transform_animation) => {
-                    let updated_transform = transform_animation.apply(transform.clone());
-                    self.transforms
-                        .set_local(*transform_key, updated_transform)?;
+            if *player.state() == AnimationState::Stopped {
+                continue;
+            }
+
+            let blend = transform_blends.entry(*transform_key).or_default();
+            match (player.blend_mode, player.sample()) {
+                (AnimationBlendMode::Override, AnimationData::Transform(sample)) => {
+                    blend.add(&sample, player.weight);
+                }
+                (AnimationBlendMode::Additive, AnimationData::Transform(sample)) => {
+                    if let AnimationData::Transform(reference) = player.sample_reference() {
+                        blend.add_additive(&sample, &reference, player.weight);
+                    }
                 }
                 _ => {
                     return Err(AwsmAnimationError::WrongKind("weird, animation player has a transform key but the animation data is not a transform".to_string()));
@@ -105,38 +407,75 @@ impl AwsmRenderer {
             }
         }
 
+        for (transform_key, blend) in transform_blends {
+            let transform = self.transforms.get_local(transform_key)?;
+            // the pose before any animation touched it is the base to blend from
+            if !self.animations.rest_transforms.contains_key(transform_key) {
+                self.animations
+                    .rest_transforms
+                    .insert(transform_key, transform.clone());
+            }
+            let rest = &self.animations.rest_transforms[transform_key];
+
+            let updated_transform = blend.apply(rest, transform.clone());
+            self.transforms
+                .set_local(transform_key, updated_transform)?;
+        }
+
+        let mut morph_blends: HashMap<AnimationMorphKey, VertexBlend> = HashMap::new();
+
         for (animation_key, morph_key) in self.animations.morphs.iter() {
             let player = self
                 .animations
                 .players
                 .get(animation_key)
                 .ok_or(AwsmAnimationError::MissingKey(animation_key))?;
+            if *player.state() == AnimationState::Stopped {
+                continue;
+            }
 
-            match player.sample() {
-                AnimationData::Vertex(vertex_animation) => match morph_key {
-                    AnimationMorphKey::Geometry(morph_key) => {
-                        self.meshes.morphs.geometry.update_morph_weights_with(
-                            *morph_key,
-                            |target| {
-                                target.copy_from_slice(&vertex_animation.weights);
-                            },
-                        )?;
-                    }
-                    AnimationMorphKey::Material(morph_key) => {
-                        self.meshes.morphs.material.update_morph_weights_with(
-                            *morph_key,
-                            |target| {
-                                target.copy_from_slice(&vertex_animation.weights);
-                            },
-                        )?;
+            let blend = morph_blends.entry(*morph_key).or_default();
+            match (player.blend_mode, player.sample()) {
+                (AnimationBlendMode::Override, AnimationData::Vertex(sample)) => {
+                    blend.add(&sample, player.weight);
+                }
+                (AnimationBlendMode::Additive, AnimationData::Vertex(sample)) => {
+                    if let AnimationData::Vertex(reference) = player.sample_reference() {
+                        blend.add_additive(&sample, &reference, player.weight);
                     }
-                },
+                }
                 _ => {
                     return Err(AwsmAnimationError::WrongKind("weird, animation player has a mesh key but the animation data is not for a mesh".to_string()));
                 }
             }
         }
 
+        for (morph_key, blend) in morph_blends {
+            let rest_weights = self.animations.rest_morph_weights.entry(morph_key);
+            let apply = |target: &mut [f32]| {
+                let rest = rest_weights.or_insert_with(|| target.to_vec());
+                blend.apply(rest, target);
+            };
+
+            match morph_key {
+                AnimationMorphKey::Geometry(morph_key) => {
+                    self.meshes
+                        .morphs
+                        .geometry
+                        .update_morph_weights_with(morph_key, apply)?;
+                }
+                AnimationMorphKey::Material(morph_key) => {
+                    self.meshes
+                        .morphs
+                        .material
+                        .update_morph_weights_with(morph_key, apply)?;
+                }
+            }
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,80 @@
+use slotmap::SlotMap;
+
+use crate::animation::{AnimationClip, AnimationData, AnimationPlayer, AnimationSampler};
+use crate::meshes::morphs::GeometryMorphKey;
+use crate::transforms::{Transform, TransformKey};
+
+use super::*;
+
+fn player() -> AnimationPlayer {
+    AnimationPlayer::new(AnimationClip::new(
+        None,
+        1.0,
+        AnimationSampler::new_linear(vec![0.0], vec![AnimationData::F32(0.0)]),
+    ))
+}
+
+#[test]
+fn rest_transform_outlives_all_but_the_last_player() {
+    let mut transform_keys = SlotMap::<TransformKey, ()>::with_key();
+    let transform_key = transform_keys.insert(());
+
+    let mut animations = Animations::new();
+    let first = animations.insert_transform(player(), transform_key);
+    let second = animations.insert_transform(player(), transform_key);
+    animations.set_rest_transform(transform_key, Transform::IDENTITY);
+
+    animations.remove(first);
+    assert!(animations.rest_transforms.contains_key(transform_key));
+
+    animations.remove(second);
+    assert!(!animations.rest_transforms.contains_key(transform_key));
+}
+
+#[test]
+fn removing_a_group_forgets_its_rest_poses() {
+    let mut transform_keys = SlotMap::<TransformKey, ()>::with_key();
+    let transform_key = transform_keys.insert(());
+    let mut morph_keys = SlotMap::<GeometryMorphKey, ()>::with_key();
+    let morph_key = AnimationMorphKey::from(morph_keys.insert(()));
+
+    let mut animations = Animations::new();
+    let players = vec![
+        animations.insert_transform(player(), transform_key),
+        animations.insert_morph(player(), morph_key),
+    ];
+    let group_key = animations.insert_group(AnimationGroup {
+        name: None,
+        players,
+    });
+    animations.set_rest_transform(transform_key, Transform::IDENTITY);
+    animations.rest_morph_weights.insert(morph_key, vec![0.5]);
+
+    animations.remove_group(group_key);
+    assert!(animations.rest_transforms.is_empty());
+    assert!(animations.rest_morph_weights.is_empty());
+}
+
+#[test]
+fn removing_targets_removes_their_players_only() {
+    let mut transform_keys = SlotMap::<TransformKey, ()>::with_key();
+    let removed = transform_keys.insert(());
+    let kept = transform_keys.insert(());
+    let unanimated = transform_keys.insert(());
+
+    let mut animations = Animations::new();
+    let removed_player = animations.insert_transform(player(), removed);
+    let kept_player = animations.insert_transform(player(), kept);
+    animations.set_rest_transform(removed, Transform::IDENTITY);
+    animations.set_rest_transform(kept, Transform::IDENTITY);
+    animations.set_rest_transform(unanimated, Transform::IDENTITY);
+
+    animations.remove_targets(&HashSet::from([removed, unanimated]), &HashSet::new());
+
+    assert!(animations.get(removed_player).is_err());
+    assert!(animations.get(kept_player).is_ok());
+    assert_eq!(
+        animations.rest_transforms.keys().collect::<Vec<_>>(),
+        [kept]
+    );
+}
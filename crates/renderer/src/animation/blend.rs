@@ -0,0 +1,244 @@
+//! Weighted blending of player samples that share a target.
+
+use glam::{Quat, Vec3};
+
+use crate::transforms::Transform;
+
+use super::{
+    data::{Animatable, TransformAnimation, VertexAnimation},
+    interpolate::interpolate_linear_f32,
+};
+
+/// How a player's sample combines with the other players on the same target.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnimationBlendMode {
+    /// Weighted average with the other override players.
+    ///
+    /// If the weights sum to less than 1, the rest pose fills in the remainder.
+    #[default]
+    Override,
+    /// Adds the difference from the clip's first keyframe on top of the override result.
+    ///
+    /// e.g. an aim offset layered over locomotion.
+    Additive,
+}
+
+/// Accumulates the weighted samples of every player animating one transform.
+#[derive(Debug, Clone, Default)]
+pub struct TransformBlend {
+    blended: TransformAnimation,
+    translation_weight: f64,
+    rotation_weight: f64,
+    scale_weight: f64,
+    additive: Option<TransformAnimation>,
+}
+
+impl TransformBlend {
+    /// Mixes in an override sample.
+    pub fn add(&mut self, sample: &TransformAnimation, weight: f64) {
+        if weight <= 0.0 {
+            return;
+        }
+
+        // each channel is a running weighted average, so mix them one at a time
+        if let Some(translation) = sample.translation {
+            self.translation_weight += weight;
+            self.mix(
+                TransformAnimation::new_translation(translation),
+                weight / self.translation_weight,
+            );
+        }
+        if let Some(rotation) = sample.rotation {
+            self.rotation_weight += weight;
+            self.mix(
+                TransformAnimation::new_rotation(rotation),
+                weight / self.rotation_weight,
+            );
+        }
+        if let Some(scale) = sample.scale {
+            self.scale_weight += weight;
+            self.mix(
+                TransformAnimation::new_scale(scale),
+                weight / self.scale_weight,
+            );
+        }
+    }
+
+    /// Layers an additive sample, relative to the reference pose of its clip.
+    pub fn add_additive(
+        &mut self,
+        sample: &TransformAnimation,
+        reference: &TransformAnimation,
+        weight: f64,
+    ) {
+        if weight <= 0.0 {
+            return;
+        }
+
+        let delta = TransformAnimation {
+            translation: sample
+                .translation
+                .zip(reference.translation)
+                .map(|(sample, reference)| sample - reference),
+            rotation: sample
+                .rotation
+                .zip(reference.rotation)
+                .map(|(sample, reference)| reference.inverse() * sample),
+            scale: sample
+                .scale
+                .zip(reference.scale)
+                .map(|(sample, reference)| sample / reference),
+        };
+        let delta = TransformAnimation::interpolate_linear(&identity_delta(&delta), &delta, weight);
+
+        let additive = self
+            .additive
+            .get_or_insert_with(TransformAnimation::default);
+        if let Some(translation) = delta.translation {
+            *additive.translation.get_or_insert(Vec3::ZERO) += translation;
+        }
+        if let Some(rotation) = delta.rotation {
+            let accumulated = additive.rotation.get_or_insert(Quat::IDENTITY);
+            *accumulated = (*accumulated * rotation).normalize();
+        }
+        if let Some(scale) = delta.scale {
+            *additive.scale.get_or_insert(Vec3::ONE) *= scale;
+        }
+    }
+
+    /// Applies the blend onto a transform.
+    ///
+    /// Channels no player touches are left as they are. The rest pose is the base for the
+    /// channels that are touched, so the result doesn't depend on the previous frame.
+    pub fn apply(&self, rest: &Transform, mut input: Transform) -> Transform {
+        let rest_animation = TransformAnimation {
+            translation: Some(rest.translation),
+            rotation: Some(rest.rotation),
+            scale: Some(rest.scale),
+        };
+        let base = |weight: f64, blended: TransformAnimation| {
+            TransformAnimation::interpolate_linear(&rest_animation, &blended, weight.min(1.0))
+        };
+
+        let additive = self.additive.clone().unwrap_or_default();
+
+        if self.translation_weight > 0.0 || additive.translation.is_some() {
+            let base = base(
+                self.translation_weight,
+                TransformAnimation {
+                    translation: self.blended.translation,
+                    ..Default::default()
+                },
+            );
+            input.translation = base.translation.unwrap_or(rest.translation)
+                + additive.translation.unwrap_or(Vec3::ZERO);
+        }
+        if self.rotation_weight > 0.0 || additive.rotation.is_some() {
+            let base = base(
+                self.rotation_weight,
+                TransformAnimation {
+                    rotation: self.blended.rotation,
+                    ..Default::default()
+                },
+            );
+            input.rotation = (base.rotation.unwrap_or(rest.rotation)
+                * additive.rotation.unwrap_or(Quat::IDENTITY))
+            .normalize();
+        }
+        if self.scale_weight > 0.0 || additive.scale.is_some() {
+            let base = base(
+                self.scale_weight,
+                TransformAnimation {
+                    scale: self.blended.scale,
+                    ..Default::default()
+                },
+            );
+            input.scale = base.scale.unwrap_or(rest.scale) * additive.scale.unwrap_or(Vec3::ONE);
+        }
+
+        input
+    }
+
+    fn mix(&mut self, channel: TransformAnimation, t: f64) {
+        self.blended = TransformAnimation::interpolate_linear(&self.blended, &channel, t);
+    }
+}
+
+// the delta that changes nothing, with the same channels as `delta`
+fn identity_delta(delta: &TransformAnimation) -> TransformAnimation {
+    TransformAnimation {
+        translation: delta.translation.map(|_| Vec3::ZERO),
+        rotation: delta.rotation.map(|_| Quat::IDENTITY),
+        scale: delta.scale.map(|_| Vec3::ONE),
+    }
+}
+
+/// Accumulates the weighted samples of every player animating one set of morph weights.
+#[derive(Debug, Clone, Default)]
+pub struct VertexBlend {
+    blended: Option<VertexAnimation>,
+    weight: f64,
+    additive: Option<Vec<f32>>,
+}
+
+impl VertexBlend {
+    /// Mixes in an override sample.
+    pub fn add(&mut self, sample: &VertexAnimation, weight: f64) {
+        if weight <= 0.0 {
+            return;
+        }
+
+        self.weight += weight;
+        self.blended = Some(match &self.blended {
+            None => sample.clone(),
+            Some(blended) => {
+                VertexAnimation::interpolate_linear(blended, sample, weight / self.weight)
+            }
+        });
+    }
+
+    /// Layers an additive sample, relative to the reference weights of its clip.
+    pub fn add_additive(
+        &mut self,
+        sample: &VertexAnimation,
+        reference: &VertexAnimation,
+        weight: f64,
+    ) {
+        if weight <= 0.0 {
+            return;
+        }
+
+        let additive = self
+            .additive
+            .get_or_insert_with(|| vec![0.0; sample.weights.len()]);
+        for ((total, sample), reference) in additive
+            .iter_mut()
+            .zip(sample.weights.iter())
+            .zip(reference.weights.iter())
+        {
+            *total += interpolate_linear_f32(0.0, sample - reference, weight);
+        }
+    }
+
+    /// Writes the blended weights, starting from the rest weights.
+    pub fn apply(&self, rest: &[f32], target: &mut [f32]) {
+        let rest = VertexAnimation::new(rest.to_vec());
+        let base = match &self.blended {
+            Some(blended) if blended.weights.len() == rest.weights.len() => {
+                VertexAnimation::interpolate_linear(&rest, blended, self.weight.min(1.0))
+            }
+            _ => rest,
+        };
+
+        base.apply_mut(target);
+
+        if let Some(additive) = &self.additive {
+            for (target, additive) in target.iter_mut().zip(additive.iter()) {
+                *target += additive;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
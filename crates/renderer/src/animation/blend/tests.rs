@@ -0,0 +1,132 @@
+use glam::{Quat, Vec3};
+
+use crate::animation::{
+    AnimationClip, AnimationData, AnimationPlayer, AnimationSampler, AnimationState,
+    TransformAnimation, TransformBlend, VertexAnimation, VertexBlend,
+};
+use crate::transforms::Transform;
+
+fn rest() -> Transform {
+    Transform::IDENTITY.with_translation(Vec3::new(0.0, 10.0, 0.0))
+}
+
+fn player() -> AnimationPlayer {
+    AnimationPlayer::new(AnimationClip::new(
+        None,
+        1.0,
+        AnimationSampler::new_linear(
+            vec![0.0, 1.0],
+            vec![AnimationData::F32(0.0), AnimationData::F32(1.0)],
+        ),
+    ))
+}
+
+#[test]
+fn override_samples_are_weighted() {
+    let mut blend = TransformBlend::default();
+    blend.add(
+        &TransformAnimation::new_translation(Vec3::new(4.0, 0.0, 0.0)),
+        0.25,
+    );
+    blend.add(
+        &TransformAnimation::new_translation(Vec3::new(0.0, 0.0, 4.0)),
+        0.75,
+    );
+    // a different channel doesn't affect the translation average
+    blend.add(
+        &TransformAnimation::new_rotation(Quat::from_rotation_y(1.0)),
+        1.0,
+    );
+
+    let result = blend.apply(&rest(), rest());
+
+    assert!(result
+        .translation
+        .abs_diff_eq(Vec3::new(1.0, 0.0, 3.0), 1e-5));
+    assert!(result
+        .rotation
+        .abs_diff_eq(Quat::from_rotation_y(1.0), 1e-5));
+    assert_eq!(result.scale, Vec3::ONE);
+}
+
+#[test]
+fn partial_weight_fills_in_with_the_rest_pose() {
+    let mut blend = TransformBlend::default();
+    blend.add(&TransformAnimation::new_translation(Vec3::ZERO), 0.5);
+
+    // the input is ignored for touched channels, so the previous frame doesn't feed back
+    let input = Transform::IDENTITY.with_translation(Vec3::splat(100.0));
+    let result = blend.apply(&rest(), input);
+
+    assert!(result
+        .translation
+        .abs_diff_eq(Vec3::new(0.0, 5.0, 0.0), 1e-5));
+}
+
+#[test]
+fn additive_layers_on_top() {
+    let mut blend = TransformBlend::default();
+    blend.add(
+        &TransformAnimation::new_rotation(Quat::from_rotation_y(1.0)),
+        1.0,
+    );
+    // the aim offset clip starts at 0.2 and is currently at 0.6, so it adds 0.4 at full weight
+    blend.add_additive(
+        &TransformAnimation::new_rotation(Quat::from_rotation_x(0.6)),
+        &TransformAnimation::new_rotation(Quat::from_rotation_x(0.2)),
+        0.5,
+    );
+
+    let result = blend.apply(&rest(), rest());
+
+    let expected = Quat::from_rotation_y(1.0) * Quat::from_rotation_x(0.2);
+    assert!(result.rotation.abs_diff_eq(expected, 1e-5));
+    assert_eq!(result.translation, rest().translation);
+}
+
+#[test]
+fn morph_weights_blend_and_add() {
+    let mut blend = VertexBlend::default();
+    blend.add(&VertexAnimation::new(vec![1.0, 0.0]), 0.5);
+    blend.add_additive(
+        &VertexAnimation::new(vec![0.0, 0.5]),
+        &VertexAnimation::new(vec![0.0, 0.25]),
+        1.0,
+    );
+
+    let mut target = vec![9.0, 9.0];
+    blend.apply(&[0.0, 0.0], &mut target);
+
+    assert_eq!(target, vec![0.5, 0.25]);
+}
+
+#[test]
+fn fade_out_stops_and_restores_weight() {
+    let mut player = player();
+    player.weight = 0.8;
+
+    player.fade_out(100.0);
+    player.update(50.0);
+    assert!((player.weight - 0.4).abs() < 1e-9);
+    assert_eq!(*player.state(), AnimationState::Playing);
+
+    player.update(50.0);
+    assert_eq!(*player.state(), AnimationState::Stopped);
+    assert!(!player.is_fading());
+    assert_eq!(player.weight, 0.8);
+}
+
+#[test]
+fn interrupted_fade_keeps_its_target_weight() {
+    let mut player = player();
+    player.weight = 0.0;
+    player.fade_to(1.0, 100.0);
+    player.update(25.0);
+
+    // fading out halfway through a fade in
+    player.fade_out(100.0);
+    player.update(100.0);
+
+    assert_eq!(*player.state(), AnimationState::Stopped);
+    assert_eq!(player.weight, 1.0);
+}
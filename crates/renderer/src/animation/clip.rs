@@ -8,6 +8,25 @@ pub struct AnimationClip<T = AnimationData> {
     pub name: Option<String>,
     pub duration: f64,
     pub sampler: AnimationSampler<T>,
+    // sorted by time
+    events: Vec<AnimationEvent>,
+}
+
+/// A named marker at a clip-local time, e.g. a footstep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationEvent {
+    pub name: String,
+    pub time: f64,
+}
+
+impl AnimationEvent {
+    /// Creates a new animation event.
+    pub fn new(name: impl Into<String>, time: f64) -> Self {
+        Self {
+            name: name.into(),
+            time,
+        }
+    }
 }
 
 impl<T> AnimationClip<T> {
@@ -17,6 +36,53 @@ impl<T> AnimationClip<T> {
             name,
             duration,
             sampler,
+            events: Vec::new(),
         }
     }
+
+    /// Adds an event, returning the clip for chaining.
+    pub fn with_event(mut self, event: AnimationEvent) -> Self {
+        self.add_event(event);
+        self
+    }
+
+    /// Adds an event.
+    pub fn add_event(&mut self, event: AnimationEvent) {
+        let index = self
+            .events
+            .partition_point(|other| other.time <= event.time);
+        self.events.insert(index, event);
+    }
+
+    /// Removes all events with the given name.
+    pub fn remove_events(&mut self, name: &str) {
+        self.events.retain(|event| event.name != name);
+    }
+
+    /// Returns the events, sorted by time.
+    pub fn events(&self) -> &[AnimationEvent] {
+        &self.events
+    }
+
+    /// Returns the events crossed moving from `from` to `to` (either direction), in the order
+    /// they're crossed.
+    ///
+    /// The end time is included, the start time only with `include_from`.
+    pub fn events_between(&self, from: f64, to: f64, include_from: bool) -> Vec<&AnimationEvent> {
+        let (low, high) = if from <= to { (from, to) } else { (to, from) };
+
+        let mut events: Vec<&AnimationEvent> = self
+            .events
+            .iter()
+            .filter(|event| {
+                event.time >= low && event.time <= high && (include_from || event.time != from)
+            })
+            .collect();
+
+        if from > to {
+            events.reverse();
+        }
+
+        events
+    }
 }
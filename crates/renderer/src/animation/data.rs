@@ -152,7 +152,7 @@ pub trait Animatable: Clone {
 }
 
 /// Translation/rotation/scale animation data.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct TransformAnimation {
     pub translation: Option<Vec3>,
     pub rotation: Option<Quat>,
@@ -213,22 +213,20 @@ impl TransformAnimation {
 }
 
 impl Animatable for TransformAnimation {
+    // a channel only one side has is kept as-is, so partial animations can be mixed per channel
     fn interpolate_linear(first: &Self, second: &Self, t: f64) -> Self {
         let translation = match (first.translation, second.translation) {
             (Some(first), Some(second)) => Some(interpolate_linear_vec3(first, second, t)),
-            (Some(first), _) => Some(first),
-            _ => None,
+            (first, second) => first.or(second),
         };
 
         let rotation = match (first.rotation, second.rotation) {
             (Some(first), Some(second)) => Some(interpolate_linear_quat(first, second, t)),
-            (Some(first), _) => Some(first),
-            _ => None,
+            (first, second) => first.or(second),
         };
         let scale = match (first.scale, second.scale) {
             (Some(first), Some(second)) => Some(interpolate_linear_vec3(first, second, t)),
-            (Some(first), _) => Some(first),
-            _ => None,
+            (first, second) => first.or(second),
         };
 
         Self {
@@ -5,7 +5,7 @@ use thiserror::Error;
 
 use crate::{meshes::error::AwsmMeshError, transforms::AwsmTransformError};
 
-use super::AnimationKey;
+use super::{AnimationGroupKey, AnimationKey};
 
 /// Animation result type.
 pub type Result<T> = std::result::Result<T, AwsmAnimationError>;
@@ -27,4 +27,13 @@ pub enum AwsmAnimationError {
 
     #[error("[animation] missing animation key {0:?}")]
     MissingKey(AnimationKey),
+
+    #[error("[animation] missing animation group key {0:?}")]
+    MissingGroupKey(AnimationGroupKey),
+
+    #[error("[animation] animation group {0:?} has no players")]
+    EmptyGroup(AnimationGroupKey),
+
+    #[error("[animation] no animation group named {0}")]
+    MissingGroupName(String),
 }
@@ -1,4 +1,5 @@
 mod animations;
+mod blend;
 mod clip;
 mod data;
 mod error;
@@ -6,9 +7,13 @@ mod interpolate;
 mod player;
 mod sampler;
 
-pub use animations::{AnimationKey, AnimationMorphKey, Animations};
-pub use clip::AnimationClip;
+pub use animations::{
+    AnimationGroup, AnimationGroupKey, AnimationKey, AnimationMorphKey, Animations,
+    FiredAnimationEvent,
+};
+pub use blend::{AnimationBlendMode, TransformBlend, VertexBlend};
+pub use clip::{AnimationClip, AnimationEvent};
 pub use data::{Animatable, AnimationData, TransformAnimation, VertexAnimation};
 pub use error::AwsmAnimationError;
-pub use player::{AnimationPlayer, AnimationState};
+pub use player::{AnimationLoopStyle, AnimationPlayDirection, AnimationPlayer, AnimationState};
 pub use sampler::AnimationSampler;
@@ -1,6 +1,10 @@
 //! Animation playback state and controls.
 
-use super::{clip::AnimationClip, data::AnimationData};
+use super::{
+    blend::AnimationBlendMode,
+    clip::{AnimationClip, AnimationEvent},
+    data::AnimationData,
+};
 
 /// Animation player for a clip.
 #[derive(Debug, Clone)]
@@ -9,17 +13,39 @@ pub struct AnimationPlayer<T = AnimationData> {
     pub loop_style: Option<AnimationLoopStyle>,
     // will change with ping-pong as each end is hit
     pub play_direction: AnimationPlayDirection,
+    // influence when blending with other players on the same target
+    pub weight: f64,
+    pub blend_mode: AnimationBlendMode,
     clip: AnimationClip<T>,
     state: AnimationState,
     local_time: f64,
+    // events exactly at the current time fire on the next update (e.g. at 0 when starting out)
+    events_include_current: bool,
+    // events crossed during the latest update
+    fired_events: Vec<AnimationEvent>,
+    fade: Option<AnimationFade>,
+}
+
+// a weight change over time, in global time units (the same as `update`)
+#[derive(Debug, Clone)]
+struct AnimationFade {
+    from: f64,
+    to: f64,
+    duration: f64,
+    elapsed: f64,
+    // stop once faded, and put the weight back to this
+    stop_and_restore: Option<f64>,
 }
 
 /// Playback state for an animation player.
+///
+/// Paused and ended players keep applying their current pose, stopped players aren't applied.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AnimationState {
     Playing,
     Paused,
     Ended,
+    Stopped,
 }
 
 /// Looping behavior for animation playback.
@@ -43,61 +69,235 @@ impl<T> AnimationPlayer<T> {
             speed: 1.0 / 1000.0,
             loop_style: Some(AnimationLoopStyle::Loop),
             play_direction: AnimationPlayDirection::Forward,
+            weight: 1.0,
+            blend_mode: AnimationBlendMode::Override,
             clip,
             state: AnimationState::Playing,
             local_time: 0.0,
+            events_include_current: true,
+            fired_events: Vec::new(),
+            fade: None,
+        }
+    }
+
+    /// Returns the clip this player samples.
+    pub fn clip(&self) -> &AnimationClip<T> {
+        &self.clip
+    }
+
+    /// Returns the clip for editing, e.g. to add events.
+    pub fn clip_mut(&mut self) -> &mut AnimationClip<T> {
+        &mut self.clip
+    }
+
+    /// Returns the playback state.
+    pub fn state(&self) -> &AnimationState {
+        &self.state
+    }
+
+    /// Returns the current time within the clip.
+    pub fn local_time(&self) -> f64 {
+        self.local_time
+    }
+
+    /// Seeks to a time within the clip.
+    pub fn set_local_time(&mut self, local_time: f64) {
+        self.local_time = local_time.clamp(0.0, self.clip.duration);
+        self.events_include_current = true;
+    }
+
+    /// Starts or resumes playback, restarting if the animation ended or was stopped.
+    pub fn play(&mut self) {
+        if matches!(self.state, AnimationState::Ended | AnimationState::Stopped) {
+            self.rewind();
+        }
+        self.state = AnimationState::Playing;
+    }
+
+    /// Pauses playback, holding the current pose.
+    pub fn pause(&mut self) {
+        if self.state == AnimationState::Playing {
+            self.state = AnimationState::Paused;
+        }
+    }
+
+    /// Stops playback and rewinds, the player is no longer applied until played again.
+    pub fn stop(&mut self) {
+        self.rewind();
+        self.state = AnimationState::Stopped;
+        self.fade = None;
+    }
+
+    /// Moves the weight to `weight` over `duration`, in the same time units as `update`.
+    pub fn fade_to(&mut self, weight: f64, duration: f64) {
+        self.start_fade(weight, duration, false);
+    }
+
+    /// Fades the weight out over `duration` and then stops.
+    ///
+    /// The weight is restored once stopped, so playing again doesn't start out invisible.
+    pub fn fade_out(&mut self, duration: f64) {
+        if self.state == AnimationState::Stopped {
+            return;
+        }
+        self.start_fade(0.0, duration, true);
+    }
+
+    /// Returns true while a fade is in progress.
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+
+    fn start_fade(&mut self, to: f64, duration: f64, stop_when_done: bool) {
+        // interrupting a fade, the weight it was heading for (or restoring) is the one to keep
+        let settled_weight = match &self.fade {
+            Some(fade) => fade.stop_and_restore.unwrap_or(fade.to),
+            None => self.weight,
+        };
+
+        self.fade = Some(AnimationFade {
+            from: self.weight,
+            to,
+            duration,
+            elapsed: 0.0,
+            stop_and_restore: stop_when_done.then_some(settled_weight),
+        });
+        self.update_fade(0.0);
+    }
+
+    fn update_fade(&mut self, global_time_delta: f64) {
+        let Some(fade) = &mut self.fade else {
+            return;
+        };
+
+        fade.elapsed += global_time_delta;
+        let t = if fade.duration > 0.0 {
+            (fade.elapsed / fade.duration).min(1.0)
+        } else {
+            1.0
+        };
+        self.weight = fade.from + (fade.to - fade.from) * t;
+
+        if t >= 1.0 {
+            let fade = self.fade.take().unwrap();
+            if let Some(weight) = fade.stop_and_restore {
+                self.stop();
+                self.weight = weight;
+            }
         }
     }
 
+    fn rewind(&mut self) {
+        self.local_time = match self.play_direction {
+            AnimationPlayDirection::Forward => 0.0,
+            AnimationPlayDirection::Backward => self.clip.duration,
+        };
+        self.events_include_current = true;
+    }
+
     /// Advances the animation by the given global time delta.
+    ///
+    /// Clip events crossed along the way are collected in the order they were crossed,
+    /// following loops, ping-pong bounces and backward playback, so e.g. a looping clip
+    /// fires an event once per time around. A delta spanning several cycles only fires one
+    /// cycle's worth of events plus the remainder's, so a long pause doesn't flood them.
+    ///
+    /// Read them with [`Self::take_fired_events`], or for players in
+    /// [`Animations`](super::Animations), with
+    /// [`Animations::take_events`](super::Animations::take_events).
     pub fn update(&mut self, global_time_delta: f64) {
+        self.fired_events.clear();
+
+        // fades carry on while paused, so a paused layer can still be faded out
+        if self.state != AnimationState::Stopped {
+            self.update_fade(global_time_delta);
+        }
+
         if self.state != AnimationState::Playing {
             return;
         }
 
-        let local_time_delta = global_time_delta * self.speed;
-
-        match self.play_direction {
-            AnimationPlayDirection::Forward => {
-                self.local_time += local_time_delta;
-                if self.local_time >= self.clip.duration {
-                    match self.loop_style {
-                        Some(AnimationLoopStyle::Loop) => {
-                            self.local_time = self.local_time.rem_euclid(self.clip.duration);
-                        }
-                        Some(AnimationLoopStyle::PingPong) => {
-                            self.play_direction = AnimationPlayDirection::Backward;
-                            self.local_time = self.clip.duration;
-                        }
-                        None => {
-                            self.local_time = self.clip.duration;
-                            self.state = AnimationState::Ended;
-                        }
-                    }
-                }
+        let duration = self.clip.duration;
+        let mut remaining = global_time_delta * self.speed;
+
+        // a whole cycle ends where it started, so all but one can be skipped, rather than
+        // walking (and firing events for) every one of them after e.g. a backgrounded tab
+        let cycle = match self.loop_style {
+            Some(AnimationLoopStyle::Loop) => duration,
+            Some(AnimationLoopStyle::PingPong) => duration * 2.0,
+            None => 0.0,
+        };
+        if cycle > 0.0 && remaining > cycle {
+            remaining = cycle + remaining.rem_euclid(cycle);
+        }
+
+        // each time an end is hit (looping, bouncing or stopping) a new stretch starts
+        loop {
+            let (target, end) = match self.play_direction {
+                AnimationPlayDirection::Forward => (self.local_time + remaining, duration),
+                AnimationPlayDirection::Backward => (self.local_time - remaining, 0.0),
+            };
+
+            let reached_end = match self.play_direction {
+                AnimationPlayDirection::Forward => target >= duration,
+                AnimationPlayDirection::Backward => target <= 0.0,
+            };
+
+            if !reached_end {
+                self.fired_events.extend(
+                    self.clip
+                        .events_between(self.local_time, target, self.events_include_current)
+                        .into_iter()
+                        .cloned(),
+                );
+                self.local_time = target;
+                self.events_include_current = false;
+                break;
             }
 
-            AnimationPlayDirection::Backward => {
-                self.local_time -= local_time_delta;
-                if self.local_time <= 0.0 {
-                    match self.loop_style {
-                        Some(AnimationLoopStyle::Loop) => {
-                            self.local_time =
-                                self.clip.duration - self.local_time.rem_euclid(self.clip.duration);
-                        }
-                        Some(AnimationLoopStyle::PingPong) => {
-                            self.play_direction = AnimationPlayDirection::Forward;
-                            self.local_time = 0.0;
-                        }
-                        None => {
-                            self.local_time = 0.0;
-                            self.state = AnimationState::Ended;
-                        }
-                    }
+            self.fired_events.extend(
+                self.clip
+                    .events_between(self.local_time, end, self.events_include_current)
+                    .into_iter()
+                    .cloned(),
+            );
+            remaining -= (end - self.local_time).abs();
+
+            match self.loop_style {
+                Some(AnimationLoopStyle::Loop) => {
+                    // jump to the other end, events there haven't fired yet this time around
+                    self.local_time = duration - end;
+                    self.events_include_current = true;
+                }
+                Some(AnimationLoopStyle::PingPong) => {
+                    // the end's events already fired on the way in
+                    self.play_direction = match self.play_direction {
+                        AnimationPlayDirection::Forward => AnimationPlayDirection::Backward,
+                        AnimationPlayDirection::Backward => AnimationPlayDirection::Forward,
+                    };
+                    self.local_time = end;
+                    self.events_include_current = false;
+                }
+                None => {
+                    self.local_time = end;
+                    self.events_include_current = false;
+                    self.state = AnimationState::Ended;
+                    break;
                 }
             }
+
+            // nothing left to move, or nowhere to move to
+            if remaining <= 0.0 || duration <= 0.0 {
+                break;
+            }
         }
     }
+
+    /// Takes the clip events crossed during the latest [`Self::update`], in the order they
+    /// were crossed. Each update replaces them, so events not taken are dropped.
+    pub fn take_fired_events(&mut self) -> Vec<AnimationEvent> {
+        std::mem::take(&mut self.fired_events)
+    }
 }
 
 impl AnimationPlayer<AnimationData> {
@@ -105,4 +305,13 @@ impl AnimationPlayer<AnimationData> {
     pub fn sample(&self) -> AnimationData {
         self.clip.sampler.sample(self.local_time)
     }
+
+    /// Samples the first keyframe, which additive players are relative to.
+    pub fn sample_reference(&self) -> AnimationData {
+        let start = self.clip.sampler.times().first().copied().unwrap_or(0.0);
+        self.clip.sampler.sample(start)
+    }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,142 @@
+use crate::animation::{
+    AnimationClip, AnimationData, AnimationEvent, AnimationLoopStyle, AnimationPlayDirection,
+    AnimationPlayer, AnimationSampler, AnimationState,
+};
+
+// a 1 second clip with a footstep on either foot, played in seconds
+fn player(loop_style: Option<AnimationLoopStyle>) -> AnimationPlayer {
+    let clip = AnimationClip::new(
+        None,
+        1.0,
+        AnimationSampler::new_linear(
+            vec![0.0, 1.0],
+            vec![AnimationData::F32(0.0), AnimationData::F32(1.0)],
+        ),
+    )
+    .with_event(AnimationEvent::new("right", 0.75))
+    .with_event(AnimationEvent::new("start", 0.0))
+    .with_event(AnimationEvent::new("left", 0.25));
+
+    let mut player = AnimationPlayer::new(clip);
+    player.speed = 1.0;
+    player.loop_style = loop_style;
+    player
+}
+
+// the names of the events fired by one update
+fn update(player: &mut AnimationPlayer, global_time_delta: f64) -> Vec<String> {
+    player.update(global_time_delta);
+    player
+        .take_fired_events()
+        .into_iter()
+        .map(|event| event.name)
+        .collect()
+}
+
+#[test]
+fn events_fire_once_without_looping() {
+    let mut player = player(None);
+
+    assert_eq!(update(&mut player, 0.25), ["start", "left"]);
+    // the end of the last update isn't crossed again
+    assert!(update(&mut player, 0.25).is_empty());
+    assert_eq!(update(&mut player, 0.5), ["right"]);
+    assert_eq!(*player.state(), AnimationState::Ended);
+    assert!(update(&mut player, 1.0).is_empty());
+}
+
+#[test]
+fn looping_fires_every_time_around() {
+    let mut player = player(Some(AnimationLoopStyle::Loop));
+
+    assert_eq!(update(&mut player, 0.5), ["start", "left"]);
+    // wraps around, the start fires again
+    assert_eq!(update(&mut player, 0.6), ["right", "start"]);
+    assert!((player.local_time() - 0.1).abs() < 1e-9);
+
+    // a long frame spans several loops, only one of them fires on top of the remainder
+    assert_eq!(update(&mut player, 2.5), ["left", "right", "start", "left"]);
+    assert!((player.local_time() - 0.6).abs() < 1e-9);
+}
+
+#[test]
+fn huge_deltas_skip_whole_loops() {
+    let mut player = player(Some(AnimationLoopStyle::Loop));
+    assert_eq!(update(&mut player, 0.1), ["start"]);
+
+    assert_eq!(
+        update(&mut player, 1_000_000_000.25),
+        ["left", "right", "start", "left"]
+    );
+    assert!((player.local_time() - 0.35).abs() < 1e-6);
+}
+
+#[test]
+fn huge_deltas_skip_whole_ping_pong_cycles() {
+    // a cycle is there and back again
+    let mut player = player(Some(AnimationLoopStyle::PingPong));
+    assert_eq!(
+        update(&mut player, 1_000_000.5),
+        ["start", "left", "right", "right", "left", "start", "left"]
+    );
+    assert!((player.local_time() - 0.5).abs() < 1e-6);
+    assert_eq!(player.play_direction, AnimationPlayDirection::Forward);
+}
+
+#[test]
+fn landing_on_the_wrap_fires_the_start_next_update() {
+    let mut player = player(Some(AnimationLoopStyle::Loop));
+
+    assert_eq!(update(&mut player, 1.0), ["start", "left", "right"]);
+    assert_eq!(player.local_time(), 0.0);
+    assert_eq!(update(&mut player, 0.1), ["start"]);
+}
+
+#[test]
+fn ping_pong_reverses_without_firing_twice() {
+    let mut player = player(Some(AnimationLoopStyle::PingPong));
+
+    assert_eq!(update(&mut player, 0.9), ["start", "left", "right"]);
+    // bounces off the end and plays back through the right footstep
+    assert_eq!(update(&mut player, 0.4), ["right"]);
+    assert_eq!(player.play_direction, AnimationPlayDirection::Backward);
+
+    // back to the start and bouncing forward again, the start only fires on the way in
+    assert_eq!(update(&mut player, 1.0), ["left", "start", "left"]);
+    assert_eq!(player.play_direction, AnimationPlayDirection::Forward);
+}
+
+#[test]
+fn backward_playback_fires_in_reverse() {
+    let mut player = player(Some(AnimationLoopStyle::Loop));
+    player.play_direction = AnimationPlayDirection::Backward;
+    player.set_local_time(1.0);
+
+    assert_eq!(update(&mut player, 0.5), ["right"]);
+    // wraps from the start back to the end
+    assert_eq!(update(&mut player, 0.6), ["left", "start"]);
+    assert!((player.local_time() - 0.9).abs() < 1e-9);
+    assert_eq!(update(&mut player, 0.5), ["right"]);
+}
+
+#[test]
+fn restarting_fires_the_start_again() {
+    let mut player = player(None);
+    player.update(2.0);
+    assert_eq!(*player.state(), AnimationState::Ended);
+
+    player.play();
+    assert_eq!(update(&mut player, 0.1), ["start"]);
+}
+
+#[test]
+fn each_update_only_keeps_its_own_events() {
+    let mut player = player(None);
+    player.update(0.25);
+    player.update(0.25);
+    assert!(player.take_fired_events().is_empty());
+
+    player.update(0.5);
+    assert_eq!(player.take_fired_events().len(), 1);
+    assert!(player.take_fired_events().is_empty());
+}
@@ -12,6 +12,12 @@ pub struct AntiAliasing {
     pub msaa_sample_count: Option<u32>,
     pub smaa: bool,
     pub mipmap: bool,
+    /// Temporal anti-aliasing: jitters the camera every frame and resolves against a reprojected
+    /// history, using motion vectors written by the geometry pass.
+    ///
+    /// Runs before the post-processing effects (SMAA, bloom, depth of field).
+    /// Call [AwsmRenderer::reset_taa_history] on camera cuts.
+    pub taa: bool,
 }
 
 impl AntiAliasing {
@@ -33,6 +39,7 @@ impl Default for AntiAliasing {
             //msaa_sample_count: None,
             smaa: false,
             mipmap: true,
+            taa: false,
         }
     }
 }
@@ -53,6 +60,12 @@ impl AwsmRenderer {
         // so we must recreate them when anti-aliasing changes.
         //
         // DISPLAY: Pipelines depend on anti-aliasing settings, so we must recreate them when anti-aliasing changes.
+        //
+        // SSAO: Pipelines depend on the MSAA setting, and only exist while SSAO is enabled.
+        //
+        // TAA: Pipelines depend on the MSAA setting, and only exist while TAA is enabled.
+        // Whatever history there was was rendered with different settings.
+        self.reset_taa_history();
         let mut has_seen_buffer_info = SecondaryMap::new();
         let mut has_seen_material = SecondaryMap::new();
         for (key, mesh) in self.meshes.iter() {
@@ -81,6 +94,31 @@ impl AwsmRenderer {
             }
         }
 
+        self.render_passes
+            .ssao
+            .pipelines
+            .set_render_pipeline_keys(
+                &self.anti_aliasing,
+                &self.post_processing,
+                &self.gpu,
+                &mut self.shaders,
+                &mut self.pipelines,
+                &self.pipeline_layouts,
+            )
+            .await?;
+
+        self.render_passes
+            .taa
+            .pipelines
+            .set_render_pipeline_keys(
+                &self.anti_aliasing,
+                &self.gpu,
+                &mut self.shaders,
+                &mut self.pipelines,
+                &self.pipeline_layouts,
+            )
+            .await?;
+
         self.render_passes
             .effects
             .pipelines
@@ -94,6 +132,27 @@ impl AwsmRenderer {
                 &self.render_textures.formats,
             )
             .await?;
+
+        // VIEWS: Each extra view has its own SSAO, effects and display pipelines.
+        let view_keys: Vec<_> = self.views.keys().collect();
+        for key in view_keys {
+            let post_processing = self.views.post_processing(key)?.clone();
+            self.set_render_view_post_processing(key, post_processing)
+                .await?;
+        }
         Ok(())
     }
+
+    /// Drops the TAA history, e.g. on a camera cut.
+    ///
+    /// Without this, the first frames after a cut blend in the previous shot.
+    /// Applies to every view.
+    pub fn reset_taa_history(&mut self) {
+        self.render_textures.reset_taa_history();
+        self.camera.reset_history();
+        for view in self.views.values_mut() {
+            view.render_textures.reset_taa_history();
+            view.camera.reset_history();
+        }
+    }
 }
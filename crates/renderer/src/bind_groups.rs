@@ -44,6 +44,8 @@ pub enum BindGroupCreate {
     CameraInitOnly,
     LightsResize,
     LightsInfoCreate,
+    LightTilesResize,
+    ShadowMapsResize,
     BrdfLutTextures,
     IblTextures,
     EnvironmentSkyboxCreate,
@@ -61,9 +63,12 @@ pub enum BindGroupCreate {
     MeshAttributeIndexResize,
     MaterialResize,
     TextureViewRecreate,
+    // Switching to another view's textures, picking stays bound to the main view
+    RenderViewChange,
     TexturePool,
     TextureTransformsResize,
     AntiAliasingChange,
+    PickerChange,
 }
 
 /// Tracks pending bind group recreations.
@@ -116,6 +121,9 @@ impl BindGroups {
             TransparentLights,
             TransparentTextures,
             LightCulling,
+            Shadow,
+            Ssao,
+            Taa,
             Effects,
             Display,
             Picker,
@@ -128,14 +136,27 @@ impl BindGroups {
                 BindGroupCreate::CameraInitOnly => {
                     functions_to_call.insert(FunctionToCall::GeometryCamera);
                     functions_to_call.insert(FunctionToCall::TransparentMain);
+                    functions_to_call.insert(FunctionToCall::LightCulling);
                 }
                 BindGroupCreate::LightsInfoCreate => {
                     functions_to_call.insert(FunctionToCall::OpaqueLights);
                     functions_to_call.insert(FunctionToCall::TransparentLights);
+                    functions_to_call.insert(FunctionToCall::LightCulling);
                 }
                 BindGroupCreate::LightsResize => {
                     functions_to_call.insert(FunctionToCall::OpaqueLights);
                     functions_to_call.insert(FunctionToCall::TransparentLights);
+                    functions_to_call.insert(FunctionToCall::LightCulling);
+                }
+                BindGroupCreate::LightTilesResize => {
+                    functions_to_call.insert(FunctionToCall::OpaqueLights);
+                    functions_to_call.insert(FunctionToCall::TransparentLights);
+                    functions_to_call.insert(FunctionToCall::LightCulling);
+                }
+                BindGroupCreate::ShadowMapsResize => {
+                    functions_to_call.insert(FunctionToCall::OpaqueLights);
+                    functions_to_call.insert(FunctionToCall::TransparentLights);
+                    functions_to_call.insert(FunctionToCall::Shadow);
                 }
                 BindGroupCreate::TransformsResize => {
                     functions_to_call.insert(FunctionToCall::GeometryTransformMaterials);
@@ -161,11 +182,22 @@ impl BindGroups {
                 BindGroupCreate::TextureViewRecreate => {
                     functions_to_call.insert(FunctionToCall::LightCulling);
                     functions_to_call.insert(FunctionToCall::Display);
+                    functions_to_call.insert(FunctionToCall::Ssao);
+                    functions_to_call.insert(FunctionToCall::Taa);
                     functions_to_call.insert(FunctionToCall::Effects);
                     functions_to_call.insert(FunctionToCall::OpaqueMain);
                     functions_to_call.insert(FunctionToCall::TransparentMain);
                     functions_to_call.insert(FunctionToCall::Picker);
                 }
+                BindGroupCreate::RenderViewChange => {
+                    functions_to_call.insert(FunctionToCall::LightCulling);
+                    functions_to_call.insert(FunctionToCall::Display);
+                    functions_to_call.insert(FunctionToCall::Ssao);
+                    functions_to_call.insert(FunctionToCall::Taa);
+                    functions_to_call.insert(FunctionToCall::Effects);
+                    functions_to_call.insert(FunctionToCall::OpaqueMain);
+                    functions_to_call.insert(FunctionToCall::TransparentMain);
+                }
                 BindGroupCreate::TexturePool => {
                     functions_to_call.insert(FunctionToCall::OpaqueTextures);
                     functions_to_call.insert(FunctionToCall::TransparentTextures);
@@ -215,6 +247,9 @@ impl BindGroups {
                     functions_to_call.insert(FunctionToCall::OpaqueMain);
                     functions_to_call.insert(FunctionToCall::TransparentMain);
                 }
+                BindGroupCreate::PickerChange => {
+                    functions_to_call.insert(FunctionToCall::Picker);
+                }
             }
         }
 
@@ -285,6 +320,15 @@ impl BindGroups {
                 FunctionToCall::LightCulling => {
                     render_passes.light_culling.bind_groups.recreate(&ctx)?;
                 }
+                FunctionToCall::Shadow => {
+                    render_passes.shadow.bind_groups.recreate(&ctx)?;
+                }
+                FunctionToCall::Ssao => {
+                    render_passes.ssao.bind_groups.recreate(&ctx)?;
+                }
+                FunctionToCall::Taa => {
+                    render_passes.taa.bind_groups.recreate(&ctx)?;
+                }
                 FunctionToCall::Effects => {
                     render_passes.effects.bind_groups.recreate(&ctx)?;
                 }
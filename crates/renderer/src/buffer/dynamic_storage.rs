@@ -1,5 +1,7 @@
 //! Dynamic storage buffer utilities.
 
+use std::ops::Range;
+
 use slotmap::{Key, SecondaryMap};
 
 /// Dynamic buffer for variable-size allocations using buddy memory allocation.
@@ -119,6 +121,29 @@ impl<K: Key, const ZERO: u8> DynamicStorageBuffer<K, ZERO> {
         }
     }
 
+    /// Edits existing data in place, marking only part of the allocation dirty.
+    ///
+    /// The callback gets the whole allocation and returns the byte range (relative to the
+    /// allocation) it changed, if any. Returns false if the key doesn't exist.
+    pub fn update_partial_with(
+        &mut self,
+        key: K,
+        f: impl FnOnce(&mut [u8]) -> Option<Range<usize>>,
+    ) -> bool {
+        let Some((off, size)) = self.slot_indices.get(key).copied() else {
+            return false;
+        };
+
+        if let Some(range) = f(&mut self.raw_data[off..off + size]) {
+            let end = range.end.min(size);
+            if range.start < end {
+                self.mark_dirty_range(off + range.start, end - range.start);
+            }
+        }
+
+        true
+    }
+
     // Use update() instead; this always inserts a new allocation.
     fn insert(&mut self, key: K, bytes: &[u8]) -> usize {
         let req = round_pow2(bytes.len().max(MIN_BLOCK));
@@ -193,6 +218,12 @@ impl<K: Key, const ZERO: u8> DynamicStorageBuffer<K, ZERO> {
         size
     }
 
+    /// Makes the next `take_gpu_needs_resize()` return the current size, so the owner creates
+    /// a new GPU buffer and uploads all of the data, e.g. after the device was lost.
+    pub fn mark_gpu_lost(&mut self) {
+        self.gpu_buffer_needs_resize = true;
+    }
+
     fn mark_dirty_range(&mut self, offset: usize, size: usize) {
         if size == 0 || self.raw_data.is_empty() || offset >= self.raw_data.len() {
             return;
@@ -656,6 +687,26 @@ mod test {
         assert_eq!(buffer.take_gpu_needs_resize(), None);
     }
 
+    #[test]
+    fn test_gpu_lost_reports_full_size() {
+        let mut buffer = create_test_buffer();
+        let (_, key1, _, _) = create_keys();
+
+        buffer.update(key1, b"small");
+        let _ = buffer.take_gpu_needs_resize();
+        assert_eq!(buffer.take_gpu_needs_resize(), None);
+
+        buffer.mark_gpu_lost();
+        assert_eq!(
+            buffer.take_gpu_needs_resize(),
+            Some(buffer.raw_slice().len())
+        );
+        assert_eq!(buffer.take_gpu_needs_resize(), None);
+
+        // the data itself is untouched
+        assert!(buffer.offset(key1).is_some());
+    }
+
     #[test]
     fn test_power_of_two_rounding() {
         let mut buffer = create_test_buffer();
@@ -738,6 +789,38 @@ mod test {
         buffer.update_with_unchecked(key1, |_, _| {});
     }
 
+    #[test]
+    fn test_update_partial_with_marks_only_the_changed_range() {
+        let mut buffer = create_test_buffer();
+        let (_, key1, key2, _) = create_keys();
+
+        buffer.update(key1, &[0u8; 100]);
+        let offset = buffer.update(key2, &[0u8; 100]);
+        buffer.clear_dirty_ranges();
+
+        let found = buffer.update_partial_with(key2, |data| {
+            data[10..14].copy_from_slice(b"TEST");
+            Some(9..14)
+        });
+        assert!(found);
+        assert_eq!(&buffer.raw_data[offset + 10..offset + 14], b"TEST");
+        // widened to 4-byte alignment
+        assert_eq!(buffer.take_dirty_ranges(), vec![(offset + 8, 8)]);
+
+        // nothing changed, nothing dirty
+        buffer.update_partial_with(key2, |_| None);
+        assert!(buffer.take_dirty_ranges().is_empty());
+    }
+
+    #[test]
+    fn test_update_partial_with_missing_key() {
+        let mut buffer = create_test_buffer();
+        let (_, key1, _, _) = create_keys();
+
+        assert!(!buffer.update_partial_with(key1, |_| Some(0..4)));
+        assert!(buffer.take_dirty_ranges().is_empty());
+    }
+
     #[test]
     fn test_zero_value_variants() {
         // Test with default zero value (0)
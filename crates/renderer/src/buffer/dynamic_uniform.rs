@@ -191,6 +191,12 @@ impl<K: Key, const ZERO_VALUE: u8> DynamicUniformBuffer<K, ZERO_VALUE> {
         size
     }
 
+    /// Makes the next `take_gpu_needs_resize()` return the current size, so the owner creates
+    /// a new GPU buffer and uploads all of the data, e.g. after the device was lost.
+    pub fn mark_gpu_lost(&mut self) {
+        self.gpu_buffer_needs_resize = true;
+    }
+
     /// Removes the slot corresponding to the given key.
     /// The slot is marked as free for reuse.
     /// returns whether or not it was actually removed
@@ -422,6 +428,22 @@ mod test {
         assert_eq!(buffer.take_gpu_needs_resize(), None);
     }
 
+    #[test]
+    fn test_gpu_lost_reports_full_size() {
+        let mut buffer = create_test_buffer();
+        let (_, key1, _, _) = create_keys();
+
+        buffer.update(key1, b"test data 123456");
+        assert_eq!(buffer.take_gpu_needs_resize(), None);
+
+        buffer.mark_gpu_lost();
+        assert_eq!(buffer.take_gpu_needs_resize(), Some(buffer.size()));
+        assert_eq!(buffer.take_gpu_needs_resize(), None);
+
+        // the data itself is untouched
+        assert!(buffer.offset(key1).is_some());
+    }
+
     #[test]
     fn test_update_existing_item() {
         let mut buffer = create_test_buffer();
@@ -119,7 +119,9 @@ pub fn u8_to_u32_iter(v: &[u8]) -> impl Iterator<Item = u32> + '_ {
         .map(Result::unwrap)
         .map(u32::from_le_bytes)
 }
-use awsm_renderer_core::{error::AwsmCoreError, renderer::AwsmRendererWebGpu};
+use awsm_renderer_core::{
+    command::CommandEncoder, error::AwsmCoreError, renderer::AwsmRendererWebGpu,
+};
 
 const DIRTY_RANGE_FULL_WRITE_THRESHOLD_PERCENT: u64 = 60;
 const DIRTY_RANGE_MAX_RANGES: usize = 32;
@@ -141,6 +143,19 @@ pub fn write_buffer_with_dirty_ranges(
     )
 }
 
+/// Records a copy of a whole GPU buffer into another one of the same size.
+///
+/// Used to keep the previous frame's data around, so it has to be recorded after the last read
+/// of `destination` in the frame.
+pub fn copy_whole_buffer(
+    command_encoder: &CommandEncoder,
+    source: &web_sys::GpuBuffer,
+    destination: &web_sys::GpuBuffer,
+) -> Result<(), AwsmCoreError> {
+    let size = source.size().min(destination.size()) as u32;
+    command_encoder.copy_buffer_to_buffer(source, 0, destination, 0, size)
+}
+
 fn write_buffer_with_dirty_ranges_config(
     gpu: &AwsmRendererWebGpu,
     gpu_buffer: &web_sys::GpuBuffer,
@@ -6,23 +6,20 @@ use awsm_renderer_core::renderer::AwsmRendererWebGpu;
 use glam::{Mat4, Vec2, Vec3, Vec4};
 use thiserror::Error;
 
+use crate::anti_alias::AntiAliasing;
 use crate::bind_groups::BindGroups;
-use crate::render_textures::RenderTextures;
 use crate::{AwsmRenderer, AwsmRendererLogging};
 
-const APPLY_JITTER: bool = false;
+/// Number of frames in the TAA jitter sequence before it repeats.
+const TAA_JITTER_SEQUENCE_LEN: u32 = 8;
 
 impl AwsmRenderer {
     /// Updates the camera buffer with new matrices.
     pub fn update_camera(&mut self, camera_matrices: CameraMatrices) -> Result<()> {
         let (current_width, current_height) = self.gpu.current_context_texture_size()?;
 
-        self.camera.update(
-            camera_matrices,
-            &self.render_textures,
-            current_width as f32,
-            current_height as f32,
-        )?;
+        self.camera
+            .update(camera_matrices, current_width as f32, current_height as f32)?;
 
         Ok(())
     }
@@ -35,6 +32,27 @@ pub struct CameraBuffer {
     pub last_matrices: Option<CameraMatrices>,
     camera_moved: bool,
     gpu_dirty: bool,
+    screen_size: (f32, f32),
+    // unjittered view-projection of the last rendered frame, for motion vectors
+    rendered_view_projection: Option<Mat4>,
+}
+
+/// Per-view camera history, swapped into [`CameraBuffer`] while an extra view renders.
+///
+/// All views share one GPU buffer, it's rewritten before each view is submitted.
+#[derive(Clone, Debug, Default)]
+pub struct CameraViewState {
+    last_matrices: Option<CameraMatrices>,
+    camera_moved: bool,
+    screen_size: (f32, f32),
+    rendered_view_projection: Option<Mat4>,
+}
+
+impl CameraViewState {
+    /// Forgets the previous frame's view, like [`CameraBuffer`] does on a camera cut.
+    pub fn reset_history(&mut self) {
+        self.rendered_view_projection = None;
+    }
 }
 
 /// Camera matrices and parameters.
@@ -82,9 +100,11 @@ impl CameraBuffer {
     //  frustum corner rays (4 * vec4) 64 bytes
     //  viewport (vec4) 16 bytes
     //  dof_params (vec4: focus_distance, aperture, unused, unused) 16 bytes
-    // Total = 512 bytes (all members 16-byte aligned, no implicit gaps)
+    //  prev_view_proj      (mat4)  64 bytes
+    //  jitter (vec4: ndc x, ndc y, unused, unused) 16 bytes
+    // Total = 592 bytes (all members 16-byte aligned, no implicit gaps)
     /// Byte size of the camera uniform buffer.
-    pub const BYTE_SIZE: usize = 512;
+    pub const BYTE_SIZE: usize = 592;
 
     /// Creates a camera buffer on the GPU.
     pub fn new(gpu: &AwsmRendererWebGpu) -> Result<Self> {
@@ -102,21 +122,29 @@ impl CameraBuffer {
             gpu_dirty: true,
             last_matrices: None,
             camera_moved: false,
+            screen_size: (0.0, 0.0),
+            rendered_view_projection: None,
             gpu_buffer,
         })
     }
 
+    // after a device loss, a new buffer gets the current data on the next write
+    pub(crate) fn restore_gpu(&mut self, gpu: &AwsmRendererWebGpu) -> Result<()> {
+        self.gpu_buffer = Self::new(gpu)?.gpu_buffer;
+        self.gpu_dirty = true;
+        // the TAA history went with the old device
+        self.rendered_view_projection = None;
+        Ok(())
+    }
+
     // this is fast/cheap to call, so we can call it multiple times a frame
     // it will only update the data in the buffer once per frame, at render time
     pub(crate) fn update(
         &mut self,
-        camera_matrices_orig: CameraMatrices,
-        render_textures: &RenderTextures,
+        camera_matrices: CameraMatrices,
         screen_width: f32,
         screen_height: f32,
     ) -> Result<()> {
-        let mut camera_matrices = camera_matrices_orig.clone();
-
         self.camera_moved = match &self.last_matrices {
             Some(last_matrices) => {
                 fn matrices_equal(a: Mat4, b: Mat4, epsilon: f32) -> bool {
@@ -134,17 +162,47 @@ impl CameraBuffer {
             _ => true, // First frame, assume movement
         };
 
-        if APPLY_JITTER {
-            let jitter_strength = if self.camera_moved { 0.2 } else { 0.8 };
-            // TAA jitter
-            let jitter = get_halton_jitter(render_textures.frame_count());
-            let jitter_ndc_x = (jitter.x / screen_width) * jitter_strength;
-            let jitter_ndc_y = (jitter.y / screen_height) * jitter_strength;
+        // Store unjittered, jitter is applied per frame when writing to the gpu
+        self.last_matrices = Some(camera_matrices);
+        self.screen_size = (screen_width, screen_height);
+        self.gpu_dirty = true;
+
+        Ok(())
+    }
+
+    /// Forgets the previous frame's view, so the next frame has no camera motion.
+    ///
+    /// Used on camera cuts, where reprojecting into the old view would be meaningless.
+    pub(crate) fn reset_history(&mut self) {
+        self.rendered_view_projection = None;
+    }
 
-            // Create jitter translation matrix
-            let jitter_matrix = Mat4::from_translation(Vec3::new(jitter_ndc_x, jitter_ndc_y, 0.0));
+    /// Exchanges the camera history with another view's, see [`CameraViewState`].
+    pub(crate) fn swap_view_state(&mut self, state: &mut CameraViewState) {
+        std::mem::swap(&mut self.last_matrices, &mut state.last_matrices);
+        std::mem::swap(&mut self.camera_moved, &mut state.camera_moved);
+        std::mem::swap(&mut self.screen_size, &mut state.screen_size);
+        std::mem::swap(
+            &mut self.rendered_view_projection,
+            &mut state.rendered_view_projection,
+        );
+        // the buffer holds whichever view was written last
+        self.gpu_dirty = true;
+    }
+
+    fn write_raw(
+        &mut self,
+        camera_matrices_orig: &CameraMatrices,
+        previous_view_projection: Mat4,
+        jitter: Vec2,
+        frame_count: u32,
+    ) {
+        let mut camera_matrices = camera_matrices_orig.clone();
+        let (screen_width, screen_height) = self.screen_size;
 
-            // Apply to your projection matrix
+        if jitter != Vec2::ZERO {
+            // TAA jitter, translating in clip space moves the whole image by a sub-pixel amount
+            let jitter_matrix = Mat4::from_translation(Vec3::new(jitter.x, jitter.y, 0.0));
             camera_matrices.projection = jitter_matrix * camera_matrices.projection;
         }
 
@@ -184,11 +242,7 @@ impl CameraBuffer {
         let position = camera_matrices.position_world.extend(0.0).to_array();
         write_f32_slice(&mut self.raw_data, &mut offset, &position);
         // Write frame_count_and_padding as vec4<u32> (x = frame_count, yzw = padding)
-        write_u32(
-            &mut self.raw_data,
-            &mut offset,
-            render_textures.frame_count(),
-        );
+        write_u32(&mut self.raw_data, &mut offset, frame_count);
         write_u32(&mut self.raw_data, &mut offset, 0);
         write_u32(&mut self.raw_data, &mut offset, 0);
         write_u32(&mut self.raw_data, &mut offset, 0);
@@ -216,14 +270,20 @@ impl CameraBuffer {
             ],
         );
 
-        debug_assert_eq!(offset, Self::BYTE_SIZE, "Buffer layout mismatch!");
-
-        self.gpu_dirty = true;
-
-        // Store for next frame (unjittered versions)
-        self.last_matrices = Some(camera_matrices_orig);
+        // Previous frame, unjittered: reprojecting with it gives motion vectors without jitter noise
+        let previous_view_projection_cols = previous_view_projection.to_cols_array();
+        write_f32_slice(
+            &mut self.raw_data,
+            &mut offset,
+            &previous_view_projection_cols,
+        );
+        write_f32_slice(
+            &mut self.raw_data,
+            &mut offset,
+            &[jitter.x, jitter.y, 0.0, 0.0],
+        );
 
-        Ok(())
+        debug_assert_eq!(offset, Self::BYTE_SIZE, "Buffer layout mismatch!");
     }
 
     /// Returns true if the camera was moved since the last update.
@@ -233,12 +293,40 @@ impl CameraBuffer {
 
     // writes to the GPU
     /// Writes the camera buffer to the GPU when dirty.
+    ///
+    /// Should be called once per frame, since it also tracks the previous frame's view.
     pub fn write_gpu(
         &mut self,
         logging: &AwsmRendererLogging,
         gpu: &AwsmRendererWebGpu,
         _bind_groups: &BindGroups,
+        anti_aliasing: &AntiAliasing,
+        frame_count: u32,
     ) -> Result<()> {
+        if let Some(camera_matrices) = self.last_matrices.clone() {
+            let view_projection = camera_matrices.view_projection();
+            let previous_view_projection = self.rendered_view_projection.unwrap_or(view_projection);
+
+            // with TAA the jitter changes every frame, even if the camera doesn't
+            // and once the camera stops, the previous view has to catch up too
+            if self.gpu_dirty || anti_aliasing.taa || previous_view_projection != view_projection {
+                let jitter = if anti_aliasing.taa {
+                    taa_jitter(frame_count, self.screen_size.0, self.screen_size.1)
+                } else {
+                    Vec2::ZERO
+                };
+                self.write_raw(
+                    &camera_matrices,
+                    previous_view_projection,
+                    jitter,
+                    frame_count,
+                );
+                self.gpu_dirty = true;
+            }
+
+            self.rendered_view_projection = Some(view_projection);
+        }
+
         if self.gpu_dirty {
             let _maybe_span_guard = if logging.render_timings {
                 Some(tracing::span!(tracing::Level::INFO, "Camera GPU write").entered())
@@ -254,6 +342,22 @@ impl CameraBuffer {
         Ok(())
     }
 }
+/// Sub-pixel offset of the projection for a TAA frame, in NDC.
+///
+/// Cycles through a Halton(2, 3) sequence, so each pixel is sampled at a different spot every frame.
+pub(crate) fn taa_jitter(frame_count: u32, screen_width: f32, screen_height: f32) -> Vec2 {
+    if screen_width <= 0.0 || screen_height <= 0.0 {
+        return Vec2::ZERO;
+    }
+    // index 0 is always the pixel center, so start at 1
+    let jitter = get_halton_jitter(frame_count % TAA_JITTER_SEQUENCE_LEN + 1);
+    // NDC spans 2 units across the screen
+    Vec2::new(
+        jitter.x * 2.0 / screen_width,
+        jitter.y * 2.0 / screen_height,
+    )
+}
+
 fn get_halton_jitter(frame_count: u32) -> Vec2 {
     let x = halton(frame_count, 2) - 0.5;
     let y = halton(frame_count, 3) - 0.5;
@@ -335,3 +439,6 @@ pub enum AwsmCameraError {
     #[error("[camera] {0:?}")]
     Core(#[from] AwsmCoreError),
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,36 @@
+use super::*;
+
+#[test]
+fn halton_sequence() {
+    assert_eq!(halton(0, 2), 0.0);
+    assert_eq!(halton(1, 2), 0.5);
+    assert_eq!(halton(2, 2), 0.25);
+    assert_eq!(halton(3, 2), 0.75);
+    assert!((halton(1, 3) - 1.0 / 3.0).abs() < 1e-6);
+    assert!((halton(2, 3) - 2.0 / 3.0).abs() < 1e-6);
+}
+
+#[test]
+fn taa_jitter_stays_within_a_pixel() {
+    let (width, height) = (800.0, 600.0);
+    let pixel = Vec2::new(2.0 / width, 2.0 / height);
+
+    let jitters: Vec<Vec2> = (0..TAA_JITTER_SEQUENCE_LEN)
+        .map(|frame| taa_jitter(frame, width, height))
+        .collect();
+
+    for jitter in &jitters {
+        assert!(jitter.x.abs() <= pixel.x / 2.0 && jitter.y.abs() <= pixel.y / 2.0);
+    }
+
+    // every frame of the sequence samples a different spot, then it repeats
+    for (i, a) in jitters.iter().enumerate() {
+        assert!(jitters[i + 1..].iter().all(|b| a != b));
+    }
+    assert_eq!(
+        taa_jitter(TAA_JITTER_SEQUENCE_LEN, width, height),
+        jitters[0]
+    );
+
+    assert_eq!(taa_jitter(3, 0.0, 0.0), Vec2::ZERO);
+}
@@ -0,0 +1,138 @@
+//! Device loss recovery.
+//!
+//! A WebGPU device can be lost at any time, e.g. when a mobile browser reclaims the GPU from a
+//! backgrounded tab or the driver resets. Everything created from it stops working, and the
+//! canvas stays black until the renderer moves to a new device:
+//!
+//! ```ignore
+//! loop {
+//!     let lost = renderer.lock().await.device_lost();
+//!     let info = lost.await;
+//!     tracing::warn!("device lost ({:?}): {}", info.reason, info.message);
+//!     renderer.lock().await.recover_from_device_loss().await?;
+//! }
+//! ```
+
+use std::future::Future;
+
+use awsm_renderer_core::{
+    brdf_lut::generate::{BrdfLut, BrdfLutOptions},
+    renderer::DeviceLost,
+};
+
+use crate::{
+    bind_group_layout::BindGroupLayouts,
+    bind_groups::BindGroups,
+    error::Result,
+    picker::Picker,
+    pipeline_layouts::PipelineLayouts,
+    pipelines::Pipelines,
+    render_passes::{RenderPassInitContext, RenderPasses},
+    render_textures::RenderTextures,
+    shaders::Shaders,
+    textures::CubemapTextureKey,
+    AwsmRenderer,
+};
+
+impl AwsmRenderer {
+    /// Resolves when the current device is lost, see
+    /// [`AwsmRendererWebGpu::device_lost`](crate::core::renderer::AwsmRendererWebGpu::device_lost).
+    ///
+    /// Holds nothing borrowed, so it can be awaited without keeping the renderer locked.
+    pub fn device_lost(&self) -> impl Future<Output = DeviceLost> + 'static {
+        self.gpu.device_lost()
+    }
+
+    /// Moves the renderer to a new device after the old one was lost, and rebuilds every GPU
+    /// resource on it. All keys (meshes, materials, textures, lights, views, etc.) stay valid.
+    ///
+    /// Buffers are refilled from their CPU copies and the texture pool from its images on the
+    /// next render. Cubemaps are recreated from the image given to
+    /// [`Textures::insert_cubemap_with_source`](crate::textures::Textures::insert_cubemap_with_source),
+    /// the ones without an image come back empty and are returned so they can be refilled.
+    ///
+    /// The renderer's skybox and IBL are restored, copies of a [`Skybox`](crate::environment::Skybox)
+    /// or [`IblTexture`](crate::lights::ibl::IblTexture) kept elsewhere need their own
+    /// `restore_gpu()`.
+    pub async fn recover_from_device_loss(&mut self) -> Result<Vec<CubemapTextureKey>> {
+        self.gpu = self.gpu.recreate_device().await?;
+
+        // everything with a CPU copy is uploaded again on the next render
+        self.transforms.mark_gpu_lost();
+        self.instances.mark_gpu_lost();
+        self.meshes.mark_gpu_lost();
+        self.materials.mark_gpu_lost();
+        self.camera.restore_gpu(&self.gpu)?;
+        self.lights.restore_gpu(&self.gpu)?;
+
+        let empty_cubemaps = self.textures.restore_gpu(&self.gpu).await?;
+        self.environment
+            .skybox
+            .restore_gpu(&self.gpu, &mut self.textures)?;
+        self.lights
+            .ibl
+            .prefiltered_env
+            .restore_gpu(&self.gpu, &mut self.textures)?;
+        self.lights
+            .ibl
+            .irradiance
+            .restore_gpu(&self.gpu, &mut self.textures)?;
+        self.lights.brdf_lut = BrdfLut::new(
+            &self.gpu,
+            BrdfLutOptions::new(
+                self.lights.brdf_lut.texture.width(),
+                self.lights.brdf_lut.texture.height(),
+            ),
+        )
+        .await?;
+
+        // a setting that lives in the render passes, which are about to start over
+        let pick_alpha_threshold = self
+            .render_passes
+            .material_transparent
+            .pipelines
+            .pick_alpha_threshold();
+
+        // nothing outside the renderer holds pipeline, layout or shader keys,
+        // so these start over just like in build()
+        self.bind_group_layouts = BindGroupLayouts::new();
+        self.pipeline_layouts = PipelineLayouts::new();
+        self.pipelines = Pipelines::new();
+        self.shaders = Shaders::new();
+
+        let mut render_pass_init = RenderPassInitContext {
+            gpu: &mut self.gpu,
+            bind_group_layouts: &mut self.bind_group_layouts,
+            pipeline_layouts: &mut self.pipeline_layouts,
+            pipelines: &mut self.pipelines,
+            shaders: &mut self.shaders,
+            render_texture_formats: &mut self.render_textures.formats,
+            textures: &mut self.textures,
+        };
+        self.render_passes = RenderPasses::new(&mut render_pass_init).await?;
+
+        self.render_textures =
+            RenderTextures::new(&self.gpu, self.render_textures.formats.clone()).await?;
+
+        self.picker = Picker::new(
+            &self.gpu,
+            &mut self.bind_group_layouts,
+            &mut self.pipeline_layouts,
+            &mut self.shaders,
+            &mut self.pipelines,
+        )
+        .await?;
+
+        self.bind_groups = BindGroups::new();
+
+        self.finalize_gpu_textures().await?;
+        self.set_anti_aliasing(self.anti_aliasing.clone()).await?;
+        self.set_post_processing(self.post_processing.clone())
+            .await?;
+        self.set_transparent_picking(pick_alpha_threshold).await?;
+
+        self.restore_render_views_gpu().await?;
+
+        Ok(empty_cubemaps)
+    }
+}
@@ -3,7 +3,7 @@
 use std::sync::LazyLock;
 
 use awsm_renderer_core::cubemap::images::CubemapBitmapColors;
-use awsm_renderer_core::cubemap::{CubemapBytesLayout, CubemapFace, CubemapImage};
+use awsm_renderer_core::cubemap::{self, CubemapBytesLayout, CubemapFace, CubemapImage};
 use awsm_renderer_core::renderer::AwsmRendererWebGpu;
 use awsm_renderer_core::sampler::{AddressMode, FilterMode, MipmapFilterMode};
 
@@ -123,12 +123,12 @@ impl Skybox {
         textures: &mut Textures,
         default_colors: CubemapBitmapColors,
     ) -> Result<Self> {
-        let (texture, view, mip_count) = CubemapImage::new_colors(default_colors, 256, 256)
-            .await?
+        let image = CubemapImage::new_colors(default_colors, 256, 256).await?;
+        let (texture, view, mip_count) = image
             .create_texture_and_view(gpu, Some("Skybox Cubemap"))
             .await?;
 
-        let texture_key = textures.insert_cubemap(texture);
+        let texture_key = textures.insert_cubemap_with_source(texture, image);
 
         let sampler_key = textures.get_sampler_key(gpu, Self::sampler_cache_key())?;
 
@@ -136,6 +136,23 @@ impl Skybox {
 
         Ok(Self::new(texture_key, view, sampler, mip_count))
     }
+
+    /// Recreates the view and sampler after a device loss, from the texture and sampler that
+    /// [`AwsmRenderer::recover_from_device_loss`](crate::AwsmRenderer::recover_from_device_loss)
+    /// restored under the same keys.
+    ///
+    /// The renderer does this for its own copy, copies kept elsewhere need it too.
+    pub fn restore_gpu(&mut self, gpu: &AwsmRendererWebGpu, textures: &mut Textures) -> Result<()> {
+        self.texture_view = cubemap::create_texture_view(
+            textures.get_cubemap(self.texture_key)?,
+            Some("Skybox Cubemap"),
+        )?;
+
+        let sampler_key = textures.get_sampler_key(gpu, Self::sampler_cache_key())?;
+        self.sampler = textures.get_sampler(sampler_key)?.clone();
+
+        Ok(())
+    }
 }
 
 impl Environment {
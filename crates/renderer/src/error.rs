@@ -19,6 +19,7 @@ use crate::{
     shaders::AwsmShaderError,
     textures::AwsmTextureError,
     transforms::AwsmTransformError,
+    views::AwsmRenderViewError,
 };
 
 /// Errors returned by the renderer crate.
@@ -81,6 +82,9 @@ pub enum AwsmError {
 
     #[error("{0}")]
     Texture(#[from] AwsmTextureError),
+
+    #[error("{0}")]
+    RenderView(#[from] AwsmRenderViewError),
 }
 
 /// Renderer result type.
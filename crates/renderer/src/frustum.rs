@@ -1,6 +1,6 @@
 //! Frustum extraction and culling helpers.
 
-use glam::{Mat4, Vec3, Vec4};
+use glam::{Mat4, Vec2, Vec3, Vec4};
 
 use crate::bounds::Aabb;
 
@@ -62,6 +62,45 @@ impl Frustum {
         }
     }
 
+    /// Builds the frustum through a screen-space rectangle of the view, e.g. for box selection.
+    ///
+    /// `min` and `max` are pixel edges with the origin at the top-left, in a viewport of
+    /// `width` x `height` pixels.
+    pub fn from_screen_rect(
+        view_projection: Mat4,
+        min: Vec2,
+        max: Vec2,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let size = Vec2::new(width.max(1) as f32, height.max(1) as f32);
+        // y flipped from pixel space to NDC
+        let ndc_min = Vec2::new(min.x / size.x * 2.0 - 1.0, 1.0 - max.y / size.y * 2.0);
+        let ndc_max = Vec2::new(max.x / size.x * 2.0 - 1.0, 1.0 - min.y / size.y * 2.0);
+        let extent = (ndc_max - ndc_min).max(Vec2::splat(f32::EPSILON));
+
+        // stretches the rectangle over the whole clip space, so its edges become the side planes
+        let scale = Vec2::splat(2.0) / extent;
+        let offset = -(ndc_max + ndc_min) / extent;
+        let rect_to_clip = Mat4::from_cols(
+            Vec4::new(scale.x, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, scale.y, 0.0, 0.0),
+            Vec4::Z,
+            Vec4::new(offset.x, offset.y, 0.0, 1.0),
+        );
+
+        Self::from_view_projection(rect_to_clip * view_projection)
+    }
+
+    /// Returns true if the AABB is entirely inside the frustum.
+    pub fn contains_aabb(&self, aabb: &Aabb) -> bool {
+        self.planes.iter().all(|plane| {
+            // the corner furthest behind the plane
+            let p = Vec3::select(plane.normal.cmpge(Vec3::ZERO), aabb.min, aabb.max);
+            plane.distance(p) >= 0.0
+        })
+    }
+
     /// Returns true if the AABB intersects the frustum.
     pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
         for plane in &self.planes {
@@ -1,4 +1,4 @@
-use glam::{Mat4, Vec3};
+use glam::{Mat4, Vec2, Vec3};
 
 use crate::bounds::Aabb;
 use crate::frustum::Frustum;
@@ -86,3 +86,47 @@ fn orthographic_frustum_culls_instanced_union() {
     let union_outside = instance_union_aabb(&base, base_world_far, &outside_instances);
     assert!(!frustum.intersects_aabb(&union_outside));
 }
+
+#[test]
+fn screen_rect_frustum_selects_inside_the_rect() {
+    let projection = Mat4::perspective_rh(90.0_f32.to_radians(), 1.0, 1.0, 10.0);
+    let view_projection = projection * Mat4::IDENTITY;
+    let (width, height) = (100, 100);
+
+    // the top-left quarter of the screen
+    let frustum = Frustum::from_screen_rect(
+        view_projection,
+        Vec2::new(0.0, 0.0),
+        Vec2::new(50.0, 50.0),
+        width,
+        height,
+    );
+
+    let base = Aabb::new_cube(0.5, 0.5);
+    let top_left = base.transformed(&Mat4::from_translation(Vec3::new(-2.0, 2.0, -5.0)));
+    let bottom_right = base.transformed(&Mat4::from_translation(Vec3::new(2.0, -2.0, -5.0)));
+    let center = base.transformed(&Mat4::from_translation(Vec3::new(0.0, 0.0, -5.0)));
+
+    assert!(frustum.intersects_aabb(&top_left));
+    assert!(frustum.contains_aabb(&top_left));
+    assert!(!frustum.intersects_aabb(&bottom_right));
+    // straddles the corner of the rect
+    assert!(frustum.intersects_aabb(&center));
+    assert!(!frustum.contains_aabb(&center));
+}
+
+#[test]
+fn full_screen_rect_matches_the_view_frustum() {
+    let projection = Mat4::orthographic_rh(-2.0, 2.0, -2.0, 2.0, 1.0, 10.0);
+    let frustum = Frustum::from_screen_rect(projection, Vec2::ZERO, Vec2::new(64.0, 32.0), 64, 32);
+
+    let base = Aabb::new_cube(1.0, 1.0);
+    let inside = base.transformed(&Mat4::from_translation(Vec3::new(1.0, -1.0, -5.0)));
+    let edge = base.transformed(&Mat4::from_translation(Vec3::new(2.0, 0.0, -5.0)));
+    let behind = base.transformed(&Mat4::from_translation(Vec3::new(0.0, 0.0, 5.0)));
+
+    assert!(frustum.contains_aabb(&inside));
+    assert!(frustum.intersects_aabb(&edge));
+    assert!(!frustum.contains_aabb(&edge));
+    assert!(!frustum.intersects_aabb(&behind));
+}
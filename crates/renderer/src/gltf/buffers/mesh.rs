@@ -1,3 +1,4 @@
+mod mesh_data;
 mod transparency;
 mod visibility;
 
@@ -23,7 +24,9 @@ use crate::meshes::buffer_info::MeshBufferVertexAttributeInfo;
 
 use super::Result;
 
-pub(super) enum GltfMeshBufferGeometryKind {
+pub(crate) use mesh_data::convert_mesh_data;
+
+pub(crate) enum GltfMeshBufferGeometryKind {
     Visibility,
     Transparency,
     Both,
@@ -0,0 +1,249 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use awsm_renderer_core::pipeline::primitive::FrontFace;
+
+use crate::gltf::buffers::attributes::pack_vertex_attributes;
+use crate::gltf::buffers::index::extract_triangle_indices;
+use crate::gltf::buffers::mesh::transparency::create_transparency_vertices;
+use crate::gltf::buffers::mesh::visibility::create_visibility_vertices;
+use crate::gltf::buffers::morph::{pack_morph_targets, MorphTargetBufferData};
+use crate::gltf::buffers::normals::ensure_normals;
+use crate::gltf::buffers::skin::push_skin_joint_weights;
+use crate::gltf::buffers::tangents::generate_missing_tangents;
+use crate::gltf::buffers::triangle::pack_triangle_data;
+use crate::gltf::buffers::{
+    GltfBuffers, MeshBufferAttributeIndexInfoWithOffset, MeshBufferInfoWithOffset,
+    MeshBufferSkinInfoWithOffset, MeshBufferTriangleInfoWithOffset, MeshBufferVertexInfoWithOffset,
+};
+use crate::meshes::buffer_info::{
+    MeshBufferCustomVertexAttributeInfo, MeshBufferVertexAttributeInfo,
+    MeshBufferVisibilityVertexAttributeInfo,
+};
+use crate::meshes::data::MeshData;
+
+use super::{GltfMeshBufferGeometryKind, Result};
+
+/// Packs runtime mesh data the same way as a glTF primitive.
+///
+/// The result holds a single mesh with a single primitive, at offset 0 of every buffer.
+pub(crate) fn convert_mesh_data(
+    data: &MeshData,
+    geometry_kind: GltfMeshBufferGeometryKind,
+    double_sided: bool,
+) -> Result<GltfBuffers> {
+    // the vertex order is given as counter-clockwise, there's no node transform to flip it
+    let front_face = FrontFace::Ccw;
+    let vertex_count = data.vertex_count();
+    let triangle_count = data.triangle_count();
+
+    let index_bytes: Vec<u8> = data
+        .indices
+        .iter()
+        .flat_map(|index| index.to_le_bytes())
+        .collect();
+    let custom_attribute_index = MeshBufferAttributeIndexInfoWithOffset {
+        offset: 0,
+        count: data.indices.len(),
+    };
+    let triangle_indices = extract_triangle_indices(&custom_attribute_index, &index_bytes)?;
+
+    let mut attribute_data_by_kind: BTreeMap<MeshBufferVertexAttributeInfo, Cow<'_, [u8]>> =
+        BTreeMap::new();
+    attribute_data_by_kind.insert(
+        MeshBufferVertexAttributeInfo::Visibility(
+            MeshBufferVisibilityVertexAttributeInfo::Positions {
+                data_size: 4,
+                component_len: 3,
+            },
+        ),
+        Cow::Owned(f32_bytes(data.positions.iter().flatten())),
+    );
+    if let Some(normals) = &data.normals {
+        attribute_data_by_kind.insert(
+            MeshBufferVertexAttributeInfo::Visibility(
+                MeshBufferVisibilityVertexAttributeInfo::Normals {
+                    data_size: 4,
+                    component_len: 3,
+                },
+            ),
+            Cow::Owned(f32_bytes(normals.iter().flatten())),
+        );
+    }
+    if let Some(tangents) = &data.tangents {
+        attribute_data_by_kind.insert(
+            MeshBufferVertexAttributeInfo::Visibility(
+                MeshBufferVisibilityVertexAttributeInfo::Tangents {
+                    data_size: 4,
+                    component_len: 4,
+                },
+            ),
+            Cow::Owned(f32_bytes(tangents.iter().flatten())),
+        );
+    }
+    for (index, uvs) in data.uvs.iter().enumerate() {
+        attribute_data_by_kind.insert(
+            MeshBufferVertexAttributeInfo::Custom(MeshBufferCustomVertexAttributeInfo::TexCoords {
+                index: index as u32,
+                data_size: 4,
+                component_len: 2,
+            }),
+            Cow::Owned(f32_bytes(uvs.iter().flatten())),
+        );
+    }
+    for (index, colors) in data.colors.iter().enumerate() {
+        attribute_data_by_kind.insert(
+            MeshBufferVertexAttributeInfo::Custom(MeshBufferCustomVertexAttributeInfo::Colors {
+                index: index as u32,
+                data_size: 4,
+                component_len: 4,
+            }),
+            Cow::Owned(f32_bytes(colors.iter().flatten())),
+        );
+    }
+
+    let attribute_data_by_kind = ensure_normals(attribute_data_by_kind, &triangle_indices)?;
+    // unlike glTF we don't know if a normal map will be used later, so generate whenever possible
+    let attribute_data_by_kind = if data.tangents.is_none() {
+        generate_missing_tangents(attribute_data_by_kind, &triangle_indices)?
+    } else {
+        attribute_data_by_kind
+    };
+
+    let mut visibility_geometry_vertex_bytes = Vec::new();
+    let visibility_geometry_vertex = match geometry_kind {
+        GltfMeshBufferGeometryKind::Visibility | GltfMeshBufferGeometryKind::Both => {
+            create_visibility_vertices(
+                &attribute_data_by_kind,
+                &triangle_indices,
+                front_face,
+                &mut visibility_geometry_vertex_bytes,
+            )?;
+            Some(MeshBufferVertexInfoWithOffset {
+                offset: 0,
+                count: triangle_count * 3, // 3 vertices per triangle (i.e. exploded)
+            })
+        }
+        GltfMeshBufferGeometryKind::Transparency => None,
+    };
+
+    let mut transparency_geometry_vertex_bytes = Vec::new();
+    let transparency_geometry_vertex = match geometry_kind {
+        GltfMeshBufferGeometryKind::Transparency | GltfMeshBufferGeometryKind::Both => {
+            create_transparency_vertices(
+                &attribute_data_by_kind,
+                &custom_attribute_index,
+                &index_bytes,
+                triangle_count,
+                front_face,
+                &mut transparency_geometry_vertex_bytes,
+            )?;
+            Some(MeshBufferVertexInfoWithOffset {
+                offset: 0,
+                count: vertex_count,
+            })
+        }
+        GltfMeshBufferGeometryKind::Visibility => None,
+    };
+
+    let mut custom_attribute_vertex_bytes = Vec::new();
+    pack_vertex_attributes(
+        attribute_data_by_kind
+            .iter()
+            .filter_map(|x| match x.0 {
+                MeshBufferVertexAttributeInfo::Custom(custom) => Some((custom, x.1)),
+                _ => None,
+            })
+            .collect(),
+        &mut custom_attribute_vertex_bytes,
+    )?;
+
+    let mut triangle_data_bytes = Vec::new();
+    let triangle_data = pack_triangle_data(
+        &triangle_indices,
+        triangle_count,
+        0,
+        &mut triangle_data_bytes,
+        front_face,
+        double_sided,
+    )?;
+
+    let mut geometry_morph_bytes = Vec::new();
+    let geometry_morph = if data.morph_targets.is_empty() {
+        None
+    } else {
+        let morph_targets_buffer_data: Vec<MorphTargetBufferData> = data
+            .morph_targets
+            .iter()
+            .map(|target| MorphTargetBufferData {
+                positions: target
+                    .positions
+                    .as_ref()
+                    .map(|values| Cow::Owned(f32_bytes(values.iter().flatten()))),
+                normals: target
+                    .normals
+                    .as_ref()
+                    .map(|values| Cow::Owned(f32_bytes(values.iter().flatten()))),
+                tangents: target
+                    .tangents
+                    .as_ref()
+                    .map(|values| Cow::Owned(f32_bytes(values.iter().flatten()))),
+            })
+            .collect();
+
+        Some(pack_morph_targets(
+            &morph_targets_buffer_data,
+            vertex_count,
+            &mut geometry_morph_bytes,
+        )?)
+    };
+
+    let mut skin_joint_index_weight_bytes = Vec::new();
+    let skin = data.skin.as_ref().map(|skin| {
+        for (joints, weights) in skin.joints.iter().zip(skin.weights.iter()) {
+            push_skin_joint_weights(*joints, *weights, &mut skin_joint_index_weight_bytes);
+        }
+        MeshBufferSkinInfoWithOffset {
+            set_count: 1,
+            index_weights_size: skin_joint_index_weight_bytes.len(),
+            index_weights_offset: 0,
+        }
+    });
+
+    let mesh_buffer_info = MeshBufferInfoWithOffset {
+        visibility_geometry_vertex,
+        transparency_geometry_vertex,
+        triangles: MeshBufferTriangleInfoWithOffset {
+            count: triangle_count,
+            vertex_attribute_indices: custom_attribute_index,
+            vertex_attributes: attribute_data_by_kind
+                .keys()
+                .filter(|attr| attr.is_custom_attribute())
+                .cloned()
+                .collect(),
+            vertex_attributes_offset: 0,
+            vertex_attributes_size: custom_attribute_vertex_bytes.len(),
+            triangle_data,
+        },
+        geometry_morph,
+        material_morph: None,
+        skin,
+    };
+
+    Ok(GltfBuffers {
+        raw: Vec::new(),
+        index_bytes,
+        visibility_geometry_vertex_bytes,
+        transparency_geometry_vertex_bytes,
+        custom_attribute_vertex_bytes,
+        triangle_data_bytes,
+        geometry_morph_bytes,
+        material_morph_bytes: Vec::new(),
+        skin_joint_index_weight_bytes,
+        meshes: vec![vec![mesh_buffer_info]],
+    })
+}
+
+fn f32_bytes<'a>(values: impl Iterator<Item = &'a f32>) -> Vec<u8> {
+    values.flat_map(|value| value.to_le_bytes()).collect()
+}
@@ -96,10 +96,7 @@ pub(super) fn convert_skin(
                 vertex_index,
             )?;
 
-            for i in 0..4 {
-                skin_joint_index_weight_bytes.extend_from_slice(&indices_u32[i].to_le_bytes());
-                skin_joint_index_weight_bytes.extend_from_slice(&weights_f32[i].to_le_bytes());
-            }
+            push_skin_joint_weights(indices_u32, weights_f32, skin_joint_index_weight_bytes);
         }
     }
 
@@ -112,6 +109,18 @@ pub(super) fn convert_skin(
     }))
 }
 
+/// Writes one vertex of one skin set: each joint index (u32) followed by its weight (f32).
+pub(super) fn push_skin_joint_weights(
+    indices: [u32; 4],
+    weights: [f32; 4],
+    skin_joint_index_weight_bytes: &mut Vec<u8>,
+) {
+    for (index, weight) in indices.iter().zip(weights.iter()) {
+        skin_joint_index_weight_bytes.extend_from_slice(&index.to_le_bytes());
+        skin_joint_index_weight_bytes.extend_from_slice(&weight.to_le_bytes());
+    }
+}
+
 /// Converts joint indices from GLTF format to standardized u32
 fn convert_indices_to_u32(
     data: &[u8],
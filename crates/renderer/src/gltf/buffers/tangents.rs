@@ -13,7 +13,7 @@ use crate::{
 /// - The primitive doesn't already have tangent attributes
 /// - UV coordinates exist (required for tangent calculation)
 pub(super) fn ensure_tangents<'a>(
-    mut attribute_data: BTreeMap<MeshBufferVertexAttributeInfo, Cow<'a, [u8]>>,
+    attribute_data: BTreeMap<MeshBufferVertexAttributeInfo, Cow<'a, [u8]>>,
     primitive: &gltf::Primitive<'_>,
     triangle_indices: &[[usize; 3]],
 ) -> Result<BTreeMap<MeshBufferVertexAttributeInfo, Cow<'a, [u8]>>> {
@@ -48,6 +48,16 @@ pub(super) fn ensure_tangents<'a>(
         return Ok(attribute_data);
     }
 
+    generate_missing_tangents(attribute_data, triangle_indices)
+}
+
+/// Generates tangents using MikkTSpace, regardless of material.
+///
+/// Requires positions, normals and TEXCOORD_0, otherwise the data is returned as-is.
+pub(super) fn generate_missing_tangents<'a>(
+    mut attribute_data: BTreeMap<MeshBufferVertexAttributeInfo, Cow<'a, [u8]>>,
+    triangle_indices: &[[usize; 3]],
+) -> Result<BTreeMap<MeshBufferVertexAttributeInfo, Cow<'a, [u8]>>> {
     // Check if we have the required data for tangent generation
     let positions = attribute_data.iter().find_map(|(k, v)| match k {
         MeshBufferVertexAttributeInfo::Visibility(
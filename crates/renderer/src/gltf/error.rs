@@ -9,6 +9,7 @@ use crate::{
     bind_group_layout::AwsmBindGroupLayoutError,
     bind_groups::AwsmBindGroupError,
     error::AwsmError,
+    lights::AwsmLightError,
     materials::AwsmMaterialError,
     meshes::{buffer_info::MeshBufferInfoKey, error::AwsmMeshError, skins::AwsmSkinError},
     pipeline_layouts::AwsmPipelineLayoutError,
@@ -27,6 +28,9 @@ pub enum AwsmGltfError {
     #[error("[gltf] Error loading file")]
     Load,
 
+    #[error("[gltf] Unable to resolve uri: {0}")]
+    UnresolvedUri(String),
+
     #[error("[gltf] No scene at index {0}")]
     InvalidScene(usize),
 
@@ -87,6 +91,9 @@ pub enum AwsmGltfError {
     #[error("[gltf] invalid morph buffer size: {0}")]
     InvalidMorphBufferSize(String),
 
+    #[error("[gltf] no transform for light node {0}")]
+    MissingLightNodeTransform(usize),
+
     #[error("[gltf] {0:?}")]
     Mesh(#[from] AwsmMeshError),
 
@@ -102,6 +109,9 @@ pub enum AwsmGltfError {
     #[error("[gltf] {0:?}")]
     Skin(#[from] AwsmSkinError),
 
+    #[error("[gltf] {0:?}")]
+    Light(#[from] AwsmLightError),
+
     #[error("[gltf] {0:?}")]
     BindGroup(#[from] AwsmBindGroupError),
 
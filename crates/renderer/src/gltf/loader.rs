@@ -6,12 +6,16 @@
 //! 2. Uses web APIs (via the internal `ImageData` helper).
 //! 3. No image_data_reference feature (avoids base64/image crate dependencies).
 //! 4. Some error checks are omitted because the web APIs enforce them (for example, mime type).
+//! 5. Works in a Web Worker too: images are decoded to `ImageBitmap`s from the fetched blobs
+//!    through whichever global scope is current.
 
 use awsm_renderer_core::image::{
     ColorSpaceConversion, ImageBitmapOptions, ImageData, PremultiplyAlpha,
 };
 use futures::future::try_join_all;
 use gltf::{buffer, image, Document, Error as GltfError, Gltf};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::future::Future;
 use std::sync::{Arc, Mutex};
 
@@ -43,44 +47,38 @@ pub fn get_type_from_filename(url: &str) -> Option<GltfFileType> {
 
 impl GltfLoader {
     /// Loads a glTF asset from a URL.
+    ///
+    /// Relative buffer and image URIs are fetched next to it.
     pub async fn load(url: &str, file_type: Option<GltfFileType>) -> anyhow::Result<Self> {
-        let url = url.to_owned();
         let file_type = match file_type {
             Some(file_type) => file_type,
-            None => get_type_from_filename(&url).unwrap_or(GltfFileType::Json),
+            None => get_type_from_filename(url).unwrap_or(GltfFileType::Json),
         };
 
+        if matches!(file_type, GltfFileType::Draco) {
+            return Err(AwsmGltfError::Load.into());
+        }
+
+        let bytes = fetch_bytes(url).await?;
+
+        Self::load_bytes(&bytes, &GltfUrlResolver::new(url)).await
+    }
+
+    /// Loads a glTF asset from `.gltf` or `.glb` bytes already in memory.
+    ///
+    /// The format is detected from the bytes. Relative buffer and image URIs are handed to
+    /// `resolver`, while `data:` and `http(s)` URIs are still fetched.
+    pub async fn load_bytes(bytes: &[u8], resolver: &impl GltfUriResolver) -> anyhow::Result<Self> {
         let Gltf {
             document: doc,
             blob,
-        } = match file_type {
-            GltfFileType::Json => {
-                let text = gloo_net::http::Request::get(&url)
-                    .send()
-                    .await?
-                    .text()
-                    .await?;
-
-                let bytes: &[u8] = text.as_bytes();
-                Gltf::from_slice(bytes)
-            }
-            GltfFileType::Glb => {
-                let bytes = gloo_net::http::Request::get(&url)
-                    .send()
-                    .await?
-                    .binary()
-                    .await?;
-                Gltf::from_slice(&bytes)
-            }
-            _ => return Err(AwsmGltfError::Load.into()),
-        }?;
+        } = Gltf::from_slice(bytes)?;
 
-        let base_path = get_base_path(&url);
-        let buffers = import_buffer_data(&doc, base_path, blob).await?;
+        let buffers = import_buffer_data(&doc, resolver, blob).await?;
 
         //info!("loaded {} buffers", buffer_data.len());
 
-        let images = import_image_data(&doc, base_path, &buffers).await?;
+        let images = import_image_data(&doc, resolver, &buffers).await?;
 
         //info!("loaded {} images", image_data.len());
 
@@ -91,6 +89,34 @@ impl GltfLoader {
         })
     }
 
+    /// Loads a glTF asset from a `Blob`, e.g. from IndexedDB.
+    pub async fn load_blob(
+        blob: &web_sys::Blob,
+        resolver: &impl GltfUriResolver,
+    ) -> anyhow::Result<Self> {
+        let bytes = read_blob(blob).await?;
+
+        Self::load_bytes(&bytes, resolver).await
+    }
+
+    /// Loads a glTF asset from a `File`, e.g. from drag-and-drop or a file input.
+    ///
+    /// Sibling files a `.gltf` references can be supplied with a `HashMap<String, web_sys::File>`
+    /// resolver keyed by file name.
+    pub async fn load_file(
+        file: &web_sys::File,
+        resolver: &impl GltfUriResolver,
+    ) -> anyhow::Result<Self> {
+        if matches!(
+            get_type_from_filename(&file.name()),
+            Some(GltfFileType::Draco)
+        ) {
+            return Err(AwsmGltfError::Load.into());
+        }
+
+        Self::load_blob(file, resolver).await
+    }
+
     /// Clones the loaded glTF data and buffers.
     pub fn heavy_clone(&self) -> Self {
         Self {
@@ -101,23 +127,162 @@ impl GltfLoader {
     }
 }
 
-fn get_base_path(url: &str) -> &str {
-    let idx1: i32 = url.rfind('/').map(|n| n as i32).unwrap_or(-1) + 1;
-    let idx2: i32 = url.rfind('\\').map(|n| n as i32).unwrap_or(-1) + 1;
+/// Resolves the relative buffer and image URIs a glTF references.
+///
+/// URIs are passed as written in the glTF, so they may still be percent-encoded.
+pub trait GltfUriResolver {
+    /// Returns the bytes behind a relative URI.
+    fn resolve(&self, uri: &str) -> impl Future<Output = anyhow::Result<Vec<u8>>>;
+}
+
+/// Fetches relative URIs from the same location as the glTF itself.
+#[derive(Debug, Clone)]
+pub struct GltfUrlResolver {
+    base: String,
+}
+
+impl GltfUrlResolver {
+    /// Creates a resolver relative to the glTF URL (or a base ending in `/`).
+    pub fn new(url: &str) -> Self {
+        Self {
+            base: get_base_path(url).to_owned(),
+        }
+    }
+
+    fn url(&self, uri: &str) -> String {
+        format!("{}{uri}", self.base)
+    }
+}
+
+impl GltfUriResolver for GltfUrlResolver {
+    fn resolve(&self, uri: &str) -> impl Future<Output = anyhow::Result<Vec<u8>>> {
+        let url = self.url(uri);
+        async move { fetch_bytes(&url).await }
+    }
+}
+
+/// Fails on every relative URI, for self-contained assets (`.glb` or embedded `data:` URIs).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GltfNoResolver;
+
+impl GltfUriResolver for GltfNoResolver {
+    fn resolve(&self, uri: &str) -> impl Future<Output = anyhow::Result<Vec<u8>>> {
+        let uri = uri.to_owned();
+        async move { Err(AwsmGltfError::UnresolvedUri(uri).into()) }
+    }
+}
+
+/// Resolves from bytes keyed by URI, e.g. entries of an asset pack.
+impl GltfUriResolver for HashMap<String, Vec<u8>> {
+    fn resolve(&self, uri: &str) -> impl Future<Output = anyhow::Result<Vec<u8>>> {
+        let bytes: anyhow::Result<Vec<u8>> = lookup_uri(self, uri)
+            .cloned()
+            .ok_or_else(|| AwsmGltfError::UnresolvedUri(uri.to_owned()).into());
+        async move { bytes }
+    }
+}
+
+/// Resolves from files keyed by name, e.g. everything dropped alongside a `.gltf`.
+impl GltfUriResolver for HashMap<String, web_sys::File> {
+    fn resolve(&self, uri: &str) -> impl Future<Output = anyhow::Result<Vec<u8>>> {
+        let file = lookup_uri(self, uri).cloned();
+        let uri = uri.to_owned();
+        async move {
+            match file {
+                Some(file) => read_blob(&file).await,
+                None => Err(AwsmGltfError::UnresolvedUri(uri).into()),
+            }
+        }
+    }
+}
+
+// tries the uri as written, then percent-decoded, then without a leading "./"
+fn lookup_uri<'a, T>(map: &'a HashMap<String, T>, uri: &str) -> Option<&'a T> {
+    let decoded = percent_decode(uri);
+    [uri, decoded.as_ref()]
+        .into_iter()
+        .find_map(|key| map.get(key).or_else(|| map.get(key.strip_prefix("./")?)))
+}
 
-    if idx1 == 0 && idx2 == 0 {
-        url
+fn percent_decode(uri: &str) -> Cow<'_, str> {
+    if !uri.contains('%') {
+        return Cow::Borrowed(uri);
+    }
+
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes[i] {
+            b'%' => bytes
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    match String::from_utf8(decoded) {
+        Ok(decoded) => Cow::Owned(decoded),
+        Err(_) => Cow::Borrowed(uri),
+    }
+}
+
+async fn fetch_bytes(url: &str) -> anyhow::Result<Vec<u8>> {
+    Ok(gloo_net::http::Request::get(url)
+        .send()
+        .await?
+        .binary()
+        .await?)
+}
+
+async fn read_blob(blob: &web_sys::Blob) -> anyhow::Result<Vec<u8>> {
+    // reading through a Response avoids pulling in FileReader/js-sys just for this
+    let response = web_sys::Response::new_with_opt_blob(Some(blob))
+        .map_err(|err| anyhow::anyhow!("{err:?}"))?;
+    Ok(gloo_net::http::Response::from(response).binary().await?)
+}
+
+async fn load_uri(resolver: &impl GltfUriResolver, uri: &str) -> anyhow::Result<Vec<u8>> {
+    if uri.contains(":") {
+        //absolute
+        if uri.starts_with("data:") || uri.starts_with("http:") || uri.starts_with("https://") {
+            fetch_bytes(uri).await
+        } else {
+            Err(GltfError::UnsupportedScheme.into())
+        }
     } else {
-        &url[0..(std::cmp::max(idx1, idx2) as usize)]
+        //relative
+        resolver.resolve(uri).await
+    }
+}
+
+// everything up to and including the last separator, a bare filename has no base
+fn get_base_path(url: &str) -> &str {
+    match url.rfind(['/', '\\']) {
+        Some(idx) => &url[..=idx],
+        None => "",
     }
 }
 
 async fn import_buffer_data<'a>(
     document: &'a Document,
-    base: &'a str,
+    resolver: &'a impl GltfUriResolver,
     blob: Option<Vec<u8>>,
 ) -> anyhow::Result<Vec<Vec<u8>>> {
-    let futures = get_buffer_futures(document, base, blob);
+    let futures = get_buffer_futures(document, resolver, blob);
 
     let datas: Vec<Vec<u8>> = try_join_all(futures).await?;
 
@@ -141,30 +306,20 @@ async fn import_buffer_data<'a>(
 
 fn get_buffer_futures<'a>(
     document: &'a Document,
-    base: &str,
+    resolver: &'a impl GltfUriResolver,
     blob: Option<Vec<u8>>,
 ) -> Vec<impl Future<Output = anyhow::Result<Vec<u8>>> + 'a> {
     //these need to be owned by each future simultaneously
     let blob = Arc::new(Mutex::new(blob));
-    let base = Arc::new(base.to_owned());
 
     document
         .buffers()
         .map(|buffer| {
             let blob = blob.clone();
-            let base = base.clone();
 
             async move {
                 match buffer.source() {
-                    buffer::Source::Uri(uri) => {
-                        let url = get_url(base.as_ref(), uri)?;
-                        let bytes = gloo_net::http::Request::get(&url)
-                            .send()
-                            .await?
-                            .binary()
-                            .await?;
-                        Ok(bytes)
-                    }
+                    buffer::Source::Uri(uri) => load_uri(resolver, uri).await,
                     buffer::Source::Bin => {
                         // should this be cloned?
                         blob.lock()
@@ -180,26 +335,22 @@ fn get_buffer_futures<'a>(
 
 async fn import_image_data<'a>(
     document: &'a Document,
-    base: &'a str,
+    resolver: &'a impl GltfUriResolver,
     buffer_data: &'a [Vec<u8>],
 ) -> anyhow::Result<Vec<ImageData>> {
-    let futures = get_image_futures(document, base, buffer_data);
+    let futures = get_image_futures(document, resolver, buffer_data);
 
     try_join_all(futures).await
 }
 
 fn get_image_futures<'a>(
     document: &'a Document,
-    base: &str,
+    resolver: &'a impl GltfUriResolver,
     buffer_data: &'a [Vec<u8>],
 ) -> Vec<impl Future<Output = anyhow::Result<ImageData>> + 'a> {
-    //these need to be owned by each future simultaneously
-    let base = Arc::new(base.to_owned());
-
     document
         .images()
         .map(|image| {
-            let base = Arc::clone(&base);
             // We very intentionally set these. See notes on `ImageData::load_url` for why.
             let options = Some(
                 ImageBitmapOptions::new()
@@ -208,9 +359,13 @@ fn get_image_futures<'a>(
             );
             async move {
                 match image.source() {
-                    image::Source::Uri { uri, mime_type: _ } => {
-                        let url = get_url(base.as_ref(), uri)?;
-                        Ok(ImageData::load_url(&url, options).await?)
+                    image::Source::Uri { uri, mime_type } => {
+                        let bytes = load_uri(resolver, uri).await?;
+                        // the browser sniffs the format if the glTF doesn't say
+                        Ok(
+                            ImageData::load_bytes(bytes, mime_type.unwrap_or_default(), options)
+                                .await?,
+                        )
                     }
                     image::Source::View { view, mime_type } => {
                         let parent_buffer_data = &buffer_data[view.buffer().index()];
@@ -231,16 +386,5 @@ fn get_image_futures<'a>(
         .collect()
 }
 
-fn get_url(base: &str, uri: &str) -> anyhow::Result<String> {
-    if uri.contains(":") {
-        //absolute
-        if uri.starts_with("data:") || uri.starts_with("http:") || uri.starts_with("https://") {
-            Ok(uri.to_owned())
-        } else {
-            Err(GltfError::UnsupportedScheme.into())
-        }
-    } else {
-        //relative
-        Ok(format!("{base}{uri}"))
-    }
-}
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use super::*;
+
+#[test]
+fn base_path_keeps_the_trailing_separator() {
+    assert_eq!(get_base_path("models/box/box.gltf"), "models/box/");
+    assert_eq!(get_base_path("C:\\models\\box.gltf"), "C:\\models\\");
+    assert_eq!(get_base_path("models/box/"), "models/box/");
+    // a bare filename sits in the current directory
+    assert_eq!(get_base_path("box.gltf"), "");
+}
+
+#[test]
+fn url_resolver_is_relative_to_the_gltf() {
+    assert_eq!(GltfUrlResolver::new("box.gltf").url("box.bin"), "box.bin");
+    assert_eq!(
+        GltfUrlResolver::new("box.gltf").url("textures/albedo.png"),
+        "textures/albedo.png"
+    );
+    assert_eq!(
+        GltfUrlResolver::new("https://example.com/models/box.gltf").url("box.bin"),
+        "https://example.com/models/box.bin"
+    );
+    assert_eq!(
+        GltfUrlResolver::new("models/").url("box.bin"),
+        "models/box.bin"
+    );
+}
+
+#[test]
+fn percent_decode_handles_escapes() {
+    assert_eq!(percent_decode("box.bin"), "box.bin");
+    assert_eq!(percent_decode("my%20box%2Bmore.bin"), "my box+more.bin");
+    // malformed escapes are left alone
+    assert_eq!(percent_decode("100%.png"), "100%.png");
+    assert_eq!(percent_decode("a%+fb.png"), "a%+fb.png");
+}
+
+#[test]
+fn lookup_uri_tries_decoded_and_relative_names() {
+    let map: HashMap<String, u32> = [
+        ("plain.bin".to_string(), 1),
+        ("my box.png".to_string(), 2),
+        ("textures/albedo.png".to_string(), 3),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(lookup_uri(&map, "plain.bin"), Some(&1));
+    assert_eq!(lookup_uri(&map, "my%20box.png"), Some(&2));
+    assert_eq!(lookup_uri(&map, "./textures/albedo.png"), Some(&3));
+    assert_eq!(lookup_uri(&map, "missing.bin"), None);
+}
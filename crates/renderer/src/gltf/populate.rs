@@ -8,17 +8,22 @@ use std::{
 use awsm_renderer_core::texture::texture_pool::TextureColorInfo;
 use glam::Mat4;
 
+use crate::animation::{AnimationGroupKey, AnimationKey};
 use crate::materials::MaterialKey;
-use crate::{meshes::MeshKey, textures::TextureKey, transforms::TransformKey, AwsmRenderer};
+use crate::{
+    lights::LightKey, meshes::MeshKey, textures::TextureKey, transforms::TransformKey, AwsmRenderer,
+};
 
 use super::{data::GltfData, error::AwsmGltfError};
 
 mod animation;
 mod extensions;
+mod light;
 pub mod material;
 mod mesh;
 mod skin;
 pub(super) mod transforms;
+mod unload;
 
 /// Context and shared state used while populating glTF data.
 pub struct GltfPopulateContext {
@@ -29,7 +34,12 @@ pub struct GltfPopulateContext {
         Mutex<HashMap<GltfIndex, Arc<(Vec<TransformKey>, Vec<SkinInverseBindMatrix>)>>>,
     pub transform_is_joint: Mutex<HashSet<TransformKey>>,
     pub transform_is_instanced: Mutex<HashSet<TransformKey>>,
-    pub(super) node_animation_samplers: HashMap<GltfIndex, GltfNodeAnimationSamplers>,
+    // per node, the samplers of each animation targeting it (in animation order)
+    pub(super) node_animation_samplers: HashMap<GltfIndex, Vec<GltfNodeAnimationSamplers>>,
+    // per animation, the end time of its longest channel
+    pub(super) animation_durations: Vec<f64>,
+    // per animation, the players created for it so far
+    pub(super) animation_players: Mutex<HashMap<GltfIndex, Vec<AnimationKey>>>,
     pub key_lookups: Arc<Mutex<GltfKeyLookups>>,
 }
 
@@ -43,6 +53,12 @@ pub struct GltfKeyLookups {
     pub mesh_primitives: HashMap<String, Vec<MeshKey>>,
     pub node_index_to_transform: HashMap<GltfIndex, TransformKey>,
     pub all_mesh_keys: HashMap<GltfIndex, Vec<MeshKey>>,
+    // for all nodes with a name, get the KHR_lights_punctual light attached to that node
+    pub node_lights: HashMap<String, LightKey>,
+    pub node_index_to_light: HashMap<GltfIndex, LightKey>,
+    // for all animations with a name, get the group that plays all of its channels
+    pub animation_groups: HashMap<String, AnimationGroupKey>,
+    pub animation_index_to_group: HashMap<GltfIndex, AnimationGroupKey>,
 }
 
 impl GltfKeyLookups {
@@ -55,6 +71,24 @@ impl GltfKeyLookups {
         self.node_index_to_transform.insert(node.index(), key);
     }
 
+    /// Records a light key for a glTF node.
+    pub fn insert_light(&mut self, node: &gltf::Node, key: LightKey) {
+        if let Some(name) = node.name() {
+            self.node_lights.insert(name.to_string(), key);
+        }
+
+        self.node_index_to_light.insert(node.index(), key);
+    }
+
+    /// Records the animation group for a glTF animation.
+    pub fn insert_animation_group(&mut self, animation: &gltf::Animation, key: AnimationGroupKey) {
+        if let Some(name) = animation.name() {
+            self.animation_groups.insert(name.to_string(), key);
+        }
+
+        self.animation_index_to_group.insert(animation.index(), key);
+    }
+
     /// Records a mesh key for a glTF node and mesh.
     pub fn insert_mesh(&mut self, node: &gltf::Node, mesh: &gltf::Mesh, mesh_key: MeshKey) {
         self.all_mesh_keys
@@ -127,6 +161,7 @@ pub(super) struct GltfAnimationSamplerRef {
 
 #[derive(Clone, Debug, Default)]
 pub(super) struct GltfNodeAnimationSamplers {
+    pub animation_index: usize,
     pub translation: Option<GltfAnimationSamplerRef>,
     pub rotation: Option<GltfAnimationSamplerRef>,
     pub scale: Option<GltfAnimationSamplerRef>,
@@ -142,6 +177,8 @@ pub(super) struct GltfMaterialLookupKey {
 
 impl AwsmRenderer {
     /// Populates renderer resources from a glTF asset.
+    ///
+    /// Keep the returned context to remove the asset again with `unload_gltf`.
     pub async fn populate_gltf(
         &mut self,
         gltf_data: impl Into<Arc<GltfData>>,
@@ -152,6 +189,7 @@ impl AwsmRenderer {
 
         let mut mesh_keys = Vec::new();
         let node_animation_samplers = build_node_animation_sampler_lookup(&gltf_data.doc);
+        let animation_durations = animation::gltf_animation_durations(&gltf_data)?;
 
         let ctx = GltfPopulateContext {
             data: gltf_data,
@@ -161,6 +199,8 @@ impl AwsmRenderer {
             transform_is_joint: Mutex::new(HashSet::new()),
             transform_is_instanced: Mutex::new(HashSet::new()),
             node_animation_samplers,
+            animation_durations,
+            animation_players: Mutex::new(HashMap::new()),
             key_lookups: Arc::new(Mutex::new(GltfKeyLookups::default())),
         };
 
@@ -198,10 +238,17 @@ impl AwsmRenderer {
             self.populate_gltf_node_animation(&ctx, &node)?;
         }
 
+        for node in scene.nodes() {
+            self.populate_gltf_node_light(&ctx, &node, Mat4::IDENTITY)?;
+        }
+
         for node in scene.nodes() {
             mesh_keys.push(self.populate_gltf_node_mesh(&ctx, &node).await?);
         }
 
+        // after meshes, since morph animations are created along with them
+        self.populate_gltf_animation_groups(&ctx)?;
+
         self.finalize_gpu_textures().await?;
 
         Ok(ctx)
@@ -209,6 +256,16 @@ impl AwsmRenderer {
 }
 
 impl GltfPopulateContext {
+    /// Returns the animation group for a named glTF animation.
+    pub fn animation_group(&self, name: &str) -> Option<AnimationGroupKey> {
+        self.key_lookups
+            .lock()
+            .unwrap()
+            .animation_groups
+            .get(name)
+            .copied()
+    }
+
     pub(super) fn resolve_animation_sampler(
         &self,
         sampler_ref: GltfAnimationSamplerRef,
@@ -228,19 +285,32 @@ impl GltfPopulateContext {
 
 fn build_node_animation_sampler_lookup(
     doc: &gltf::Document,
-) -> HashMap<GltfIndex, GltfNodeAnimationSamplers> {
-    let mut out = HashMap::<GltfIndex, GltfNodeAnimationSamplers>::new();
+) -> HashMap<GltfIndex, Vec<GltfNodeAnimationSamplers>> {
+    let mut out = HashMap::<GltfIndex, Vec<GltfNodeAnimationSamplers>>::new();
 
     for animation in doc.animations() {
         for channel in animation.channels() {
             let node_index = channel.target().node().index();
-            let entry = out.entry(node_index).or_default();
+            let node_samplers = out.entry(node_index).or_default();
+            // animations are visited in order, so this node's entry for the current animation is last
+            let entry = match node_samplers.last_mut() {
+                Some(entry) if entry.animation_index == animation.index() => entry,
+                _ => {
+                    node_samplers.push(GltfNodeAnimationSamplers {
+                        animation_index: animation.index(),
+                        ..Default::default()
+                    });
+                    node_samplers.last_mut().unwrap()
+                }
+            };
             let sampler_ref = GltfAnimationSamplerRef {
                 animation_index: animation.index(),
                 channel_index: channel.index(),
                 sampler_index: channel.sampler().index(),
             };
 
+            // within one animation a node property can only be targeted once,
+            // so first one wins if the file breaks that rule
             match channel.target().property() {
                 gltf::animation::Property::Translation => {
                     if entry.translation.is_none() {
@@ -268,3 +338,6 @@ fn build_node_animation_sampler_lookup(
 
     out
 }
+
+#[cfg(test)]
+mod tests;
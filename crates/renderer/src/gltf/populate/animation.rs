@@ -2,12 +2,14 @@ use glam::{Quat, Vec3};
 
 use crate::{
     animation::{
-        AnimationClip, AnimationData, AnimationKey, AnimationMorphKey, AnimationPlayer,
-        AnimationSampler, TransformAnimation, VertexAnimation,
+        AnimationClip, AnimationData, AnimationGroup, AnimationGroupKey, AnimationKey,
+        AnimationMorphKey, AnimationPlayer, AnimationSampler, AwsmAnimationError,
+        TransformAnimation, VertexAnimation,
     },
     buffer::helpers::u8_to_f32_vec,
     gltf::{
         buffers::accessor::accessor_to_bytes,
+        data::GltfData,
         error::{AwsmGltfError, Result},
     },
     meshes::morphs::{GeometryMorphKey, MaterialMorphKey},
@@ -18,6 +20,55 @@ use crate::{
 use super::GltfPopulateContext;
 
 impl AwsmRenderer {
+    /// Plays a named animation of a populated glTF, stopping its other animations.
+    pub fn play_gltf_animation(
+        &mut self,
+        ctx: &GltfPopulateContext,
+        name: &str,
+    ) -> crate::error::Result<AnimationGroupKey> {
+        let lookups = ctx.key_lookups.lock().unwrap();
+        let group_key = lookups
+            .animation_groups
+            .get(name)
+            .copied()
+            .ok_or_else(|| AwsmAnimationError::MissingGroupName(name.to_string()))?;
+
+        for other_key in lookups.animation_index_to_group.values() {
+            if *other_key != group_key {
+                self.animations.stop_group(*other_key)?;
+            }
+        }
+        self.animations.play_group(group_key)?;
+
+        Ok(group_key)
+    }
+
+    /// Crossfades to a named animation of a populated glTF, fading out its other animations.
+    ///
+    /// `duration` is in the same time units as `update_animations`.
+    pub fn crossfade_gltf_animation(
+        &mut self,
+        ctx: &GltfPopulateContext,
+        name: &str,
+        duration: f64,
+    ) -> crate::error::Result<AnimationGroupKey> {
+        let lookups = ctx.key_lookups.lock().unwrap();
+        let group_key = lookups
+            .animation_groups
+            .get(name)
+            .copied()
+            .ok_or_else(|| AwsmAnimationError::MissingGroupName(name.to_string()))?;
+
+        for other_key in lookups.animation_index_to_group.values() {
+            if *other_key != group_key {
+                self.animations.fade_out_group(*other_key, duration)?;
+            }
+        }
+        self.animations.fade_in_group(group_key, duration)?;
+
+        Ok(group_key)
+    }
+
     pub(super) fn populate_gltf_node_animation<'a, 'b: 'a, 'c: 'a>(
         &'a mut self,
         ctx: &'c GltfPopulateContext,
@@ -32,30 +83,44 @@ impl AwsmRenderer {
             .cloned()
             .unwrap();
 
-        if let Some(node_samplers) = ctx.node_animation_samplers.get(&gltf_node.index()) {
+        for node_samplers in ctx
+            .node_animation_samplers
+            .get(&gltf_node.index())
+            .into_iter()
+            .flatten()
+        {
+            let mut animation_keys = Vec::new();
+
             if let Some(sampler_ref) = node_samplers.translation {
-                self.populate_gltf_animation_transform_translation(
+                animation_keys.push(self.populate_gltf_animation_transform_translation(
                     ctx,
                     ctx.resolve_animation_sampler(sampler_ref)?,
                     transform_key,
-                )?;
+                )?);
             }
 
             if let Some(sampler_ref) = node_samplers.rotation {
-                self.populate_gltf_animation_transform_rotation(
+                animation_keys.push(self.populate_gltf_animation_transform_rotation(
                     ctx,
                     ctx.resolve_animation_sampler(sampler_ref)?,
                     transform_key,
-                )?;
+                )?);
             }
 
             if let Some(sampler_ref) = node_samplers.scale {
-                self.populate_gltf_animation_transform_scale(
+                animation_keys.push(self.populate_gltf_animation_transform_scale(
                     ctx,
                     ctx.resolve_animation_sampler(sampler_ref)?,
                     transform_key,
-                )?;
+                )?);
             }
+
+            ctx.animation_players
+                .lock()
+                .unwrap()
+                .entry(node_samplers.animation_index)
+                .or_default()
+                .extend(animation_keys);
         }
 
         for child in gltf_node.children() {
@@ -65,6 +130,40 @@ impl AwsmRenderer {
         Ok(())
     }
 
+    // Groups the players of each glTF animation so it can be controlled as one clip.
+    // Like most viewers, only the first animation starts out playing.
+    pub(super) fn populate_gltf_animation_groups(
+        &mut self,
+        ctx: &GltfPopulateContext,
+    ) -> Result<()> {
+        let mut animation_players = ctx.animation_players.lock().unwrap();
+        let mut first = true;
+
+        for animation in ctx.data.doc.animations() {
+            // e.g. the animation only targets nodes outside of the populated scene
+            let Some(players) = animation_players.remove(&animation.index()) else {
+                continue;
+            };
+
+            let group_key = self.animations.insert_group(AnimationGroup {
+                name: animation.name().map(|name| name.to_string()),
+                players,
+            });
+
+            if !first {
+                self.animations.stop_group(group_key)?;
+            }
+            first = false;
+
+            ctx.key_lookups
+                .lock()
+                .unwrap()
+                .insert_animation_group(&animation, group_key);
+        }
+
+        Ok(())
+    }
+
     pub(super) fn populate_gltf_animation_morph<'a, 'b: 'a, 'c: 'a>(
         &'a mut self,
         ctx: &'c GltfPopulateContext,
@@ -103,8 +202,8 @@ impl AwsmRenderer {
                 }
             };
 
-            let times = sampler_timestamps(ctx, &gltf_sampler)?;
-            let duration = (times.last().copied().unwrap_or(0.0) - times[0]) as f64;
+            let times = sampler_timestamps(&ctx.data, &gltf_sampler)?;
+            let duration = animation_duration(ctx, &gltf_sampler);
             let values = accessor_to_bytes(&gltf_sampler.output(), &ctx.data.buffers.raw)?;
             let values = u8_to_f32_vec(&values);
 
@@ -186,16 +285,37 @@ impl AwsmRenderer {
 }
 
 fn sampler_timestamps(
-    ctx: &GltfPopulateContext,
+    data: &GltfData,
     gltf_sampler: &gltf::animation::Sampler,
 ) -> Result<Vec<f64>> {
-    let bytes = accessor_to_bytes(&gltf_sampler.input(), &ctx.data.buffers.raw)?;
+    let bytes = accessor_to_bytes(&gltf_sampler.input(), &data.buffers.raw)?;
     Ok(u8_to_f32_vec(&bytes)
         .into_iter()
         .map(|v| v as f64)
         .collect())
 }
 
+/// Returns the end time of the longest channel of each animation.
+pub(super) fn gltf_animation_durations(data: &GltfData) -> Result<Vec<f64>> {
+    data.doc
+        .animations()
+        .map(|animation| {
+            animation.samplers().try_fold(0.0_f64, |duration, sampler| {
+                let times = sampler_timestamps(data, &sampler)?;
+                Ok(duration.max(times.last().copied().unwrap_or(0.0)))
+            })
+        })
+        .collect()
+}
+
+// every channel of an animation shares its duration, so looping keeps them in sync
+fn animation_duration(ctx: &GltfPopulateContext, gltf_sampler: &gltf::animation::Sampler) -> f64 {
+    ctx.animation_durations
+        .get(gltf_sampler.animation().index())
+        .copied()
+        .unwrap_or(0.0)
+}
+
 enum TransformTarget {
     Translation,
     Rotation,
@@ -225,8 +345,8 @@ fn gltf_animation_clip_transform(
     gltf_sampler: &gltf::animation::Sampler,
     target: TransformTarget,
 ) -> Result<AnimationClip> {
-    let times = sampler_timestamps(ctx, gltf_sampler)?;
-    let duration = (times.last().copied().unwrap_or(0.0) - times[0]) as f64;
+    let times = sampler_timestamps(&ctx.data, gltf_sampler)?;
+    let duration = animation_duration(ctx, gltf_sampler);
     let values = accessor_to_bytes(&gltf_sampler.output(), &ctx.data.buffers.raw)?;
     let values = u8_to_f32_vec(&values);
 
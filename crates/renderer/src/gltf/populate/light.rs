@@ -0,0 +1,92 @@
+//! glTF `KHR_lights_punctual` population.
+
+use glam::Mat4;
+use gltf::khr_lights_punctual::Kind;
+
+use crate::{
+    gltf::error::{AwsmGltfError, Result},
+    lights::Light,
+    AwsmRenderer,
+};
+
+use super::{transforms::transform_gltf_node, GltfPopulateContext};
+
+impl AwsmRenderer {
+    pub(super) fn populate_gltf_node_light<'a, 'b: 'a, 'c: 'a>(
+        &'a mut self,
+        ctx: &'c GltfPopulateContext,
+        gltf_node: &'b gltf::Node<'b>,
+        parent_world_matrix: Mat4,
+    ) -> Result<()> {
+        // the transform hierarchy hasn't been updated yet, so track the world matrix as we go
+        // to give the light its initial pose (after that, it follows the node's transform)
+        let world_matrix = parent_world_matrix * transform_gltf_node(gltf_node).to_matrix();
+
+        if let Some(gltf_light) = gltf_node.light() {
+            let transform_key = ctx
+                .key_lookups
+                .lock()
+                .unwrap()
+                .node_index_to_transform
+                .get(&gltf_node.index())
+                .cloned()
+                .ok_or(AwsmGltfError::MissingLightNodeTransform(gltf_node.index()))?;
+
+            let mut light = convert_gltf_light(&gltf_light);
+            light.apply_world_matrix(&world_matrix);
+
+            let light_key =
+                self.lights
+                    .insert_with_transform(light, transform_key, &self.transforms)?;
+
+            ctx.key_lookups
+                .lock()
+                .unwrap()
+                .insert_light(gltf_node, light_key);
+        }
+
+        for child in gltf_node.children() {
+            self.populate_gltf_node_light(ctx, &child, world_matrix)?;
+        }
+
+        Ok(())
+    }
+}
+
+// the light starts at the origin pointing down -Z, same as an untransformed glTF light
+pub(super) fn convert_gltf_light(gltf_light: &gltf::khr_lights_punctual::Light) -> Light {
+    // https://github.com/KhronosGroup/glTF/tree/main/extensions/2.0/Khronos/KHR_lights_punctual
+    let color = gltf_light.color();
+    let intensity = gltf_light.intensity();
+    // undefined range is infinite
+    let range = gltf_light.range().unwrap_or(0.0);
+    let position = [0.0, 0.0, 0.0];
+    let direction = [0.0, 0.0, -1.0];
+
+    match gltf_light.kind() {
+        Kind::Directional => Light::Directional {
+            color,
+            intensity,
+            direction,
+        },
+        Kind::Point => Light::Point {
+            color,
+            intensity,
+            position,
+            range,
+        },
+        Kind::Spot {
+            inner_cone_angle,
+            outer_cone_angle,
+        } => Light::Spot {
+            color,
+            intensity,
+            position,
+            direction,
+            range,
+            // glTF gives radians, the light takes cosines
+            inner_angle: inner_cone_angle.cos(),
+            outer_angle: outer_cone_angle.cos(),
+        },
+    }
+}
@@ -618,11 +618,119 @@ impl LocalPbrMaterialExtensions {
             });
         }
 
-        // TODO:
-        // pub diffuse_transmission: Option<PbrMaterialDiffuseTransmission>,
-        // pub dispersion: Option<PbrMaterialDispersion>,
-        // pub anisotropy: Option<PbrMaterialAnisotropy>,
-        // pub iridescence: Option<PbrMaterialIridescence>,
+        // the extensions below aren't exposed by the gltf crate, so they're read from the raw json
+        if let Some(anisotropy) = gltf_material.extension_value("KHR_materials_anisotropy") {
+            let tex = match anisotropy
+                .get("anisotropyTexture")
+                .and_then(GltfTextureInfo::from_json)
+            {
+                Some(tex_info) => Some(
+                    tex_info
+                        .create_material_texture(renderer, ctx, data_texture_color_info())
+                        .await?,
+                ),
+                None => None,
+            };
+
+            extensions.anisotropy = Some(PbrMaterialAnisotropy {
+                tex,
+                strength: json_f32(anisotropy, "anisotropyStrength").unwrap_or(0.0),
+                rotation: json_f32(anisotropy, "anisotropyRotation").unwrap_or(0.0),
+            });
+        }
+
+        if let Some(iridescence) = gltf_material.extension_value("KHR_materials_iridescence") {
+            let tex = match iridescence
+                .get("iridescenceTexture")
+                .and_then(GltfTextureInfo::from_json)
+            {
+                Some(tex_info) => Some(
+                    tex_info
+                        .create_material_texture(renderer, ctx, data_texture_color_info())
+                        .await?,
+                ),
+                None => None,
+            };
+
+            let thickness_tex = match iridescence
+                .get("iridescenceThicknessTexture")
+                .and_then(GltfTextureInfo::from_json)
+            {
+                Some(tex_info) => Some(
+                    tex_info
+                        .create_material_texture(renderer, ctx, data_texture_color_info())
+                        .await?,
+                ),
+                None => None,
+            };
+
+            // defaults from the extension spec
+            extensions.iridescence = Some(PbrMaterialIridescence {
+                tex,
+                factor: json_f32(iridescence, "iridescenceFactor").unwrap_or(0.0),
+                ior: json_f32(iridescence, "iridescenceIor").unwrap_or(1.3),
+                thickness_tex,
+                thickness_min: json_f32(iridescence, "iridescenceThicknessMinimum")
+                    .unwrap_or(100.0),
+                thickness_max: json_f32(iridescence, "iridescenceThicknessMaximum")
+                    .unwrap_or(400.0),
+            });
+        }
+
+        if let Some(dispersion) = gltf_material
+            .extension_value("KHR_materials_dispersion")
+            .and_then(|dispersion| json_f32(dispersion, "dispersion"))
+        {
+            extensions.dispersion = Some(PbrMaterialDispersion { dispersion });
+        }
+
+        if let Some(diffuse_transmission) =
+            gltf_material.extension_value("KHR_materials_diffuse_transmission")
+        {
+            let tex = match diffuse_transmission
+                .get("diffuseTransmissionTexture")
+                .and_then(GltfTextureInfo::from_json)
+            {
+                Some(tex_info) => Some(
+                    tex_info
+                        .create_material_texture(renderer, ctx, data_texture_color_info())
+                        .await?,
+                ),
+                None => None,
+            };
+
+            let color_tex = match diffuse_transmission
+                .get("diffuseTransmissionColorTexture")
+                .and_then(GltfTextureInfo::from_json)
+            {
+                Some(tex_info) => Some(
+                    tex_info
+                        .create_material_texture(
+                            renderer,
+                            ctx,
+                            TextureColorInfo {
+                                mipmap_kind: MipmapTextureKind::Albedo,
+                                srgb_to_linear: true,
+                                premultiplied_alpha: None,
+                            },
+                        )
+                        .await?,
+                ),
+                None => None,
+            };
+
+            // defaults from the extension spec
+            extensions.diffuse_transmission = Some(PbrMaterialDiffuseTransmission {
+                tex,
+                factor: json_f32(diffuse_transmission, "diffuseTransmissionFactor").unwrap_or(0.0),
+                color_tex,
+                color_factor: json_f32_array(
+                    diffuse_transmission,
+                    "diffuseTransmissionColorFactor",
+                )
+                .unwrap_or([1.0; 3]),
+            });
+        }
 
         Ok(extensions)
     }
@@ -665,47 +773,8 @@ impl<'a> From<gltf::material::NormalTexture<'a>> for GltfTextureInfo {
     fn from(info: gltf::material::NormalTexture<'a>) -> Self {
         // Extract KHR_texture_transform from extensions if present
         let texture_transform = info.extensions().and_then(|ext| {
-            ext.get("KHR_texture_transform").map(|transform_json| {
-                // Parse the extension manually
-                let offset = transform_json
-                    .get("offset")
-                    .and_then(|v| v.as_array())
-                    .map(|arr| {
-                        [
-                            arr.first().and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
-                            arr.get(1).and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
-                        ]
-                    })
-                    .unwrap_or([0.0, 0.0]);
-
-                let rotation = transform_json
-                    .get("rotation")
-                    .and_then(|v| v.as_f64())
-                    .unwrap_or(0.0) as f32;
-
-                let scale = transform_json
-                    .get("scale")
-                    .and_then(|v| v.as_array())
-                    .map(|arr| {
-                        [
-                            arr.first().and_then(|v| v.as_f64()).unwrap_or(1.0) as f32,
-                            arr.get(1).and_then(|v| v.as_f64()).unwrap_or(1.0) as f32,
-                        ]
-                    })
-                    .unwrap_or([1.0, 1.0]);
-
-                GltfTextureTransform {
-                    offset: [
-                        ordered_float::OrderedFloat(offset[0]),
-                        ordered_float::OrderedFloat(offset[1]),
-                    ],
-                    rotation: ordered_float::OrderedFloat(rotation),
-                    scale: [
-                        ordered_float::OrderedFloat(scale[0]),
-                        ordered_float::OrderedFloat(scale[1]),
-                    ],
-                }
-            })
+            ext.get("KHR_texture_transform")
+                .map(GltfTextureTransform::from_json)
         });
 
         let tex_coord_override = info
@@ -727,47 +796,8 @@ impl<'a> From<gltf::material::OcclusionTexture<'a>> for GltfTextureInfo {
     fn from(info: gltf::material::OcclusionTexture<'a>) -> Self {
         // Extract KHR_texture_transform from extensions if present
         let texture_transform = info.extensions().and_then(|ext| {
-            ext.get("KHR_texture_transform").map(|transform_json| {
-                // Parse the extension manually
-                let offset = transform_json
-                    .get("offset")
-                    .and_then(|v| v.as_array())
-                    .map(|arr| {
-                        [
-                            arr.first().and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
-                            arr.get(1).and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
-                        ]
-                    })
-                    .unwrap_or([0.0, 0.0]);
-
-                let rotation = transform_json
-                    .get("rotation")
-                    .and_then(|v| v.as_f64())
-                    .unwrap_or(0.0) as f32;
-
-                let scale = transform_json
-                    .get("scale")
-                    .and_then(|v| v.as_array())
-                    .map(|arr| {
-                        [
-                            arr.first().and_then(|v| v.as_f64()).unwrap_or(1.0) as f32,
-                            arr.get(1).and_then(|v| v.as_f64()).unwrap_or(1.0) as f32,
-                        ]
-                    })
-                    .unwrap_or([1.0, 1.0]);
-
-                GltfTextureTransform {
-                    offset: [
-                        ordered_float::OrderedFloat(offset[0]),
-                        ordered_float::OrderedFloat(offset[1]),
-                    ],
-                    rotation: ordered_float::OrderedFloat(rotation),
-                    scale: [
-                        ordered_float::OrderedFloat(scale[0]),
-                        ordered_float::OrderedFloat(scale[1]),
-                    ],
-                }
-            })
+            ext.get("KHR_texture_transform")
+                .map(GltfTextureTransform::from_json)
         });
 
         let tex_coord_override = info
@@ -785,6 +815,87 @@ impl<'a> From<gltf::material::OcclusionTexture<'a>> for GltfTextureInfo {
     }
 }
 
+impl GltfTextureInfo {
+    /// Parses a raw textureInfo, for extensions the gltf crate doesn't expose.
+    pub(crate) fn from_json(info: &gltf::json::Value) -> Option<Self> {
+        let index = info.get("index")?.as_u64()? as usize;
+        let transform_json = info
+            .get("extensions")
+            .and_then(|ext| ext.get("KHR_texture_transform"));
+
+        let tex_coord_override = transform_json
+            .and_then(|t| t.get("texCoord"))
+            .and_then(|v| v.as_u64());
+        let tex_coord = info.get("texCoord").and_then(|v| v.as_u64()).unwrap_or(0);
+
+        Some(Self {
+            index,
+            tex_coord_index: tex_coord_override.unwrap_or(tex_coord) as usize,
+            texture_transform: transform_json.map(GltfTextureTransform::from_json),
+        })
+    }
+}
+
+impl GltfTextureTransform {
+    /// Parses a raw KHR_texture_transform extension.
+    pub(crate) fn from_json(transform_json: &gltf::json::Value) -> Self {
+        let offset = transform_json
+            .get("offset")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                [
+                    arr.first().and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
+                    arr.get(1).and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
+                ]
+            })
+            .unwrap_or([0.0, 0.0]);
+
+        let rotation = json_f32(transform_json, "rotation").unwrap_or(0.0);
+
+        let scale = transform_json
+            .get("scale")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                [
+                    arr.first().and_then(|v| v.as_f64()).unwrap_or(1.0) as f32,
+                    arr.get(1).and_then(|v| v.as_f64()).unwrap_or(1.0) as f32,
+                ]
+            })
+            .unwrap_or([1.0, 1.0]);
+
+        Self {
+            offset: [OrderedFloat(offset[0]), OrderedFloat(offset[1])],
+            rotation: OrderedFloat(rotation),
+            scale: [OrderedFloat(scale[0]), OrderedFloat(scale[1])],
+        }
+    }
+}
+
+// color info for textures that hold linear data rather than color
+fn data_texture_color_info() -> TextureColorInfo {
+    TextureColorInfo {
+        mipmap_kind: MipmapTextureKind::MetallicRoughness,
+        srgb_to_linear: false,
+        premultiplied_alpha: None,
+    }
+}
+
+fn json_f32(value: &gltf::json::Value, key: &str) -> Option<f32> {
+    value.get(key).and_then(|v| v.as_f64()).map(|v| v as f32)
+}
+
+fn json_f32_array<const N: usize>(value: &gltf::json::Value, key: &str) -> Option<[f32; N]> {
+    let values = value.get(key)?.as_array()?;
+    if values.len() != N {
+        return None;
+    }
+    let mut out = [0.0; N];
+    for (out, value) in out.iter_mut().zip(values) {
+        *out = value.as_f64()? as f32;
+    }
+    Some(out)
+}
+
 impl<'a> From<gltf::texture::TextureTransform<'a>> for GltfTextureTransform {
     fn from(transform: gltf::texture::TextureTransform<'a>) -> Self {
         Self {
@@ -809,6 +920,28 @@ pub struct GLtfMaterialCacheKey {
     pub texture_transform_key: Option<TextureTransformKey>,
 }
 impl GltfTextureInfo {
+    /// Creates (or reuses) the texture and sampler and wraps them as a material texture.
+    pub async fn create_material_texture(
+        &self,
+        renderer: &mut AwsmRenderer,
+        ctx: &GltfPopulateContext,
+        color: TextureColorInfo,
+    ) -> Result<MaterialTexture> {
+        let GLtfMaterialCacheKey {
+            uv_index,
+            texture_key,
+            sampler_key,
+            texture_transform_key,
+        } = self.create_material_cache_key(renderer, ctx, color).await?;
+
+        Ok(MaterialTexture {
+            key: texture_key,
+            sampler_key: Some(sampler_key),
+            uv_index: Some(uv_index as u32),
+            transform_key: texture_transform_key,
+        })
+    }
+
     pub async fn create_material_cache_key(
         &self,
         renderer: &mut AwsmRenderer,
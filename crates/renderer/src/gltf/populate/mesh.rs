@@ -294,17 +294,27 @@ impl AwsmRenderer {
             )?
         };
 
-        if let Some(sampler_ref) = ctx
+        for node_samplers in ctx
             .node_animation_samplers
             .get(&gltf_node.index())
-            .and_then(|samplers| samplers.morph)
+            .into_iter()
+            .flatten()
         {
-            self.populate_gltf_animation_morph(
-                ctx,
-                ctx.resolve_animation_sampler(sampler_ref)?,
-                geometry_morph_key,
-                material_morph_key,
-            )?;
+            if let Some(sampler_ref) = node_samplers.morph {
+                let animation_keys = self.populate_gltf_animation_morph(
+                    ctx,
+                    ctx.resolve_animation_sampler(sampler_ref)?,
+                    geometry_morph_key,
+                    material_morph_key,
+                )?;
+
+                ctx.animation_players
+                    .lock()
+                    .unwrap()
+                    .entry(node_samplers.animation_index)
+                    .or_default()
+                    .extend(animation_keys);
+            }
         }
 
         Ok(mesh_key)
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+use slotmap::SlotMap;
+
+use crate::animation::AnimationGroupKey;
+use crate::gltf::populate::build_node_animation_sampler_lookup;
+use crate::gltf::populate::light::convert_gltf_light;
+use crate::gltf::populate::material::{GltfTextureInfo, GltfTextureTransform};
+use crate::gltf::populate::unload::GltfUnloadKeys;
+use crate::gltf::populate::GltfKeyLookups;
+use crate::lights::{Light, LightKey};
+use crate::materials::MaterialKey;
+use crate::meshes::MeshKey;
+use crate::textures::TextureKey;
+use crate::transforms::TransformKey;
+
+// two nodes, an "idle" clip on node 0 and a "walk" clip on both nodes
+// (only the structure matters here, the buffer is never read)
+const ANIMATED_GLTF: &str = r#"{
+    "asset": { "version": "2.0" },
+    "scenes": [{ "nodes": [0, 1] }],
+    "nodes": [{ "name": "body" }, { "name": "legs" }],
+    "buffers": [{ "byteLength": 64 }],
+    "bufferViews": [{ "buffer": 0, "byteLength": 64 }],
+    "accessors": [
+        { "bufferView": 0, "componentType": 5126, "count": 2, "type": "SCALAR", "min": [0.0], "max": [1.0] },
+        { "bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC3" },
+        { "bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC4" }
+    ],
+    "animations": [
+        {
+            "name": "idle",
+            "samplers": [{ "input": 0, "output": 1 }],
+            "channels": [{ "sampler": 0, "target": { "node": 0, "path": "translation" } }]
+        },
+        {
+            "name": "walk",
+            "samplers": [{ "input": 0, "output": 1 }, { "input": 0, "output": 2 }],
+            "channels": [
+                { "sampler": 0, "target": { "node": 0, "path": "translation" } },
+                { "sampler": 1, "target": { "node": 0, "path": "rotation" } },
+                { "sampler": 1, "target": { "node": 1, "path": "rotation" } }
+            ]
+        }
+    ]
+}"#;
+
+#[test]
+fn every_animation_is_kept_per_node() {
+    let gltf = gltf::Gltf::from_slice(ANIMATED_GLTF.as_bytes()).unwrap();
+    let lookup = build_node_animation_sampler_lookup(&gltf.document);
+
+    let body = &lookup[&0];
+    assert_eq!(body.len(), 2);
+
+    assert_eq!(body[0].animation_index, 0);
+    assert_eq!(body[0].translation.unwrap().animation_index, 0);
+    assert!(body[0].rotation.is_none());
+
+    assert_eq!(body[1].animation_index, 1);
+    assert_eq!(body[1].translation.unwrap().animation_index, 1);
+    assert_eq!(body[1].rotation.unwrap().sampler_index, 1);
+
+    let legs = &lookup[&1];
+    assert_eq!(legs.len(), 1);
+    assert_eq!(legs[0].animation_index, 1);
+    assert_eq!(legs[0].rotation.unwrap().channel_index, 2);
+    assert!(legs[0].translation.is_none());
+}
+
+// extensions the gltf crate doesn't know about are read from the raw json
+const ANISOTROPY_GLTF: &str = r#"{
+    "asset": { "version": "2.0" },
+    "extensionsUsed": ["KHR_materials_anisotropy", "KHR_texture_transform"],
+    "materials": [{
+        "extensions": {
+            "KHR_materials_anisotropy": {
+                "anisotropyStrength": 0.6,
+                "anisotropyRotation": 1.5,
+                "anisotropyTexture": {
+                    "index": 2,
+                    "texCoord": 1,
+                    "extensions": {
+                        "KHR_texture_transform": { "rotation": 0.5, "scale": [2.0, 3.0], "texCoord": 0 }
+                    }
+                }
+            }
+        }
+    }]
+}"#;
+
+#[test]
+fn extension_texture_info_is_read_from_json() {
+    let gltf = gltf::Gltf::from_slice(ANISOTROPY_GLTF.as_bytes()).unwrap();
+    let material = gltf.document.materials().next().unwrap();
+    let anisotropy = material
+        .extension_value("KHR_materials_anisotropy")
+        .unwrap();
+
+    let info = GltfTextureInfo::from_json(anisotropy.get("anisotropyTexture").unwrap()).unwrap();
+    assert_eq!(info.index, 2);
+    // the transform's texCoord wins
+    assert_eq!(info.tex_coord_index, 0);
+
+    let transform = info.texture_transform.unwrap();
+    assert_eq!(transform.rotation.0, 0.5);
+    assert_eq!(transform.scale.map(|v| v.0), [2.0, 3.0]);
+    assert_eq!(transform.offset.map(|v| v.0), [0.0, 0.0]);
+
+    // no index means no texture
+    assert!(GltfTextureInfo::from_json(&gltf::json::Value::Null).is_none());
+    assert_eq!(
+        GltfTextureTransform::from_json(&gltf::json::Value::Null)
+            .scale
+            .map(|v| v.0),
+        [1.0, 1.0]
+    );
+}
+
+const SPOT_LIGHT_GLTF: &str = r#"{
+    "asset": { "version": "2.0" },
+    "extensionsUsed": ["KHR_lights_punctual"],
+    "extensions": {
+        "KHR_lights_punctual": {
+            "lights": [{ "type": "spot", "spot": { "innerConeAngle": 0.25, "outerConeAngle": 0.5 } }]
+        }
+    }
+}"#;
+
+#[test]
+fn spot_cone_angles_are_converted_to_cosines() {
+    let gltf = gltf::Gltf::from_slice(SPOT_LIGHT_GLTF.as_bytes()).unwrap();
+    let gltf_light = gltf.document.lights().unwrap().next().unwrap();
+
+    match convert_gltf_light(&gltf_light) {
+        Light::Spot {
+            inner_angle,
+            outer_angle,
+            range,
+            ..
+        } => {
+            assert_eq!(inner_angle, 0.25_f32.cos());
+            assert_eq!(outer_angle, 0.5_f32.cos());
+            assert_eq!(range, 0.0);
+        }
+        light => panic!("not a spot light: {light:?}"),
+    }
+}
+
+// stands in for the renderer's key spaces, so assets loaded into it get distinct keys
+#[derive(Default)]
+struct LoadedKeys {
+    transforms: SlotMap<TransformKey, ()>,
+    meshes: SlotMap<MeshKey, ()>,
+    materials: SlotMap<MaterialKey, ()>,
+    textures: SlotMap<TextureKey, ()>,
+    lights: SlotMap<LightKey, ()>,
+    groups: SlotMap<AnimationGroupKey, ()>,
+    mesh_transforms: HashMap<MeshKey, TransformKey>,
+}
+
+struct LoadedGltf {
+    lookups: GltfKeyLookups,
+    materials: Vec<MaterialKey>,
+    textures: Vec<TextureKey>,
+    // the transform made for its skinned mesh
+    skin_transform: TransformKey,
+}
+
+impl LoadedKeys {
+    // two nodes, the first with a mesh and a light, the second with a skinned mesh
+    fn load(&mut self) -> LoadedGltf {
+        let mut lookups = GltfKeyLookups::default();
+
+        let nodes = [self.transforms.insert(()), self.transforms.insert(())];
+        let skin_transform = self.transforms.insert(());
+        let meshes = [self.meshes.insert(()), self.meshes.insert(())];
+        self.mesh_transforms.insert(meshes[0], nodes[0]);
+        self.mesh_transforms.insert(meshes[1], skin_transform);
+
+        for (index, (node, mesh)) in nodes.into_iter().zip(meshes).enumerate() {
+            lookups.node_index_to_transform.insert(index, node);
+            lookups.all_mesh_keys.insert(index, vec![mesh]);
+        }
+        lookups
+            .node_index_to_light
+            .insert(0, self.lights.insert(()));
+        lookups
+            .animation_index_to_group
+            .insert(0, self.groups.insert(()));
+
+        LoadedGltf {
+            lookups,
+            materials: vec![self.materials.insert(())],
+            textures: vec![self.textures.insert(()), self.textures.insert(())],
+            skin_transform,
+        }
+    }
+
+    fn unload_keys(&self, gltf: &LoadedGltf) -> GltfUnloadKeys {
+        GltfUnloadKeys::new(
+            &gltf.lookups,
+            gltf.materials.iter().copied(),
+            gltf.textures.iter().copied(),
+            [],
+            |mesh_key| self.mesh_transforms.get(&mesh_key).copied(),
+        )
+    }
+}
+
+#[test]
+fn unloading_one_gltf_keeps_the_other() {
+    let mut keys = LoadedKeys::default();
+    let first = keys.load();
+    let second = keys.load();
+    let user_transform = keys.transforms.insert(());
+
+    let unload = keys.unload_keys(&first);
+
+    // everything the first one made
+    assert_eq!(unload.meshes.len(), 2);
+    assert_eq!(unload.transforms.len(), 3);
+    assert!(unload.transforms.contains(&first.skin_transform));
+    assert_eq!(unload.materials, first.materials);
+    assert_eq!(unload.textures, first.textures);
+    assert_eq!(unload.lights.len(), 1);
+    assert_eq!(unload.animation_groups.len(), 1);
+
+    // and nothing of the second one, or of the user's
+    let kept = keys.unload_keys(&second);
+    assert!(unload.meshes.iter().all(|key| !kept.meshes.contains(key)));
+    assert!(unload.transforms.is_disjoint(&kept.transforms));
+    assert!(!unload.transforms.contains(&user_transform));
+    assert!(unload
+        .materials
+        .iter()
+        .all(|key| !second.materials.contains(key)));
+    assert!(unload
+        .textures
+        .iter()
+        .all(|key| !second.textures.contains(key)));
+    assert!(unload.lights.iter().all(|key| !kept.lights.contains(key)));
+    assert!(unload
+        .animation_groups
+        .iter()
+        .all(|key| !kept.animation_groups.contains(key)));
+}
@@ -0,0 +1,159 @@
+//! Removing a populated glTF asset.
+
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    animation::{AnimationGroupKey, AnimationMorphKey},
+    lights::LightKey,
+    materials::MaterialKey,
+    meshes::MeshKey,
+    textures::TextureKey,
+    transforms::TransformKey,
+    AwsmRenderer,
+};
+
+use super::{GltfKeyLookups, GltfPopulateContext};
+
+impl AwsmRenderer {
+    /// Removes everything `populate_gltf` created for this asset, including its cache entry.
+    ///
+    /// Materials and textures are only freed once nothing else uses them, and resources
+    /// shared with other assets (like samplers) are kept.
+    ///
+    /// Things that weren't created by the asset but depend on it are kept too: transforms
+    /// parented under its nodes move up to the root with their world pose, lights following its
+    /// nodes keep their last pose, and animation players on its nodes or morphs are removed.
+    pub async fn unload_gltf(&mut self, ctx: GltfPopulateContext) -> crate::error::Result<()> {
+        let keys = GltfUnloadKeys::new(
+            &ctx.key_lookups.lock().unwrap(),
+            ctx.material_keys.lock().unwrap().values().copied(),
+            ctx.textures.lock().unwrap().values().copied(),
+            ctx.transform_is_instanced.lock().unwrap().iter().copied(),
+            |mesh_key| {
+                self.meshes
+                    .get(mesh_key)
+                    .ok()
+                    .map(|mesh| mesh.transform_key)
+            },
+        );
+
+        for group_key in &keys.animation_groups {
+            self.animations.remove_group(*group_key);
+        }
+
+        // morphs belong to the mesh resource, which duplicates elsewhere may keep alive
+        let mut morph_keys = HashSet::new();
+        for mesh_key in &keys.meshes {
+            if let Ok(resource) = self.meshes.resource(*mesh_key) {
+                morph_keys.extend(resource.geometry_morph_key.map(AnimationMorphKey::from));
+                morph_keys.extend(resource.material_morph_key.map(AnimationMorphKey::from));
+            }
+        }
+
+        for mesh_key in &keys.meshes {
+            self.remove_mesh(*mesh_key);
+        }
+
+        let morphs = &self.meshes.morphs;
+        morph_keys.retain(|morph_key| match morph_key {
+            AnimationMorphKey::Geometry(key) => morphs.geometry.get_info(*key).is_err(),
+            AnimationMorphKey::Material(key) => morphs.material.get_info(*key).is_err(),
+        });
+
+        for material_key in &keys.materials {
+            if self.meshes.material_ref_count(*material_key) > 0 {
+                continue;
+            }
+
+            if let Some(material) = self.materials.remove(*material_key) {
+                // each material texture slot got its own transform
+                for material_texture in material.textures() {
+                    if let Some(transform_key) = material_texture.transform_key {
+                        self.textures.remove_texture_transform(transform_key);
+                    }
+                }
+            }
+        }
+
+        let mut removed_textures = false;
+        for texture_key in &keys.textures {
+            if self.materials.texture_ref_count(*texture_key) == 0 {
+                removed_textures |= self.textures.remove_image(*texture_key);
+            }
+        }
+
+        for light_key in &keys.lights {
+            self.lights.remove(*light_key);
+        }
+        self.lights.unbind_transforms(&keys.transforms);
+
+        self.animations
+            .remove_targets(&keys.transforms, &morph_keys);
+
+        for transform_key in &keys.instanced_transforms {
+            self.instances.transform_remove(*transform_key);
+        }
+
+        for transform_key in &keys.transforms {
+            self.transforms.remove(*transform_key);
+        }
+
+        self.gltf
+            .raw_datas
+            .retain(|data| !Arc::ptr_eq(data, &ctx.data));
+
+        if removed_textures {
+            // pool entries and sampler indices may have moved
+            self.materials.rewrite_all(&self.textures);
+            self.finalize_gpu_textures().await?;
+        }
+
+        Ok(())
+    }
+}
+
+// the keys one populated asset created, some are only freed if nothing else uses them
+#[derive(Debug, Default)]
+pub(super) struct GltfUnloadKeys {
+    pub animation_groups: Vec<AnimationGroupKey>,
+    pub meshes: Vec<MeshKey>,
+    pub materials: Vec<MaterialKey>,
+    pub textures: Vec<TextureKey>,
+    pub lights: Vec<LightKey>,
+    pub transforms: HashSet<TransformKey>,
+    pub instanced_transforms: Vec<TransformKey>,
+}
+
+impl GltfUnloadKeys {
+    pub fn new(
+        lookups: &GltfKeyLookups,
+        materials: impl IntoIterator<Item = MaterialKey>,
+        textures: impl IntoIterator<Item = TextureKey>,
+        instanced_transforms: impl IntoIterator<Item = TransformKey>,
+        mesh_transform: impl Fn(MeshKey) -> Option<TransformKey>,
+    ) -> Self {
+        let meshes: Vec<MeshKey> = lookups.all_mesh_keys.values().flatten().copied().collect();
+
+        // skinned meshes get their own transform next to the node's
+        let transforms = lookups
+            .node_index_to_transform
+            .values()
+            .copied()
+            .chain(
+                meshes
+                    .iter()
+                    .filter_map(|mesh_key| mesh_transform(*mesh_key)),
+            )
+            .collect();
+
+        Self {
+            animation_groups: lookups.animation_index_to_group.values().copied().collect(),
+            meshes,
+            materials: materials.into_iter().collect(),
+            textures: textures.into_iter().collect(),
+            lights: lookups.node_index_to_light.values().copied().collect(),
+            transforms,
+            instanced_transforms: instanced_transforms.into_iter().collect(),
+        }
+    }
+}
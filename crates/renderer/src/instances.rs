@@ -59,6 +59,16 @@ impl Instances {
         self.transform_dirty.insert(key);
     }
 
+    /// Removes the instance transforms for a key.
+    pub fn transform_remove(&mut self, key: TransformKey) {
+        if self.cpu_transforms.remove(key).is_some() {
+            self.transform_buffer.remove(key);
+            self.transform_count.remove(key);
+            self.transform_dirty.remove(&key);
+            self.transform_gpu_dirty = true;
+        }
+    }
+
     /// Updates a single instance transform.
     pub fn transform_update(&mut self, key: TransformKey, index: usize, transform: &Transform) {
         if let Some(list) = self.cpu_transforms.get_mut(key) {
@@ -197,6 +207,12 @@ impl Instances {
         std::mem::take(&mut self.transform_dirty)
     }
 
+    // after a device loss, the next write_gpu recreates the buffer from the CPU data
+    pub(crate) fn mark_gpu_lost(&mut self) {
+        self.transform_buffer.mark_gpu_lost();
+        self.transform_gpu_dirty = true;
+    }
+
     // This *does* write to the gpu, should be called only once per frame
     // just write the entire buffer in one fell swoop
     /// Writes instance transforms to the GPU.
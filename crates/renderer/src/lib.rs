@@ -11,6 +11,7 @@ pub mod bounds;
 pub mod buffer;
 pub mod camera;
 pub mod debug;
+pub mod device;
 pub mod environment;
 pub mod error;
 pub mod frustum;
@@ -22,6 +23,7 @@ pub mod picker;
 pub mod pipeline_layouts;
 pub mod pipelines;
 pub mod post_process;
+pub mod raycast;
 pub mod render;
 pub mod render_passes;
 pub mod render_textures;
@@ -30,6 +32,7 @@ pub mod shaders;
 pub mod textures;
 pub mod transforms;
 pub mod update;
+pub mod views;
 // re-export
 pub mod core {
     pub use awsm_renderer_core::*;
@@ -71,6 +74,7 @@ use crate::{
     post_process::PostProcessing,
     render_passes::{RenderPassInitContext, RenderPasses},
     render_textures::{RenderTextureFormats, RenderTextures},
+    views::RenderViews,
 };
 
 /// Main renderer state and GPU resources.
@@ -95,6 +99,7 @@ pub struct AwsmRenderer {
     pub anti_aliasing: AntiAliasing,
     pub post_processing: PostProcessing,
     pub picker: Picker,
+    pub views: RenderViews,
     // we pick between these on the fly
     _clear_color_perceptual_to_linear: Color,
     _clear_color: Color,
@@ -109,7 +114,7 @@ pub struct AwsmRenderer {
 /// Compatibility requirements for this renderer.
 pub static COMPATIBITLIY_REQUIREMENTS: LazyLock<CompatibilityRequirements> =
     LazyLock::new(|| CompatibilityRequirements {
-        storage_buffers: Some(9),
+        storage_buffers: Some(10),
     });
 
 impl AwsmRenderer {
@@ -364,6 +369,7 @@ impl AwsmRendererBuilder {
             anti_aliasing,
             post_processing,
             picker,
+            views: RenderViews::default(),
             #[cfg(feature = "gltf")]
             gltf,
             #[cfg(feature = "animation")]
@@ -1,7 +1,10 @@
 //! Lighting data and GPU uploads.
 
+pub mod culling;
 pub mod ibl;
+pub mod shadows;
 
+use std::collections::HashSet;
 use std::sync::LazyLock;
 
 use awsm_renderer_core::{
@@ -11,12 +14,15 @@ use awsm_renderer_core::{
     error::AwsmCoreError,
     renderer::AwsmRendererWebGpu,
 };
-use slotmap::{new_key_type, SlotMap};
+use glam::{Mat4, Vec3};
+use slotmap::{new_key_type, SecondaryMap, SlotMap};
 use thiserror::Error;
 
 use crate::{
     bind_groups::{BindGroupCreate, BindGroups},
-    lights::ibl::Ibl,
+    camera::CameraMatrices,
+    lights::{culling::LightTiles, ibl::Ibl, shadows::Shadows},
+    transforms::{TransformKey, Transforms},
     AwsmRenderer, AwsmRendererLogging,
 };
 
@@ -143,9 +149,13 @@ impl AwsmRenderer {
 pub struct Lights {
     pub gpu_punctual_buffer: web_sys::GpuBuffer,
     pub gpu_info_buffer: web_sys::GpuBuffer,
+    pub tiles: LightTiles,
+    pub shadows: Shadows,
     pub ibl: Ibl,
     pub brdf_lut: BrdfLut,
     lights: SlotMap<LightKey, Light>,
+    // lights that follow a node in the transform hierarchy
+    transforms: SecondaryMap<LightKey, TransformKey>,
     // We do not use DynamicUniformBuffer here because we need dense sequential access in the gpu
     // not stable offsets per-key that DynamicUniformBuffer provides (with holes, etc)
     // instead, we rebuild a fresh Vec<u8> when the gpu is dirty
@@ -179,8 +189,12 @@ impl Lights {
             &BufferDescriptor::new(Some("Lights Info"), Self::INFO_SIZE, *INFO_BUFFER_USAGE).into(),
         )?;
 
+        let tiles = LightTiles::new(gpu)?;
+        let shadows = Shadows::new(gpu)?;
+
         Ok(Lights {
             lights: SlotMap::with_key(),
+            transforms: SecondaryMap::new(),
             ibl,
             brdf_lut,
             punctual_gpu_size,
@@ -188,12 +202,16 @@ impl Lights {
             lighting_info_gpu_dirty: true,
             gpu_punctual_buffer,
             gpu_info_buffer,
+            tiles,
+            shadows,
         })
     }
 
     /// Removes all lights.
     pub fn clear(&mut self) {
         self.lights.clear();
+        self.transforms.clear();
+        self.shadows.clear();
         self.punctual_gpu_dirty = true;
         self.lighting_info_gpu_dirty = true;
     }
@@ -210,11 +228,15 @@ impl Lights {
     /// Removes a light by key.
     pub fn remove(&mut self, key: LightKey) {
         self.lights.remove(key);
+        self.transforms.remove(key);
+        self.shadows.remove(key);
         self.punctual_gpu_dirty = true;
         self.lighting_info_gpu_dirty = true;
     }
 
     /// Updates a light in place.
+    ///
+    /// The position and direction of a light bound to a transform follow the transform instead.
     pub fn update(&mut self, key: LightKey, f: impl FnOnce(&mut Light)) {
         if let Some(light) = self.lights.get_mut(key) {
             f(light);
@@ -222,13 +244,123 @@ impl Lights {
         }
     }
 
+    /// Returns a light by key.
+    pub fn get(&self, key: LightKey) -> Option<&Light> {
+        self.lights.get(key)
+    }
+
+    /// Inserts a light that follows a transform, see [`Lights::set_transform`].
+    pub fn insert_with_transform(
+        &mut self,
+        light: Light,
+        transform_key: TransformKey,
+        transforms: &Transforms,
+    ) -> Result<LightKey> {
+        if transforms.get_local(transform_key).is_err() {
+            return Err(AwsmLightError::TransformNotFound(transform_key));
+        }
+
+        let key = self.insert(light)?;
+        self.transforms.insert(key, transform_key);
+        Ok(key)
+    }
+
+    /// Binds a light to a transform, or unbinds it with `None`.
+    ///
+    /// A bound light sits at the origin of the transform's world matrix and points down its -Z
+    /// axis (the glTF convention), so use a child transform to offset or aim it. The position and
+    /// direction are refreshed in `write_gpu`, which means animations and parent changes carry
+    /// over without any syncing, and values written through `update` are overwritten.
+    /// If the transform is removed, the light keeps its last pose. Binding to a transform that
+    /// doesn't exist is an error.
+    pub fn set_transform(
+        &mut self,
+        key: LightKey,
+        transform_key: Option<TransformKey>,
+        transforms: &Transforms,
+    ) -> Result<()> {
+        if !self.lights.contains_key(key) {
+            return Err(AwsmLightError::NotFound(key));
+        }
+
+        match transform_key {
+            Some(transform_key) => {
+                if transforms.get_local(transform_key).is_err() {
+                    return Err(AwsmLightError::TransformNotFound(transform_key));
+                }
+                self.transforms.insert(key, transform_key);
+            }
+            None => {
+                self.transforms.remove(key);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unbinds every light that follows one of these transforms, e.g. before removing them.
+    ///
+    /// The lights keep their last pose.
+    pub fn unbind_transforms(&mut self, transform_keys: &HashSet<TransformKey>) {
+        self.transforms
+            .retain(|_, transform_key| !transform_keys.contains(transform_key));
+    }
+
+    /// Returns the transform a light follows, if any.
+    pub fn transform(&self, key: LightKey) -> Option<TransformKey> {
+        self.transforms.get(key).copied()
+    }
+
+    /// Iterates lights in GPU buffer order (the order used by the light tile indices).
+    pub fn values(&self) -> impl Iterator<Item = &Light> {
+        self.lights.values()
+    }
+
+    // after a device loss, recreates the buffers, tiles and shadow map and marks everything
+    // for upload. IBL and the BRDF LUT are restored by the renderer since they need textures.
+    pub(crate) fn restore_gpu(&mut self, gpu: &AwsmRendererWebGpu) -> Result<()> {
+        self.gpu_punctual_buffer = gpu.create_buffer(
+            &BufferDescriptor::new(
+                Some("Punctual Lights"),
+                self.punctual_gpu_size,
+                *PUNCTUAL_BUFFER_USAGE,
+            )
+            .into(),
+        )?;
+
+        self.gpu_info_buffer = gpu.create_buffer(
+            &BufferDescriptor::new(Some("Lights Info"), Self::INFO_SIZE, *INFO_BUFFER_USAGE).into(),
+        )?;
+
+        self.tiles = LightTiles::new(gpu)?;
+        self.shadows.restore_gpu(gpu)?;
+
+        self.punctual_gpu_dirty = true;
+        self.lighting_info_gpu_dirty = true;
+
+        Ok(())
+    }
+
     /// Writes lighting buffers to the GPU if dirty.
+    ///
+    /// Lights attached to a transform first pick up its current world matrix.
+    /// The camera is used to fit directional light shadow cascades.
     pub fn write_gpu(
         &mut self,
         logging: &AwsmRendererLogging,
         gpu: &AwsmRendererWebGpu,
+        transforms: &Transforms,
+        camera: Option<&CameraMatrices>,
         bind_groups: &mut BindGroups,
     ) -> Result<()> {
+        if apply_light_transforms(
+            &mut self.lights,
+            &self.transforms,
+            transforms.world_matrices_ref(),
+        ) {
+            self.punctual_gpu_dirty = true;
+        }
+
         if self.punctual_gpu_dirty {
             let _maybe_span_guard = if logging.render_timings {
                 Some(
@@ -242,10 +374,20 @@ impl Lights {
                 None
             };
 
+            let shadow_slots = self.shadows.slots(self.lights.iter());
+
             let punctual_light_buffer: Vec<u8> = self
                 .lights
-                .values()
-                .flat_map(|light| light.storage_buffer_data())
+                .iter()
+                .flat_map(|(key, light)| {
+                    let mut data = light.storage_buffer_data();
+                    // kind_outer_pad.z: shadow slot + 1, 0 for no shadow
+                    if let Some(slot) = shadow_slots.iter().position(|slot| slot.light_key == key) {
+                        data[Light::SHADOW_BYTE_OFFSET..Light::SHADOW_BYTE_OFFSET + 4]
+                            .copy_from_slice(&((slot + 1) as f32).to_ne_bytes());
+                    }
+                    data
+                })
                 .collect();
 
             // GPU size should never be 0, so use at least PUNCTUAL_LIGHT_SIZE
@@ -306,12 +448,41 @@ impl Lights {
 
             self.lighting_info_gpu_dirty = false;
         }
+
+        self.shadows
+            .write_gpu(logging, gpu, &self.lights, camera, bind_groups)?;
+
         Ok(())
     }
 }
 
+/// Moves lights bound to a transform to its current world matrix.
+///
+/// Lights whose transform no longer exists keep their last pose.
+/// Returns `true` if any light moved.
+pub(crate) fn apply_light_transforms(
+    lights: &mut SlotMap<LightKey, Light>,
+    bindings: &SecondaryMap<LightKey, TransformKey>,
+    world_matrices: &SecondaryMap<TransformKey, Mat4>,
+) -> bool {
+    let mut changed = false;
+
+    for (key, transform_key) in bindings.iter() {
+        if let (Some(light), Some(world)) =
+            (lights.get_mut(key), world_matrices.get(*transform_key))
+        {
+            changed |= light.apply_world_matrix(world);
+        }
+    }
+
+    changed
+}
+
 /// Punctual light definitions.
-#[derive(Debug, Clone)]
+///
+/// Directions point the way the light travels, spot cone angles are the cosines of the cone
+/// half-angles (as used by the shader), and a range of 0 means infinite.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Light {
     Directional {
         color: [f32; 3],
@@ -338,6 +509,8 @@ pub enum Light {
 impl Light {
     /// Packed byte size for a light in the storage buffer.
     pub const BYTE_SIZE: usize = 64;
+    /// Byte offset of the shadow slot in the packed data (`kind_outer_pad.z`).
+    pub const SHADOW_BYTE_OFFSET: usize = 56;
 
     /// Returns a numeric tag for shader selection.
     pub fn enum_value(&self) -> f32 {
@@ -349,6 +522,44 @@ impl Light {
         }
     }
 
+    /// Places the light at the origin of a world matrix, pointing down its -Z axis.
+    ///
+    /// This is the glTF `KHR_lights_punctual` convention. Returns `true` if the light moved.
+    pub fn apply_world_matrix(&mut self, world: &Mat4) -> bool {
+        let world_position = world.transform_point3(Vec3::ZERO).to_array();
+        let world_direction = world
+            .transform_vector3(Vec3::NEG_Z)
+            .normalize_or(Vec3::NEG_Z)
+            .to_array();
+
+        let mut changed = false;
+        let mut set = |target: &mut [f32; 3], value: [f32; 3]| {
+            if *target != value {
+                *target = value;
+                changed = true;
+            }
+        };
+
+        match self {
+            Light::Directional { direction, .. } => {
+                set(direction, world_direction);
+            }
+            Light::Point { position, .. } => {
+                set(position, world_position);
+            }
+            Light::Spot {
+                position,
+                direction,
+                ..
+            } => {
+                set(position, world_position);
+                set(direction, world_direction);
+            }
+        }
+
+        changed
+    }
+
     // matches LightPacked
     /// Returns the packed storage buffer payload for this light.
     pub fn storage_buffer_data(&self) -> [u8; Self::BYTE_SIZE] {
@@ -399,11 +610,12 @@ impl Light {
         // struct LightPacked {
         //   // pos.xyz + range
         //   pos_range: vec4<f32>,
-        //   // dir.xyz + inner_cone
+        //   // dir.xyz + inner_cone (radians)
         //   dir_inner: vec4<f32>,
         //   // color.rgb + intensity
         //   color_intensity: vec4<f32>,
-        //   // kind (as uint) + outer_cone + 2 pads (or extra params)
+        //   // kind (as uint) + outer_cone (radians) + shadow slot + 1 pad
+        //   // (the shadow slot is written by Lights::write_gpu)
         //   kind_outer_pad: vec4<f32>,
         // };
 
@@ -486,4 +698,13 @@ type Result<T> = std::result::Result<T, AwsmLightError>;
 pub enum AwsmLightError {
     #[error("[light] {0:?}")]
     Core(#[from] AwsmCoreError),
+
+    #[error("[light] not found: {0:?}")]
+    NotFound(LightKey),
+
+    #[error("[light] transform not found: {0:?}")]
+    TransformNotFound(TransformKey),
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,283 @@
+//! Tiled light culling buffers and CPU reference binning.
+//!
+//! The screen is split into `LIGHT_TILE_SIZE` square tiles. Each frame the light culling
+//! pass reduces the geometry-pass depth of every tile to a min/max range, builds a view-space
+//! box from it, and writes the indices of the lights whose range intersects that box.
+//!
+//! Each tile stores two lists back to back:
+//! - opaque: bounded by the tile's min/max geometry depth
+//! - transparent: bounded by the near plane and the tile's max geometry depth,
+//!   since transparent surfaces may sit anywhere in front of the opaque depth
+//!
+//! A list is `[count, index_0, index_1, ...]` with room for `LIGHT_TILE_MAX_LIGHTS` indices.
+//! Lights beyond that limit are dropped for the tile.
+//!
+//! [`bin_lights`] mirrors the GPU algorithm on the CPU so the binning can be tested
+//! without a device.
+
+use std::sync::LazyLock;
+
+use awsm_renderer_core::{
+    buffers::{BufferDescriptor, BufferUsage},
+    renderer::AwsmRendererWebGpu,
+};
+use glam::{Mat4, Vec3, Vec4};
+
+use crate::{
+    bind_groups::{BindGroupCreate, BindGroups},
+    bounds::Aabb,
+    lights::{Light, Result},
+};
+
+/// Width and height of a light tile in pixels (matches the culling workgroup size).
+pub const LIGHT_TILE_SIZE: u32 = 16;
+/// Maximum number of lights stored in one tile list.
+pub const LIGHT_TILE_MAX_LIGHTS: u32 = 63;
+/// Number of u32 words in one tile list (count + indices).
+pub const LIGHT_TILE_LIST_STRIDE: u32 = LIGHT_TILE_MAX_LIGHTS + 1;
+/// Number of u32 words per tile (opaque list + transparent list).
+pub const LIGHT_TILE_STRIDE: u32 = LIGHT_TILE_LIST_STRIDE * 2;
+
+static TILE_BUFFER_USAGE: LazyLock<BufferUsage> =
+    LazyLock::new(|| BufferUsage::new().with_storage());
+
+/// GPU storage for the per-tile light index lists.
+pub struct LightTiles {
+    pub gpu_buffer: web_sys::GpuBuffer,
+    width: u32,
+    height: u32,
+}
+
+impl LightTiles {
+    /// Creates a tile buffer for a 1x1 screen, resized on the first render.
+    pub fn new(gpu: &AwsmRendererWebGpu) -> Result<Self> {
+        Ok(Self {
+            gpu_buffer: Self::create_buffer(gpu, 1, 1)?,
+            width: 1,
+            height: 1,
+        })
+    }
+
+    /// Returns the number of tiles horizontally and vertically for a screen size.
+    pub fn tile_count(width: u32, height: u32) -> (u32, u32) {
+        (
+            width.div_ceil(LIGHT_TILE_SIZE).max(1),
+            height.div_ceil(LIGHT_TILE_SIZE).max(1),
+        )
+    }
+
+    /// Resizes the tile buffer to cover the given screen size.
+    pub fn resize(
+        &mut self,
+        gpu: &AwsmRendererWebGpu,
+        width: u32,
+        height: u32,
+        bind_groups: &mut BindGroups,
+    ) -> Result<()> {
+        if Self::tile_count(width, height) != Self::tile_count(self.width, self.height) {
+            self.gpu_buffer = Self::create_buffer(gpu, width, height)?;
+            bind_groups.mark_create(BindGroupCreate::LightTilesResize);
+        }
+
+        self.width = width;
+        self.height = height;
+
+        Ok(())
+    }
+
+    fn create_buffer(
+        gpu: &AwsmRendererWebGpu,
+        width: u32,
+        height: u32,
+    ) -> Result<web_sys::GpuBuffer> {
+        let (tiles_x, tiles_y) = Self::tile_count(width, height);
+        let size = (tiles_x * tiles_y * LIGHT_TILE_STRIDE) as usize * 4;
+
+        Ok(gpu.create_buffer(
+            &BufferDescriptor::new(Some("Light Tiles"), size, *TILE_BUFFER_USAGE).into(),
+        )?)
+    }
+}
+
+/// CPU-side result of binning lights into screen tiles.
+#[derive(Debug, Clone, Default)]
+pub struct LightTileGrid {
+    pub tiles_x: u32,
+    pub tiles_y: u32,
+    /// Light indices per tile for opaque shading, row-major.
+    pub opaque: Vec<Vec<u32>>,
+    /// Light indices per tile for transparent shading, row-major.
+    pub transparent: Vec<Vec<u32>>,
+}
+
+impl LightTileGrid {
+    /// Returns the tile index containing a pixel.
+    pub fn tile_index(&self, x: u32, y: u32) -> usize {
+        ((y / LIGHT_TILE_SIZE) * self.tiles_x + (x / LIGHT_TILE_SIZE)) as usize
+    }
+}
+
+/// Bins lights into screen tiles, mirroring the light culling compute shader.
+///
+/// `lights` must be in the same order as the GPU light buffer, `depth` is the
+/// row-major geometry-pass depth (NDC, 1.0 = cleared) of `width * height` pixels.
+pub fn bin_lights(
+    lights: &[Light],
+    view: &Mat4,
+    projection: &Mat4,
+    width: u32,
+    height: u32,
+    depth: &[f32],
+) -> LightTileGrid {
+    let (tiles_x, tiles_y) = LightTiles::tile_count(width, height);
+    let inv_projection = projection.inverse();
+
+    let mut grid = LightTileGrid {
+        tiles_x,
+        tiles_y,
+        opaque: Vec::with_capacity((tiles_x * tiles_y) as usize),
+        transparent: Vec::with_capacity((tiles_x * tiles_y) as usize),
+    };
+
+    for tile_y in 0..tiles_y {
+        for tile_x in 0..tiles_x {
+            let depth_range = tile_depth_range(depth, width, height, tile_x, tile_y);
+
+            let opaque_bounds = depth_range.map(|(min, max)| {
+                tile_view_bounds(&inv_projection, width, height, tile_x, tile_y, min, max)
+            });
+            let transparent_bounds = tile_view_bounds(
+                &inv_projection,
+                width,
+                height,
+                tile_x,
+                tile_y,
+                0.0,
+                depth_range.map(|(_, max)| max).unwrap_or(1.0),
+            );
+
+            let mut opaque = Vec::new();
+            let mut transparent = Vec::new();
+
+            for (index, light) in lights.iter().enumerate() {
+                if let Some(bounds) = &opaque_bounds {
+                    if opaque.len() < LIGHT_TILE_MAX_LIGHTS as usize
+                        && light_intersects_view_bounds(light, view, bounds)
+                    {
+                        opaque.push(index as u32);
+                    }
+                }
+                if transparent.len() < LIGHT_TILE_MAX_LIGHTS as usize
+                    && light_intersects_view_bounds(light, view, &transparent_bounds)
+                {
+                    transparent.push(index as u32);
+                }
+            }
+
+            grid.opaque.push(opaque);
+            grid.transparent.push(transparent);
+        }
+    }
+
+    grid
+}
+
+/// Returns the min/max depth of the geometry inside a tile, or `None` if the tile
+/// only contains cleared depth.
+pub fn tile_depth_range(
+    depth: &[f32],
+    width: u32,
+    height: u32,
+    tile_x: u32,
+    tile_y: u32,
+) -> Option<(f32, f32)> {
+    let x_start = tile_x * LIGHT_TILE_SIZE;
+    let y_start = tile_y * LIGHT_TILE_SIZE;
+    let x_end = (x_start + LIGHT_TILE_SIZE).min(width);
+    let y_end = (y_start + LIGHT_TILE_SIZE).min(height);
+
+    let mut range: Option<(f32, f32)> = None;
+
+    for y in y_start..y_end {
+        for x in x_start..x_end {
+            let d = depth[(y * width + x) as usize];
+            if d >= 1.0 {
+                continue;
+            }
+            range = Some(match range {
+                Some((min, max)) => (min.min(d), max.max(d)),
+                None => (d, d),
+            });
+        }
+    }
+
+    range
+}
+
+/// Returns the view-space bounds of a tile between two NDC depths.
+pub fn tile_view_bounds(
+    inv_projection: &Mat4,
+    width: u32,
+    height: u32,
+    tile_x: u32,
+    tile_y: u32,
+    min_depth: f32,
+    max_depth: f32,
+) -> Aabb {
+    let x0 = (tile_x * LIGHT_TILE_SIZE) as f32;
+    let y0 = (tile_y * LIGHT_TILE_SIZE) as f32;
+    let x1 = ((tile_x + 1) * LIGHT_TILE_SIZE).min(width) as f32;
+    let y1 = ((tile_y + 1) * LIGHT_TILE_SIZE).min(height) as f32;
+
+    let ndc_x = |px: f32| (px / width as f32) * 2.0 - 1.0;
+    let ndc_y = |py: f32| 1.0 - (py / height as f32) * 2.0;
+
+    let mut bounds: Option<Aabb> = None;
+
+    for x in [x0, x1] {
+        for y in [y0, y1] {
+            for z in [min_depth, max_depth] {
+                let p = *inv_projection * Vec4::new(ndc_x(x), ndc_y(y), z, 1.0);
+                let p = p.truncate() / p.w;
+                match &mut bounds {
+                    Some(bounds) => {
+                        bounds.min = bounds.min.min(p);
+                        bounds.max = bounds.max.max(p);
+                    }
+                    None => bounds = Some(Aabb::new(p, p)),
+                }
+            }
+        }
+    }
+
+    // always Some, the loops above run 8 times
+    bounds.unwrap()
+}
+
+/// Returns true if a light may affect anything inside view-space bounds.
+///
+/// Directional lights and lights with infinite range (0) always pass.
+/// Spot lights are tested as spheres, which is conservative.
+pub fn light_intersects_view_bounds(light: &Light, view: &Mat4, bounds: &Aabb) -> bool {
+    let (position, range) = match light {
+        Light::Directional { .. } => return true,
+        Light::Point {
+            position, range, ..
+        } => (position, *range),
+        Light::Spot {
+            position, range, ..
+        } => (position, *range),
+    };
+
+    if range <= 0.0 {
+        return true;
+    }
+
+    let center = view.transform_point3(Vec3::from_array(*position));
+    let closest = center.clamp(bounds.min, bounds.max);
+
+    closest.distance_squared(center) <= range * range
+}
+
+#[cfg(test)]
+mod tests;
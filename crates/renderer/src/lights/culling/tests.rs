@@ -0,0 +1,130 @@
+use glam::{Mat4, Vec3, Vec4};
+
+use crate::lights::culling::{
+    bin_lights, tile_depth_range, LightTileGrid, LightTiles, LIGHT_TILE_MAX_LIGHTS,
+};
+use crate::lights::Light;
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 64;
+
+fn projection() -> Mat4 {
+    Mat4::perspective_rh(90.0_f32.to_radians(), 1.0, 0.1, 100.0)
+}
+
+fn point(position: [f32; 3], range: f32) -> Light {
+    Light::Point {
+        color: [1.0, 1.0, 1.0],
+        intensity: 1.0,
+        position,
+        range,
+    }
+}
+
+fn bin(lights: &[Light], view: &Mat4, depth: &[f32]) -> LightTileGrid {
+    bin_lights(lights, view, &projection(), WIDTH, HEIGHT, depth)
+}
+
+// depth buffer with a flat wall at view-space z
+fn flat_depth(view_z: f32) -> Vec<f32> {
+    let clip = projection() * Vec4::new(0.0, 0.0, view_z, 1.0);
+    vec![clip.z / clip.w; (WIDTH * HEIGHT) as usize]
+}
+
+#[test]
+fn tile_count_rounds_up() {
+    assert_eq!(LightTiles::tile_count(64, 64), (4, 4));
+    assert_eq!(LightTiles::tile_count(65, 17), (5, 2));
+    assert_eq!(LightTiles::tile_count(0, 0), (1, 1));
+}
+
+#[test]
+fn tile_depth_range_skips_cleared_depth() {
+    let mut depth = vec![1.0; (WIDTH * HEIGHT) as usize];
+    assert_eq!(tile_depth_range(&depth, WIDTH, HEIGHT, 0, 0), None);
+
+    depth[0] = 0.5;
+    depth[(3 * WIDTH + 5) as usize] = 0.25;
+    assert_eq!(
+        tile_depth_range(&depth, WIDTH, HEIGHT, 0, 0),
+        Some((0.25, 0.5))
+    );
+    assert_eq!(tile_depth_range(&depth, WIDTH, HEIGHT, 1, 0), None);
+}
+
+#[test]
+fn point_light_only_reaches_nearby_tiles() {
+    let depth = flat_depth(-10.0);
+    // top-left corner of the wall, small range
+    let corner = projection().inverse() * Vec4::new(-0.9, 0.9, 0.0, 1.0);
+    let dir = (corner.truncate() / corner.w).normalize();
+    let position = dir * (10.0 / -dir.z);
+    let lights = vec![point(position.to_array(), 1.0)];
+
+    let grid = bin(&lights, &Mat4::IDENTITY, &depth);
+
+    assert_eq!(grid.opaque[grid.tile_index(0, 0)], vec![0]);
+    assert!(grid.opaque[grid.tile_index(WIDTH - 1, HEIGHT - 1)].is_empty());
+}
+
+#[test]
+fn light_behind_geometry_is_culled_for_opaque_only() {
+    let depth = flat_depth(-10.0);
+    // between the camera and the wall, but far from it
+    let lights = vec![point([0.0, 0.0, -3.0], 1.0)];
+
+    let grid = bin(&lights, &Mat4::IDENTITY, &depth);
+    let center = grid.tile_index(WIDTH / 2, HEIGHT / 2);
+
+    assert!(grid.opaque[center].is_empty());
+    assert_eq!(grid.transparent[center], vec![0]);
+}
+
+#[test]
+fn directional_and_infinite_lights_reach_every_tile() {
+    let depth = flat_depth(-10.0);
+    let lights = vec![
+        Light::Directional {
+            color: [1.0, 1.0, 1.0],
+            intensity: 1.0,
+            direction: [0.0, -1.0, 0.0],
+        },
+        point([500.0, 0.0, 0.0], 0.0),
+    ];
+
+    let grid = bin(&lights, &Mat4::IDENTITY, &depth);
+
+    for tile in grid.opaque.iter().chain(grid.transparent.iter()) {
+        assert_eq!(tile, &vec![0, 1]);
+    }
+}
+
+#[test]
+fn view_matrix_moves_lights_into_view_space() {
+    let depth = flat_depth(-10.0);
+    // light sits on the wall in world space once the camera moves back along +z
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 20.0), Vec3::ZERO, Vec3::Y);
+    let lights = vec![point([0.0, 0.0, 10.0], 1.0), point([0.0, 0.0, -10.0], 1.0)];
+
+    let grid = bin(&lights, &view, &depth);
+    let center = grid.tile_index(WIDTH / 2, HEIGHT / 2);
+
+    assert_eq!(grid.opaque[center], vec![0]);
+}
+
+#[test]
+fn tile_lists_are_capped() {
+    let depth = flat_depth(-10.0);
+    let lights: Vec<Light> = (0..LIGHT_TILE_MAX_LIGHTS + 10)
+        .map(|_| point([0.0, 0.0, -10.0], 5.0))
+        .collect();
+
+    let grid = bin(&lights, &Mat4::IDENTITY, &depth);
+    let center = grid.tile_index(WIDTH / 2, HEIGHT / 2);
+
+    assert_eq!(grid.opaque[center].len(), LIGHT_TILE_MAX_LIGHTS as usize);
+    assert_eq!(
+        grid.transparent[center].len(),
+        LIGHT_TILE_MAX_LIGHTS as usize
+    );
+}
@@ -2,7 +2,7 @@
 
 use std::sync::LazyLock;
 
-use awsm_renderer_core::cubemap::CubemapImage;
+use awsm_renderer_core::cubemap::{self, CubemapImage};
 use awsm_renderer_core::sampler::{AddressMode, FilterMode, MipmapFilterMode};
 use awsm_renderer_core::{cubemap::images::CubemapBitmapColors, renderer::AwsmRendererWebGpu};
 
@@ -73,12 +73,12 @@ impl IblTexture {
         textures: &mut Textures,
         default_colors: CubemapBitmapColors,
     ) -> Result<Self> {
-        let (texture, view, mip_count) = CubemapImage::new_colors(default_colors, 256, 256)
-            .await?
+        let image = CubemapImage::new_colors(default_colors, 256, 256).await?;
+        let (texture, view, mip_count) = image
             .create_texture_and_view(gpu, Some("IBL Cubemap"))
             .await?;
 
-        let texture_key = textures.insert_cubemap(texture);
+        let texture_key = textures.insert_cubemap_with_source(texture, image);
 
         let sampler_key = textures.get_sampler_key(gpu, Self::sampler_cache_key())?;
 
@@ -86,4 +86,21 @@ impl IblTexture {
 
         Ok(Self::new(texture_key, view, sampler, mip_count))
     }
+
+    /// Recreates the view and sampler after a device loss, from the texture and sampler that
+    /// [`AwsmRenderer::recover_from_device_loss`](crate::AwsmRenderer::recover_from_device_loss)
+    /// restored under the same keys.
+    ///
+    /// The renderer does this for its own copy, copies kept elsewhere need it too.
+    pub fn restore_gpu(&mut self, gpu: &AwsmRendererWebGpu, textures: &mut Textures) -> Result<()> {
+        self.texture_view = cubemap::create_texture_view(
+            textures.get_cubemap(self.texture_key)?,
+            Some("IBL Cubemap"),
+        )?;
+
+        let sampler_key = textures.get_sampler_key(gpu, Self::sampler_cache_key())?;
+        self.sampler = textures.get_sampler(sampler_key)?.clone();
+
+        Ok(())
+    }
 }
@@ -0,0 +1,782 @@
+//! Shadow maps for punctual lights.
+//!
+//! Every shadowed light renders depth into one or more layers ("views") of a shared
+//! depth texture array:
+//! - directional: one orthographic view per cascade, each fit to a slice of the camera frustum
+//! - spot: one perspective view along the cone
+//! - point: six perspective views, one per cube face (+X, -X, +Y, -Y, +Z, -Z)
+//!
+//! All layers share the resolution from [`ShadowSettings`]. Lights are assigned views in
+//! GPU buffer order, lights that don't fit in [`SHADOW_MAX_LIGHTS`] or [`SHADOW_MAX_VIEWS`]
+//! are lit without shadows.
+//!
+//! The material passes find a light's shadow through the packed light data
+//! (`kind_outer_pad.z` holds the shadow slot + 1, 0 means unshadowed) and sample it with PCF.
+
+use std::sync::LazyLock;
+
+use awsm_renderer_core::{
+    buffers::{BufferDescriptor, BufferUsage},
+    compare::CompareFunction,
+    renderer::AwsmRendererWebGpu,
+    sampler::{AddressMode, FilterMode, SamplerDescriptor},
+    texture::{
+        Extent3d, TextureDescriptor, TextureFormat, TextureUsage, TextureViewDescriptor,
+        TextureViewDimension,
+    },
+};
+use glam::{Mat4, Vec3, Vec4};
+use slotmap::{SecondaryMap, SlotMap};
+
+use crate::{
+    bind_groups::{BindGroupCreate, BindGroups},
+    camera::CameraMatrices,
+    lights::{AwsmLightError, Light, LightKey, Lights, Result},
+    AwsmRenderer, AwsmRendererLogging,
+};
+
+/// Maximum number of lights with shadows.
+pub const SHADOW_MAX_LIGHTS: usize = 8;
+/// Maximum number of shadow map layers across all lights.
+pub const SHADOW_MAX_VIEWS: usize = 16;
+/// Maximum number of cascades for a directional light.
+pub const SHADOW_MAX_CASCADES: usize = 4;
+/// Byte alignment of one view in the shadow pass view buffer (dynamic offset).
+pub const SHADOW_VIEW_BYTE_ALIGNMENT: usize = 256;
+/// Depth format of the shadow map texture array.
+pub const SHADOW_MAP_FORMAT: TextureFormat = TextureFormat::Depth32float;
+
+const SHADOW_LIGHT_BYTE_SIZE: usize = 48;
+/// Byte size of the shadow info uniform (see `shadows.wgsl`).
+pub const SHADOWS_INFO_BYTE_SIZE: usize =
+    16 + (SHADOW_LIGHT_BYTE_SIZE * SHADOW_MAX_LIGHTS) + (64 * SHADOW_MAX_VIEWS);
+
+static INFO_BUFFER_USAGE: LazyLock<BufferUsage> =
+    LazyLock::new(|| BufferUsage::new().with_uniform().with_copy_dst());
+
+impl AwsmRenderer {
+    /// Sets the global shadow settings.
+    pub fn set_shadow_settings(&mut self, settings: ShadowSettings) {
+        self.lights.shadows.set_settings(settings);
+    }
+
+    /// Enables (or with `None`, disables) shadows for a light.
+    pub fn set_light_shadow(
+        &mut self,
+        key: LightKey,
+        shadow: Option<LightShadow>,
+    ) -> crate::error::Result<()> {
+        self.lights.set_shadow(key, shadow)?;
+        Ok(())
+    }
+}
+
+impl Lights {
+    /// Enables (or with `None`, disables) shadows for a light.
+    pub fn set_shadow(&mut self, key: LightKey, shadow: Option<LightShadow>) -> Result<()> {
+        if !self.lights.contains_key(key) {
+            return Err(AwsmLightError::NotFound(key));
+        }
+
+        match shadow {
+            Some(shadow) => {
+                self.shadows.lights.insert(key, shadow);
+            }
+            None => {
+                self.shadows.lights.remove(key);
+            }
+        }
+
+        // shadow slots are packed into the light data
+        self.punctual_gpu_dirty = true;
+        self.shadows.gpu_dirty = true;
+
+        Ok(())
+    }
+
+    /// Returns the shadow settings of a light, if it casts shadows.
+    pub fn shadow(&self, key: LightKey) -> Option<&LightShadow> {
+        self.shadows.lights.get(key)
+    }
+}
+
+/// Global shadow map settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowSettings {
+    /// Width and height of every shadow map layer, in texels.
+    pub resolution: u32,
+    /// Furthest view distance covered by directional light cascades, and the far plane of
+    /// spot and point lights with infinite range.
+    pub max_distance: f32,
+    /// Blend between uniform (0.0) and logarithmic (1.0) cascade splits.
+    pub cascade_split_lambda: f32,
+    /// Distance behind each cascade (towards the light) where casters are still rendered.
+    pub cascade_caster_extension: f32,
+}
+
+impl Default for ShadowSettings {
+    fn default() -> Self {
+        Self {
+            resolution: 1024,
+            max_distance: 100.0,
+            cascade_split_lambda: 0.75,
+            cascade_caster_extension: 50.0,
+        }
+    }
+}
+
+/// Per-light shadow settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightShadow {
+    /// Constant depth bias, in shadow map depth units.
+    pub bias: f32,
+    /// Offset along the surface normal in world units, scaled up at grazing angles.
+    pub normal_bias: f32,
+    /// Filtering kernel.
+    pub pcf: ShadowPcf,
+    /// Number of cascades, directional lights only (clamped to `1..=SHADOW_MAX_CASCADES`).
+    pub cascades: u32,
+    /// Near plane of the shadow views, spot and point lights only.
+    pub near: f32,
+}
+
+impl Default for LightShadow {
+    fn default() -> Self {
+        Self {
+            bias: 0.0005,
+            normal_bias: 0.02,
+            pcf: ShadowPcf::default(),
+            cascades: SHADOW_MAX_CASCADES as u32,
+            near: 0.05,
+        }
+    }
+}
+
+/// Percentage-closer filtering kernel size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShadowPcf {
+    /// Single hardware-filtered (2x2) tap.
+    None,
+    #[default]
+    Kernel3x3,
+    Kernel5x5,
+}
+
+impl ShadowPcf {
+    /// Returns the kernel radius in texels.
+    pub fn radius(&self) -> u32 {
+        match self {
+            ShadowPcf::None => 0,
+            ShadowPcf::Kernel3x3 => 1,
+            ShadowPcf::Kernel5x5 => 2,
+        }
+    }
+}
+
+/// Shadow map layers assigned to a light.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowSlot {
+    pub light_key: LightKey,
+    pub first_view: u32,
+    pub view_count: u32,
+}
+
+/// Shadow map GPU resources and per-light settings.
+pub struct Shadows {
+    pub gpu_info_buffer: web_sys::GpuBuffer,
+    pub gpu_views_buffer: web_sys::GpuBuffer,
+    pub texture: web_sys::GpuTexture,
+    /// All layers, for sampling in the material passes.
+    pub texture_view: web_sys::GpuTextureView,
+    /// One view per layer, for rendering in the shadow pass.
+    pub layer_views: Vec<web_sys::GpuTextureView>,
+    pub sampler: web_sys::GpuSampler,
+    settings: ShadowSettings,
+    lights: SecondaryMap<LightKey, LightShadow>,
+    view_projections: Vec<Mat4>,
+    texture_resolution: u32,
+    texture_layers: u32,
+    gpu_dirty: bool,
+}
+
+impl Shadows {
+    /// Creates shadow buffers with an empty 1x1 shadow map.
+    pub fn new(gpu: &AwsmRendererWebGpu) -> Result<Self> {
+        let gpu_info_buffer = gpu.create_buffer(
+            &BufferDescriptor::new(
+                Some("Shadows Info"),
+                SHADOWS_INFO_BYTE_SIZE,
+                *INFO_BUFFER_USAGE,
+            )
+            .into(),
+        )?;
+
+        let gpu_views_buffer = gpu.create_buffer(
+            &BufferDescriptor::new(
+                Some("Shadow Views"),
+                SHADOW_VIEW_BYTE_ALIGNMENT * SHADOW_MAX_VIEWS,
+                *INFO_BUFFER_USAGE,
+            )
+            .into(),
+        )?;
+
+        let (texture, texture_view, layer_views) = Self::create_texture(gpu, 1, 1)?;
+
+        let sampler = gpu.create_sampler(Some(
+            &SamplerDescriptor {
+                label: Some("Shadow Map Sampler"),
+                address_mode_u: Some(AddressMode::ClampToEdge),
+                address_mode_v: Some(AddressMode::ClampToEdge),
+                mag_filter: Some(FilterMode::Linear),
+                min_filter: Some(FilterMode::Linear),
+                compare: Some(CompareFunction::LessEqual),
+                ..Default::default()
+            }
+            .into(),
+        ));
+
+        Ok(Self {
+            gpu_info_buffer,
+            gpu_views_buffer,
+            texture,
+            texture_view,
+            layer_views,
+            sampler,
+            settings: ShadowSettings::default(),
+            lights: SecondaryMap::new(),
+            view_projections: Vec::new(),
+            texture_resolution: 1,
+            texture_layers: 1,
+            gpu_dirty: true,
+        })
+    }
+
+    // after a device loss, keeps the settings and per-light shadows but starts over with
+    // fresh GPU resources, the shadow map is resized on the next write
+    pub(super) fn restore_gpu(&mut self, gpu: &AwsmRendererWebGpu) -> Result<()> {
+        let fresh = Self::new(gpu)?;
+
+        self.gpu_info_buffer = fresh.gpu_info_buffer;
+        self.gpu_views_buffer = fresh.gpu_views_buffer;
+        self.texture = fresh.texture;
+        self.texture_view = fresh.texture_view;
+        self.layer_views = fresh.layer_views;
+        self.sampler = fresh.sampler;
+        self.texture_resolution = fresh.texture_resolution;
+        self.texture_layers = fresh.texture_layers;
+        self.gpu_dirty = true;
+
+        Ok(())
+    }
+
+    /// Returns the global shadow settings.
+    pub fn settings(&self) -> &ShadowSettings {
+        &self.settings
+    }
+
+    /// Replaces the global shadow settings.
+    pub fn set_settings(&mut self, settings: ShadowSettings) {
+        self.settings = settings;
+        self.gpu_dirty = true;
+    }
+
+    /// Returns the light-space view-projection of every shadow map layer rendered this frame.
+    pub fn view_projections(&self) -> &[Mat4] {
+        &self.view_projections
+    }
+
+    pub(super) fn remove(&mut self, key: LightKey) {
+        if self.lights.remove(key).is_some() {
+            self.gpu_dirty = true;
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.lights.clear();
+        self.gpu_dirty = true;
+    }
+
+    /// Assigns shadow map layers to the shadowed lights, in GPU buffer order.
+    pub fn slots<'a>(
+        &self,
+        lights: impl Iterator<Item = (LightKey, &'a Light)>,
+    ) -> Vec<ShadowSlot> {
+        assign_shadow_slots(lights, &self.lights)
+    }
+
+    /// Computes shadow views and writes the shadow buffers, resizing the shadow map if needed.
+    ///
+    /// Directional cascades follow the camera, so this runs every frame while any light
+    /// has shadows.
+    pub fn write_gpu(
+        &mut self,
+        logging: &AwsmRendererLogging,
+        gpu: &AwsmRendererWebGpu,
+        lights: &SlotMap<LightKey, Light>,
+        camera: Option<&CameraMatrices>,
+        bind_groups: &mut BindGroups,
+    ) -> Result<()> {
+        let slots = self.slots(lights.iter());
+
+        if slots.is_empty() && !self.gpu_dirty {
+            return Ok(());
+        }
+
+        let _maybe_span_guard = if logging.render_timings {
+            Some(tracing::span!(tracing::Level::INFO, "Shadows GPU write").entered())
+        } else {
+            None
+        };
+
+        let view_count = slots.iter().map(|slot| slot.view_count).sum::<u32>();
+
+        // don't hold on to full resolution layers when nothing casts shadows
+        let (resolution, layers) = if view_count == 0 {
+            (1, 1)
+        } else {
+            (self.settings.resolution.max(1), view_count)
+        };
+
+        if resolution != self.texture_resolution || layers != self.texture_layers {
+            let (texture, texture_view, layer_views) =
+                Self::create_texture(gpu, resolution, layers)?;
+            self.texture.destroy();
+            self.texture = texture;
+            self.texture_view = texture_view;
+            self.layer_views = layer_views;
+            self.texture_resolution = resolution;
+            self.texture_layers = layers;
+            bind_groups.mark_create(BindGroupCreate::ShadowMapsResize);
+        }
+
+        let mut info = vec![0u8; SHADOWS_INFO_BYTE_SIZE];
+        let mut views = vec![0u8; SHADOW_VIEW_BYTE_ALIGNMENT * SHADOW_MAX_VIEWS];
+
+        write_u32s(
+            &mut info[0..16],
+            &[slots.len() as u32, view_count, resolution, 0],
+        );
+
+        self.view_projections.clear();
+
+        for (index, slot) in slots.iter().enumerate() {
+            // slots only exist for lights with settings
+            let (Some(light), Some(shadow)) =
+                (lights.get(slot.light_key), self.lights.get(slot.light_key))
+            else {
+                continue;
+            };
+
+            let light_views = light_shadow_views(light, shadow, &self.settings, camera);
+
+            let offset = 16 + index * SHADOW_LIGHT_BYTE_SIZE;
+            let entry = &mut info[offset..offset + SHADOW_LIGHT_BYTE_SIZE];
+            write_f32s(
+                &mut entry[0..16],
+                &[
+                    shadow.bias,
+                    shadow.normal_bias,
+                    shadow.pcf.radius() as f32,
+                    1.0 / resolution as f32,
+                ],
+            );
+            write_u32s(
+                &mut entry[16..32],
+                &[
+                    slot.first_view,
+                    slot.view_count,
+                    light.enum_value() as u32,
+                    0,
+                ],
+            );
+            let mut splits = [0.0f32; SHADOW_MAX_CASCADES];
+            for (split, value) in splits.iter_mut().zip(light_views.cascade_splits.iter()) {
+                *split = *value;
+            }
+            write_f32s(&mut entry[32..48], &splits);
+
+            for (i, view_projection) in light_views
+                .view_projections
+                .iter()
+                .take(slot.view_count as usize)
+                .enumerate()
+            {
+                let view_index = slot.first_view as usize + i;
+                let matrix = view_projection.to_cols_array();
+
+                let offset = 16 + SHADOW_LIGHT_BYTE_SIZE * SHADOW_MAX_LIGHTS + view_index * 64;
+                write_f32s(&mut info[offset..offset + 64], &matrix);
+
+                let offset = view_index * SHADOW_VIEW_BYTE_ALIGNMENT;
+                write_f32s(&mut views[offset..offset + 64], &matrix);
+
+                self.view_projections.push(*view_projection);
+            }
+        }
+
+        gpu.write_buffer(&self.gpu_info_buffer, None, info.as_slice(), None, None)?;
+        gpu.write_buffer(&self.gpu_views_buffer, None, views.as_slice(), None, None)?;
+
+        self.gpu_dirty = false;
+
+        Ok(())
+    }
+
+    fn create_texture(
+        gpu: &AwsmRendererWebGpu,
+        resolution: u32,
+        layers: u32,
+    ) -> Result<(
+        web_sys::GpuTexture,
+        web_sys::GpuTextureView,
+        Vec<web_sys::GpuTextureView>,
+    )> {
+        let texture = gpu.create_texture(
+            &TextureDescriptor::new(
+                SHADOW_MAP_FORMAT,
+                Extent3d::new(resolution, Some(resolution), Some(layers)),
+                TextureUsage::new()
+                    .with_render_attachment()
+                    .with_texture_binding(),
+            )
+            .with_label("Shadow Maps")
+            .into(),
+        )?;
+
+        // explicit dimension, a single layer texture would default to a 2d view
+        let texture_view = texture
+            .create_view_with_descriptor(
+                &TextureViewDescriptor::new(Some("Shadow Maps"))
+                    .with_dimension(TextureViewDimension::N2dArray)
+                    .with_array_layer_count(layers)
+                    .into(),
+            )
+            .map_err(awsm_renderer_core::error::AwsmCoreError::create_texture_view)?;
+
+        let layer_views = (0..layers)
+            .map(|layer| {
+                texture
+                    .create_view_with_descriptor(
+                        &TextureViewDescriptor::new(Some("Shadow Map Layer"))
+                            .with_dimension(TextureViewDimension::N2d)
+                            .with_base_array_layer(layer)
+                            .with_array_layer_count(1)
+                            .into(),
+                    )
+                    .map_err(awsm_renderer_core::error::AwsmCoreError::create_texture_view)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok((texture, texture_view, layer_views))
+    }
+}
+
+/// Returns the number of shadow map layers a light needs.
+pub fn shadow_view_count(light: &Light, shadow: &LightShadow) -> u32 {
+    match light {
+        Light::Directional { .. } => shadow.cascades.clamp(1, SHADOW_MAX_CASCADES as u32),
+        Light::Point { .. } => 6,
+        Light::Spot { .. } => 1,
+    }
+}
+
+/// Assigns shadow map layers to shadowed lights, in iteration order.
+///
+/// Lights that would exceed [`SHADOW_MAX_LIGHTS`] or [`SHADOW_MAX_VIEWS`] are skipped,
+/// a later light with fewer views may still fit.
+pub fn assign_shadow_slots<'a>(
+    lights: impl Iterator<Item = (LightKey, &'a Light)>,
+    shadows: &SecondaryMap<LightKey, LightShadow>,
+) -> Vec<ShadowSlot> {
+    let mut slots = Vec::new();
+    let mut next_view = 0;
+
+    for (light_key, light) in lights {
+        if slots.len() == SHADOW_MAX_LIGHTS {
+            break;
+        }
+
+        let Some(shadow) = shadows.get(light_key) else {
+            continue;
+        };
+
+        let view_count = shadow_view_count(light, shadow);
+        if (next_view + view_count) as usize > SHADOW_MAX_VIEWS {
+            continue;
+        }
+
+        slots.push(ShadowSlot {
+            light_key,
+            first_view: next_view,
+            view_count,
+        });
+        next_view += view_count;
+    }
+
+    slots
+}
+
+/// Light-space matrices for one shadowed light.
+#[derive(Debug, Clone, Default)]
+pub struct LightShadowViews {
+    pub view_projections: Vec<Mat4>,
+    /// View-space far distance of each cascade (directional lights only).
+    pub cascade_splits: Vec<f32>,
+}
+
+/// Computes the shadow views of a light.
+///
+/// Directional lights need the camera to fit their cascades, without it the cascades
+/// are left empty (and unshadowed).
+pub fn light_shadow_views(
+    light: &Light,
+    shadow: &LightShadow,
+    settings: &ShadowSettings,
+    camera: Option<&CameraMatrices>,
+) -> LightShadowViews {
+    match light {
+        Light::Directional { direction, .. } => {
+            let cascades = shadow_view_count(light, shadow) as usize;
+            let Some(camera) = camera else {
+                return LightShadowViews {
+                    view_projections: vec![Mat4::ZERO; cascades],
+                    cascade_splits: vec![0.0; cascades],
+                };
+            };
+
+            let (near, far) = camera_depth_range(camera);
+            let far = far.min(settings.max_distance).max(near + f32::EPSILON);
+            let splits = cascade_splits(near, far, cascades, settings.cascade_split_lambda);
+
+            let mut slice_near = near;
+            let view_projections = splits
+                .iter()
+                .map(|&slice_far| {
+                    let corners = camera_frustum_slice_corners(camera, slice_near, slice_far);
+                    slice_near = slice_far;
+                    directional_view_projection(
+                        Vec3::from_array(*direction),
+                        &corners,
+                        settings.resolution,
+                        settings.cascade_caster_extension,
+                    )
+                })
+                .collect();
+
+            LightShadowViews {
+                view_projections,
+                cascade_splits: splits,
+            }
+        }
+        Light::Point {
+            position, range, ..
+        } => LightShadowViews {
+            view_projections: point_view_projections(
+                Vec3::from_array(*position),
+                shadow.near,
+                shadow_far(*range, settings),
+            )
+            .to_vec(),
+            cascade_splits: Vec::new(),
+        },
+        Light::Spot {
+            position,
+            direction,
+            range,
+            outer_angle,
+            ..
+        } => LightShadowViews {
+            view_projections: vec![spot_view_projection(
+                Vec3::from_array(*position),
+                Vec3::from_array(*direction),
+                // the light holds the cosine
+                outer_angle.clamp(-1.0, 1.0).acos(),
+                shadow.near,
+                shadow_far(*range, settings),
+            )],
+            cascade_splits: Vec::new(),
+        },
+    }
+}
+
+fn shadow_far(range: f32, settings: &ShadowSettings) -> f32 {
+    if range > 0.0 {
+        range
+    } else {
+        settings.max_distance
+    }
+}
+
+/// Returns the view-space near and far distances of a camera projection.
+///
+/// The far distance is infinite for infinite projections.
+pub fn camera_depth_range(camera: &CameraMatrices) -> (f32, f32) {
+    let inv_projection = camera.projection.inverse();
+    let unproject = |z: f32| {
+        let p = inv_projection * Vec4::new(0.0, 0.0, z, 1.0);
+        -p.z / p.w
+    };
+
+    let near = unproject(0.0);
+    let far = unproject(1.0);
+
+    if far.is_finite() && far > near {
+        (near, far)
+    } else {
+        (near, f32::INFINITY)
+    }
+}
+
+/// Splits `near..far` into cascades, blending logarithmic and uniform splits by `lambda`.
+///
+/// Returns the far distance of each cascade, the last one is always `far`.
+pub fn cascade_splits(near: f32, far: f32, count: usize, lambda: f32) -> Vec<f32> {
+    // the logarithmic scheme is undefined for a near plane at 0 (e.g. orthographic cameras)
+    let log_near = near.max(0.01);
+    let lambda = lambda.clamp(0.0, 1.0);
+
+    (1..=count)
+        .map(|i| {
+            if i == count {
+                return far;
+            }
+            let p = i as f32 / count as f32;
+            let log = log_near * (far / log_near).powf(p);
+            let uniform = near + (far - near) * p;
+            lambda * log + (1.0 - lambda) * uniform
+        })
+        .collect()
+}
+
+/// Returns the world-space corners of the camera frustum between two view distances.
+pub fn camera_frustum_slice_corners(
+    camera: &CameraMatrices,
+    near_distance: f32,
+    far_distance: f32,
+) -> [Vec3; 8] {
+    let inv_projection = camera.projection.inverse();
+    let inv_view = camera.view.inverse();
+
+    let unproject = |x: f32, y: f32, z: f32| {
+        let p = inv_projection * Vec4::new(x, y, z, 1.0);
+        p.truncate() / p.w
+    };
+
+    let mut corners = [Vec3::ZERO; 8];
+    let mut index = 0;
+
+    for (x, y) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+        // walk the corner ray from the near plane, this works for perspective, orthographic
+        // and infinite projections alike
+        let start = unproject(x, y, 0.0);
+        let ray = unproject(x, y, 0.5) - start;
+        let start_distance = -start.z;
+
+        for distance in [near_distance, far_distance] {
+            let t = (distance - start_distance) / -ray.z;
+            corners[index] = inv_view.transform_point3(start + ray * t);
+            index += 1;
+        }
+    }
+
+    corners
+}
+
+/// Fits an orthographic light view around a set of world-space points.
+///
+/// The fit uses a bounding sphere and snaps to shadow map texels so the cascade
+/// doesn't shimmer as the camera moves or rotates.
+pub fn directional_view_projection(
+    direction: Vec3,
+    corners: &[Vec3],
+    resolution: u32,
+    caster_extension: f32,
+) -> Mat4 {
+    let direction = direction.normalize_or(Vec3::NEG_Y);
+    let up = stable_up(direction);
+
+    let center = corners.iter().copied().sum::<Vec3>() / corners.len().max(1) as f32;
+    let radius = corners
+        .iter()
+        .map(|corner| corner.distance(center))
+        .fold(0.0f32, f32::max);
+    // quantize the radius so the projection size only changes in steps
+    let radius = ((radius * 16.0).ceil() / 16.0).max(1.0 / 16.0);
+
+    let rotation = Mat4::look_at_rh(Vec3::ZERO, direction, up);
+    let texel = (2.0 * radius) / resolution.max(1) as f32;
+    let mut light_space_center = rotation.transform_point3(center);
+    light_space_center.x = (light_space_center.x / texel).floor() * texel;
+    light_space_center.y = (light_space_center.y / texel).floor() * texel;
+    let center = rotation.inverse().transform_point3(light_space_center);
+
+    let back = radius + caster_extension.max(0.0);
+    let view = Mat4::look_at_rh(center - direction * back, center, up);
+    let projection = Mat4::orthographic_rh(-radius, radius, -radius, radius, 0.0, back + radius);
+
+    projection * view
+}
+
+/// Returns the view-projection of a spot light cone.
+///
+/// `outer_angle` is the cone half-angle in radians.
+pub fn spot_view_projection(
+    position: Vec3,
+    direction: Vec3,
+    outer_angle: f32,
+    near: f32,
+    far: f32,
+) -> Mat4 {
+    let direction = direction.normalize_or(Vec3::NEG_Z);
+    let view = Mat4::look_at_rh(position, position + direction, stable_up(direction));
+    let fov = (outer_angle * 2.0).clamp(0.01, std::f32::consts::PI - 0.01);
+    let near = near.max(0.001);
+    let projection = Mat4::perspective_rh(fov, 1.0, near, far.max(near * 2.0));
+
+    projection * view
+}
+
+/// Returns the six cube face view-projections of a point light (+X, -X, +Y, -Y, +Z, -Z).
+///
+/// The shader picks the face by the major axis of the light-to-surface vector, so the
+/// faces only need to cover their 90 degree frustum, not match cubemap conventions.
+pub fn point_view_projections(position: Vec3, near: f32, far: f32) -> [Mat4; 6] {
+    let near = near.max(0.001);
+    let projection =
+        Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, near, far.max(near * 2.0));
+
+    [
+        Vec3::X,
+        Vec3::NEG_X,
+        Vec3::Y,
+        Vec3::NEG_Y,
+        Vec3::Z,
+        Vec3::NEG_Z,
+    ]
+    .map(|direction| {
+        projection * Mat4::look_at_rh(position, position + direction, stable_up(direction))
+    })
+}
+
+fn stable_up(direction: Vec3) -> Vec3 {
+    if direction.y.abs() > 0.99 {
+        Vec3::Z
+    } else {
+        Vec3::Y
+    }
+}
+
+fn write_u32s(dest: &mut [u8], values: &[u32]) {
+    for (chunk, value) in dest.chunks_exact_mut(4).zip(values) {
+        chunk.copy_from_slice(&value.to_ne_bytes());
+    }
+}
+
+fn write_f32s(dest: &mut [u8], values: &[f32]) {
+    for (chunk, value) in dest.chunks_exact_mut(4).zip(values) {
+        chunk.copy_from_slice(&value.to_ne_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests;
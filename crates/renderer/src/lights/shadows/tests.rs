@@ -0,0 +1,244 @@
+use glam::{Mat4, Vec3};
+use slotmap::{SecondaryMap, SlotMap};
+
+use crate::camera::CameraMatrices;
+use crate::lights::shadows::{
+    assign_shadow_slots, camera_depth_range, camera_frustum_slice_corners, cascade_splits,
+    directional_view_projection, point_view_projections, spot_view_projection, LightShadow,
+    SHADOW_MAX_LIGHTS, SHADOW_MAX_VIEWS,
+};
+use crate::lights::{Light, LightKey};
+
+fn camera() -> CameraMatrices {
+    let position_world = Vec3::new(0.0, 2.0, 10.0);
+    CameraMatrices {
+        view: Mat4::look_at_rh(position_world, Vec3::ZERO, Vec3::Y),
+        projection: Mat4::perspective_rh(60.0_f32.to_radians(), 16.0 / 9.0, 0.1, 200.0),
+        position_world,
+        focus_distance: 10.0,
+        aperture: 5.6,
+    }
+}
+
+// true if the point lands inside the clip volume, with a little slack for float error
+fn in_clip_volume(view_projection: &Mat4, point: Vec3) -> bool {
+    let clip = *view_projection * point.extend(1.0);
+    let ndc = clip.truncate() / clip.w;
+    const EPSILON: f32 = 1e-3;
+    clip.w > 0.0
+        && ndc.x.abs() <= 1.0 + EPSILON
+        && ndc.y.abs() <= 1.0 + EPSILON
+        && ndc.z >= -EPSILON
+        && ndc.z <= 1.0 + EPSILON
+}
+
+fn point_light() -> Light {
+    Light::Point {
+        color: [1.0, 1.0, 1.0],
+        intensity: 1.0,
+        position: [0.0, 0.0, 0.0],
+        range: 10.0,
+    }
+}
+
+fn spot_light() -> Light {
+    Light::Spot {
+        color: [1.0, 1.0, 1.0],
+        intensity: 1.0,
+        position: [0.0, 0.0, 0.0],
+        direction: [0.0, -1.0, 0.0],
+        range: 10.0,
+        inner_angle: 0.95,
+        outer_angle: 0.88,
+    }
+}
+
+#[test]
+fn camera_depth_range_matches_projection() {
+    let (near, far) = camera_depth_range(&camera());
+    assert!((near - 0.1).abs() < 1e-4);
+    assert!((far - 200.0).abs() < 0.5);
+
+    let mut infinite = camera();
+    infinite.projection = Mat4::perspective_infinite_rh(1.0, 1.0, 0.1);
+    assert_eq!(camera_depth_range(&infinite).1, f32::INFINITY);
+}
+
+#[test]
+fn cascade_splits_are_increasing_and_end_at_far() {
+    for lambda in [0.0, 0.5, 1.0] {
+        let splits = cascade_splits(0.1, 100.0, 4, lambda);
+        assert_eq!(splits.len(), 4);
+        assert_eq!(*splits.last().unwrap(), 100.0);
+        assert!(splits[0] > 0.1);
+        assert!(splits.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    // uniform
+    let splits = cascade_splits(0.0, 100.0, 4, 0.0);
+    assert!((splits[0] - 25.0).abs() < 1e-4);
+}
+
+#[test]
+fn directional_cascade_covers_frustum_slice() {
+    let camera = camera();
+    let direction = Vec3::new(-0.3, -1.0, -0.2);
+
+    let mut near = 0.1;
+    for far in cascade_splits(0.1, 100.0, 4, 0.75) {
+        let corners = camera_frustum_slice_corners(&camera, near, far);
+        let view_projection = directional_view_projection(direction, &corners, 1024, 50.0);
+
+        for corner in corners {
+            assert!(
+                in_clip_volume(&view_projection, corner),
+                "corner {corner} outside cascade {near}..{far}"
+            );
+        }
+        near = far;
+    }
+}
+
+#[test]
+fn directional_cascade_includes_casters_towards_the_light() {
+    let direction = Vec3::NEG_Y;
+    let corners = [Vec3::new(-1.0, 0.0, -1.0), Vec3::new(1.0, 0.0, 1.0)];
+    let view_projection = directional_view_projection(direction, &corners, 1024, 50.0);
+
+    // above the receivers, within the caster extension
+    assert!(in_clip_volume(&view_projection, Vec3::new(0.0, 40.0, 0.0)));
+    // nearer the light is a smaller depth
+    let depth = |p: Vec3| {
+        let clip = view_projection * p.extend(1.0);
+        clip.z / clip.w
+    };
+    assert!(depth(Vec3::new(0.0, 10.0, 0.0)) < depth(Vec3::ZERO));
+}
+
+#[test]
+fn spot_projection_contains_the_cone() {
+    let position = Vec3::new(1.0, 5.0, 0.0);
+    let direction = Vec3::NEG_Y;
+    let outer_angle = 0.5_f32;
+    let view_projection = spot_view_projection(position, direction, outer_angle, 0.05, 10.0);
+
+    // points on the cone edge, just inside
+    let edge = (outer_angle * 0.99).tan() * 4.0;
+    for offset in [
+        Vec3::new(edge, 0.0, 0.0),
+        Vec3::new(-edge, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, edge),
+        Vec3::new(0.0, 0.0, -edge),
+    ] {
+        assert!(in_clip_volume(
+            &view_projection,
+            position + direction * 4.0 + offset
+        ));
+    }
+
+    // behind the light
+    assert!(!in_clip_volume(&view_projection, position - direction));
+}
+
+#[test]
+fn point_faces_cover_their_axis() {
+    let position = Vec3::new(2.0, 1.0, -3.0);
+    let faces = point_view_projections(position, 0.05, 10.0);
+    let axes = [
+        Vec3::X,
+        Vec3::NEG_X,
+        Vec3::Y,
+        Vec3::NEG_Y,
+        Vec3::Z,
+        Vec3::NEG_Z,
+    ];
+
+    for (face, axis) in faces.iter().zip(axes) {
+        assert!(in_clip_volume(face, position + axis * 5.0));
+        assert!(!in_clip_volume(face, position - axis * 5.0));
+        // the corner of the face frustum is still covered
+        let corner = axis + (Vec3::ONE - axis.abs()) * 0.99;
+        assert!(in_clip_volume(face, position + corner * 2.0));
+    }
+}
+
+#[test]
+fn shadow_slots_respect_limits() {
+    let mut lights: SlotMap<LightKey, Light> = SlotMap::with_key();
+    let mut shadows = SecondaryMap::new();
+
+    // 2 point lights use 12 views
+    for _ in 0..2 {
+        let key = lights.insert(point_light());
+        shadows.insert(key, LightShadow::default());
+    }
+    // unshadowed lights take no slot
+    lights.insert(spot_light());
+    // a third point light doesn't fit
+    let skipped = lights.insert(point_light());
+    shadows.insert(skipped, LightShadow::default());
+    // but spot lights still do
+    for _ in 0..SHADOW_MAX_LIGHTS {
+        let key = lights.insert(spot_light());
+        shadows.insert(key, LightShadow::default());
+    }
+
+    let slots = assign_shadow_slots(lights.iter(), &shadows);
+
+    assert!(slots.iter().all(|slot| slot.light_key != skipped));
+    assert_eq!(slots.len(), 6);
+    assert_eq!(
+        slots.iter().map(|slot| slot.view_count).sum::<u32>() as usize,
+        SHADOW_MAX_VIEWS
+    );
+    for pair in slots.windows(2) {
+        assert_eq!(pair[0].first_view + pair[0].view_count, pair[1].first_view);
+    }
+
+    // the light count limit applies on its own too
+    let mut lights: SlotMap<LightKey, Light> = SlotMap::with_key();
+    let mut shadows = SecondaryMap::new();
+    for _ in 0..SHADOW_MAX_LIGHTS + 2 {
+        let key = lights.insert(spot_light());
+        shadows.insert(key, LightShadow::default());
+    }
+    assert_eq!(
+        assign_shadow_slots(lights.iter(), &shadows).len(),
+        SHADOW_MAX_LIGHTS
+    );
+}
+
+#[test]
+fn directional_cascade_count_is_clamped() {
+    let light = Light::Directional {
+        color: [1.0, 1.0, 1.0],
+        intensity: 1.0,
+        direction: [0.0, -1.0, 0.0],
+    };
+    let mut lights: SlotMap<LightKey, Light> = SlotMap::with_key();
+    let key = lights.insert(light);
+    let mut shadows = SecondaryMap::new();
+    shadows.insert(
+        key,
+        LightShadow {
+            cascades: 0,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        assign_shadow_slots(lights.iter(), &shadows)[0].view_count,
+        1
+    );
+
+    shadows.insert(
+        key,
+        LightShadow {
+            cascades: 10,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        assign_shadow_slots(lights.iter(), &shadows)[0].view_count,
+        4
+    );
+}
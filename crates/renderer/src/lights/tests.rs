@@ -0,0 +1,135 @@
+use glam::{Mat4, Quat, Vec3};
+use slotmap::{SecondaryMap, SlotMap};
+
+use crate::lights::{apply_light_transforms, Light, LightKey};
+use crate::transforms::TransformKey;
+
+fn spot_light() -> Light {
+    Light::Spot {
+        color: [1.0, 1.0, 1.0],
+        intensity: 1.0,
+        position: [0.0, 0.0, 0.0],
+        direction: [0.0, 0.0, -1.0],
+        range: 10.0,
+        inner_angle: 0.95,
+        outer_angle: 0.88,
+    }
+}
+
+fn spot_position(lights: &SlotMap<LightKey, Light>, key: LightKey) -> [f32; 3] {
+    match lights[key] {
+        Light::Spot { position, .. } => position,
+        _ => unreachable!(),
+    }
+}
+
+fn assert_close(a: [f32; 3], b: Vec3) {
+    assert!(Vec3::from_array(a).abs_diff_eq(b, 1e-5), "{a:?} != {b}");
+}
+
+#[test]
+fn world_matrix_places_and_orients_lights() {
+    let world = Mat4::from_scale_rotation_translation(
+        Vec3::splat(3.0),
+        Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+        Vec3::new(1.0, 2.0, 3.0),
+    );
+
+    let mut light = spot_light();
+    assert!(light.apply_world_matrix(&world));
+
+    match light {
+        Light::Spot {
+            position,
+            direction,
+            ..
+        } => {
+            assert_close(position, Vec3::new(1.0, 2.0, 3.0));
+            // -Z rotated down to -Y, and scale doesn't leak into the direction
+            assert_close(direction, Vec3::NEG_Y);
+        }
+        _ => unreachable!(),
+    }
+
+    // nothing moved
+    assert!(!light.apply_world_matrix(&world));
+}
+
+#[test]
+fn world_matrix_only_touches_relevant_fields() {
+    let world = Mat4::from_translation(Vec3::new(5.0, 0.0, 0.0));
+
+    let mut directional = Light::Directional {
+        color: [1.0, 1.0, 1.0],
+        intensity: 1.0,
+        direction: [0.0, 0.0, -1.0],
+    };
+    // translation doesn't change a directional light
+    assert!(!directional.apply_world_matrix(&world));
+
+    let mut point = Light::Point {
+        color: [1.0, 1.0, 1.0],
+        intensity: 1.0,
+        position: [0.0, 0.0, 0.0],
+        range: 0.0,
+    };
+    assert!(point.apply_world_matrix(&world));
+    assert_eq!(
+        point,
+        Light::Point {
+            color: [1.0, 1.0, 1.0],
+            intensity: 1.0,
+            position: [5.0, 0.0, 0.0],
+            range: 0.0,
+        }
+    );
+}
+
+#[test]
+fn bound_lights_follow_their_transform() {
+    let mut lights: SlotMap<LightKey, Light> = SlotMap::with_key();
+    let bound = lights.insert(spot_light());
+    let unbound = lights.insert(spot_light());
+    let orphaned = lights.insert(spot_light());
+
+    let mut transform_keys: SlotMap<TransformKey, ()> = SlotMap::with_key();
+    let transform_key = transform_keys.insert(());
+    let removed_transform_key = transform_keys.insert(());
+
+    let mut bindings = SecondaryMap::new();
+    bindings.insert(bound, transform_key);
+    bindings.insert(orphaned, removed_transform_key);
+
+    let mut world_matrices = SecondaryMap::new();
+    world_matrices.insert(
+        transform_key,
+        Mat4::from_translation(Vec3::new(0.0, 4.0, 0.0)),
+    );
+
+    assert!(apply_light_transforms(
+        &mut lights,
+        &bindings,
+        &world_matrices
+    ));
+    assert!(!apply_light_transforms(
+        &mut lights,
+        &bindings,
+        &world_matrices
+    ));
+
+    assert_eq!(spot_position(&lights, bound), [0.0, 4.0, 0.0]);
+    assert_eq!(spot_position(&lights, unbound), [0.0, 0.0, 0.0]);
+    assert_eq!(spot_position(&lights, orphaned), [0.0, 0.0, 0.0]);
+
+    // e.g. an animation moved the parent
+    world_matrices.insert(
+        transform_key,
+        Mat4::from_translation(Vec3::new(1.0, 4.0, 0.0)),
+    );
+    assert!(apply_light_transforms(
+        &mut lights,
+        &bindings,
+        &world_matrices
+    ));
+    assert_eq!(spot_position(&lights, bound), [1.0, 4.0, 0.0]);
+}
@@ -1,6 +1,6 @@
 //! Material definitions and GPU uploads.
 
-use std::sync::LazyLock;
+use std::{collections::HashSet, sync::LazyLock};
 
 use awsm_renderer_core::{
     buffers::{BufferDescriptor, BufferUsage},
@@ -28,6 +28,55 @@ impl AwsmRenderer {
     pub fn update_material(&mut self, key: MaterialKey, f: impl FnMut(&mut Material)) {
         self.materials.update(key, &self.textures, f);
     }
+
+    /// Removes a material that no mesh uses, releasing its textures.
+    ///
+    /// Released textures stay in the pool until `collect_garbage` or `remove_texture`.
+    pub fn remove_material(&mut self, key: MaterialKey) -> crate::error::Result<Option<Material>> {
+        let count = self.meshes.material_ref_count(key);
+        if count > 0 {
+            return Err(AwsmMaterialError::InUse { key, count }.into());
+        }
+
+        Ok(self.materials.remove(key))
+    }
+
+    /// Frees the materials that meshes stopped using, then the textures that materials
+    /// stopped using.
+    ///
+    /// Only resources whose last reference was dropped are freed, so materials and textures
+    /// that were never assigned are kept. Freed textures compact the pool, which is then
+    /// re-uploaded once with its bind groups recreated.
+    pub async fn collect_garbage(&mut self) -> crate::error::Result<CollectedGarbage> {
+        let mut collected = CollectedGarbage::default();
+
+        for key in self.meshes.take_released_materials() {
+            if self.meshes.material_ref_count(key) == 0 && self.materials.remove(key).is_some() {
+                collected.materials.push(key);
+            }
+        }
+
+        for key in self.materials.take_released_textures() {
+            if self.materials.texture_ref_count(key) == 0 && self.textures.remove_image(key) {
+                collected.textures.push(key);
+            }
+        }
+
+        if !collected.textures.is_empty() {
+            // pool entries and sampler indices may have moved
+            self.materials.rewrite_all(&self.textures);
+            self.finalize_gpu_textures().await?;
+        }
+
+        Ok(collected)
+    }
+}
+
+/// Resources freed by `AwsmRenderer::collect_garbage`.
+#[derive(Debug, Default, Clone)]
+pub struct CollectedGarbage {
+    pub materials: Vec<MaterialKey>,
+    pub textures: Vec<TextureKey>,
 }
 
 /// Material variants supported by the renderer.
@@ -47,6 +96,14 @@ impl Material {
         }
     }
 
+    /// Returns whether the material is double sided.
+    pub fn double_sided(&self) -> bool {
+        match self {
+            Material::Pbr(pbr_material) => pbr_material.double_sided(),
+            Material::Unlit(unlit_material) => unlit_material.double_sided(),
+        }
+    }
+
     /// Returns the alpha mask cutoff if applicable.
     pub fn alpha_mask(&self) -> Option<f32> {
         match self {
@@ -55,6 +112,14 @@ impl Material {
         }
     }
 
+    /// Returns every texture the material references.
+    pub fn textures(&self) -> Vec<&MaterialTexture> {
+        match self {
+            Material::Pbr(pbr_material) => pbr_material.textures(),
+            Material::Unlit(unlit_material) => unlit_material.textures(),
+        }
+    }
+
     /// Returns the packed uniform buffer data for the material.
     pub fn uniform_buffer_data(&self, textures: &Textures) -> Result<Vec<u8>> {
         match self {
@@ -88,6 +153,9 @@ pub struct Materials {
     buffer: DynamicStorageBuffer<MaterialKey>,
     gpu_dirty: bool,
     _is_transparency_pass: SecondaryMap<MaterialKey, ()>,
+    // how many material texture slots point at each texture
+    texture_ref_counts: SecondaryMap<TextureKey, usize>,
+    released_textures: HashSet<TextureKey>,
 }
 
 impl Materials {
@@ -105,6 +173,8 @@ impl Materials {
             buffer,
             gpu_dirty: true,
             _is_transparency_pass: SecondaryMap::new(),
+            texture_ref_counts: SecondaryMap::new(),
+            released_textures: HashSet::new(),
         })
     }
 
@@ -127,11 +197,17 @@ impl Materials {
     pub fn insert(&mut self, material: Material, textures: &Textures) -> MaterialKey {
         let is_transparency_pass = material.is_transparency_pass();
 
+        let texture_keys = texture_keys(&material);
+
         let key = self.lookup.insert(material);
         if is_transparency_pass {
             self._is_transparency_pass.insert(key, ());
         }
 
+        for texture_key in texture_keys {
+            self.retain_texture(texture_key);
+        }
+
         self.update(key, textures, |_| {});
 
         key
@@ -167,7 +243,9 @@ impl Materials {
     ) {
         if let Some(material) = self.lookup.get_mut(key) {
             let old_is_transparency_pass = material.is_transparency_pass();
+            let old_texture_keys = texture_keys(material);
             f(material);
+            let new_texture_keys = texture_keys(material);
             let new_is_transparency_pass = material.is_transparency_pass();
             if old_is_transparency_pass != new_is_transparency_pass {
                 if new_is_transparency_pass {
@@ -190,6 +268,72 @@ impl Materials {
                     );
                 }
             }
+
+            if old_texture_keys != new_texture_keys {
+                for texture_key in new_texture_keys {
+                    self.retain_texture(texture_key);
+                }
+                for texture_key in old_texture_keys {
+                    self.release_texture(texture_key);
+                }
+            }
+        }
+    }
+
+    /// Removes a material, releasing its texture references. Returns it if found.
+    ///
+    /// Meshes still pointing at it are not checked, see `AwsmRenderer::remove_material`.
+    pub fn remove(&mut self, key: MaterialKey) -> Option<Material> {
+        let material = self.lookup.remove(key)?;
+
+        self._is_transparency_pass.remove(key);
+        self.buffer.remove(key);
+        self.gpu_dirty = true;
+
+        for texture_key in texture_keys(&material) {
+            self.release_texture(texture_key);
+        }
+
+        Some(material)
+    }
+
+    /// Rewrites the GPU data of every material, e.g. after pooled textures moved.
+    pub fn rewrite_all(&mut self, textures: &Textures) {
+        let keys: Vec<MaterialKey> = self.lookup.keys().collect();
+        for key in keys {
+            self.update(key, textures, |_| {});
+        }
+    }
+
+    /// Returns how many material texture slots reference the texture.
+    pub fn texture_ref_count(&self, key: TextureKey) -> usize {
+        self.texture_ref_counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// Takes the textures whose last material reference was dropped since the last call.
+    ///
+    /// They may have been referenced again since, check `texture_ref_count` before freeing.
+    pub fn take_released_textures(&mut self) -> Vec<TextureKey> {
+        self.released_textures.drain().collect()
+    }
+
+    fn retain_texture(&mut self, key: TextureKey) {
+        match self.texture_ref_counts.get_mut(key) {
+            Some(count) => *count += 1,
+            None => {
+                self.texture_ref_counts.insert(key, 1);
+            }
+        }
+        self.released_textures.remove(&key);
+    }
+
+    fn release_texture(&mut self, key: TextureKey) {
+        if let Some(count) = self.texture_ref_counts.get_mut(key) {
+            *count -= 1;
+            if *count == 0 {
+                self.texture_ref_counts.remove(key);
+                self.released_textures.insert(key);
+            }
         }
     }
 
@@ -198,6 +342,12 @@ impl Materials {
         self._is_transparency_pass.contains_key(key)
     }
 
+    // after a device loss, the next write_gpu recreates the buffer from the CPU data
+    pub(crate) fn mark_gpu_lost(&mut self) {
+        self.buffer.mark_gpu_lost();
+        self.gpu_dirty = true;
+    }
+
     /// Writes material data to the GPU.
     pub fn write_gpu(
         &mut self,
@@ -241,6 +391,10 @@ impl Materials {
     }
 }
 
+fn texture_keys(material: &Material) -> Vec<TextureKey> {
+    material.textures().into_iter().map(|tex| tex.key).collect()
+}
+
 /// Texture reference used by materials.
 #[derive(Clone, Debug)]
 pub struct MaterialTexture {
@@ -285,6 +439,10 @@ pub type Result<T> = std::result::Result<T, AwsmMaterialError>;
 pub enum AwsmMaterialError {
     #[error("[material] not found: {0:?}")]
     NotFound(MaterialKey),
+
+    #[error("[material] material {key:?} is still used by {count} mesh(es)")]
+    InUse { key: MaterialKey, count: usize },
+
     #[error("[material] missing alpha blend lookup: {0:?}")]
     MissingAlphaBlendLookup(MaterialKey),
 
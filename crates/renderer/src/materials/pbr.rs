@@ -59,6 +59,7 @@ pub enum PbrMaterialDebug {
     Occlusion,
     Emissive,
     Specular,
+    Anisotropy,
 }
 
 impl PbrMaterialDebug {
@@ -72,6 +73,7 @@ impl PbrMaterialDebug {
             PbrMaterialDebug::Occlusion => 1 << 3,
             PbrMaterialDebug::Emissive => 1 << 4,
             PbrMaterialDebug::Specular => 1 << 5,
+            PbrMaterialDebug::Anisotropy => 1 << 6,
         }
     }
 }
@@ -159,8 +161,10 @@ pub struct PbrMaterialDispersion {
 /// Anisotropy extension data.
 #[derive(Clone, Debug)]
 pub struct PbrMaterialAnisotropy {
+    /// Direction in RG (tangent space, encoded to [0, 1]) and strength in B.
     pub tex: Option<MaterialTexture>,
     pub strength: f32,
+    /// Counter-clockwise rotation of the direction from the tangent, in radians.
     pub rotation: f32,
 }
 
@@ -254,6 +258,48 @@ impl PbrMaterial {
         }
     }
 
+    /// Returns every texture the material references, including extensions.
+    pub fn textures(&self) -> Vec<&MaterialTexture> {
+        let mut textures = vec![
+            &self.base_color_tex,
+            &self.metallic_roughness_tex,
+            &self.normal_tex,
+            &self.occlusion_tex,
+            &self.emissive_tex,
+        ];
+
+        if let Some(specular) = &self.specular {
+            textures.extend([&specular.tex, &specular.color_tex]);
+        }
+        if let Some(transmission) = &self.transmission {
+            textures.push(&transmission.tex);
+        }
+        if let Some(diffuse_transmission) = &self.diffuse_transmission {
+            textures.extend([&diffuse_transmission.tex, &diffuse_transmission.color_tex]);
+        }
+        if let Some(volume) = &self.volume {
+            textures.push(&volume.thickness_tex);
+        }
+        if let Some(clearcoat) = &self.clearcoat {
+            textures.extend([
+                &clearcoat.tex,
+                &clearcoat.roughness_tex,
+                &clearcoat.normal_tex,
+            ]);
+        }
+        if let Some(sheen) = &self.sheen {
+            textures.extend([&sheen.roughness_tex, &sheen.color_tex]);
+        }
+        if let Some(anisotropy) = &self.anisotropy {
+            textures.push(&anisotropy.tex);
+        }
+        if let Some(iridescence) = &self.iridescence {
+            textures.extend([&iridescence.tex, &iridescence.thickness_tex]);
+        }
+
+        textures.into_iter().flatten().collect()
+    }
+
     /// Builds the uniform buffer payload for this material.
     pub fn uniform_buffer_data(&self, textures: &Textures) -> Result<Vec<u8>> {
         let mut data: Vec<u8> = Vec::with_capacity(256);
@@ -588,3 +634,6 @@ impl PbrMaterial {
         Ok(data)
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use slotmap::SlotMap;
+
+use super::*;
+use crate::textures::TextureKey;
+
+fn material_texture(key: TextureKey) -> MaterialTexture {
+    MaterialTexture {
+        key,
+        sampler_key: None,
+        uv_index: Some(0),
+        transform_key: None,
+    }
+}
+
+#[test]
+fn textures_include_extension_slots() {
+    let mut keys: SlotMap<TextureKey, ()> = SlotMap::with_key();
+    let base_color = keys.insert(());
+    let clearcoat_normal = keys.insert(());
+    let sheen_color = keys.insert(());
+
+    let mut material = PbrMaterial::new(MaterialAlphaMode::Opaque, false);
+    material.base_color_tex = Some(material_texture(base_color));
+    material.clearcoat = Some(PbrMaterialClearCoat {
+        tex: None,
+        factor: 1.0,
+        roughness_tex: None,
+        roughness_factor: 0.0,
+        normal_tex: Some(material_texture(clearcoat_normal)),
+        normal_scale: 1.0,
+    });
+    material.sheen = Some(PbrMaterialSheen {
+        roughness_tex: None,
+        roughness_factor: 0.0,
+        color_tex: Some(material_texture(sheen_color)),
+        color_factor: [1.0, 1.0, 1.0],
+    });
+
+    let texture_keys: Vec<TextureKey> = material.textures().iter().map(|tex| tex.key).collect();
+
+    assert_eq!(
+        texture_keys,
+        vec![base_color, clearcoat_normal, sheen_color]
+    );
+}
+
+#[test]
+fn textures_are_empty_without_any_set() {
+    let material = PbrMaterial::new(MaterialAlphaMode::Blend, true);
+
+    assert!(material.textures().is_empty());
+}
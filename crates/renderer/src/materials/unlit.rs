@@ -68,6 +68,14 @@ impl UnlitMaterial {
         }
     }
 
+    /// Returns every texture the material references.
+    pub fn textures(&self) -> Vec<&MaterialTexture> {
+        [&self.base_color_tex, &self.emissive_tex]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
     /// Builds the uniform buffer payload for this material.
     pub fn uniform_buffer_data(&self, textures: &Textures) -> Result<Vec<u8>> {
         let mut data = Vec::with_capacity(128);
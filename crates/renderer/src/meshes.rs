@@ -1,13 +1,16 @@
 //! Mesh storage and GPU buffer management.
 
 pub mod buffer_info;
+pub mod data;
 pub mod error;
 pub mod mesh;
 pub mod meta;
 pub mod morphs;
+pub mod shapes;
 pub mod skins;
+mod vertices;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use awsm_renderer_core::buffers::{BufferDescriptor, BufferUsage};
 use awsm_renderer_core::renderer::AwsmRendererWebGpu;
@@ -19,7 +22,7 @@ use crate::bounds::Aabb;
 use crate::buffer::dynamic_storage::DynamicStorageBuffer;
 use crate::buffer::helpers::write_buffer_with_dirty_ranges;
 use crate::instances::Instances;
-use crate::materials::Materials;
+use crate::materials::{MaterialKey, Materials};
 use crate::meshes::buffer_info::MeshBufferVertexInfo;
 use crate::transforms::{Transform, TransformKey, Transforms};
 use crate::{AwsmRenderer, AwsmRendererLogging};
@@ -32,6 +35,129 @@ use mesh::Mesh;
 use morphs::{GeometryMorphKey, MaterialMorphKey, Morphs};
 
 impl AwsmRenderer {
+    /// Inserts a mesh built at runtime, see [`data::MeshData`].
+    ///
+    /// Goes through the same packing as glTF primitives (so requires the `gltf` feature):
+    /// missing normals and tangents are generated, and the geometry is laid out for the
+    /// opaque or transparent pass depending on the material.
+    #[cfg(feature = "gltf")]
+    pub async fn insert_mesh(
+        &mut self,
+        data: &data::MeshData,
+        transform_key: TransformKey,
+        material_key: crate::materials::MaterialKey,
+        options: data::MeshInsertOptions,
+    ) -> crate::error::Result<MeshKey> {
+        use crate::gltf::buffers::mesh::{convert_mesh_data, GltfMeshBufferGeometryKind};
+        use buffer_info::MeshBufferInfo;
+
+        data.validate()?;
+
+        let material = self.materials.get(material_key)?;
+        let double_sided = options
+            .double_sided
+            .unwrap_or_else(|| material.double_sided());
+        // this should match `mesh_buffer_geometry_kind()`
+        let geometry_kind = if options.hud {
+            GltfMeshBufferGeometryKind::Both
+        } else if material.is_transparency_pass() {
+            GltfMeshBufferGeometryKind::Transparency
+        } else {
+            GltfMeshBufferGeometryKind::Visibility
+        };
+
+        let buffers = convert_mesh_data(data, geometry_kind, double_sided)
+            .map_err(|err| AwsmMeshError::InvalidMeshData(err.to_string()))?;
+        let buffer_info = &buffers.meshes[0][0];
+
+        let geometry_morph_key = match buffer_info.geometry_morph.clone() {
+            None => None,
+            Some(morph_buffer_info) => {
+                let weights = if data.morph_weights.is_empty() {
+                    vec![0.0; data.morph_targets.len()]
+                } else {
+                    data.morph_weights.clone()
+                };
+                let weights_u8 = unsafe {
+                    std::slice::from_raw_parts(weights.as_ptr() as *const u8, weights.len() * 4)
+                };
+
+                Some(self.meshes.morphs.geometry.insert_raw(
+                    morph_buffer_info.into(),
+                    weights_u8,
+                    &buffers.geometry_morph_bytes,
+                )?)
+            }
+        };
+
+        let skin_key = match (&data.skin, buffer_info.skin.clone()) {
+            (Some(skin), Some(info)) => Some(self.meshes.skins.insert(
+                skin.joint_transforms.clone(),
+                &skin.inverse_bind_matrices,
+                info.set_count,
+                &buffers.skin_joint_index_weight_bytes,
+            )?),
+            _ => None,
+        };
+
+        let buffer_info_key = self
+            .meshes
+            .buffer_infos
+            .insert(MeshBufferInfo::from(buffer_info.clone()));
+
+        let mesh = Mesh::new(
+            transform_key,
+            material_key,
+            double_sided,
+            false,
+            options.hud,
+            options.hidden,
+        );
+
+        let mesh_key = self.meshes.insert(
+            mesh,
+            &self.materials,
+            &self.transforms,
+            buffer_info_key,
+            buffer_info
+                .visibility_geometry_vertex
+                .as_ref()
+                .map(|_| buffers.visibility_geometry_vertex_bytes.as_slice()),
+            buffer_info
+                .transparency_geometry_vertex
+                .as_ref()
+                .map(|_| buffers.transparency_geometry_vertex_bytes.as_slice()),
+            &buffers.custom_attribute_vertex_bytes,
+            &buffers.index_bytes,
+            data.aabb(),
+            geometry_morph_key,
+            None,
+            skin_key,
+        )?;
+
+        let mesh = self.meshes.get(mesh_key)?;
+        self.render_passes
+            .material_transparent
+            .pipelines
+            .set_render_pipeline_key(
+                &self.gpu,
+                mesh,
+                mesh_key,
+                buffer_info_key,
+                &mut self.shaders,
+                &mut self.pipelines,
+                &self.render_passes.material_transparent.bind_groups,
+                &self.pipeline_layouts,
+                &self.meshes.buffer_infos,
+                &self.anti_aliasing,
+                &self.textures,
+                &self.render_textures.formats,
+            )
+            .await?;
+
+        Ok(mesh_key)
+    }
+
     /// Duplicates a mesh into an existing transform key and mirrors transparent pass pipeline state.
     pub fn duplicate_mesh_with_transform(
         &mut self,
@@ -102,6 +228,23 @@ impl AwsmRenderer {
         Ok(())
     }
 
+    /// Sets whether a mesh casts and receives shadows.
+    pub fn set_mesh_shadows(
+        &mut self,
+        mesh_key: MeshKey,
+        cast_shadows: bool,
+        receive_shadows: bool,
+    ) -> crate::error::Result<()> {
+        self.meshes.set_shadows(
+            mesh_key,
+            cast_shadows,
+            receive_shadows,
+            &self.materials,
+            &self.transforms,
+        )?;
+        Ok(())
+    }
+
     /// Removes all meshes under a transform and clears any pass-local mesh state.
     pub fn remove_meshes_by_transform_key(&mut self, transform_key: TransformKey) -> Vec<MeshKey> {
         let mesh_keys = self
@@ -173,6 +316,19 @@ impl AwsmRenderer {
         )?)
     }
 
+    /// Overwrites part of a mesh's vertices in place, e.g. for cloth or deformable terrain.
+    ///
+    /// See [`data::MeshVertexUpdate`]. The bounds are refreshed when positions change.
+    pub fn update_mesh_vertices(
+        &mut self,
+        mesh_key: MeshKey,
+        update: &data::MeshVertexUpdate,
+    ) -> crate::error::Result<()> {
+        Ok(self
+            .meshes
+            .update_vertices(mesh_key, update, &self.transforms, &self.instances)?)
+    }
+
     /// Enables GPU instancing for a mesh with explicit instance transforms.
     pub async fn enable_mesh_instancing(
         &mut self,
@@ -319,6 +475,9 @@ pub struct Meshes {
     resources: DenseSlotMap<MeshResourceKey, MeshResource>,
     mesh_to_resource: SecondaryMap<MeshKey, MeshResourceKey>,
     transform_to_meshes: SecondaryMap<TransformKey, Vec<MeshKey>>,
+    // how many meshes use each material
+    material_ref_counts: SecondaryMap<MaterialKey, usize>,
+    released_materials: HashSet<MaterialKey>,
     // visibility geometry data buffers (position, triangle-id, barycentric)
     visibility_geometry_data_buffers: DynamicStorageBuffer<MeshResourceKey>,
     visibility_geometry_data_gpu_buffer: web_sys::GpuBuffer,
@@ -370,6 +529,8 @@ impl Meshes {
             resources: DenseSlotMap::with_key(),
             mesh_to_resource: SecondaryMap::new(),
             transform_to_meshes: SecondaryMap::new(),
+            material_ref_counts: SecondaryMap::new(),
+            released_materials: HashSet::new(),
             buffer_infos: MeshBufferInfos::new(),
             // visibility data
             visibility_geometry_data_buffers: DynamicStorageBuffer::new(
@@ -645,6 +806,7 @@ impl Meshes {
 
         let mesh_key = self.list.insert(mesh.clone());
         self.mesh_to_resource.insert(mesh_key, resource_key);
+        self.retain_material(mesh.material_key);
 
         self.transform_to_meshes
             .entry(transform_key)
@@ -892,50 +1054,60 @@ impl Meshes {
                 None => continue,
             };
 
-            if let Some(mesh_keys) = self.transform_to_meshes.get(transform_key) {
-                for mesh_key in mesh_keys {
-                    let resource_aabb = self
-                        .resource(*mesh_key)
-                        .ok()
-                        .and_then(|resource| resource.aabb.clone());
-
-                    let world_aabb = match resource_aabb {
-                        Some(aabb) => {
-                            let mesh = match self.list.get(*mesh_key) {
-                                Some(mesh) => mesh,
-                                None => continue,
-                            };
-
-                            if mesh.instanced {
-                                match instances.transform_list(mesh.transform_key) {
-                                    Some(transforms_list) if !transforms_list.is_empty() => {
-                                        let first = world_mat * transforms_list[0].to_matrix();
-                                        let mut combined = aabb.transformed(&first);
-                                        for transform in &transforms_list[1..] {
-                                            let world = world_mat * transform.to_matrix();
-                                            let transformed = aabb.transformed(&world);
-                                            combined.extend(&transformed);
-                                        }
-                                        Some(combined)
+            self.update_world_aabbs(transform_key, world_mat, instances);
+        }
+
+        // This does update the GPU as dirty, bit skins manage their own GPU dirty state
+        self.skins.update_transforms(dirty_transforms);
+    }
+
+    // Recomputes the world AABB of every mesh under a transform from its resource AABB
+    fn update_world_aabbs(
+        &mut self,
+        transform_key: TransformKey,
+        world_mat: Mat4,
+        instances: &Instances,
+    ) {
+        if let Some(mesh_keys) = self.transform_to_meshes.get(transform_key) {
+            for mesh_key in mesh_keys {
+                let resource_aabb = self
+                    .resource(*mesh_key)
+                    .ok()
+                    .and_then(|resource| resource.aabb.clone());
+
+                let world_aabb = match resource_aabb {
+                    Some(aabb) => {
+                        let mesh = match self.list.get(*mesh_key) {
+                            Some(mesh) => mesh,
+                            None => continue,
+                        };
+
+                        if mesh.instanced {
+                            match instances.transform_list(mesh.transform_key) {
+                                Some(transforms_list) if !transforms_list.is_empty() => {
+                                    let first = world_mat * transforms_list[0].to_matrix();
+                                    let mut combined = aabb.transformed(&first);
+                                    for transform in &transforms_list[1..] {
+                                        let world = world_mat * transform.to_matrix();
+                                        let transformed = aabb.transformed(&world);
+                                        combined.extend(&transformed);
                                     }
-                                    _ => None,
+                                    Some(combined)
                                 }
-                            } else {
-                                Some(aabb.transformed(&world_mat))
+                                _ => None,
                             }
+                        } else {
+                            Some(aabb.transformed(&world_mat))
                         }
-                        None => None,
-                    };
-
-                    if let Some(mesh) = self.list.get_mut(*mesh_key) {
-                        mesh.world_aabb = world_aabb;
                     }
+                    None => None,
+                };
+
+                if let Some(mesh) = self.list.get_mut(*mesh_key) {
+                    mesh.world_aabb = world_aabb;
                 }
             }
         }
-
-        // This does update the GPU as dirty, bit skins manage their own GPU dirty state
-        self.skins.update_transforms(dirty_transforms);
     }
 
     fn update_mesh_transform(
@@ -974,6 +1146,26 @@ impl Meshes {
         Ok(())
     }
 
+    /// Sets shadow flags for a mesh, `receive_shadows` lives in the material meta.
+    pub(crate) fn set_shadows(
+        &mut self,
+        mesh_key: MeshKey,
+        cast_shadows: bool,
+        receive_shadows: bool,
+        materials: &Materials,
+        transforms: &Transforms,
+    ) -> Result<()> {
+        let mesh = self.get_mut(mesh_key)?;
+        mesh.cast_shadows = cast_shadows;
+
+        if mesh.receive_shadows != receive_shadows {
+            mesh.receive_shadows = receive_shadows;
+            self.refresh_meta_for_mesh(mesh_key, materials, transforms)?;
+        }
+
+        Ok(())
+    }
+
     fn refresh_meta_for_mesh(
         &mut self,
         mesh_key: MeshKey,
@@ -1155,6 +1347,38 @@ impl Meshes {
             .ok_or(AwsmMeshError::MeshNotFound(mesh_key))
     }
 
+    /// Returns how many meshes use the material.
+    pub fn material_ref_count(&self, key: MaterialKey) -> usize {
+        self.material_ref_counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// Takes the materials whose last mesh was removed since the last call.
+    ///
+    /// They may have been used again since, check `material_ref_count` before freeing.
+    pub fn take_released_materials(&mut self) -> Vec<MaterialKey> {
+        self.released_materials.drain().collect()
+    }
+
+    fn retain_material(&mut self, key: MaterialKey) {
+        match self.material_ref_counts.get_mut(key) {
+            Some(count) => *count += 1,
+            None => {
+                self.material_ref_counts.insert(key, 1);
+            }
+        }
+        self.released_materials.remove(&key);
+    }
+
+    fn release_material(&mut self, key: MaterialKey) {
+        if let Some(count) = self.material_ref_counts.get_mut(key) {
+            *count -= 1;
+            if *count == 0 {
+                self.material_ref_counts.remove(key);
+                self.released_materials.insert(key);
+            }
+        }
+    }
+
     /// Removes all meshes that share the given transform key.
     pub(crate) fn remove_by_transform_key(
         &mut self,
@@ -1176,6 +1400,7 @@ impl Meshes {
     pub(crate) fn remove(&mut self, mesh_key: MeshKey) -> Option<Mesh> {
         if let Some(mesh) = self.list.remove(mesh_key) {
             self.meta.remove(mesh_key);
+            self.release_material(mesh.material_key);
 
             if let Some(meshes) = self.transform_to_meshes.get_mut(mesh.transform_key) {
                 meshes.retain(|&key| key != mesh_key)
@@ -1237,6 +1462,25 @@ impl Meshes {
         }
     }
 
+    // after a device loss, the next write_gpu calls (here and in meta, morphs and skins)
+    // recreate the buffers from the CPU data
+    pub(crate) fn mark_gpu_lost(&mut self) {
+        self.visibility_geometry_data_buffers.mark_gpu_lost();
+        self.visibility_geometry_index_buffers.mark_gpu_lost();
+        self.transparency_geometry_data_buffers.mark_gpu_lost();
+        self.custom_attribute_data_buffers.mark_gpu_lost();
+        self.custom_attribute_index_buffers.mark_gpu_lost();
+        self.visibility_geometry_data_dirty = true;
+        self.visibility_geometry_index_dirty = true;
+        self.transparency_geometry_data_dirty = true;
+        self.custom_attribute_data_dirty = true;
+        self.custom_attribute_index_dirty = true;
+
+        self.meta.mark_gpu_lost();
+        self.morphs.mark_gpu_lost();
+        self.skins.mark_gpu_lost();
+    }
+
     /// Writes dirty mesh buffers to the GPU and updates bind groups.
     pub fn write_gpu(
         &mut self,
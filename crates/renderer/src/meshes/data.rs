@@ -0,0 +1,280 @@
+//! Mesh geometry built at runtime, independent of any asset format.
+
+use glam::{Mat4, Vec3};
+
+use crate::{bounds::Aabb, transforms::TransformKey};
+
+use super::error::{AwsmMeshError, Result};
+
+/// Geometry for a mesh built at runtime, e.g. terrain, debug shapes or CAD data.
+///
+/// Triangles wind counter-clockwise when seen from the front.
+/// Missing normals are computed from the triangles, and missing tangents are generated
+/// (MikkTSpace) whenever there's a first UV set, so normal maps work regardless of material.
+///
+/// Insert it with `AwsmRenderer::insert_mesh`.
+#[derive(Debug, Clone, Default)]
+pub struct MeshData {
+    pub positions: Vec<[f32; 3]>,
+    pub indices: Vec<u32>,
+    pub normals: Option<Vec<[f32; 3]>>,
+    /// xyz is the tangent, w is the bitangent sign.
+    pub tangents: Option<Vec<[f32; 4]>>,
+    /// One entry per UV set, in set order.
+    pub uvs: Vec<Vec<[f32; 2]>>,
+    /// One entry per RGBA color set, in set order.
+    ///
+    /// Like glTF, the material decides whether a set is used (see `PbrMaterialVertexColorInfo`).
+    pub colors: Vec<Vec<[f32; 4]>>,
+    pub skin: Option<MeshDataSkin>,
+    pub morph_targets: Vec<MeshDataMorphTarget>,
+    /// Initial morph weights, one per target. Empty means all zero.
+    pub morph_weights: Vec<f32>,
+}
+
+/// Per-vertex skinning data, and the joints it refers to.
+#[derive(Debug, Clone, Default)]
+pub struct MeshDataSkin {
+    /// Indices into `joint_transforms`.
+    pub joints: Vec<[u32; 4]>,
+    pub weights: Vec<[f32; 4]>,
+    pub joint_transforms: Vec<TransformKey>,
+    /// One per joint transform.
+    pub inverse_bind_matrices: Vec<Mat4>,
+}
+
+/// Per-vertex deltas from the base mesh. Missing attributes don't change.
+#[derive(Debug, Clone, Default)]
+pub struct MeshDataMorphTarget {
+    pub positions: Option<Vec<[f32; 3]>>,
+    pub normals: Option<Vec<[f32; 3]>>,
+    pub tangents: Option<Vec<[f32; 3]>>,
+}
+
+/// Per-mesh settings for `AwsmRenderer::insert_mesh`.
+#[derive(Debug, Clone, Default)]
+pub struct MeshInsertOptions {
+    /// Overrides the material's double-sided setting.
+    pub double_sided: Option<bool>,
+    pub hud: bool,
+    pub hidden: bool,
+}
+
+/// New values for a run of an existing mesh's vertices, see `AwsmRenderer::update_mesh_vertices`.
+///
+/// Every attribute starts at `first_vertex` and may cover a different number of vertices.
+/// Nothing is derived: e.g. moving positions doesn't recompute normals.
+#[derive(Debug, Clone, Default)]
+pub struct MeshVertexUpdate {
+    pub first_vertex: usize,
+    pub positions: Option<Vec<[f32; 3]>>,
+    pub normals: Option<Vec<[f32; 3]>>,
+    /// xyz is the tangent, w is the bitangent sign.
+    pub tangents: Option<Vec<[f32; 4]>>,
+    /// (UV set index, values)
+    pub uvs: Vec<(u32, Vec<[f32; 2]>)>,
+    /// (color set index, RGBA values)
+    pub colors: Vec<(u32, Vec<[f32; 4]>)>,
+}
+
+impl MeshVertexUpdate {
+    /// Creates an empty update starting at `first_vertex`.
+    pub fn new(first_vertex: usize) -> Self {
+        Self {
+            first_vertex,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the positions.
+    pub fn with_positions(mut self, positions: Vec<[f32; 3]>) -> Self {
+        self.positions = Some(positions);
+        self
+    }
+
+    /// Sets the normals.
+    pub fn with_normals(mut self, normals: Vec<[f32; 3]>) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    /// Sets the tangents.
+    pub fn with_tangents(mut self, tangents: Vec<[f32; 4]>) -> Self {
+        self.tangents = Some(tangents);
+        self
+    }
+
+    /// Adds values for a UV set.
+    pub fn with_uvs(mut self, set: u32, uvs: Vec<[f32; 2]>) -> Self {
+        self.uvs.push((set, uvs));
+        self
+    }
+
+    /// Adds values for a color set.
+    pub fn with_colors(mut self, set: u32, colors: Vec<[f32; 4]>) -> Self {
+        self.colors.push((set, colors));
+        self
+    }
+}
+
+impl MeshData {
+    /// Creates mesh data from positions and triangle indices.
+    pub fn new(positions: Vec<[f32; 3]>, indices: Vec<u32>) -> Self {
+        Self {
+            positions,
+            indices,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the normals.
+    pub fn with_normals(mut self, normals: Vec<[f32; 3]>) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    /// Sets the tangents.
+    pub fn with_tangents(mut self, tangents: Vec<[f32; 4]>) -> Self {
+        self.tangents = Some(tangents);
+        self
+    }
+
+    /// Adds the next UV set.
+    pub fn with_uvs(mut self, uvs: Vec<[f32; 2]>) -> Self {
+        self.uvs.push(uvs);
+        self
+    }
+
+    /// Adds the next color set.
+    pub fn with_colors(mut self, colors: Vec<[f32; 4]>) -> Self {
+        self.colors.push(colors);
+        self
+    }
+
+    /// Sets the skin.
+    pub fn with_skin(mut self, skin: MeshDataSkin) -> Self {
+        self.skin = Some(skin);
+        self
+    }
+
+    /// Adds a morph target.
+    pub fn with_morph_target(mut self, morph_target: MeshDataMorphTarget) -> Self {
+        self.morph_targets.push(morph_target);
+        self
+    }
+
+    /// Sets the initial morph weights.
+    pub fn with_morph_weights(mut self, weights: Vec<f32>) -> Self {
+        self.morph_weights = weights;
+        self
+    }
+
+    /// Returns the number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns the number of triangles.
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Returns the bounds of the positions, if there are any.
+    pub fn aabb(&self) -> Option<Aabb> {
+        let mut positions = self.positions.iter().copied().map(Vec3::from_array);
+        let first = positions.next()?;
+        let (min, max) = positions.fold((first, first), |(min, max), position| {
+            (min.min(position), max.max(position))
+        });
+
+        Some(Aabb::new(min, max))
+    }
+
+    /// Checks that every attribute has one entry per vertex and every index is in range.
+    pub fn validate(&self) -> Result<()> {
+        let vertex_count = self.vertex_count();
+
+        if self.indices.is_empty() || self.indices.len() % 3 != 0 {
+            return Err(AwsmMeshError::InvalidMeshData(format!(
+                "index count {} doesn't make whole triangles",
+                self.indices.len()
+            )));
+        }
+        if let Some(index) = self
+            .indices
+            .iter()
+            .find(|index| **index as usize >= vertex_count)
+        {
+            return Err(AwsmMeshError::InvalidMeshData(format!(
+                "index {index} is out of range for {vertex_count} vertices"
+            )));
+        }
+
+        let check_len = |name: &str, len: usize| {
+            if len == vertex_count {
+                Ok(())
+            } else {
+                Err(AwsmMeshError::InvalidMeshData(format!(
+                    "{name} has {len} entries for {vertex_count} vertices"
+                )))
+            }
+        };
+
+        if let Some(normals) = &self.normals {
+            check_len("normals", normals.len())?;
+        }
+        if let Some(tangents) = &self.tangents {
+            check_len("tangents", tangents.len())?;
+        }
+        for uvs in &self.uvs {
+            check_len("uvs", uvs.len())?;
+        }
+        for colors in &self.colors {
+            check_len("colors", colors.len())?;
+        }
+
+        if let Some(skin) = &self.skin {
+            check_len("skin joints", skin.joints.len())?;
+            check_len("skin weights", skin.weights.len())?;
+            if skin.inverse_bind_matrices.len() != skin.joint_transforms.len() {
+                return Err(AwsmMeshError::InvalidMeshData(format!(
+                    "skin has {} inverse bind matrices for {} joints",
+                    skin.inverse_bind_matrices.len(),
+                    skin.joint_transforms.len()
+                )));
+            }
+            let joint_count = skin.joint_transforms.len();
+            if let Some(joint) = skin
+                .joints
+                .iter()
+                .flatten()
+                .find(|joint| **joint as usize >= joint_count)
+            {
+                return Err(AwsmMeshError::InvalidMeshData(format!(
+                    "skin joint {joint} is out of range for {joint_count} joints"
+                )));
+            }
+        }
+
+        for morph_target in &self.morph_targets {
+            if let Some(positions) = &morph_target.positions {
+                check_len("morph target positions", positions.len())?;
+            }
+            if let Some(normals) = &morph_target.normals {
+                check_len("morph target normals", normals.len())?;
+            }
+            if let Some(tangents) = &morph_target.tangents {
+                check_len("morph target tangents", tangents.len())?;
+            }
+        }
+
+        if !self.morph_weights.is_empty() && self.morph_weights.len() != self.morph_targets.len() {
+            return Err(AwsmMeshError::MorphWeightsTargetsMismatch {
+                weights: self.morph_weights.len(),
+                targets: self.morph_targets.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
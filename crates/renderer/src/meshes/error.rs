@@ -74,6 +74,23 @@ pub enum AwsmMeshError {
     #[error("[mesh] morph must have same number of weights as targets: {weights} weights != {targets} targets")]
     MorphWeightsTargetsMismatch { weights: usize, targets: usize },
 
+    #[error("[mesh] invalid mesh data: {0}")]
+    InvalidMeshData(String),
+
+    #[error("[mesh] vertex update {start}..{end} is out of range for {vertex_count} vertices: {mesh_key:?}")]
+    VertexUpdateOutOfRange {
+        mesh_key: MeshKey,
+        start: usize,
+        end: usize,
+        vertex_count: usize,
+    },
+
+    #[error("[mesh] no f32 {attribute} to update: {mesh_key:?}")]
+    VertexAttributeNotFound {
+        mesh_key: MeshKey,
+        attribute: String,
+    },
+
     #[error("[mesh] {0:?}")]
     BindGroup(#[from] AwsmBindGroupError),
 
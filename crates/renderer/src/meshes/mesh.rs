@@ -1,12 +1,14 @@
 //! Mesh data and rendering helpers.
 
 use awsm_renderer_core::{
-    command::render_pass::RenderPassEncoder, pipeline::primitive::IndexFormat,
+    command::render_pass::RenderPassEncoder,
+    pipeline::primitive::{CullMode, IndexFormat},
 };
 
+use crate::instances::Instances;
 use crate::materials::MaterialKey;
 use crate::meshes::error::AwsmMeshError;
-use crate::meshes::MeshKey;
+use crate::meshes::{MeshKey, Meshes};
 use crate::render::RenderContext;
 use crate::render_passes::geometry::bind_group::GeometryBindGroups;
 use crate::render_passes::geometry::pipeline::GeometryRenderPipelineKeyOpts;
@@ -27,6 +29,8 @@ pub struct Mesh {
     pub instanced: bool,
     pub hud: bool,
     pub hidden: bool,
+    pub cast_shadows: bool,
+    pub receive_shadows: bool,
 }
 
 impl Mesh {
@@ -47,6 +51,17 @@ impl Mesh {
             hud,
             world_aabb: None,
             hidden,
+            cast_shadows: true,
+            receive_shadows: true,
+        }
+    }
+
+    /// Returns the cull mode used when rasterizing this mesh.
+    pub fn cull_mode(&self) -> CullMode {
+        if self.double_sided {
+            CullMode::None
+        } else {
+            CullMode::Back
         }
     }
 
@@ -58,11 +73,7 @@ impl Mesh {
             .get_render_pipeline_key(GeometryRenderPipelineKeyOpts {
                 anti_aliasing: ctx.anti_aliasing,
                 instancing: self.instanced,
-                cull_mode: if self.double_sided {
-                    awsm_renderer_core::pipeline::primitive::CullMode::None
-                } else {
-                    awsm_renderer_core::pipeline::primitive::CullMode::Back
-                },
+                cull_mode: self.cull_mode(),
             })
     }
 
@@ -126,16 +137,20 @@ impl Mesh {
     }
 
     /// Pushes transparent material pass commands for this mesh.
+    ///
+    /// Takes the meshes and instances directly rather than a [`RenderContext`], since picking
+    /// draws transparent meshes outside of a frame.
     pub fn push_material_transparent_pass_commands(
         &self,
-        ctx: &RenderContext,
+        meshes: &Meshes,
+        instances: &Instances,
         mesh_key: MeshKey,
         render_pass: &RenderPassEncoder,
         mesh_material_bind_group: &web_sys::GpuBindGroup,
     ) -> Result<()> {
-        let geometry_meta_offset = ctx.meshes.meta.geometry_buffer_offset(mesh_key)? as u32;
-        let material_meta_offset = ctx.meshes.meta.material_buffer_offset(mesh_key)? as u32;
-        let buffer_info = ctx.meshes.buffer_info(mesh_key)?;
+        let geometry_meta_offset = meshes.meta.geometry_buffer_offset(mesh_key)? as u32;
+        let material_meta_offset = meshes.meta.material_buffer_offset(mesh_key)? as u32;
+        let buffer_info = meshes.buffer_info(mesh_key)?;
 
         render_pass.set_bind_group(
             3,
@@ -146,20 +161,17 @@ impl Mesh {
         // Geometry stuff Slot 0 (locations 0-4)
         render_pass.set_vertex_buffer(
             0,
-            ctx.meshes.transparency_geometry_data_gpu_buffer(),
-            Some(
-                ctx.meshes
-                    .transparency_geometry_data_buffer_offset(mesh_key)? as u64,
-            ),
+            meshes.transparency_geometry_data_gpu_buffer(),
+            Some(meshes.transparency_geometry_data_buffer_offset(mesh_key)? as u64),
             None,
         );
 
         // Instancing Slot 1 (locations 5-8)
         let attribute_slot = if self.instanced {
-            let offset = ctx.instances.transform_buffer_offset(self.transform_key)?;
+            let offset = instances.transform_buffer_offset(self.transform_key)?;
             render_pass.set_vertex_buffer(
                 1,
-                ctx.instances.gpu_transform_buffer(),
+                instances.gpu_transform_buffer(),
                 Some(offset as u64),
                 None,
             );
@@ -174,26 +186,22 @@ impl Mesh {
         // If not instanced: slot 1 (locations 5+)
         render_pass.set_vertex_buffer(
             attribute_slot,
-            ctx.meshes.custom_attribute_data_gpu_buffer(),
-            Some(ctx.meshes.custom_attribute_data_buffer_offset(mesh_key)? as u64),
+            meshes.custom_attribute_data_gpu_buffer(),
+            Some(meshes.custom_attribute_data_buffer_offset(mesh_key)? as u64),
             None,
         );
 
         render_pass.set_index_buffer(
-            ctx.meshes.transparency_geometry_index_gpu_buffer(),
+            meshes.transparency_geometry_index_gpu_buffer(),
             IndexFormat::Uint32,
-            Some(
-                ctx.meshes
-                    .transparency_geometry_index_buffer_offset(mesh_key)? as u64,
-            ),
+            Some(meshes.transparency_geometry_index_buffer_offset(mesh_key)? as u64),
             None,
         );
 
         let index_count = buffer_info.triangles.vertex_attribute_indices.count as u32;
 
         if self.instanced {
-            let instance_count = ctx
-                .instances
+            let instance_count = instances
                 .transform_instance_count(self.transform_key)
                 .ok_or(AwsmMeshError::InstancingMissingTransforms(mesh_key))?;
             render_pass.draw_indexed_with_instance_count(index_count, instance_count as u32);
@@ -178,6 +178,14 @@ impl MeshMeta {
         }
     }
 
+    // after a device loss, the next write_gpu recreates the buffers from the CPU data
+    pub(crate) fn mark_gpu_lost(&mut self) {
+        self.geometry_buffers.mark_gpu_lost();
+        self.material_buffers.mark_gpu_lost();
+        self.geometry_dirty = true;
+        self.material_dirty = true;
+    }
+
     /// Writes dirty metadata buffers to the GPU.
     pub fn write_gpu(
         &mut self,
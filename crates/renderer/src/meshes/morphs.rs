@@ -3,6 +3,7 @@
 use std::sync::LazyLock;
 
 use awsm_renderer_core::buffers::{BufferDescriptor, BufferUsage};
+use awsm_renderer_core::command::CommandEncoder;
 use awsm_renderer_core::renderer::AwsmRendererWebGpu;
 use slotmap::{new_key_type, SlotMap};
 
@@ -10,7 +11,7 @@ use super::error::{AwsmMeshError, Result};
 use crate::bind_groups::BindGroupCreate;
 use crate::bind_groups::BindGroups;
 use crate::buffer::dynamic_storage::DynamicStorageBuffer;
-use crate::buffer::helpers::write_buffer_with_dirty_ranges;
+use crate::buffer::helpers::{copy_whole_buffer, write_buffer_with_dirty_ranges};
 use crate::meshes::buffer_info::{MeshBufferGeometryMorphInfo, MeshBufferMaterialMorphInfo};
 use crate::AwsmRendererLogging;
 
@@ -32,6 +33,12 @@ impl Morphs {
         })
     }
 
+    // after a device loss, the next write_gpu recreates the buffers from the CPU data
+    pub(crate) fn mark_gpu_lost(&mut self) {
+        self.geometry.mark_gpu_lost();
+        self.material.mark_gpu_lost();
+    }
+
     /// Writes morph target data to the GPU.
     pub fn write_gpu(
         &mut self,
@@ -74,8 +81,12 @@ impl MorphInfo for MeshBufferMaterialMorphInfo {
     }
 }
 
-static BUFFER_USAGE_WEIGHTS: LazyLock<BufferUsage> =
-    LazyLock::new(|| BufferUsage::new().with_storage().with_copy_dst());
+static BUFFER_USAGE_WEIGHTS: LazyLock<BufferUsage> = LazyLock::new(|| {
+    BufferUsage::new()
+        .with_storage()
+        .with_copy_dst()
+        .with_copy_src()
+});
 static BUFFER_USAGE_VALUES: LazyLock<BufferUsage> =
     LazyLock::new(|| BufferUsage::new().with_storage().with_copy_dst());
 
@@ -95,6 +106,14 @@ impl<Key: slotmap::Key, Info: MorphInfo> MorphData<Key, Info> {
             )
             .into(),
         )?;
+        let previous_gpu_buffer_weights = gpu.create_buffer(
+            &BufferDescriptor::new(
+                Some("Previous Morph Weights"),
+                Self::WEIGHTS_INITIAL_SIZE,
+                *BUFFER_USAGE_WEIGHTS,
+            )
+            .into(),
+        )?;
 
         let gpu_buffer_values = gpu.create_buffer(
             &BufferDescriptor::new(
@@ -119,6 +138,7 @@ impl<Key: slotmap::Key, Info: MorphInfo> MorphData<Key, Info> {
             infos: SlotMap::with_key(),
             gpu_buffer_weights,
             gpu_buffer_values,
+            previous_gpu_buffer_weights,
         })
     }
 
@@ -216,6 +236,23 @@ impl<Key: slotmap::Key, Info: MorphInfo> MorphData<Key, Info> {
         Ok(())
     }
 
+    /// Records a copy of this frame's weights, read back as the previous ones next frame.
+    pub(crate) fn copy_to_previous(&self, command_encoder: &CommandEncoder) -> Result<()> {
+        copy_whole_buffer(
+            command_encoder,
+            &self.gpu_buffer_weights,
+            &self.previous_gpu_buffer_weights,
+        )?;
+        Ok(())
+    }
+
+    fn mark_gpu_lost(&mut self) {
+        self.weights.mark_gpu_lost();
+        self.values.mark_gpu_lost();
+        self.weights_dirty = true;
+        self.values_dirty = true;
+    }
+
     // This *does* write to the gpu, should be called only once per frame
     // just write the entire buffer in one fell swoop
     fn write_gpu(
@@ -239,6 +276,14 @@ impl<Key: slotmap::Key, Info: MorphInfo> MorphData<Key, Info> {
                     &BufferDescriptor::new(Some("Morph Weights"), new_size, *BUFFER_USAGE_WEIGHTS)
                         .into(),
                 )?;
+                self.previous_gpu_buffer_weights = gpu.create_buffer(
+                    &BufferDescriptor::new(
+                        Some("Previous Morph Weights"),
+                        new_size,
+                        *BUFFER_USAGE_WEIGHTS,
+                    )
+                    .into(),
+                )?;
 
                 bind_groups.mark_create(bind_group_create_weight_kind);
                 resized = true;
@@ -252,6 +297,14 @@ impl<Key: slotmap::Key, Info: MorphInfo> MorphData<Key, Info> {
                     None,
                     None,
                 )?;
+                // no history survives the resize, so this frame has no motion
+                gpu.write_buffer(
+                    &self.previous_gpu_buffer_weights,
+                    None,
+                    self.weights.raw_slice(),
+                    None,
+                    None,
+                )?;
             } else {
                 let ranges = self.weights.take_dirty_ranges();
                 write_buffer_with_dirty_ranges(
@@ -316,6 +369,8 @@ pub struct MorphData<Key: slotmap::Key, Info> {
     infos: SlotMap<Key, Info>,
     pub(crate) gpu_buffer_weights: web_sys::GpuBuffer,
     pub(crate) gpu_buffer_values: web_sys::GpuBuffer,
+    // last frame's weights, for motion vectors
+    pub(crate) previous_gpu_buffer_weights: web_sys::GpuBuffer,
 }
 
 new_key_type! {
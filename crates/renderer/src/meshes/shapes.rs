@@ -0,0 +1,229 @@
+//! Common shapes as [`MeshData`].
+//!
+//! All shapes are centered on the origin with +Y up, and have normals and UVs
+//! (so tangents get generated on insert).
+
+use std::f32::consts::PI;
+
+use glam::{Vec2, Vec3};
+
+use super::data::MeshData;
+
+impl MeshData {
+    /// Creates a box with the given full size, with hard edges (4 vertices per face).
+    pub fn new_box(size: Vec3) -> Self {
+        let half = size / 2.0;
+        // (normal, u axis, v axis) with u x v = normal, so the corners below wind counter-clockwise
+        let faces = [
+            (Vec3::X, Vec3::NEG_Z, Vec3::Y),
+            (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+            (Vec3::Y, Vec3::X, Vec3::NEG_Z),
+            (Vec3::NEG_Y, Vec3::X, Vec3::Z),
+            (Vec3::Z, Vec3::X, Vec3::Y),
+            (Vec3::NEG_Z, Vec3::NEG_X, Vec3::Y),
+        ];
+
+        let mut data = ShapeBuilder::default();
+        for (normal, u, v) in faces {
+            let center = normal * half.dot(normal.abs());
+            let u_extent = u * half.dot(u.abs());
+            let v_extent = v * half.dot(v.abs());
+
+            let first = data.positions.len() as u32;
+            for (a, b) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                data.push(
+                    center + u_extent * a + v_extent * b,
+                    normal,
+                    [(a + 1.0) / 2.0, (1.0 - b) / 2.0],
+                );
+            }
+            data.indices
+                .extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+        }
+
+        data.into()
+    }
+
+    /// Creates a flat plane on XZ facing +Y, split into `subdivisions` quads per side.
+    pub fn new_plane(size: Vec2, subdivisions: u32) -> Self {
+        let subdivisions = subdivisions.max(1);
+        let row_len = subdivisions + 1;
+
+        let mut data = ShapeBuilder::default();
+        for j in 0..row_len {
+            for i in 0..row_len {
+                let uv = Vec2::new(i as f32, j as f32) / subdivisions as f32;
+                let position = Vec3::new((uv.x - 0.5) * size.x, 0.0, (uv.y - 0.5) * size.y);
+                data.push(position, Vec3::Y, uv.to_array());
+            }
+        }
+        data.push_grid_indices(row_len, subdivisions, |_| (false, false));
+
+        data.into()
+    }
+
+    /// Creates a UV sphere with `segments` around and `rings` from pole to pole.
+    pub fn new_sphere(radius: f32, segments: u32, rings: u32) -> Self {
+        let rings = rings.max(2);
+        let rows = (0..=rings).map(|ring| (ring as f32 / rings as f32 * PI, 0.0));
+
+        lathe(radius, segments, rows, radius * 2.0).into()
+    }
+
+    /// Creates a capped cylinder of the given full height.
+    pub fn new_cylinder(radius: f32, height: f32, segments: u32) -> Self {
+        let segments = segments.max(3);
+        let half_height = height / 2.0;
+
+        let mut data = lathe(
+            radius,
+            segments,
+            [(PI / 2.0, half_height), (PI / 2.0, -half_height)].into_iter(),
+            height,
+        );
+
+        for (y, normal) in [(half_height, Vec3::Y), (-half_height, Vec3::NEG_Y)] {
+            let center = data.positions.len() as u32;
+            data.push(Vec3::new(0.0, y, 0.0), normal, [0.5, 0.5]);
+            for segment in 0..segments {
+                let (x, z) = ring_direction(segment, segments);
+                data.push(
+                    Vec3::new(x * radius, y, z * radius),
+                    normal,
+                    [0.5 + x / 2.0, 0.5 + z / 2.0],
+                );
+            }
+            for segment in 0..segments {
+                let current = center + 1 + segment;
+                let next = center + 1 + (segment + 1) % segments;
+                if normal == Vec3::Y {
+                    data.indices.extend([center, current, next]);
+                } else {
+                    data.indices.extend([center, next, current]);
+                }
+            }
+        }
+
+        data.into()
+    }
+
+    /// Creates a capsule: a cylinder of `length` with a hemisphere of `radius` on each end.
+    ///
+    /// The full height is `length + radius * 2`. `rings` is per hemisphere.
+    pub fn new_capsule(radius: f32, length: f32, segments: u32, rings: u32) -> Self {
+        let rings = rings.max(1);
+        let half_length = length / 2.0;
+
+        let top = (0..=rings).map(|ring| (ring as f32 / rings as f32 * PI / 2.0, half_length));
+        let bottom = (0..=rings).map(|ring| {
+            (
+                PI / 2.0 + ring as f32 / rings as f32 * PI / 2.0,
+                -half_length,
+            )
+        });
+
+        lathe(radius, segments, top.chain(bottom), length + radius * 2.0).into()
+    }
+}
+
+// Sweeps rows of (polar angle from +Y, y offset) around the Y axis.
+// Rows at a pole collapse to a point, the triangles that would be degenerate there are skipped.
+fn lathe(
+    radius: f32,
+    segments: u32,
+    rows: impl Iterator<Item = (f32, f32)>,
+    total_height: f32,
+) -> ShapeBuilder {
+    let segments = segments.max(3);
+    let row_len = segments + 1;
+    let rows: Vec<(f32, f32)> = rows.collect();
+    let top = rows
+        .first()
+        .map(|(theta, offset)| theta.cos() * radius + offset)
+        .unwrap_or(0.0);
+
+    let mut data = ShapeBuilder::default();
+    for (theta, offset) in rows.iter().copied() {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        // repeat the first column at the end, so the UV seam has its own vertices
+        for segment in 0..row_len {
+            let (x, z) = ring_direction(segment, segments);
+            let normal = Vec3::new(x * sin_theta, cos_theta, z * sin_theta);
+            let position = normal * radius + Vec3::new(0.0, offset, 0.0);
+            data.push(
+                position,
+                normal,
+                [
+                    segment as f32 / segments as f32,
+                    (top - position.y) / total_height,
+                ],
+            );
+        }
+    }
+
+    let is_pole = |row: usize| rows[row].0.sin().abs() < 1e-6;
+    data.push_grid_indices(row_len, rows.len() as u32 - 1, |row| {
+        (is_pole(row as usize), is_pole(row as usize + 1))
+    });
+
+    data
+}
+
+// unit circle on XZ, counter-clockwise when seen from +Y
+fn ring_direction(segment: u32, segments: u32) -> (f32, f32) {
+    let (sin, cos) = (segment as f32 / segments as f32 * PI * 2.0).sin_cos();
+    (cos, -sin)
+}
+
+#[derive(Default)]
+struct ShapeBuilder {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+}
+
+impl ShapeBuilder {
+    fn push(&mut self, position: Vec3, normal: Vec3, uv: [f32; 2]) {
+        self.positions.push(position.to_array());
+        self.normals.push(normal.to_array());
+        self.uvs.push(uv);
+    }
+
+    // quads between consecutive rows of `row_len` vertices, laid out so that going along a row
+    // then down to the next one is counter-clockwise from the front
+    // `skip(row)` returns whether the first or second triangle of that row's quads is degenerate
+    fn push_grid_indices(
+        &mut self,
+        row_len: u32,
+        quad_rows: u32,
+        skip: impl Fn(u32) -> (bool, bool),
+    ) {
+        for row in 0..quad_rows {
+            let (skip_top, skip_bottom) = skip(row);
+            for column in 0..row_len - 1 {
+                let a = row * row_len + column;
+                let b = a + 1;
+                let c = a + row_len;
+                let d = c + 1;
+                if !skip_top {
+                    self.indices.extend([a, c, b]);
+                }
+                if !skip_bottom {
+                    self.indices.extend([b, c, d]);
+                }
+            }
+        }
+    }
+}
+
+impl From<ShapeBuilder> for MeshData {
+    fn from(builder: ShapeBuilder) -> Self {
+        MeshData::new(builder.positions, builder.indices)
+            .with_normals(builder.normals)
+            .with_uvs(builder.uvs)
+    }
+}
+
+#[cfg(test)]
+mod tests;
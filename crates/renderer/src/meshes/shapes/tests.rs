@@ -0,0 +1,122 @@
+use glam::{Vec2, Vec3};
+
+use crate::meshes::data::{MeshData, MeshDataMorphTarget};
+use crate::meshes::error::AwsmMeshError;
+
+fn shapes() -> Vec<(&'static str, MeshData, Vec3)> {
+    vec![
+        (
+            "box",
+            MeshData::new_box(Vec3::new(1.0, 2.0, 3.0)),
+            Vec3::new(1.0, 2.0, 3.0),
+        ),
+        (
+            "plane",
+            MeshData::new_plane(Vec2::new(4.0, 2.0), 3),
+            Vec3::new(4.0, 0.0, 2.0),
+        ),
+        ("sphere", MeshData::new_sphere(0.5, 16, 8), Vec3::splat(1.0)),
+        (
+            "cylinder",
+            MeshData::new_cylinder(0.5, 2.0, 12),
+            Vec3::new(1.0, 2.0, 1.0),
+        ),
+        (
+            "capsule",
+            MeshData::new_capsule(0.5, 1.0, 12, 4),
+            Vec3::new(1.0, 2.0, 1.0),
+        ),
+    ]
+}
+
+#[test]
+fn shapes_are_valid() {
+    for (name, data, _) in shapes() {
+        data.validate()
+            .unwrap_or_else(|err| panic!("{name}: {err}"));
+        assert_eq!(data.uvs.len(), 1, "{name}");
+
+        for normal in data.normals.as_ref().unwrap() {
+            let length = Vec3::from_array(*normal).length();
+            assert!(
+                (length - 1.0).abs() < 1e-5,
+                "{name}: normal length {length}"
+            );
+        }
+    }
+}
+
+#[test]
+fn shapes_fit_their_size() {
+    for (name, data, size) in shapes() {
+        let aabb = data.aabb().unwrap();
+        assert!(aabb.size().abs_diff_eq(size, 1e-5), "{name}: {:?}", aabb);
+        assert!(
+            (aabb.min + aabb.max).abs_diff_eq(Vec3::ZERO, 1e-5),
+            "{name}: not centered"
+        );
+    }
+}
+
+#[test]
+fn triangles_wind_counter_clockwise_from_the_outside() {
+    for (name, data, _) in shapes() {
+        let normals = data.normals.as_ref().unwrap();
+        for triangle in data.indices.chunks_exact(3) {
+            let [a, b, c] =
+                [0, 1, 2].map(|i| Vec3::from_array(data.positions[triangle[i] as usize]));
+            let face_normal = (b - a).cross(c - a);
+            assert!(
+                face_normal.length() > 1e-8,
+                "{name}: degenerate {triangle:?}"
+            );
+
+            let vertex_normal: Vec3 = triangle
+                .iter()
+                .map(|index| Vec3::from_array(normals[*index as usize]))
+                .sum();
+            assert!(
+                face_normal.dot(vertex_normal) > 0.0,
+                "{name}: {triangle:?} faces inward"
+            );
+        }
+    }
+}
+
+#[test]
+fn validate_rejects_mismatched_data() {
+    let triangle = || {
+        MeshData::new(
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            vec![0, 1, 2],
+        )
+    };
+    assert!(triangle().validate().is_ok());
+
+    let invalid = [
+        MeshData::new(triangle().positions, vec![0, 1]),
+        MeshData::new(triangle().positions, vec![0, 1, 3]),
+        triangle().with_normals(vec![[0.0, 0.0, 1.0]]),
+        triangle()
+            .with_uvs(vec![[0.0, 0.0]; 3])
+            .with_uvs(vec![[0.0, 0.0]; 2]),
+        triangle().with_morph_target(MeshDataMorphTarget {
+            positions: Some(vec![[0.0, 0.0, 0.0]; 4]),
+            ..Default::default()
+        }),
+    ];
+    for data in invalid {
+        assert!(matches!(
+            data.validate(),
+            Err(AwsmMeshError::InvalidMeshData(_))
+        ));
+    }
+
+    let mismatched_weights = triangle()
+        .with_morph_target(MeshDataMorphTarget::default())
+        .with_morph_weights(vec![0.5, 0.5]);
+    assert!(matches!(
+        mismatched_weights.validate(),
+        Err(AwsmMeshError::MorphWeightsTargetsMismatch { .. })
+    ));
+}
@@ -4,6 +4,7 @@ use std::{collections::HashMap, sync::LazyLock};
 
 use awsm_renderer_core::{
     buffers::{BufferDescriptor, BufferUsage},
+    command::CommandEncoder,
     error::AwsmCoreError,
     renderer::AwsmRendererWebGpu,
 };
@@ -14,7 +15,7 @@ use thiserror::Error;
 use crate::{
     bind_groups::{AwsmBindGroupError, BindGroupCreate, BindGroups},
     buffer::dynamic_storage::DynamicStorageBuffer,
-    buffer::helpers::write_buffer_with_dirty_ranges,
+    buffer::helpers::{copy_whole_buffer, write_buffer_with_dirty_ranges},
     transforms::TransformKey,
     AwsmRendererLogging,
 };
@@ -31,10 +32,16 @@ pub struct Skins {
     joint_index_weights_gpu_dirty: bool,
     pub(crate) matrices_gpu_buffer: web_sys::GpuBuffer,
     pub(crate) joint_index_weights_gpu_buffer: web_sys::GpuBuffer,
+    // last frame's joint matrices, for motion vectors
+    pub(crate) previous_matrices_gpu_buffer: web_sys::GpuBuffer,
 }
 
-static BUFFER_USAGE: LazyLock<BufferUsage> =
-    LazyLock::new(|| BufferUsage::new().with_storage().with_copy_dst());
+static BUFFER_USAGE: LazyLock<BufferUsage> = LazyLock::new(|| {
+    BufferUsage::new()
+        .with_storage()
+        .with_copy_dst()
+        .with_copy_src()
+});
 impl Skins {
     /// Initial size for skin matrix storage.
     pub const SKIN_MATRICES_INITIAL_SIZE: usize = 16 * 4 * 32; // 32 elements is a good starting point
@@ -51,6 +58,14 @@ impl Skins {
             )
             .into(),
         )?;
+        let previous_matrices_gpu_buffer = gpu.create_buffer(
+            &BufferDescriptor::new(
+                Some("Previous Skin Matrices"),
+                Self::SKIN_MATRICES_INITIAL_SIZE,
+                *BUFFER_USAGE,
+            )
+            .into(),
+        )?;
 
         let joint_index_weights_gpu_buffer = gpu.create_buffer(
             &BufferDescriptor::new(
@@ -77,6 +92,7 @@ impl Skins {
             joint_index_weights_gpu_dirty: true,
             matrices_gpu_buffer,
             joint_index_weights_gpu_buffer,
+            previous_matrices_gpu_buffer,
         })
     }
 
@@ -158,6 +174,45 @@ impl Skins {
             .ok_or(AwsmSkinError::SkinNotFound(skin_key))
     }
 
+    /// Returns the blended joint matrix for one vertex, from the CPU copy of the skin data.
+    ///
+    /// Matches `apply_position_skin` in the vertex shaders, `vertex_index` is the original
+    /// (unexploded) vertex index.
+    pub fn vertex_skin_matrix(&self, skin_key: SkinKey, vertex_index: u32) -> Option<Mat4> {
+        // 4 interleaved (joint index, weight) pairs per set, see skin.wgsl
+        const FLOATS_PER_SET: usize = 8;
+
+        let sets_len = *self.sets_len.get(skin_key)?;
+        let matrices = self.skin_matrices.get(skin_key)?;
+        let index_weights = self.joint_index_weights.get(skin_key)?;
+
+        let read_4 = |data: &[u8], offset: usize| -> Option<[u8; 4]> {
+            data.get(offset..offset + 4)?.try_into().ok()
+        };
+
+        let base = vertex_index as usize * sets_len * FLOATS_PER_SET * 4;
+        let mut skin_matrix = Mat4::ZERO;
+
+        for pair in 0..sets_len * 4 {
+            let offset = base + pair * 8;
+            let joint_index = u32::from_le_bytes(read_4(index_weights, offset)?) as usize;
+            let weight = f32::from_le_bytes(read_4(index_weights, offset + 4)?);
+
+            if weight == 0.0 {
+                continue;
+            }
+
+            let mut joint_matrix = [0.0f32; 16];
+            for (index, value) in joint_matrix.iter_mut().enumerate() {
+                *value = f32::from_le_bytes(read_4(matrices, joint_index * 64 + index * 4)?);
+            }
+
+            skin_matrix += Mat4::from_cols_array(&joint_matrix) * weight;
+        }
+
+        Some(skin_matrix)
+    }
+
     /// Updates skin matrices from dirty joint transforms.
     pub fn update_transforms(&mut self, dirty_skin_joints: HashMap<TransformKey, Mat4>) {
         // different skins can theoretically share the same joint, so, iterate over them all
@@ -193,6 +248,24 @@ impl Skins {
         }
     }
 
+    /// Records a copy of this frame's joint matrices, read back as the previous ones next frame.
+    pub(crate) fn copy_to_previous(&self, command_encoder: &CommandEncoder) -> Result<()> {
+        copy_whole_buffer(
+            command_encoder,
+            &self.matrices_gpu_buffer,
+            &self.previous_matrices_gpu_buffer,
+        )?;
+        Ok(())
+    }
+
+    // after a device loss, the next write_gpu recreates the buffers from the CPU data
+    pub(crate) fn mark_gpu_lost(&mut self) {
+        self.skin_matrices.mark_gpu_lost();
+        self.joint_index_weights.mark_gpu_lost();
+        self.matrices_gpu_dirty = true;
+        self.joint_index_weights_gpu_dirty = true;
+    }
+
     /// Writes skin buffers to the GPU.
     pub fn write_gpu(
         &mut self,
@@ -212,6 +285,9 @@ impl Skins {
                 self.matrices_gpu_buffer = gpu.create_buffer(
                     &BufferDescriptor::new(Some("Skins"), new_size, *BUFFER_USAGE).into(),
                 )?;
+                self.previous_matrices_gpu_buffer = gpu.create_buffer(
+                    &BufferDescriptor::new(Some("Previous Skins"), new_size, *BUFFER_USAGE).into(),
+                )?;
 
                 bind_groups.mark_create(BindGroupCreate::SkinJointMatricesResize);
                 resized = true;
@@ -226,6 +302,14 @@ impl Skins {
                     None,
                     None,
                 )?;
+                // no history survives the resize, so this frame has no motion
+                gpu.write_buffer(
+                    &self.previous_matrices_gpu_buffer,
+                    None,
+                    self.skin_matrices.raw_slice(),
+                    None,
+                    None,
+                )?;
             } else {
                 let ranges = self.skin_matrices.take_dirty_ranges();
                 write_buffer_with_dirty_ranges(
@@ -0,0 +1,521 @@
+//! In-place updates of mesh vertex data.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use glam::Vec3;
+
+use crate::bounds::Aabb;
+use crate::instances::Instances;
+use crate::transforms::Transforms;
+
+use super::buffer_info::{
+    MeshBufferCustomVertexAttributeInfo, MeshBufferInfo, MeshBufferVertexAttributeInfo,
+    MeshBufferVertexInfo,
+};
+use super::data::MeshVertexUpdate;
+use super::error::{AwsmMeshError, Result};
+use super::{MeshKey, Meshes};
+
+// byte offsets within one visibility vertex, see `MeshBufferVertexInfo::VISIBILITY_GEOMETRY_BYTE_SIZE`
+const VISIBILITY_POSITION_OFFSET: usize = 0;
+const VISIBILITY_NORMAL_OFFSET: usize = 24;
+const VISIBILITY_TANGENT_OFFSET: usize = 36;
+const VISIBILITY_ORIGINAL_INDEX_OFFSET: usize = 52;
+
+// byte offsets within one transparency vertex, see `MeshBufferVertexInfo::TRANSPARENCY_GEOMETRY_BYTE_SIZE`
+const TRANSPARENCY_POSITION_OFFSET: usize = 0;
+const TRANSPARENCY_NORMAL_OFFSET: usize = 12;
+const TRANSPARENCY_TANGENT_OFFSET: usize = 24;
+
+impl Meshes {
+    /// Overwrites part of a mesh's vertex data in place and refreshes its bounds.
+    ///
+    /// The change is made on the shared resource, so duplicates of the mesh change too.
+    /// Only the touched byte ranges are uploaded on the next GPU write.
+    pub fn update_vertices(
+        &mut self,
+        mesh_key: MeshKey,
+        update: &MeshVertexUpdate,
+        transforms: &Transforms,
+        instances: &Instances,
+    ) -> Result<()> {
+        let resource_key = self.resource_key(mesh_key)?;
+        let buffer_info = self
+            .buffer_infos
+            .get(self.resource(mesh_key)?.buffer_info_key)?;
+
+        let vertex_count = original_vertex_count(
+            buffer_info,
+            self.visibility_geometry_data_buffers.get(resource_key),
+        );
+        let check_range = |len: usize| {
+            let end = update.first_vertex + len;
+            if end > vertex_count {
+                Err(AwsmMeshError::VertexUpdateOutOfRange {
+                    mesh_key,
+                    start: update.first_vertex,
+                    end,
+                    vertex_count,
+                })
+            } else {
+                Ok(())
+            }
+        };
+
+        check_range(update.positions.as_ref().map_or(0, Vec::len))?;
+        check_range(update.normals.as_ref().map_or(0, Vec::len))?;
+        check_range(update.tangents.as_ref().map_or(0, Vec::len))?;
+
+        // resolve every custom attribute before writing anything, so a bad set doesn't leave a partial update
+        let mut custom_writes: Vec<(usize, usize, Vec<&[f32]>)> = Vec::new();
+        for (set, uvs) in &update.uvs {
+            check_range(uvs.len())?;
+            let (offset, component_len) = custom_attribute_layout(buffer_info, |attribute| {
+                matches!(attribute, MeshBufferCustomVertexAttributeInfo::TexCoords { index, .. } if index == set)
+            })
+            .ok_or_else(|| AwsmMeshError::VertexAttributeNotFound {
+                mesh_key,
+                attribute: format!("uv set {set}"),
+            })?;
+            custom_writes.push((
+                offset,
+                component_len,
+                uvs.iter().map(|uv| &uv[..]).collect(),
+            ));
+        }
+        for (set, colors) in &update.colors {
+            check_range(colors.len())?;
+            let (offset, component_len) = custom_attribute_layout(buffer_info, |attribute| {
+                matches!(attribute, MeshBufferCustomVertexAttributeInfo::Colors { index, .. } if index == set)
+            })
+            .ok_or_else(|| AwsmMeshError::VertexAttributeNotFound {
+                mesh_key,
+                attribute: format!("color set {set}"),
+            })?;
+            custom_writes.push((
+                offset,
+                component_len,
+                colors.iter().map(|color| &color[..]).collect(),
+            ));
+        }
+
+        let visibility_count = buffer_info
+            .visibility_geometry_vertex
+            .as_ref()
+            .map(|info| info.count);
+        let transparency_count = buffer_info
+            .transparency_geometry_vertex
+            .as_ref()
+            .map(|info| info.count);
+        let custom_attribute_stride = buffer_info.triangles.vertex_attribute_stride();
+
+        if let Some(count) = visibility_count {
+            self.visibility_geometry_data_buffers
+                .update_partial_with(resource_key, |data| {
+                    write_visibility_vertices(data, count, update)
+                });
+            self.visibility_geometry_data_dirty = true;
+        }
+
+        if let Some(count) = transparency_count {
+            self.transparency_geometry_data_buffers
+                .update_partial_with(resource_key, |data| {
+                    write_transparency_vertices(data, count, update)
+                });
+            self.transparency_geometry_data_dirty = true;
+        }
+
+        if !custom_writes.is_empty() {
+            self.custom_attribute_data_buffers
+                .update_partial_with(resource_key, |data| {
+                    let mut touched = None;
+                    for (offset, component_len, values) in &custom_writes {
+                        let range = write_custom_attribute(
+                            data,
+                            custom_attribute_stride,
+                            *offset,
+                            *component_len,
+                            update.first_vertex,
+                            values,
+                        );
+                        extend_range(&mut touched, range);
+                    }
+                    touched
+                });
+            self.custom_attribute_data_dirty = true;
+        }
+
+        if update.positions.is_some() {
+            let aabb = match (transparency_count, visibility_count) {
+                (Some(count), _) => self
+                    .transparency_geometry_data_buffers
+                    .get(resource_key)
+                    .and_then(|data| {
+                        positions_aabb(
+                            data,
+                            MeshBufferVertexInfo::TRANSPARENCY_GEOMETRY_BYTE_SIZE,
+                            TRANSPARENCY_POSITION_OFFSET,
+                            count,
+                        )
+                    }),
+                (None, Some(count)) => self
+                    .visibility_geometry_data_buffers
+                    .get(resource_key)
+                    .and_then(|data| {
+                        positions_aabb(
+                            data,
+                            MeshBufferVertexInfo::VISIBILITY_GEOMETRY_BYTE_SIZE,
+                            VISIBILITY_POSITION_OFFSET,
+                            count,
+                        )
+                    }),
+                (None, None) => None,
+            };
+
+            if let Some(resource) = self.resources.get_mut(resource_key) {
+                resource.aabb = aabb;
+            }
+
+            let transform_keys: HashSet<_> = self
+                .mesh_to_resource
+                .iter()
+                .filter(|(_, key)| **key == resource_key)
+                .filter_map(|(key, _)| self.list.get(key).map(|mesh| mesh.transform_key))
+                .collect();
+            for transform_key in transform_keys {
+                if let Ok(world_mat) = transforms.get_world(transform_key) {
+                    self.update_world_aabbs(transform_key, *world_mat, instances);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Meshes {
+    /// Returns the object-space positions of one triangle, from the CPU copy of the visibility
+    /// geometry data.
+    ///
+    /// These are the undeformed positions, before skinning and morphing.
+    pub fn visibility_triangle_positions(
+        &self,
+        key: MeshKey,
+        triangle_index: u32,
+    ) -> Result<[Vec3; 3]> {
+        let data = self
+            .visibility_geometry_data_buffers
+            .get(self.resource_key(key)?)
+            .ok_or(AwsmMeshError::VisibilityGeometryBufferNotFound(key))?;
+
+        visibility_triangle_positions(data, triangle_index)
+            .ok_or(AwsmMeshError::VisibilityGeometryBufferNotFound(key))
+    }
+
+    /// Calls `f` with the index, object-space positions and original vertex indices of every
+    /// triangle, from the CPU copy of the geometry.
+    ///
+    /// These are the undeformed positions, before skinning and morphing. The vertex indices are
+    /// what skin and morph data is indexed by.
+    pub fn for_each_triangle(
+        &self,
+        key: MeshKey,
+        mut f: impl FnMut(u32, [Vec3; 3], [u32; 3]),
+    ) -> Result<()> {
+        let resource_key = self.resource_key(key)?;
+        let buffer_info = self.buffer_info(key)?;
+
+        if let Some(info) = &buffer_info.visibility_geometry_vertex {
+            let data = self
+                .visibility_geometry_data_buffers
+                .get(resource_key)
+                .ok_or(AwsmMeshError::VisibilityGeometryBufferNotFound(key))?;
+
+            // exploded, every three vertices are a triangle
+            let stride = MeshBufferVertexInfo::VISIBILITY_GEOMETRY_BYTE_SIZE;
+            for (triangle_index, triangle) in data
+                .chunks_exact(stride * 3)
+                .take(info.count / 3)
+                .enumerate()
+            {
+                let corner = |index: usize| &triangle[index * stride..(index + 1) * stride];
+                f(
+                    triangle_index as u32,
+                    [0, 1, 2].map(|index| read_position(corner(index), VISIBILITY_POSITION_OFFSET)),
+                    [0, 1, 2]
+                        .map(|index| read_u32(corner(index), VISIBILITY_ORIGINAL_INDEX_OFFSET)),
+                );
+            }
+
+            return Ok(());
+        }
+
+        if buffer_info.transparency_geometry_vertex.is_some() {
+            let data = self
+                .transparency_geometry_data_buffers
+                .get(resource_key)
+                .ok_or(AwsmMeshError::TransparencyGeometryBufferNotFound(key))?;
+            let indices = self
+                .custom_attribute_index_buffers
+                .get(resource_key)
+                .ok_or(AwsmMeshError::CustomAttributeBufferNotFound(key))?;
+
+            // indexed, like the transparency pass draws it
+            let stride = MeshBufferVertexInfo::TRANSPARENCY_GEOMETRY_BYTE_SIZE;
+            let index_count = buffer_info.triangles.vertex_attribute_indices.count;
+            for (triangle_index, triangle) in indices
+                .chunks_exact(4 * 3)
+                .take(index_count / 3)
+                .enumerate()
+            {
+                let vertex_indices = [0, 1, 2].map(|corner| read_u32(triangle, corner * 4));
+                let mut positions = [Vec3::ZERO; 3];
+                for (position, vertex_index) in positions.iter_mut().zip(vertex_indices) {
+                    let start = vertex_index as usize * stride;
+                    let vertex = data
+                        .get(start..start + stride)
+                        .ok_or(AwsmMeshError::TransparencyGeometryBufferNotFound(key))?;
+                    *position = read_position(vertex, TRANSPARENCY_POSITION_OFFSET);
+                }
+
+                f(triangle_index as u32, positions, vertex_indices);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// the number of vertices before visibility explosion
+fn original_vertex_count(buffer_info: &MeshBufferInfo, visibility_data: Option<&[u8]>) -> usize {
+    if let Some(info) = &buffer_info.transparency_geometry_vertex {
+        return info.count;
+    }
+
+    if let Some(count) = buffer_info
+        .triangles
+        .vertex_attributes_size
+        .checked_div(buffer_info.triangles.vertex_attribute_stride())
+    {
+        return count;
+    }
+
+    // no indexed data at all, so find the highest vertex the triangles refer to
+    match (&buffer_info.visibility_geometry_vertex, visibility_data) {
+        (Some(info), Some(data)) => data
+            .chunks_exact(MeshBufferVertexInfo::VISIBILITY_GEOMETRY_BYTE_SIZE)
+            .take(info.count)
+            .map(|vertex| read_u32(vertex, VISIBILITY_ORIGINAL_INDEX_OFFSET) as usize + 1)
+            .max()
+            .unwrap_or(0),
+        _ => 0,
+    }
+}
+
+// byte offset within one interleaved custom attribute vertex, and the number of components
+fn custom_attribute_layout(
+    buffer_info: &MeshBufferInfo,
+    matches: impl Fn(&MeshBufferCustomVertexAttributeInfo) -> bool,
+) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    for attribute in &buffer_info.triangles.vertex_attributes {
+        if let MeshBufferVertexAttributeInfo::Custom(custom) = attribute {
+            // only f32 data can be written as-is
+            if matches(custom) && attribute.data_size() == 4 {
+                return Some((offset, attribute.component_len()));
+            }
+        }
+        offset += attribute.vertex_size();
+    }
+
+    None
+}
+
+/// Reads one triangle's positions from exploded visibility vertices, `None` if it's out of range.
+fn visibility_triangle_positions(data: &[u8], triangle_index: u32) -> Option<[Vec3; 3]> {
+    // a whole vertex, not just the attributes the triangle needs
+    let stride = MeshBufferVertexInfo::VISIBILITY_GEOMETRY_BYTE_SIZE;
+    let start = triangle_index as usize * 3 * stride;
+    let triangle = data.get(start..start + 3 * stride)?;
+
+    Some(
+        [0, 1, 2]
+            .map(|corner| read_position(&triangle[corner * stride..], VISIBILITY_POSITION_OFFSET)),
+    )
+}
+
+/// Writes positions, normals and tangents into exploded visibility vertices.
+///
+/// Every corner that refers to an updated vertex is written, and the touched byte range returned.
+pub(super) fn write_visibility_vertices(
+    data: &mut [u8],
+    count: usize,
+    update: &MeshVertexUpdate,
+) -> Option<Range<usize>> {
+    let stride = MeshBufferVertexInfo::VISIBILITY_GEOMETRY_BYTE_SIZE;
+    let mut touched = None;
+
+    for (index, vertex) in data.chunks_exact_mut(stride).take(count).enumerate() {
+        let original = read_u32(vertex, VISIBILITY_ORIGINAL_INDEX_OFFSET) as usize;
+        let Some(local) = original.checked_sub(update.first_vertex) else {
+            continue;
+        };
+
+        let wrote = write_geometry_vertex(
+            vertex,
+            local,
+            update,
+            VISIBILITY_POSITION_OFFSET,
+            VISIBILITY_NORMAL_OFFSET,
+            VISIBILITY_TANGENT_OFFSET,
+        );
+        if wrote {
+            extend_range(&mut touched, Some(index * stride..(index + 1) * stride));
+        }
+    }
+
+    touched
+}
+
+/// Writes positions, normals and tangents into indexed transparency vertices.
+pub(super) fn write_transparency_vertices(
+    data: &mut [u8],
+    count: usize,
+    update: &MeshVertexUpdate,
+) -> Option<Range<usize>> {
+    let stride = MeshBufferVertexInfo::TRANSPARENCY_GEOMETRY_BYTE_SIZE;
+    let mut touched = None;
+
+    for (index, vertex) in data
+        .chunks_exact_mut(stride)
+        .take(count)
+        .enumerate()
+        .skip(update.first_vertex)
+    {
+        let wrote = write_geometry_vertex(
+            vertex,
+            index - update.first_vertex,
+            update,
+            TRANSPARENCY_POSITION_OFFSET,
+            TRANSPARENCY_NORMAL_OFFSET,
+            TRANSPARENCY_TANGENT_OFFSET,
+        );
+        if !wrote {
+            break;
+        }
+        extend_range(&mut touched, Some(index * stride..(index + 1) * stride));
+    }
+
+    touched
+}
+
+/// Writes one attribute into interleaved custom attribute vertices.
+pub(super) fn write_custom_attribute(
+    data: &mut [u8],
+    stride: usize,
+    attribute_offset: usize,
+    component_len: usize,
+    first_vertex: usize,
+    values: &[&[f32]],
+) -> Option<Range<usize>> {
+    let mut touched = None;
+
+    for (index, value) in values.iter().enumerate() {
+        let start = (first_vertex + index) * stride + attribute_offset;
+        let components = &value[..component_len.min(value.len())];
+        write_f32s(data, start, components);
+        extend_range(&mut touched, Some(start..start + components.len() * 4));
+    }
+
+    touched
+}
+
+// returns whether anything was written
+fn write_geometry_vertex(
+    vertex: &mut [u8],
+    local: usize,
+    update: &MeshVertexUpdate,
+    position_offset: usize,
+    normal_offset: usize,
+    tangent_offset: usize,
+) -> bool {
+    let mut wrote = false;
+
+    if let Some(position) = update
+        .positions
+        .as_ref()
+        .and_then(|values| values.get(local))
+    {
+        write_f32s(vertex, position_offset, position);
+        wrote = true;
+    }
+    if let Some(normal) = update.normals.as_ref().and_then(|values| values.get(local)) {
+        write_f32s(vertex, normal_offset, normal);
+        wrote = true;
+    }
+    if let Some(tangent) = update
+        .tangents
+        .as_ref()
+        .and_then(|values| values.get(local))
+    {
+        write_f32s(vertex, tangent_offset, tangent);
+        wrote = true;
+    }
+
+    wrote
+}
+
+/// Bounds of the positions in a geometry buffer.
+pub(super) fn positions_aabb(
+    data: &[u8],
+    stride: usize,
+    position_offset: usize,
+    count: usize,
+) -> Option<Aabb> {
+    let mut positions = data
+        .chunks_exact(stride)
+        .take(count)
+        .map(|vertex| read_position(vertex, position_offset));
+    let first = positions.next()?;
+    let (min, max) = positions.fold((first, first), |(min, max), position| {
+        (min.min(position), max.max(position))
+    });
+
+    Some(Aabb::new(min, max))
+}
+
+fn extend_range(touched: &mut Option<Range<usize>>, range: Option<Range<usize>>) {
+    if let Some(range) = range {
+        *touched = Some(match touched.take() {
+            Some(current) => current.start.min(range.start)..current.end.max(range.end),
+            None => range,
+        });
+    }
+}
+
+fn write_f32s(data: &mut [u8], offset: usize, values: &[f32]) {
+    for (index, value) in values.iter().enumerate() {
+        let start = offset + index * 4;
+        data[start..start + 4].copy_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn read_f32(data: &[u8], offset: usize) -> f32 {
+    f32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_position(data: &[u8], offset: usize) -> Vec3 {
+    Vec3::new(
+        read_f32(data, offset),
+        read_f32(data, offset + 4),
+        read_f32(data, offset + 8),
+    )
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[cfg(all(test, feature = "gltf"))]
+mod tests;
@@ -0,0 +1,176 @@
+use glam::{Vec2, Vec3};
+
+use crate::gltf::buffers::mesh::{convert_mesh_data, GltfMeshBufferGeometryKind};
+use crate::gltf::buffers::GltfBuffers;
+use crate::meshes::buffer_info::{MeshBufferCustomVertexAttributeInfo, MeshBufferInfo};
+use crate::meshes::data::{MeshData, MeshVertexUpdate};
+
+use super::*;
+
+// a 1x1 quad (4 vertices, 2 triangles) with a color set and a UV set
+fn quad() -> (GltfBuffers, MeshBufferInfo) {
+    let data = MeshData::new_plane(Vec2::ONE, 1).with_colors(vec![[1.0; 4]; 4]);
+    let buffers = convert_mesh_data(&data, GltfMeshBufferGeometryKind::Both, false).unwrap();
+    let info = MeshBufferInfo::from(buffers.meshes[0][0].clone());
+    (buffers, info)
+}
+
+fn read_vec3(data: &[u8], offset: usize) -> Vec3 {
+    Vec3::new(
+        read_f32(data, offset),
+        read_f32(data, offset + 4),
+        read_f32(data, offset + 8),
+    )
+}
+
+#[test]
+fn vertex_count_is_before_explosion() {
+    let (buffers, info) = quad();
+
+    assert_eq!(info.visibility_geometry_vertex.as_ref().unwrap().count, 6);
+    assert_eq!(original_vertex_count(&info, None), 4);
+
+    // without the indexed data, the exploded vertices still know where they came from
+    let mut visibility_only = info.clone();
+    visibility_only.transparency_geometry_vertex = None;
+    visibility_only.triangles.vertex_attributes.clear();
+    assert_eq!(
+        original_vertex_count(
+            &visibility_only,
+            Some(&buffers.visibility_geometry_vertex_bytes)
+        ),
+        4
+    );
+}
+
+#[test]
+fn visibility_triangles_are_read_at_the_full_vertex_stride() {
+    let data = MeshData::new_plane(Vec2::ONE, 2);
+    let buffers = convert_mesh_data(&data, GltfMeshBufferGeometryKind::Both, false).unwrap();
+    let bytes = &buffers.visibility_geometry_vertex_bytes;
+    let triangle_count = data.indices.len() / 3;
+    assert!(triangle_count > 2);
+
+    // past the first triangle, a short stride drifts into the neighbouring vertex attributes
+    for triangle_index in 1..triangle_count {
+        let expected = [0, 1, 2].map(|corner| {
+            Vec3::from(data.positions[data.indices[triangle_index * 3 + corner] as usize])
+        });
+        assert_eq!(
+            visibility_triangle_positions(bytes, triangle_index as u32),
+            Some(expected),
+            "triangle {triangle_index}"
+        );
+    }
+
+    assert_eq!(
+        visibility_triangle_positions(bytes, triangle_count as u32),
+        None
+    );
+}
+
+#[test]
+fn every_corner_of_an_updated_vertex_is_written() {
+    let (mut buffers, _) = quad();
+    let stride = MeshBufferVertexInfo::VISIBILITY_GEOMETRY_BYTE_SIZE;
+    let before = buffers.visibility_geometry_vertex_bytes.clone();
+
+    let update = MeshVertexUpdate::new(1)
+        .with_positions(vec![[5.0, 6.0, 7.0]])
+        .with_normals(vec![[1.0, 0.0, 0.0]]);
+    let touched =
+        write_visibility_vertices(&mut buffers.visibility_geometry_vertex_bytes, 6, &update)
+            .unwrap();
+
+    let data = &buffers.visibility_geometry_vertex_bytes;
+    let corners: Vec<usize> = (0..6)
+        .filter(|corner| read_u32(&data[corner * stride..], VISIBILITY_ORIGINAL_INDEX_OFFSET) == 1)
+        .collect();
+    assert!(!corners.is_empty());
+
+    for corner in 0..6 {
+        let vertex = &data[corner * stride..(corner + 1) * stride];
+        if corners.contains(&corner) {
+            assert_eq!(
+                read_vec3(vertex, VISIBILITY_POSITION_OFFSET),
+                Vec3::new(5.0, 6.0, 7.0)
+            );
+            assert_eq!(read_vec3(vertex, VISIBILITY_NORMAL_OFFSET), Vec3::X);
+            assert!(touched.contains(&(corner * stride)));
+        } else {
+            assert_eq!(vertex, &before[corner * stride..(corner + 1) * stride]);
+        }
+    }
+
+    // only the span between the first and last touched corner
+    assert_eq!(touched.start, corners[0] * stride);
+    assert_eq!(touched.end, (corners.last().unwrap() + 1) * stride);
+}
+
+#[test]
+fn transparency_vertices_are_written_in_order() {
+    let (mut buffers, _) = quad();
+    let stride = MeshBufferVertexInfo::TRANSPARENCY_GEOMETRY_BYTE_SIZE;
+
+    let update = MeshVertexUpdate::new(2)
+        .with_positions(vec![[0.0, 3.0, 0.0], [0.0, -3.0, 0.0]])
+        .with_tangents(vec![[0.0, 0.0, 1.0, -1.0]]);
+    let touched =
+        write_transparency_vertices(&mut buffers.transparency_geometry_vertex_bytes, 4, &update);
+    assert_eq!(touched, Some(2 * stride..4 * stride));
+
+    let data = &buffers.transparency_geometry_vertex_bytes;
+    assert_eq!(
+        read_vec3(&data[2 * stride..], TRANSPARENCY_POSITION_OFFSET),
+        Vec3::new(0.0, 3.0, 0.0)
+    );
+    assert_eq!(
+        read_f32(&data[2 * stride..], TRANSPARENCY_TANGENT_OFFSET + 12),
+        -1.0
+    );
+
+    let aabb = positions_aabb(data, stride, TRANSPARENCY_POSITION_OFFSET, 4).unwrap();
+    assert_eq!(aabb.min.y, -3.0);
+    assert_eq!(aabb.max.y, 3.0);
+    assert_eq!(aabb.max.x, 0.5);
+}
+
+#[test]
+fn custom_attributes_are_written_at_their_interleaved_offset() {
+    let (mut buffers, info) = quad();
+    let stride = info.triangles.vertex_attribute_stride();
+
+    // colors sort before UVs
+    let (uv_offset, uv_components) = custom_attribute_layout(&info, |attribute| {
+        matches!(
+            attribute,
+            MeshBufferCustomVertexAttributeInfo::TexCoords { index: 0, .. }
+        )
+    })
+    .unwrap();
+    assert_eq!((uv_offset, uv_components, stride), (16, 2, 24));
+    assert!(custom_attribute_layout(&info, |attribute| {
+        matches!(
+            attribute,
+            MeshBufferCustomVertexAttributeInfo::TexCoords { index: 1, .. }
+        )
+    })
+    .is_none());
+
+    let before = buffers.custom_attribute_vertex_bytes.clone();
+    let uv = [0.25, 0.75];
+    let touched = write_custom_attribute(
+        &mut buffers.custom_attribute_vertex_bytes,
+        stride,
+        uv_offset,
+        uv_components,
+        3,
+        &[&uv[..]],
+    );
+    assert_eq!(touched, Some(3 * stride + 16..4 * stride));
+
+    let data = &buffers.custom_attribute_vertex_bytes;
+    assert_eq!(read_f32(data, 3 * stride + 16), 0.25);
+    assert_eq!(read_f32(data, 3 * stride + 20), 0.75);
+    assert_eq!(data[..3 * stride + 16], before[..3 * stride + 16]);
+}
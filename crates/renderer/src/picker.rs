@@ -6,15 +6,22 @@ use crate::{
     bind_group_layout::{
         BindGroupLayoutCacheKey, BindGroupLayoutCacheKeyEntry, BindGroupLayoutKey, BindGroupLayouts,
     },
-    bind_groups::BindGroupRecreateContext,
+    bind_groups::{BindGroupCreate, BindGroupRecreateContext},
     error::Result,
     meshes::MeshKey,
-    picker::state::{PickerState, OUTPUT_BYTE_SIZE},
+    picker::{
+        hit::{closest_instance, face_normal, unproject_pixel},
+        region::PickerRegion,
+        state::{PickerState, OUTPUT_BYTE_SIZE},
+        transparent::{PickerTransparent, PickerTransparentTextures},
+    },
     pipeline_layouts::{PipelineLayoutCacheKey, PipelineLayoutKey, PipelineLayouts},
     pipelines::{
         compute_pipeline::{ComputePipelineCacheKey, ComputePipelineKey},
         Pipelines,
     },
+    render::RenderContext,
+    render_textures::RenderTextureFormats,
     shaders::{ShaderCacheKey, Shaders},
     AwsmRenderer,
 };
@@ -28,15 +35,24 @@ use awsm_renderer_core::{
     renderer::AwsmRendererWebGpu,
     texture::{TextureSampleType, TextureViewDimension},
 };
+use glam::Vec3;
 use slotmap::KeyData;
 
+mod hit;
+mod region;
 mod state;
+mod transparent;
+
+pub use region::{
+    PickRect, RegionPickHit, RegionPickMode, RegionPickResult, ShaderCacheKeyPickerRegion,
+    ShaderTemplatePickerRegion,
+};
 
 /// Result of a GPU pick request.
 #[derive(Debug, Clone)]
 pub enum PickResult {
     Initializing,
-    Hit(MeshKey),
+    Hit(PickHit),
     Miss,
     InFlight,
 }
@@ -44,30 +60,132 @@ pub enum PickResult {
 impl PickResult {
     /// Returns the hit mesh key if this is a hit result.
     pub fn mesh_key(&self) -> Option<MeshKey> {
+        self.hit().map(|hit| hit.mesh_key)
+    }
+
+    /// Returns the hit details if this is a hit result.
+    pub fn hit(&self) -> Option<&PickHit> {
         match self {
-            PickResult::Hit(mesh_key) => Some(*mesh_key),
+            PickResult::Hit(hit) => Some(hit),
             _ => None,
         }
     }
 }
 
+/// Which layer a pick hit the front-most surface of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickLayer {
+    Opaque,
+    /// Only hit while transparent picking is enabled, see
+    /// [`AwsmRenderer::set_transparent_picking`].
+    Transparent,
+    /// HUD meshes, opaque or transparent, drawn over the world regardless of depth.
+    Hud,
+}
+
+impl PickLayer {
+    // make sure this matches the PICK_LAYER_* constants in the shaders
+    fn from_u32(value: u32) -> Self {
+        match value {
+            1 => PickLayer::Transparent,
+            2 => PickLayer::Hud,
+            _ => PickLayer::Opaque,
+        }
+    }
+}
+
+/// Surface details of a pick hit.
+#[derive(Debug, Clone)]
+pub struct PickHit {
+    pub mesh_key: MeshKey,
+    pub layer: PickLayer,
+    /// World-space position under the pixel, reconstructed from the depth buffer.
+    pub world_position: Vec3,
+    /// World-space face normal of the hit triangle, facing the camera.
+    ///
+    /// Skinned and morphed meshes, and transparent hits, use the interpolated vertex normal
+    /// instead, since their triangles aren't known on the CPU.
+    pub normal: Vec3,
+    /// Depth buffer value under the pixel (0.0 at the near plane, 1.0 at the far plane).
+    ///
+    /// HUD hits are in the HUD's own depth buffer.
+    pub depth: f32,
+    /// Index of the hit triangle within the mesh.
+    ///
+    /// `None` for transparent meshes, which don't go through the visibility buffer.
+    pub triangle_index: Option<u32>,
+    /// Which instance was hit, for instanced meshes.
+    pub instance_index: Option<usize>,
+}
+
+// Raw picker output, before it's resolved against the CPU-side scene
+#[derive(Debug, Clone)]
+struct PickOutput {
+    mesh_key: MeshKey,
+    layer: PickLayer,
+    triangle_index: Option<u32>,
+    depth: f32,
+    normal: Vec3,
+    instance_index: Option<u32>,
+}
+
+impl PickOutput {
+    // None on a miss
+    fn from_bytes(bytes: &[u8; OUTPUT_BYTE_SIZE]) -> Option<Self> {
+        let read_u32 =
+            |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        let read_f32 = |offset: usize| f32::from_bits(read_u32(offset));
+
+        // read validity
+        if read_u32(0) == 0 {
+            return None;
+        }
+
+        let hi = read_u32(4) as u64;
+        let lo = read_u32(8) as u64;
+        let mesh_key: MeshKey = KeyData::from_ffi((hi << 32) | lo).into();
+
+        // u32::MAX marks what the GPU doesn't know
+        let read_optional_u32 = |offset: usize| Some(read_u32(offset)).filter(|v| *v != u32::MAX);
+
+        Some(Self {
+            mesh_key,
+            layer: PickLayer::from_u32(read_u32(32)),
+            triangle_index: read_optional_u32(12),
+            depth: read_f32(16),
+            normal: Vec3::new(read_f32(20), read_f32(24), read_f32(28)),
+            instance_index: read_optional_u32(36),
+        })
+    }
+}
+
 impl AwsmRenderer {
     /// Performs a GPU pick at the given pixel coordinates.
+    ///
+    /// Returns the front-most hit across the opaque, transparent and HUD layers. Transparent
+    /// meshes are only considered while transparent picking is enabled, see
+    /// [`Self::set_transparent_picking`].
     pub async fn pick(&self, x: i32, y: i32) -> Result<PickResult> {
-        let pipeline_key = if self.anti_aliasing.msaa_sample_count.is_some() {
-            self.picker.multisampled_compute_pipeline_key
-        } else {
-            self.picker.singlesampled_compute_pipeline_key
+        let Some(bind_group) = self.picker._bind_group.as_ref() else {
+            return Ok(PickResult::Initializing);
         };
 
-        let (bind_group, pipeline) = match (
-            self.picker._bind_group.as_ref(),
-            self.pipelines.compute.get(pipeline_key),
-        ) {
-            (Some(bg), Ok(p)) => (bg, p),
-            _ => {
-                return Ok(PickResult::Initializing);
-            }
+        // the bind group only has the transparent bindings if it was created with them
+        let transparent = self
+            .picker
+            .transparent
+            .as_ref()
+            .filter(|_| bind_group.transparent);
+
+        let compute = match (transparent, self.anti_aliasing.msaa_sample_count.is_some()) {
+            (Some(transparent), true) => &transparent.multisampled,
+            (Some(transparent), false) => &transparent.singlesampled,
+            (None, true) => &self.picker.multisampled,
+            (None, false) => &self.picker.singlesampled,
+        };
+
+        let Ok(pipeline) = self.pipelines.compute.get(compute.pipeline_key) else {
+            return Ok(PickResult::Initializing);
         };
 
         // keep the lock scope before the await point
@@ -78,7 +196,20 @@ impl AwsmRenderer {
                 return Ok(PickResult::InFlight);
             }
 
-            if let Err(err) = state.begin_pick(&self.gpu, bind_group, pipeline, x, y) {
+            let command_encoder = self.gpu.create_command_encoder(Some("Picker"));
+
+            if let Some(textures) = transparent.and_then(|t| t.textures.as_ref()) {
+                self.encode_transparent_pick(&command_encoder, textures, x, y)?;
+            }
+
+            if let Err(err) = state.begin_pick(
+                &self.gpu,
+                command_encoder,
+                &bind_group.bind_group,
+                pipeline,
+                x,
+                y,
+            ) {
                 state.in_flight = false;
                 return Err(err);
             }
@@ -100,91 +231,360 @@ impl AwsmRenderer {
         #[allow(clippy::let_unit_value)]
         let _ = res?;
 
-        // read validity
-        if u32::from_le_bytes((&bytes[0..4]).try_into().unwrap()) == 0 {
-            Ok(PickResult::Miss)
+        match PickOutput::from_bytes(&bytes) {
+            Some(output) => self.resolve_pick(x, y, output),
+            None => Ok(PickResult::Miss),
+        }
+    }
+
+    /// Opts transparent and HUD meshes into picking, or back out with `None`.
+    ///
+    /// The picker normally only sees the opaque visibility buffer. With this enabled, [`Self::pick`]
+    /// also draws the transparent meshes under the pixel and returns whichever surface is in
+    /// front. Fragments with an alpha below `alpha_threshold` are skipped, so nearly invisible
+    /// parts of a mesh don't hide what's behind them.
+    ///
+    /// This costs a copy of the depth buffers every frame, and a small extra draw per pick.
+    pub async fn set_transparent_picking(&mut self, alpha_threshold: Option<f32>) -> Result<()> {
+        let alpha_threshold = alpha_threshold.map(|threshold| threshold.clamp(0.0, 1.0));
+
+        if self
+            .render_passes
+            .material_transparent
+            .pipelines
+            .pick_alpha_threshold()
+            == alpha_threshold
+        {
+            return Ok(());
+        }
+
+        self.render_passes
+            .material_transparent
+            .pipelines
+            .set_pick_alpha_threshold(alpha_threshold);
+
+        // The picking variants depend on per-mesh attributes, just like the regular ones
+        if alpha_threshold.is_some() {
+            for (key, mesh) in self.meshes.iter() {
+                let buffer_info_key = self.meshes.buffer_info_key(key)?;
+                self.render_passes
+                    .material_transparent
+                    .pipelines
+                    .set_render_pipeline_key(
+                        &self.gpu,
+                        mesh,
+                        key,
+                        buffer_info_key,
+                        &mut self.shaders,
+                        &mut self.pipelines,
+                        &self.render_passes.material_transparent.bind_groups,
+                        &self.pipeline_layouts,
+                        &self.meshes.buffer_infos,
+                        &self.anti_aliasing,
+                        &self.textures,
+                        &self.render_textures.formats,
+                    )
+                    .await?;
+            }
+        }
+
+        self.picker
+            .set_transparent(
+                &self.gpu,
+                &mut self.bind_group_layouts,
+                &mut self.pipeline_layouts,
+                &mut self.shaders,
+                &mut self.pipelines,
+                &self.render_textures.formats,
+                alpha_threshold.is_some(),
+            )
+            .await?;
+
+        self.bind_groups.mark_create(BindGroupCreate::PickerChange);
+
+        Ok(())
+    }
+
+    fn resolve_pick(&self, x: i32, y: i32, output: PickOutput) -> Result<PickResult> {
+        let Some(camera) = self.camera.last_matrices.as_ref() else {
+            return Ok(PickResult::Initializing);
+        };
+
+        // the mesh may have been removed while the pick was in flight
+        let Ok(mesh) = self.meshes.get(output.mesh_key) else {
+            return Ok(PickResult::Miss);
+        };
+
+        let (width, height) = self.picker.size;
+        let inv_view_projection = camera.inv_view_projection();
+        let world_position =
+            unproject_pixel(inv_view_projection, x, y, output.depth, width, height);
+        let toward_camera =
+            unproject_pixel(inv_view_projection, x, y, 0.0, width, height) - world_position;
+
+        let resource = self.meshes.resource(output.mesh_key)?;
+        let deformed = resource.skin_key.is_some() || resource.geometry_morph_key.is_some();
+        let triangle = output.triangle_index.and_then(|triangle_index| {
+            self.meshes
+                .visibility_triangle_positions(output.mesh_key, triangle_index)
+                .ok()
+        });
+        let mesh_world = *self.transforms.get_world(mesh.transform_key)?;
+
+        let instance_transforms = if mesh.instanced {
+            self.instances.transform_list(mesh.transform_key)
         } else {
-            let hi = u32::from_le_bytes((&bytes[4..8]).try_into().unwrap()) as u64;
-            let lo = u32::from_le_bytes((&bytes[8..12]).try_into().unwrap()) as u64;
-            let mesh_key = (hi << 32) | lo;
+            None
+        };
 
-            let mesh_key: MeshKey = KeyData::from_ffi(mesh_key).into();
+        let instance_index = match (instance_transforms, output.instance_index, triangle) {
+            // transparent hits know which instance was drawn
+            (Some(instance_transforms), Some(index), _) => {
+                Some(index as usize).filter(|index| *index < instance_transforms.len())
+            }
+            (Some(instance_transforms), None, Some(triangle)) => closest_instance(
+                world_position,
+                mesh_world,
+                instance_transforms.iter().map(|t| t.to_matrix()),
+                triangle,
+            ),
+            _ => None,
+        };
+
+        let world_transform = match (instance_transforms, instance_index) {
+            (Some(instance_transforms), Some(index)) => {
+                mesh_world * instance_transforms[index].to_matrix()
+            }
+            _ => mesh_world,
+        };
 
-            Ok(PickResult::Hit(mesh_key))
+        let normal = match triangle {
+            Some(triangle) if !deformed => face_normal(world_transform, triangle),
+            _ => None,
         }
+        .unwrap_or(output.normal);
+
+        let normal = if normal.dot(toward_camera) < 0.0 {
+            -normal
+        } else {
+            normal
+        };
+
+        Ok(PickResult::Hit(PickHit {
+            mesh_key: output.mesh_key,
+            layer: output.layer,
+            world_position,
+            normal,
+            depth: output.depth,
+            triangle_index: output.triangle_index,
+            instance_index,
+        }))
     }
 }
 
 /// Picker state and GPU resources.
 pub struct Picker {
-    singlesampled_compute_pipeline_key: ComputePipelineKey,
-    multisampled_compute_pipeline_key: ComputePipelineKey,
-    singlesampled_bind_group_layout_key: BindGroupLayoutKey,
-    multisampled_bind_group_layout_key: BindGroupLayoutKey,
-    _bind_group: Option<web_sys::GpuBindGroup>,
+    singlesampled: PickerCompute,
+    multisampled: PickerCompute,
+    // only while transparent picking is enabled
+    transparent: Option<PickerTransparent>,
+    region: PickerRegion,
+    _bind_group: Option<PickerBindGroup>,
+    // size of the textures the bind group reads, for unprojecting hits
+    size: (u32, u32),
 
     state: Rc<RefCell<PickerState>>,
 }
 
-impl Picker {
-    /// Creates a picker with the required bind groups and pipelines.
-    pub async fn new(
+// Bind group layout and pipeline for one variant of the picker compute shader
+struct PickerCompute {
+    bind_group_layout_key: BindGroupLayoutKey,
+    pipeline_key: ComputePipelineKey,
+}
+
+impl PickerCompute {
+    async fn new(
         gpu: &AwsmRendererWebGpu,
         bind_group_layouts: &mut BindGroupLayouts,
         pipeline_layouts: &mut PipelineLayouts,
         shaders: &mut Shaders,
         pipelines: &mut Pipelines,
+        multisampled_geometry: bool,
+        transparent: bool,
     ) -> Result<Self> {
-        let singlesampled_bind_group_layout_key =
-            create_bind_group_layout(gpu, bind_group_layouts, false)?;
-        let multisampled_bind_group_layout_key =
-            create_bind_group_layout(gpu, bind_group_layouts, true)?;
+        let bind_group_layout_key =
+            create_bind_group_layout(gpu, bind_group_layouts, multisampled_geometry, transparent)?;
 
-        let singlesampled_pipeline_layout_key = pipeline_layouts.get_key(
+        let pipeline_layout_key = pipeline_layouts.get_key(
             gpu,
             bind_group_layouts,
-            PipelineLayoutCacheKey::new(vec![singlesampled_bind_group_layout_key]),
+            PipelineLayoutCacheKey::new(vec![bind_group_layout_key]),
         )?;
 
-        let multisampled_pipeline_layout_key = pipeline_layouts.get_key(
+        let pipeline_key = create_pipeline(
             gpu,
-            bind_group_layouts,
-            PipelineLayoutCacheKey::new(vec![multisampled_bind_group_layout_key]),
-        )?;
+            pipeline_layouts,
+            shaders,
+            pipelines,
+            pipeline_layout_key,
+            multisampled_geometry,
+            transparent,
+        )
+        .await?;
+
+        Ok(Self {
+            bind_group_layout_key,
+            pipeline_key,
+        })
+    }
+}
+
+struct PickerBindGroup {
+    bind_group: web_sys::GpuBindGroup,
+    // whether it was created with the transparent picking bindings
+    transparent: bool,
+}
 
-        let singlesampled_compute_pipeline_key = create_pipeline(
+impl Picker {
+    /// Creates a picker with the required bind groups and pipelines.
+    pub async fn new(
+        gpu: &AwsmRendererWebGpu,
+        bind_group_layouts: &mut BindGroupLayouts,
+        pipeline_layouts: &mut PipelineLayouts,
+        shaders: &mut Shaders,
+        pipelines: &mut Pipelines,
+    ) -> Result<Self> {
+        let singlesampled = PickerCompute::new(
             gpu,
+            bind_group_layouts,
             pipeline_layouts,
             shaders,
             pipelines,
-            singlesampled_pipeline_layout_key,
+            false,
             false,
         )
         .await?;
 
-        let multisampled_compute_pipeline_key = create_pipeline(
+        let multisampled = PickerCompute::new(
             gpu,
+            bind_group_layouts,
             pipeline_layouts,
             shaders,
             pipelines,
-            multisampled_pipeline_layout_key,
             true,
+            false,
+        )
+        .await?;
+
+        let region = PickerRegion::new(
+            gpu,
+            bind_group_layouts,
+            pipeline_layouts,
+            shaders,
+            pipelines,
         )
         .await?;
 
         Ok(Self {
-            singlesampled_compute_pipeline_key,
-            multisampled_compute_pipeline_key,
-            singlesampled_bind_group_layout_key,
-            multisampled_bind_group_layout_key,
+            singlesampled,
+            multisampled,
+            transparent: None,
+            region,
             state: Rc::new(RefCell::new(PickerState::new(gpu)?)),
             _bind_group: None,
+            size: (0, 0),
         })
     }
 
+    // Creates or drops the transparent picking resources, the bind group is recreated on the
+    // next render
+    async fn set_transparent(
+        &mut self,
+        gpu: &AwsmRendererWebGpu,
+        bind_group_layouts: &mut BindGroupLayouts,
+        pipeline_layouts: &mut PipelineLayouts,
+        shaders: &mut Shaders,
+        pipelines: &mut Pipelines,
+        formats: &RenderTextureFormats,
+        enabled: bool,
+    ) -> Result<()> {
+        // picks in the meantime would read from textures that are about to change
+        self._bind_group = None;
+
+        if !enabled {
+            if let Some(textures) = self.transparent.take().and_then(|t| t.textures) {
+                textures.destroy();
+            }
+            return Ok(());
+        }
+
+        if self.transparent.is_none() {
+            self.transparent = Some(PickerTransparent {
+                singlesampled: PickerCompute::new(
+                    gpu,
+                    bind_group_layouts,
+                    pipeline_layouts,
+                    shaders,
+                    pipelines,
+                    false,
+                    true,
+                )
+                .await?,
+                multisampled: PickerCompute::new(
+                    gpu,
+                    bind_group_layouts,
+                    pipeline_layouts,
+                    shaders,
+                    pipelines,
+                    true,
+                    true,
+                )
+                .await?,
+                formats: formats.clone(),
+                textures: None,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Copies the opaque depth for transparent picking, before the transparent passes write
+    /// into the depth buffers.
+    ///
+    /// Does nothing unless transparent picking is enabled.
+    pub fn copy_opaque_depth(&self, ctx: &RenderContext) -> Result<()> {
+        match self.transparent.as_ref().and_then(|t| t.textures.as_ref()) {
+            Some(textures) => textures.copy_opaque_depth(ctx),
+            None => Ok(()),
+        }
+    }
+
     /// Rebuilds the bind group for the current render textures.
     pub fn recreate_bind_group(&mut self, ctx: &BindGroupRecreateContext<'_>) -> Result<()> {
+        self.region.recreate_bind_group(ctx)?;
+
         let state = self.state.borrow();
+        let msaa_sample_count = ctx.anti_aliasing.msaa_sample_count;
+
+        if let Some(transparent) = self.transparent.as_mut() {
+            let stale = !transparent.textures.as_ref().is_some_and(|textures| {
+                textures.matches(ctx.render_texture_views, msaa_sample_count)
+            });
+
+            if stale {
+                if let Some(textures) = transparent.textures.take() {
+                    textures.destroy();
+                }
+                transparent.textures = Some(PickerTransparentTextures::new(
+                    ctx.gpu,
+                    &transparent.formats,
+                    ctx.render_texture_views,
+                    msaa_sample_count,
+                )?);
+            }
+        }
+
+        let transparent_textures = self.transparent.as_ref().and_then(|t| t.textures.as_ref());
 
         let mut entries = Vec::new();
 
@@ -214,18 +614,65 @@ impl Picker {
             BindGroupResource::Buffer(BufferBinding::new(&state.gpu_output_buffer)),
         ));
 
+        // Depth texture, the opaque-only copy when transparent picking
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(match transparent_textures {
+                Some(textures) => &textures.opaque_depth_view,
+                None => &ctx.render_texture_views.depth,
+            })),
+        ));
+
+        // Normal tangent texture
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(&ctx.render_texture_views.normal_tangent)),
+        ));
+
+        // HUD depth texture, the opaque-only copy when transparent picking
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(match transparent_textures {
+                Some(textures) => &textures.opaque_hud_depth_view,
+                None => &ctx.render_texture_views.hud_depth,
+            })),
+        ));
+
+        if let Some(textures) = transparent_textures {
+            // Pick ids texture
+            entries.push(BindGroupEntry::new(
+                entries.len() as u32,
+                BindGroupResource::TextureView(Cow::Borrowed(&textures.ids_view)),
+            ));
+
+            // Pick surface texture
+            entries.push(BindGroupEntry::new(
+                entries.len() as u32,
+                BindGroupResource::TextureView(Cow::Borrowed(&textures.surface_view)),
+            ));
+        }
+
+        let compute = match (self.transparent.as_ref(), msaa_sample_count.is_some()) {
+            (Some(transparent), true) => &transparent.multisampled,
+            (Some(transparent), false) => &transparent.singlesampled,
+            (None, true) => &self.multisampled,
+            (None, false) => &self.singlesampled,
+        };
+
         let descriptor = BindGroupDescriptor::new(
-            ctx.bind_group_layouts
-                .get(if ctx.anti_aliasing.msaa_sample_count.is_some() {
-                    self.multisampled_bind_group_layout_key
-                } else {
-                    self.singlesampled_bind_group_layout_key
-                })?,
+            ctx.bind_group_layouts.get(compute.bind_group_layout_key)?,
             Some("Picker"),
             entries,
         );
 
-        self._bind_group = Some(ctx.gpu.create_bind_group(&descriptor.into()));
+        self._bind_group = Some(PickerBindGroup {
+            bind_group: ctx.gpu.create_bind_group(&descriptor.into()),
+            transparent: transparent_textures.is_some(),
+        });
+        self.size = (
+            ctx.render_texture_views.width,
+            ctx.render_texture_views.height,
+        );
 
         Ok(())
     }
@@ -235,8 +682,21 @@ fn create_bind_group_layout(
     gpu: &AwsmRendererWebGpu,
     bind_group_layouts: &mut BindGroupLayouts,
     multisampled_geometry: bool,
+    transparent: bool,
 ) -> Result<BindGroupLayoutKey> {
-    let entries = vec![
+    let depth_texture_entry = || BindGroupLayoutCacheKeyEntry {
+        resource: BindGroupLayoutResource::Texture(
+            TextureBindingLayout::new()
+                .with_view_dimension(TextureViewDimension::N2d)
+                .with_sample_type(TextureSampleType::Depth)
+                .with_multisampled(multisampled_geometry),
+        ),
+        visibility_vertex: false,
+        visibility_fragment: false,
+        visibility_compute: true,
+    };
+
+    let mut entries = vec![
         // Binding 0: Visibility data texture
         BindGroupLayoutCacheKeyEntry {
             resource: BindGroupLayoutResource::Texture(
@@ -276,8 +736,49 @@ fn create_bind_group_layout(
             visibility_fragment: false,
             visibility_compute: true,
         },
+        // Binding 4: Depth texture
+        depth_texture_entry(),
+        // Binding 5: Normal tangent texture
+        BindGroupLayoutCacheKeyEntry {
+            resource: BindGroupLayoutResource::Texture(
+                TextureBindingLayout::new()
+                    .with_view_dimension(TextureViewDimension::N2d)
+                    .with_sample_type(TextureSampleType::UnfilterableFloat)
+                    .with_multisampled(multisampled_geometry),
+            ),
+            visibility_vertex: false,
+            visibility_fragment: false,
+            visibility_compute: true,
+        },
+        // Binding 6: HUD depth texture
+        depth_texture_entry(),
     ];
 
+    if transparent {
+        // Binding 7: Pick ids texture
+        entries.push(BindGroupLayoutCacheKeyEntry {
+            resource: BindGroupLayoutResource::Texture(
+                TextureBindingLayout::new()
+                    .with_view_dimension(TextureViewDimension::N2d)
+                    .with_sample_type(TextureSampleType::Uint),
+            ),
+            visibility_vertex: false,
+            visibility_fragment: false,
+            visibility_compute: true,
+        });
+        // Binding 8: Pick surface texture
+        entries.push(BindGroupLayoutCacheKeyEntry {
+            resource: BindGroupLayoutResource::Texture(
+                TextureBindingLayout::new()
+                    .with_view_dimension(TextureViewDimension::N2d)
+                    .with_sample_type(TextureSampleType::UnfilterableFloat),
+            ),
+            visibility_vertex: false,
+            visibility_fragment: false,
+            visibility_compute: true,
+        });
+    }
+
     Ok(bind_group_layouts.get_key(gpu, BindGroupLayoutCacheKey { entries })?)
 }
 
@@ -288,12 +789,14 @@ async fn create_pipeline(
     pipelines: &mut Pipelines,
     pipeline_layout_key: PipelineLayoutKey,
     multisampled_geometry: bool,
+    transparent: bool,
 ) -> Result<ComputePipelineKey> {
     let shader_key = shaders
         .get_key(
             gpu,
             ShaderCacheKeyPicker {
                 multisampled_geometry,
+                transparent,
             },
         )
         .await?;
@@ -315,6 +818,7 @@ async fn create_pipeline(
 #[derive(Hash, Debug, Clone, PartialEq, Eq)]
 pub struct ShaderCacheKeyPicker {
     pub multisampled_geometry: bool,
+    pub transparent: bool,
 }
 
 impl From<ShaderCacheKeyPicker> for ShaderCacheKey {
@@ -328,6 +832,7 @@ impl From<ShaderCacheKeyPicker> for ShaderCacheKey {
 #[template(path = "picker_wgsl/compute.wgsl", whitespace = "minimize")]
 pub struct ShaderTemplatePicker {
     pub multisampled_geometry: bool,
+    pub transparent: bool,
 }
 
 impl ShaderTemplatePicker {
@@ -347,6 +852,10 @@ impl From<&ShaderCacheKeyPicker> for ShaderTemplatePicker {
     fn from(key: &ShaderCacheKeyPicker) -> Self {
         ShaderTemplatePicker {
             multisampled_geometry: key.multisampled_geometry,
+            transparent: key.transparent,
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
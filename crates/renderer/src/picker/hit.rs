@@ -0,0 +1,97 @@
+//! CPU-side helpers that turn the raw picker output into a surface hit.
+
+use glam::{Mat4, Vec3};
+
+/// Reconstructs the world-space position of a pixel from its depth buffer value.
+pub(super) fn unproject_pixel(
+    inv_view_projection: Mat4,
+    x: i32,
+    y: i32,
+    depth: f32,
+    width: u32,
+    height: u32,
+) -> Vec3 {
+    // sample at the pixel center, y flipped from pixel space to NDC
+    let ndc_x = ((x as f32 + 0.5) / width.max(1) as f32) * 2.0 - 1.0;
+    let ndc_y = 1.0 - ((y as f32 + 0.5) / height.max(1) as f32) * 2.0;
+
+    inv_view_projection.project_point3(Vec3::new(ndc_x, ndc_y, depth))
+}
+
+/// World-space face normal of a triangle, or `None` if it's degenerate.
+pub(super) fn face_normal(transform: Mat4, triangle: [Vec3; 3]) -> Option<Vec3> {
+    let [a, b, c] = triangle.map(|p| transform.transform_point3(p));
+    (b - a).cross(c - a).try_normalize()
+}
+
+/// Finds the instance whose transformed triangle lies closest to the hit point.
+///
+/// Every instance shares the triangle index, so the depth-reconstructed position is what tells
+/// them apart.
+pub(super) fn closest_instance(
+    point: Vec3,
+    mesh_world: Mat4,
+    instance_transforms: impl IntoIterator<Item = Mat4>,
+    triangle: [Vec3; 3],
+) -> Option<usize> {
+    instance_transforms
+        .into_iter()
+        .enumerate()
+        .map(|(index, instance_transform)| {
+            let world = mesh_world * instance_transform;
+            let [a, b, c] = triangle.map(|p| world.transform_point3(p));
+            let distance_sq = point.distance_squared(closest_point_on_triangle(point, a, b, c));
+            (index, distance_sq)
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+}
+
+/// Closest point to `p` on the triangle `abc` (Ericson, Real-Time Collision Detection 5.1.5).
+pub(super) fn closest_point_on_triangle(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    // inside the face region
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+#[cfg(test)]
+mod tests;
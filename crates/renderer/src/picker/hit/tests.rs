@@ -0,0 +1,109 @@
+use super::*;
+
+const EPSILON: f32 = 1e-4;
+
+fn triangle() -> [Vec3; 3] {
+    [
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    ]
+}
+
+#[test]
+fn unproject_pixel_round_trips_through_the_camera() {
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::Y);
+    let projection = Mat4::perspective_rh(60.0_f32.to_radians(), 1.0, 0.1, 100.0);
+    let view_projection = projection * view;
+
+    let (width, height) = (200, 100);
+    let world = Vec3::new(0.3, -0.2, 1.0);
+
+    let clip = view_projection * world.extend(1.0);
+    let ndc = clip.truncate() / clip.w;
+    // land exactly on the center of the pixel the point projects into
+    let x = ((ndc.x + 1.0) * 0.5 * width as f32 - 0.5).round() as i32;
+    let y = ((1.0 - ndc.y) * 0.5 * height as f32 - 0.5).round() as i32;
+
+    let reconstructed =
+        unproject_pixel(view_projection.inverse(), x, y, ndc.z, width, height);
+
+    // off by at most half a pixel on screen, which is tiny at this distance
+    assert!(reconstructed.distance(world) < 0.05);
+    // depth is exact, so the distance to the camera matches
+    let camera_position = Vec3::new(0.0, 0.0, 5.0);
+    let expected_view_z = (world - camera_position).z;
+    let reconstructed_view_z = (reconstructed - camera_position).z;
+    assert!((expected_view_z - reconstructed_view_z).abs() < 1e-3);
+}
+
+#[test]
+fn face_normal_follows_winding_and_transform() {
+    let normal = face_normal(Mat4::IDENTITY, triangle()).unwrap();
+    assert!(normal.distance(Vec3::Z) < EPSILON);
+
+    let rotated = face_normal(
+        Mat4::from_rotation_x(std::f32::consts::FRAC_PI_2),
+        triangle(),
+    )
+    .unwrap();
+    assert!(rotated.distance(-Vec3::Y) < EPSILON);
+
+    // non-uniform scale keeps the face normal perpendicular to the scaled face
+    let scaled = face_normal(Mat4::from_scale(Vec3::new(4.0, 1.0, 1.0)), triangle()).unwrap();
+    assert!(scaled.distance(Vec3::Z) < EPSILON);
+}
+
+#[test]
+fn face_normal_rejects_degenerate_triangles() {
+    let degenerate = [Vec3::ZERO, Vec3::X, Vec3::X * 2.0];
+    assert!(face_normal(Mat4::IDENTITY, degenerate).is_none());
+}
+
+#[test]
+fn closest_point_on_triangle_regions() {
+    let [a, b, c] = triangle();
+
+    // inside the face, projects straight down
+    let inside = closest_point_on_triangle(Vec3::new(0.25, 0.25, 3.0), a, b, c);
+    assert!(inside.distance(Vec3::new(0.25, 0.25, 0.0)) < EPSILON);
+
+    // vertex regions
+    assert!(closest_point_on_triangle(Vec3::new(-1.0, -1.0, 0.0), a, b, c).distance(a) < EPSILON);
+    assert!(closest_point_on_triangle(Vec3::new(2.0, -0.5, 0.0), a, b, c).distance(b) < EPSILON);
+    assert!(closest_point_on_triangle(Vec3::new(-0.5, 2.0, 0.0), a, b, c).distance(c) < EPSILON);
+
+    // edge regions
+    let on_ab = closest_point_on_triangle(Vec3::new(0.5, -1.0, 0.0), a, b, c);
+    assert!(on_ab.distance(Vec3::new(0.5, 0.0, 0.0)) < EPSILON);
+    let on_bc = closest_point_on_triangle(Vec3::new(1.0, 1.0, 0.0), a, b, c);
+    assert!(on_bc.distance(Vec3::new(0.5, 0.5, 0.0)) < EPSILON);
+}
+
+#[test]
+fn closest_instance_picks_the_instance_under_the_hit() {
+    let instances = [
+        Mat4::from_translation(Vec3::new(-5.0, 0.0, 0.0)),
+        Mat4::from_translation(Vec3::new(0.0, 0.0, 0.0)),
+        Mat4::from_translation(Vec3::new(5.0, 0.0, 0.0)),
+    ];
+
+    let hit = Vec3::new(5.25, 0.25, 0.0);
+    assert_eq!(
+        closest_instance(hit, Mat4::IDENTITY, instances, triangle()),
+        Some(2)
+    );
+
+    // the mesh's own transform applies on top of the instance transforms
+    let mesh_world = Mat4::from_translation(Vec3::new(0.0, 10.0, 0.0));
+    let hit = Vec3::new(-4.75, 10.25, 0.0);
+    assert_eq!(
+        closest_instance(hit, mesh_world, instances, triangle()),
+        Some(0)
+    );
+
+    assert_eq!(
+        closest_instance(hit, mesh_world, std::iter::empty(), triangle()),
+        None
+    );
+}
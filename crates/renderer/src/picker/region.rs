@@ -0,0 +1,733 @@
+//! Box selection: the meshes inside a screen rectangle.
+//!
+//! The GPU path scans the visibility buffer, so it only returns what's actually visible. The CPU
+//! path tests world bounds against the rectangle's frustum instead, which also finds occluded
+//! meshes (and transparent ones, which aren't in the visibility buffer).
+
+use std::{borrow::Cow, cell::RefCell, rc::Rc};
+
+use askama::Template;
+use awsm_renderer_core::{
+    bind_groups::{
+        BindGroupDescriptor, BindGroupEntry, BindGroupLayoutResource, BindGroupResource,
+        BufferBindingLayout, BufferBindingType, TextureBindingLayout,
+    },
+    buffers::{extract_buffer_vec, BufferBinding, BufferDescriptor, BufferUsage},
+    command::compute_pass::ComputePassDescriptor,
+    renderer::AwsmRendererWebGpu,
+    texture::{TextureSampleType, TextureViewDimension},
+};
+use glam::{Mat4, Vec2};
+use slotmap::KeyData;
+
+use crate::{
+    bind_group_layout::{BindGroupLayoutCacheKey, BindGroupLayoutCacheKeyEntry, BindGroupLayouts},
+    bind_groups::BindGroupRecreateContext,
+    bounds::Aabb,
+    error::Result,
+    frustum::Frustum,
+    meshes::{meta::material_meta::MATERIAL_MESH_META_BYTE_ALIGNMENT, MeshKey},
+    picker::PickerCompute,
+    pipeline_layouts::{PipelineLayoutCacheKey, PipelineLayouts},
+    pipelines::{compute_pipeline::ComputePipelineCacheKey, Pipelines},
+    shaders::{ShaderCacheKey, Shaders},
+    AwsmRenderer,
+};
+
+// 4 x vec2<u32>: scanned area min/max, selection rectangle min/max
+const INPUT_BYTE_SIZE: usize = 32;
+// make sure this matches RegionEntry in region.wgsl
+// u32 flags + hi/low u32 for mesh_id
+const ENTRY_BYTE_SIZE: usize = 12;
+// make sure these match the REGION_FLAG_* constants in region.wgsl
+const FLAG_INSIDE: u32 = 1;
+const FLAG_OUTSIDE: u32 = 2;
+
+const WORKGROUP_SIZE: u32 = 8;
+
+/// A screen-space rectangle in pixels, with the origin at the top-left like [`AwsmRenderer::pick`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PickRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PickRect {
+    /// Creates a rectangle from its top-left pixel and size.
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Creates the rectangle spanned by two pixels, both included, e.g. where a drag started
+    /// and where the pointer is now.
+    pub fn from_corners(a: (i32, i32), b: (i32, i32)) -> Self {
+        let (min_x, max_x) = (a.0.min(b.0), a.0.max(b.0));
+        let (min_y, max_y) = (a.1.min(b.1), a.1.max(b.1));
+
+        Self {
+            x: min_x,
+            y: min_y,
+            width: max_x.abs_diff(min_x) + 1,
+            height: max_y.abs_diff(min_y) + 1,
+        }
+    }
+
+    // The pixels covered within a viewport, as [min, max) edges, or None if there are none
+    fn clamp(&self, width: u32, height: u32) -> Option<([u32; 2], [u32; 2])> {
+        let min_x = (self.x as i64).max(0);
+        let min_y = (self.y as i64).max(0);
+        let max_x = (self.x as i64 + self.width as i64).min(width as i64);
+        let max_y = (self.y as i64 + self.height as i64).min(height as i64);
+
+        if min_x >= max_x || min_y >= max_y {
+            return None;
+        }
+
+        Some(([min_x as u32, min_y as u32], [max_x as u32, max_y as u32]))
+    }
+
+    // The frustum through this rectangle, for testing world bounds
+    fn frustum(&self, view_projection: Mat4, width: u32, height: u32) -> Frustum {
+        let min = Vec2::new(self.x as f32, self.y as f32);
+        let max = min + Vec2::new(self.width as f32, self.height as f32);
+        Frustum::from_screen_rect(view_projection, min, max, width, height)
+    }
+}
+
+/// Which meshes a region pick selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RegionPickMode {
+    /// Anything that touches the rectangle.
+    #[default]
+    Touching,
+    /// Only what's fully inside the rectangle.
+    ///
+    /// For [`AwsmRenderer::pick_region`] that's every visible pixel of the mesh, for
+    /// [`AwsmRenderer::pick_region_frustum`] it's the mesh's bounds.
+    Contained,
+}
+
+impl RegionPickMode {
+    fn accepts_aabb(self, frustum: &Frustum, aabb: &Aabb) -> bool {
+        match self {
+            RegionPickMode::Touching => frustum.intersects_aabb(aabb),
+            RegionPickMode::Contained => frustum.contains_aabb(aabb),
+        }
+    }
+
+    // flags are what the shader saw of a mesh, in and outside of the rectangle
+    fn accepts_flags(self, flags: u32) -> bool {
+        match self {
+            RegionPickMode::Touching => flags & FLAG_INSIDE != 0,
+            RegionPickMode::Contained => flags == FLAG_INSIDE,
+        }
+    }
+}
+
+/// A mesh selected by a region pick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionPickHit {
+    pub mesh_key: MeshKey,
+    /// The selected instances, for instanced meshes.
+    ///
+    /// These are chosen by their bounds, since the visibility buffer doesn't know which
+    /// instance drew a pixel.
+    pub instance_indices: Option<Vec<usize>>,
+}
+
+/// Result of a region pick request.
+#[derive(Debug, Clone)]
+pub enum RegionPickResult {
+    Initializing,
+    /// Deduplicated, in no particular order. Empty if nothing was selected.
+    Hits(Vec<RegionPickHit>),
+    InFlight,
+}
+
+impl RegionPickResult {
+    /// Returns the selected mesh keys, empty unless this is a hits result.
+    pub fn mesh_keys(&self) -> Vec<MeshKey> {
+        match self {
+            RegionPickResult::Hits(hits) => hits.iter().map(|hit| hit.mesh_key).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl AwsmRenderer {
+    /// Selects the meshes visible in a screen rectangle.
+    ///
+    /// Scans the visibility buffer, so occluded meshes aren't selected. Transparent meshes
+    /// aren't in there either, use [`Self::pick_region_frustum`] to select those.
+    ///
+    /// Runs independently of [`Self::pick`], but only one region pick can be in flight.
+    pub async fn pick_region(
+        &self,
+        rect: PickRect,
+        mode: RegionPickMode,
+    ) -> Result<RegionPickResult> {
+        let region = &self.picker.region;
+
+        let Some(bind_group) = region.bind_group.as_ref() else {
+            return Ok(RegionPickResult::Initializing);
+        };
+
+        let Some(camera) = self.camera.last_matrices.as_ref() else {
+            return Ok(RegionPickResult::Initializing);
+        };
+
+        let compute = if self.anti_aliasing.msaa_sample_count.is_some() {
+            &region.multisampled
+        } else {
+            &region.singlesampled
+        };
+
+        let Ok(pipeline) = self.pipelines.compute.get(compute.pipeline_key) else {
+            return Ok(RegionPickResult::Initializing);
+        };
+
+        let (width, height) = self.picker.size;
+        let Some((rect_min, rect_max)) = rect.clamp(width, height) else {
+            return Ok(RegionPickResult::Hits(Vec::new()));
+        };
+
+        // containment needs to know about the pixels outside the rectangle too
+        let (scan_min, scan_max) = match mode {
+            RegionPickMode::Touching => (rect_min, rect_max),
+            RegionPickMode::Contained => ([0, 0], [width, height]),
+        };
+
+        // keep the lock scope before the await point
+        let (read_buffer, read_size) = {
+            let state = &mut *region.state.borrow_mut();
+
+            if state.in_flight {
+                return Ok(RegionPickResult::InFlight);
+            }
+
+            if let Err(err) = state.begin_pick(
+                &self.gpu,
+                bind_group,
+                pipeline,
+                [scan_min, scan_max, rect_min, rect_max],
+            ) {
+                state.in_flight = false;
+                return Err(err);
+            }
+
+            (state.gpu_readback_buffer.clone(), state.entries_byte_size)
+        };
+
+        // don't error out right away, we need to set in_flight to false
+        let res = extract_buffer_vec(&read_buffer, Some(read_size as u32)).await;
+
+        {
+            region.state.borrow_mut().in_flight = false;
+        }
+
+        let bytes = res?;
+
+        let frustum = rect.frustum(camera.view_projection(), width, height);
+
+        let hits = parse_entries(&bytes)
+            .filter_map(|(mesh_key, flags)| {
+                // the mesh may have been removed while the pick was in flight
+                let mesh = self.meshes.get(mesh_key).ok()?;
+
+                if !mesh.instanced {
+                    return mode.accepts_flags(flags).then_some(RegionPickHit {
+                        mesh_key,
+                        instance_indices: None,
+                    });
+                }
+
+                // The flags cover all instances together, so only use them to find the meshes
+                // in the rectangle and let each instance's bounds decide containment
+                if !RegionPickMode::Touching.accepts_flags(flags) {
+                    return None;
+                }
+
+                let instance_indices = self.region_pick_instances(mesh_key, &frustum, mode)?;
+                Some(RegionPickHit {
+                    mesh_key,
+                    instance_indices: Some(instance_indices),
+                })
+            })
+            .collect();
+
+        Ok(RegionPickResult::Hits(hits))
+    }
+
+    /// Selects the meshes whose world bounds are in a screen rectangle.
+    ///
+    /// Unlike [`Self::pick_region`] this runs on the CPU and ignores occlusion, so it's the way
+    /// to select meshes hidden behind others, or transparent ones. Hidden meshes and meshes
+    /// without bounds are skipped. Nothing is selected before the first render.
+    pub fn pick_region_frustum(
+        &self,
+        rect: PickRect,
+        mode: RegionPickMode,
+    ) -> Result<Vec<RegionPickHit>> {
+        let Some(camera) = self.camera.last_matrices.as_ref() else {
+            return Ok(Vec::new());
+        };
+
+        let (width, height) = self.picker.size;
+        if rect.clamp(width, height).is_none() {
+            return Ok(Vec::new());
+        }
+
+        let frustum = rect.frustum(camera.view_projection(), width, height);
+
+        let mut hits = Vec::new();
+
+        for (mesh_key, mesh) in self.meshes.iter() {
+            if mesh.hidden {
+                continue;
+            }
+
+            let Some(world_aabb) = mesh.world_aabb.as_ref() else {
+                continue;
+            };
+
+            if !mesh.instanced {
+                if mode.accepts_aabb(&frustum, world_aabb) {
+                    hits.push(RegionPickHit {
+                        mesh_key,
+                        instance_indices: None,
+                    });
+                }
+                continue;
+            }
+
+            // the world bounds are the union of all instances, a cheap early out
+            if !frustum.intersects_aabb(world_aabb) {
+                continue;
+            }
+
+            if let Some(instance_indices) = self.region_pick_instances(mesh_key, &frustum, mode) {
+                hits.push(RegionPickHit {
+                    mesh_key,
+                    instance_indices: Some(instance_indices),
+                });
+            }
+        }
+
+        Ok(hits)
+    }
+
+    // The instances of a mesh whose bounds pass the mode's test, or None if there are none
+    fn region_pick_instances(
+        &self,
+        mesh_key: MeshKey,
+        frustum: &Frustum,
+        mode: RegionPickMode,
+    ) -> Option<Vec<usize>> {
+        let mesh = self.meshes.get(mesh_key).ok()?;
+        let aabb = self.meshes.resource(mesh_key).ok()?.aabb.as_ref()?;
+        let mesh_world = *self.transforms.get_world(mesh.transform_key).ok()?;
+        let instance_transforms = self.instances.transform_list(mesh.transform_key)?;
+
+        let instance_indices = instances_in_frustum(
+            frustum,
+            mode,
+            aabb,
+            mesh_world,
+            instance_transforms.iter().map(|t| t.to_matrix()),
+        );
+
+        (!instance_indices.is_empty()).then_some(instance_indices)
+    }
+}
+
+/// Region picking resources, kept apart from the single pixel picker so both can be in flight.
+pub(super) struct PickerRegion {
+    singlesampled: PickerCompute,
+    multisampled: PickerCompute,
+    bind_group: Option<web_sys::GpuBindGroup>,
+    state: Rc<RefCell<PickerRegionState>>,
+}
+
+impl PickerRegion {
+    /// Creates the region picking pipelines, buffers come with the first bind group.
+    pub async fn new(
+        gpu: &AwsmRendererWebGpu,
+        bind_group_layouts: &mut BindGroupLayouts,
+        pipeline_layouts: &mut PipelineLayouts,
+        shaders: &mut Shaders,
+        pipelines: &mut Pipelines,
+    ) -> Result<Self> {
+        let singlesampled = create_compute(
+            gpu,
+            bind_group_layouts,
+            pipeline_layouts,
+            shaders,
+            pipelines,
+            false,
+        )
+        .await?;
+        let multisampled = create_compute(
+            gpu,
+            bind_group_layouts,
+            pipeline_layouts,
+            shaders,
+            pipelines,
+            true,
+        )
+        .await?;
+
+        Ok(Self {
+            singlesampled,
+            multisampled,
+            bind_group: None,
+            state: Rc::new(RefCell::new(PickerRegionState::new(gpu)?)),
+        })
+    }
+
+    /// Rebuilds the bind group, growing the output with the mesh meta buffer.
+    pub fn recreate_bind_group(&mut self, ctx: &BindGroupRecreateContext<'_>) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+
+        // one entry per mesh meta slot, indexed the same way
+        let meta_buffer = ctx.meshes.meta.material_gpu_buffer();
+        let entry_count = meta_buffer.size() as usize / MATERIAL_MESH_META_BYTE_ALIGNMENT;
+        state.resize(ctx.gpu, entry_count * ENTRY_BYTE_SIZE)?;
+
+        let entries = vec![
+            // Visibility data texture
+            BindGroupEntry::new(
+                0,
+                BindGroupResource::TextureView(Cow::Borrowed(
+                    &ctx.render_texture_views.visibility_data,
+                )),
+            ),
+            // Mesh Meta
+            BindGroupEntry::new(
+                1,
+                BindGroupResource::Buffer(BufferBinding::new(meta_buffer)),
+            ),
+            // Region input
+            BindGroupEntry::new(
+                2,
+                BindGroupResource::Buffer(BufferBinding::new(&state.gpu_input_buffer)),
+            ),
+            // Region entries
+            BindGroupEntry::new(
+                3,
+                BindGroupResource::Buffer(BufferBinding::new(&state.gpu_entries_buffer)),
+            ),
+        ];
+
+        let compute = if ctx.anti_aliasing.msaa_sample_count.is_some() {
+            &self.multisampled
+        } else {
+            &self.singlesampled
+        };
+
+        let descriptor = BindGroupDescriptor::new(
+            ctx.bind_group_layouts.get(compute.bind_group_layout_key)?,
+            Some("Picker Region"),
+            entries,
+        );
+
+        self.bind_group = Some(ctx.gpu.create_bind_group(&descriptor.into()));
+
+        Ok(())
+    }
+}
+
+struct PickerRegionState {
+    in_flight: bool,
+    gpu_input_buffer: web_sys::GpuBuffer,
+    gpu_entries_buffer: web_sys::GpuBuffer,
+    gpu_readback_buffer: web_sys::GpuBuffer,
+    entries_byte_size: usize,
+}
+
+impl PickerRegionState {
+    fn new(gpu: &AwsmRendererWebGpu) -> Result<Self> {
+        let gpu_input_buffer = gpu.create_buffer(
+            &BufferDescriptor::new(
+                Some("Picker Region Input"),
+                INPUT_BYTE_SIZE,
+                BufferUsage::new().with_uniform().with_copy_dst(),
+            )
+            .into(),
+        )?;
+
+        let (gpu_entries_buffer, gpu_readback_buffer) =
+            create_entries_buffers(gpu, ENTRY_BYTE_SIZE)?;
+
+        Ok(Self {
+            in_flight: false,
+            gpu_input_buffer,
+            gpu_entries_buffer,
+            gpu_readback_buffer,
+            entries_byte_size: ENTRY_BYTE_SIZE,
+        })
+    }
+
+    fn resize(&mut self, gpu: &AwsmRendererWebGpu, entries_byte_size: usize) -> Result<()> {
+        let entries_byte_size = entries_byte_size.max(ENTRY_BYTE_SIZE);
+
+        if entries_byte_size != self.entries_byte_size {
+            // a pick in flight still holds the old readback buffer, it's dropped after
+            self.gpu_entries_buffer.destroy();
+            (self.gpu_entries_buffer, self.gpu_readback_buffer) =
+                create_entries_buffers(gpu, entries_byte_size)?;
+            self.entries_byte_size = entries_byte_size;
+        }
+
+        Ok(())
+    }
+
+    fn begin_pick(
+        &mut self,
+        gpu: &AwsmRendererWebGpu,
+        bind_group: &web_sys::GpuBindGroup,
+        pipeline: &web_sys::GpuComputePipeline,
+        input: [[u32; 2]; 4],
+    ) -> Result<()> {
+        self.in_flight = true;
+
+        let mut input_raw = [0u8; INPUT_BYTE_SIZE];
+        for (chunk, value) in input_raw
+            .chunks_exact_mut(4)
+            .zip(input.into_iter().flatten())
+        {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+
+        gpu.write_buffer(
+            &self.gpu_input_buffer,
+            None,
+            input_raw.as_slice(),
+            None,
+            None,
+        )?;
+
+        let [scan_min, scan_max, _, _] = input;
+
+        let command_encoder = gpu.create_command_encoder(Some("Picker Region"));
+
+        // every mesh starts out unseen
+        command_encoder.clear_buffer(&self.gpu_entries_buffer, None, None);
+
+        let compute_pass = command_encoder.begin_compute_pass(Some(
+            &ComputePassDescriptor::new(Some("Picker Region")).into(),
+        ));
+
+        compute_pass.set_bind_group(0u32, bind_group, None)?;
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(
+            (scan_max[0] - scan_min[0]).div_ceil(WORKGROUP_SIZE),
+            Some((scan_max[1] - scan_min[1]).div_ceil(WORKGROUP_SIZE)),
+            Some(1),
+        );
+        compute_pass.end();
+
+        command_encoder.copy_buffer_to_buffer(
+            &self.gpu_entries_buffer,
+            0,
+            &self.gpu_readback_buffer,
+            0,
+            self.entries_byte_size as u32,
+        )?;
+
+        gpu.submit_commands(&command_encoder.finish());
+
+        Ok(())
+    }
+}
+
+fn create_entries_buffers(
+    gpu: &AwsmRendererWebGpu,
+    byte_size: usize,
+) -> Result<(web_sys::GpuBuffer, web_sys::GpuBuffer)> {
+    let entries = gpu.create_buffer(
+        &BufferDescriptor::new(
+            Some("Picker Region Entries"),
+            byte_size,
+            BufferUsage::new()
+                .with_storage()
+                .with_copy_src()
+                .with_copy_dst(),
+        )
+        .into(),
+    )?;
+
+    let readback = gpu.create_buffer(
+        &BufferDescriptor::new(
+            Some("Picker Region Readback"),
+            byte_size,
+            BufferUsage::new().with_copy_dst().with_map_read(),
+        )
+        .into(),
+    )?;
+
+    Ok((entries, readback))
+}
+
+// The (mesh key, flags) of every mesh the shader saw
+fn parse_entries(bytes: &[u8]) -> impl Iterator<Item = (MeshKey, u32)> + '_ {
+    bytes.chunks_exact(ENTRY_BYTE_SIZE).filter_map(|entry| {
+        let read_u32 =
+            |offset: usize| u32::from_le_bytes(entry[offset..offset + 4].try_into().unwrap());
+
+        let flags = read_u32(0);
+        if flags == 0 {
+            return None;
+        }
+
+        let hi = read_u32(4) as u64;
+        let lo = read_u32(8) as u64;
+        let mesh_key: MeshKey = KeyData::from_ffi((hi << 32) | lo).into();
+
+        Some((mesh_key, flags))
+    })
+}
+
+// Indices of the instances whose bounds pass the mode's test
+fn instances_in_frustum(
+    frustum: &Frustum,
+    mode: RegionPickMode,
+    aabb: &Aabb,
+    mesh_world: Mat4,
+    instance_transforms: impl IntoIterator<Item = Mat4>,
+) -> Vec<usize> {
+    instance_transforms
+        .into_iter()
+        .enumerate()
+        .filter(|(_, instance_transform)| {
+            mode.accepts_aabb(
+                frustum,
+                &aabb.transformed(&(mesh_world * *instance_transform)),
+            )
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+async fn create_compute(
+    gpu: &AwsmRendererWebGpu,
+    bind_group_layouts: &mut BindGroupLayouts,
+    pipeline_layouts: &mut PipelineLayouts,
+    shaders: &mut Shaders,
+    pipelines: &mut Pipelines,
+    multisampled_geometry: bool,
+) -> Result<PickerCompute> {
+    let compute_entry = |resource| BindGroupLayoutCacheKeyEntry {
+        resource,
+        visibility_vertex: false,
+        visibility_fragment: false,
+        visibility_compute: true,
+    };
+
+    let bind_group_layout_key = bind_group_layouts.get_key(
+        gpu,
+        BindGroupLayoutCacheKey {
+            entries: vec![
+                // Binding 0: Visibility data texture
+                compute_entry(BindGroupLayoutResource::Texture(
+                    TextureBindingLayout::new()
+                        .with_view_dimension(TextureViewDimension::N2d)
+                        .with_sample_type(TextureSampleType::Uint)
+                        .with_multisampled(multisampled_geometry),
+                )),
+                // Binding 1: Mesh Meta
+                compute_entry(BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new()
+                        .with_binding_type(BufferBindingType::ReadOnlyStorage),
+                )),
+                // Binding 2: Region input
+                compute_entry(BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new().with_binding_type(BufferBindingType::Uniform),
+                )),
+                // Binding 3: Region entries
+                compute_entry(BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new().with_binding_type(BufferBindingType::Storage),
+                )),
+            ],
+        },
+    )?;
+
+    let pipeline_layout_key = pipeline_layouts.get_key(
+        gpu,
+        bind_group_layouts,
+        PipelineLayoutCacheKey::new(vec![bind_group_layout_key]),
+    )?;
+
+    let shader_key = shaders
+        .get_key(
+            gpu,
+            ShaderCacheKeyPickerRegion {
+                multisampled_geometry,
+            },
+        )
+        .await?;
+
+    let pipeline_key = pipelines
+        .compute
+        .get_key(
+            gpu,
+            shaders,
+            pipeline_layouts,
+            ComputePipelineCacheKey::new(shader_key, pipeline_layout_key),
+        )
+        .await?;
+
+    Ok(PickerCompute {
+        bind_group_layout_key,
+        pipeline_key,
+    })
+}
+
+/// Shader cache key for the region picker compute shader.
+#[derive(Hash, Debug, Clone, PartialEq, Eq)]
+pub struct ShaderCacheKeyPickerRegion {
+    pub multisampled_geometry: bool,
+}
+
+impl From<ShaderCacheKeyPickerRegion> for ShaderCacheKey {
+    fn from(key: ShaderCacheKeyPickerRegion) -> Self {
+        ShaderCacheKey::PickerRegion(key)
+    }
+}
+
+/// Shader template for the region picker compute shader.
+#[derive(Template, Debug)]
+#[template(path = "picker_wgsl/region.wgsl", whitespace = "minimize")]
+pub struct ShaderTemplatePickerRegion {
+    pub multisampled_geometry: bool,
+}
+
+impl ShaderTemplatePickerRegion {
+    #[cfg(debug_assertions)]
+    /// Returns an optional debug label for shader compilation.
+    pub fn debug_label(&self) -> Option<&str> {
+        Some("Picker Region")
+    }
+
+    /// Renders the template into WGSL source.
+    pub fn into_source(self) -> crate::shaders::Result<String> {
+        Ok(self.render()?)
+    }
+}
+
+impl From<&ShaderCacheKeyPickerRegion> for ShaderTemplatePickerRegion {
+    fn from(key: &ShaderCacheKeyPickerRegion) -> Self {
+        ShaderTemplatePickerRegion {
+            multisampled_geometry: key.multisampled_geometry,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,116 @@
+use super::*;
+
+use glam::Vec3;
+
+#[test]
+fn rect_from_corners_includes_both_pixels() {
+    let rect = PickRect::from_corners((10, 4), (2, 8));
+    assert_eq!(rect, PickRect::new(2, 4, 9, 5));
+
+    // a click without dragging is a single pixel
+    assert_eq!(
+        PickRect::from_corners((3, 3), (3, 3)),
+        PickRect::new(3, 3, 1, 1)
+    );
+}
+
+#[test]
+fn rect_clamps_to_the_viewport() {
+    assert_eq!(
+        PickRect::new(-5, 10, 20, 100).clamp(64, 32),
+        Some(([0, 10], [15, 32]))
+    );
+    assert_eq!(PickRect::new(70, 0, 10, 10).clamp(64, 32), None);
+    assert_eq!(PickRect::new(0, 0, 0, 10).clamp(64, 32), None);
+}
+
+#[test]
+fn mode_accepts_flags() {
+    assert!(RegionPickMode::Touching.accepts_flags(FLAG_INSIDE));
+    assert!(RegionPickMode::Touching.accepts_flags(FLAG_INSIDE | FLAG_OUTSIDE));
+    assert!(!RegionPickMode::Touching.accepts_flags(FLAG_OUTSIDE));
+
+    assert!(RegionPickMode::Contained.accepts_flags(FLAG_INSIDE));
+    assert!(!RegionPickMode::Contained.accepts_flags(FLAG_INSIDE | FLAG_OUTSIDE));
+    assert!(!RegionPickMode::Contained.accepts_flags(FLAG_OUTSIDE));
+}
+
+#[test]
+fn parse_entries_skips_unseen_meshes() {
+    let entry = |flags: u32, hi: u32, lo: u32| {
+        [flags, hi, lo]
+            .into_iter()
+            .flat_map(u32::to_le_bytes)
+            .collect::<Vec<u8>>()
+    };
+
+    let bytes = [
+        entry(0, 0, 0),
+        entry(FLAG_INSIDE, 1, 2),
+        entry(0, 0, 0),
+        entry(FLAG_INSIDE | FLAG_OUTSIDE, 3, 4),
+    ]
+    .concat();
+
+    let parsed: Vec<_> = parse_entries(&bytes).collect();
+    assert_eq!(
+        parsed,
+        vec![
+            (KeyData::from_ffi((1 << 32) | 2).into(), FLAG_INSIDE),
+            (
+                KeyData::from_ffi((3 << 32) | 4).into(),
+                FLAG_INSIDE | FLAG_OUTSIDE
+            ),
+        ]
+    );
+}
+
+#[test]
+fn instances_in_frustum_filters_by_bounds() {
+    let view_projection = Mat4::orthographic_rh(-10.0, 10.0, -10.0, 10.0, 0.1, 100.0)
+        * Mat4::look_at_rh(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, Vec3::Y);
+
+    // the right half of the screen
+    let frustum = PickRect::new(50, 0, 50, 100).frustum(view_projection, 100, 100);
+
+    let aabb = Aabb::new_cube(2.0, 2.0);
+    let instances = [
+        Mat4::from_translation(Vec3::new(-5.0, 0.0, 0.0)),
+        Mat4::from_translation(Vec3::new(5.0, 0.0, 0.0)),
+        // straddles the middle of the screen
+        Mat4::from_translation(Vec3::new(0.5, 0.0, 0.0)),
+    ];
+
+    assert_eq!(
+        instances_in_frustum(
+            &frustum,
+            RegionPickMode::Touching,
+            &aabb,
+            Mat4::IDENTITY,
+            instances
+        ),
+        vec![1, 2]
+    );
+    assert_eq!(
+        instances_in_frustum(
+            &frustum,
+            RegionPickMode::Contained,
+            &aabb,
+            Mat4::IDENTITY,
+            instances
+        ),
+        vec![1]
+    );
+
+    // the mesh's own transform applies on top of the instance transforms
+    assert_eq!(
+        instances_in_frustum(
+            &frustum,
+            RegionPickMode::Contained,
+            &aabb,
+            Mat4::from_translation(Vec3::new(-10.0, 0.0, 0.0)),
+            instances
+        ),
+        Vec::<usize>::new()
+    );
+}
@@ -10,40 +10,132 @@ struct PickInput {
     mouse_x : i32,
     mouse_y : i32,
 };
+// make sure this matches OUTPUT_BYTE_SIZE in picker/state.rs
 struct PickOutput {
     valid: u32,
     mesh_key_high: u32,
     mesh_key_low: u32,
+    // U32_MAX for transparent hits, which don't go through the visibility buffer
+    triangle_index: u32,
+    depth: f32,
+    // world-space normal (interpolated, before normal mapping)
+    normal_x: f32,
+    normal_y: f32,
+    normal_z: f32,
+    // one of the PICK_LAYER_* constants
+    layer: u32,
+    // U32_MAX when unknown, only transparent hits know which instance was drawn
+    instance_index: u32,
 };
 
+// make sure these match PickLayer in picker.rs, and the transparent picking fragment shader
+const PICK_LAYER_OPAQUE: u32 = 0u;
+const PICK_LAYER_TRANSPARENT: u32 = 1u;
+const PICK_LAYER_HUD: u32 = 2u;
+
 {% if multisampled_geometry %}
     @group(0) @binding(0) var visibility_data_tex: texture_multisampled_2d<u32>;
+    @group(0) @binding(4) var depth_tex: texture_depth_multisampled_2d;
+    @group(0) @binding(5) var normal_tangent_tex: texture_multisampled_2d<f32>;
+    @group(0) @binding(6) var hud_depth_tex: texture_depth_multisampled_2d;
 {% else %}
     @group(0) @binding(0) var visibility_data_tex: texture_2d<u32>;
+    @group(0) @binding(4) var depth_tex: texture_depth_2d;
+    @group(0) @binding(5) var normal_tangent_tex: texture_2d<f32>;
+    @group(0) @binding(6) var hud_depth_tex: texture_depth_2d;
 {% endif %}
 
 @group(0) @binding(1) var<storage, read> material_mesh_metas: array<MaterialMeshMeta>;
 @group(0) @binding(2) var<uniform> pick_input: PickInput;
 @group(0) @binding(3) var<storage, read_write> pick_output: PickOutput;
 
+{% if transparent %}
+    // written at pick time by the transparent picking pass, never multisampled
+    @group(0) @binding(7) var pick_ids_tex: texture_2d<u32>;
+    @group(0) @binding(8) var pick_surface_tex: texture_2d<f32>;
+{% endif %}
+
 
 @compute @workgroup_size(1, 1)
 fn main() {
     let coords = vec2<i32>(pick_input.mouse_x, pick_input.mouse_y);
     let visibility_data_info = textureLoad(visibility_data_tex, coords, 0);
     let material_meta_offset = join32(visibility_data_info.z, visibility_data_info.w);
-    let triangle_index = join32(visibility_data_info.x, visibility_data_info.y);
+    let visibility_triangle_index = join32(visibility_data_info.x, visibility_data_info.y);
+
+    var valid = false;
+    var mesh_key_high = 0u;
+    var mesh_key_low = 0u;
+    var layer = PICK_LAYER_OPAQUE;
+    var depth = 1.0;
+    var normal = vec3<f32>(0.0);
+    var triangle_index = U32_MAX;
+    var instance_index = U32_MAX;
+
+    // Opaque hit from the visibility buffer, HUD meshes draw over the world in there
+    if (visibility_triangle_index != U32_MAX) {
+        let material_mesh_meta = material_mesh_metas[material_meta_offset / META_SIZE_IN_BYTES];
+        let is_hud = material_mesh_meta.is_hud == 1u;
+
+        valid = true;
+        mesh_key_high = material_mesh_meta.mesh_key_high;
+        mesh_key_low = material_mesh_meta.mesh_key_low;
+        layer = select(PICK_LAYER_OPAQUE, PICK_LAYER_HUD, is_hud);
+        depth = select(textureLoad(depth_tex, coords, 0), textureLoad(hud_depth_tex, coords, 0), is_hud);
+        normal = decode_octahedral(textureLoad(normal_tangent_tex, coords, 0).xy);
+        triangle_index = visibility_triangle_index;
+    }
+
+    {% if transparent %}
+    // Front-most transparent hit, the pick pass already resolved it against the other
+    // transparent meshes (and the HUD over the world)
+    let pick_ids = textureLoad(pick_ids_tex, coords, 0);
+    if (pick_ids.w != 0u) {
+        let pick_surface = textureLoad(pick_surface_tex, coords, 0);
+        let transparent_is_hud = pick_ids.w == PICK_LAYER_HUD;
+        let opaque_is_hud = layer == PICK_LAYER_HUD;
+
+        // The HUD is in front of the world regardless of depth, within the same space the
+        // nearest wins. Ties go to the transparent surface since it's drawn over the opaque one.
+        let in_front = !valid
+            || (transparent_is_hud && !opaque_is_hud)
+            || (transparent_is_hud == opaque_is_hud && pick_surface.w <= depth);
 
-    if (triangle_index == U32_MAX) {
+        if (in_front) {
+            valid = true;
+            mesh_key_high = pick_ids.x;
+            mesh_key_low = pick_ids.y;
+            layer = pick_ids.w;
+            depth = pick_surface.w;
+            normal = pick_surface.xyz;
+            triangle_index = U32_MAX;
+            instance_index = pick_ids.z;
+        }
+    }
+    {% endif %}
+
+    if (!valid) {
         pick_output.valid = 0u;
         pick_output.mesh_key_high = 0u;
-        pick_output.mesh_key_low = 0;;
+        pick_output.mesh_key_low = 0u;
+        pick_output.triangle_index = 0u;
+        pick_output.depth = 1.0;
+        pick_output.normal_x = 0.0;
+        pick_output.normal_y = 0.0;
+        pick_output.normal_z = 0.0;
+        pick_output.layer = PICK_LAYER_OPAQUE;
+        pick_output.instance_index = U32_MAX;
         return;
     }
 
-    let material_mesh_meta = material_mesh_metas[material_meta_offset / META_SIZE_IN_BYTES];
-
     pick_output.valid = 1u;
-    pick_output.mesh_key_high = material_mesh_meta.mesh_key_high;
-    pick_output.mesh_key_low = material_mesh_meta.mesh_key_low;
+    pick_output.mesh_key_high = mesh_key_high;
+    pick_output.mesh_key_low = mesh_key_low;
+    pick_output.triangle_index = triangle_index;
+    pick_output.depth = depth;
+    pick_output.normal_x = normal.x;
+    pick_output.normal_y = normal.y;
+    pick_output.normal_z = normal.z;
+    pick_output.layer = layer;
+    pick_output.instance_index = instance_index;
 }
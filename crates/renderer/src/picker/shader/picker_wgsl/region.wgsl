@@ -0,0 +1,70 @@
+/*************** START math.wgsl ******************/
+{% include "shared_wgsl/math.wgsl" %}
+/*************** END math.wgsl ******************/
+
+/*************** START mesh_meta.wgsl ******************/
+{% include "shared_wgsl/material_mesh_meta.wgsl" %}
+/*************** END mesh_meta.wgsl ******************/
+
+// make sure this matches INPUT_BYTE_SIZE in picker/region.rs
+struct RegionInput {
+    // pixels covered by the dispatch, max exclusive
+    scan_min: vec2<u32>,
+    scan_max: vec2<u32>,
+    // the selection rectangle, max exclusive
+    rect_min: vec2<u32>,
+    rect_max: vec2<u32>,
+};
+
+// one per mesh meta slot, make sure this matches ENTRY_BYTE_SIZE in picker/region.rs
+struct RegionEntry {
+    // REGION_FLAG_* bits, zero if the mesh wasn't seen
+    flags: atomic<u32>,
+    mesh_key_high: u32,
+    mesh_key_low: u32,
+};
+
+// make sure these match FLAG_INSIDE and FLAG_OUTSIDE in picker/region.rs
+const REGION_FLAG_INSIDE: u32 = 1u;
+const REGION_FLAG_OUTSIDE: u32 = 2u;
+
+{% if multisampled_geometry %}
+    @group(0) @binding(0) var visibility_data_tex: texture_multisampled_2d<u32>;
+{% else %}
+    @group(0) @binding(0) var visibility_data_tex: texture_2d<u32>;
+{% endif %}
+
+@group(0) @binding(1) var<storage, read> material_mesh_metas: array<MaterialMeshMeta>;
+@group(0) @binding(2) var<uniform> region_input: RegionInput;
+@group(0) @binding(3) var<storage, read_write> region_entries: array<RegionEntry>;
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let coords = region_input.scan_min + global_id.xy;
+    if (any(coords >= region_input.scan_max)) {
+        return;
+    }
+
+    // first sample only, same as the single pixel picker
+    let visibility_data_info = textureLoad(visibility_data_tex, vec2<i32>(coords), 0);
+    let triangle_index = join32(visibility_data_info.x, visibility_data_info.y);
+    if (triangle_index == U32_MAX) {
+        return;
+    }
+
+    let meta_index = join32(visibility_data_info.z, visibility_data_info.w) / META_SIZE_IN_BYTES;
+    if (meta_index >= arrayLength(&region_entries)) {
+        return;
+    }
+
+    let inside = all(coords >= region_input.rect_min) && all(coords < region_input.rect_max);
+    let flag = select(REGION_FLAG_OUTSIDE, REGION_FLAG_INSIDE, inside);
+
+    // deduplicated by mesh, the first pixel to see it fills in the key
+    let previous_flags = atomicOr(&region_entries[meta_index].flags, flag);
+    if (previous_flags == 0u) {
+        let material_mesh_meta = material_mesh_metas[meta_index];
+        region_entries[meta_index].mesh_key_high = material_mesh_meta.mesh_key_high;
+        region_entries[meta_index].mesh_key_low = material_mesh_meta.mesh_key_low;
+    }
+}
@@ -1,12 +1,14 @@
 use crate::error::Result;
 use awsm_renderer_core::{
     buffers::{BufferDescriptor, BufferUsage},
-    command::compute_pass::ComputePassDescriptor,
+    command::{compute_pass::ComputePassDescriptor, CommandEncoder},
     renderer::AwsmRendererWebGpu,
 };
 
 const INPUT_BYTE_SIZE: usize = 8; // 2xi32 for x,y pixel coords
-pub(super) const OUTPUT_BYTE_SIZE: usize = 12; // hi/low u32 for mesh_id + u32 for validity
+                                  // u32 for validity + hi/low u32 for mesh_id + u32 triangle index + f32 depth + 3xf32 normal
+                                  // + u32 layer + u32 instance index
+pub(super) const OUTPUT_BYTE_SIZE: usize = 40;
 
 pub(super) struct PickerState {
     pub in_flight: bool,
@@ -55,9 +57,13 @@ impl PickerState {
     }
 
     /// Begins a GPU pick pass for the given pixel coordinates.
+    ///
+    /// Anything already encoded in `command_encoder` (e.g. the transparent picking pass) is
+    /// submitted along with it.
     pub fn begin_pick(
         &mut self,
         gpu: &AwsmRendererWebGpu,
+        command_encoder: CommandEncoder,
         bind_group: &web_sys::GpuBindGroup,
         pipeline: &web_sys::GpuComputePipeline,
         x: i32,
@@ -79,8 +85,6 @@ impl PickerState {
 
         // kick off compute pass
 
-        let command_encoder = gpu.create_command_encoder(Some("Picker"));
-
         let compute_pass = command_encoder
             .begin_compute_pass(Some(&ComputePassDescriptor::new(Some("Picker")).into()));
 
@@ -0,0 +1,71 @@
+use super::*;
+
+fn output_bytes(values: [u32; OUTPUT_BYTE_SIZE / 4]) -> [u8; OUTPUT_BYTE_SIZE] {
+    let mut bytes = [0u8; OUTPUT_BYTE_SIZE];
+    for (chunk, value) in bytes.chunks_exact_mut(4).zip(values) {
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn pick_output_miss() {
+    let mut values = [0; OUTPUT_BYTE_SIZE / 4];
+    values[3] = u32::MAX;
+    values[9] = u32::MAX;
+    assert!(PickOutput::from_bytes(&output_bytes(values)).is_none());
+}
+
+#[test]
+fn pick_output_opaque_hit() {
+    let mesh_key: MeshKey = KeyData::from_ffi((3 << 32) | 7).into();
+    let output = PickOutput::from_bytes(&output_bytes([
+        1,
+        3,
+        7,
+        42,
+        0.5f32.to_bits(),
+        0.0f32.to_bits(),
+        1.0f32.to_bits(),
+        0.0f32.to_bits(),
+        0,
+        u32::MAX,
+    ]))
+    .unwrap();
+
+    assert_eq!(output.mesh_key, mesh_key);
+    assert_eq!(output.layer, PickLayer::Opaque);
+    assert_eq!(output.triangle_index, Some(42));
+    assert_eq!(output.depth, 0.5);
+    assert_eq!(output.normal, Vec3::Y);
+    // the visibility buffer doesn't know the instance, it's resolved on the CPU
+    assert_eq!(output.instance_index, None);
+}
+
+#[test]
+fn pick_output_transparent_hit() {
+    let output = PickOutput::from_bytes(&output_bytes([
+        1,
+        0,
+        1,
+        u32::MAX,
+        0.25f32.to_bits(),
+        0.0f32.to_bits(),
+        0.0f32.to_bits(),
+        1.0f32.to_bits(),
+        1,
+        2,
+    ]))
+    .unwrap();
+
+    assert_eq!(output.layer, PickLayer::Transparent);
+    assert_eq!(output.triangle_index, None);
+    assert_eq!(output.instance_index, Some(2));
+}
+
+#[test]
+fn pick_layer_from_shader_constants() {
+    assert_eq!(PickLayer::from_u32(0), PickLayer::Opaque);
+    assert_eq!(PickLayer::from_u32(1), PickLayer::Transparent);
+    assert_eq!(PickLayer::from_u32(2), PickLayer::Hud);
+}
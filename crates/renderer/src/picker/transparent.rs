@@ -0,0 +1,260 @@
+//! Opt-in picking of transparent and HUD meshes.
+//!
+//! The visibility buffer only holds opaque geometry, so transparent meshes are drawn again at
+//! pick time, into the picked pixel of dedicated pick textures. The opaque depth is copied out
+//! every frame before the transparent passes write into it, so the picker can tell which layer
+//! is in front.
+
+use awsm_renderer_core::{
+    command::{
+        copy_texture::TexelCopyTextureInfo,
+        render_pass::{ColorAttachment, DepthStencilAttachment, RenderPassDescriptor},
+        CommandEncoder, LoadOp, StoreOp,
+    },
+    renderer::AwsmRendererWebGpu,
+    texture::{Extent3d, TextureDescriptor, TextureFormat, TextureUsage},
+};
+
+use crate::{
+    error::Result,
+    render::RenderContext,
+    render_textures::{AwsmRenderTextureError, RenderTextureFormats, RenderTextureViews},
+    AwsmRenderer,
+};
+
+use super::PickerCompute;
+
+/// Picker resources that only exist while transparent picking is enabled.
+pub(super) struct PickerTransparent {
+    pub singlesampled: PickerCompute,
+    pub multisampled: PickerCompute,
+    pub formats: RenderTextureFormats,
+    // created with the first bind group, sized to the render textures
+    pub textures: Option<PickerTransparentTextures>,
+}
+
+/// Screen-sized textures for transparent picking.
+pub(super) struct PickerTransparentTextures {
+    width: u32,
+    height: u32,
+    msaa_sample_count: Option<u32>,
+    // copies of the depth buffers before transparent meshes are drawn, matching their sample count
+    opaque_depth: web_sys::GpuTexture,
+    pub opaque_depth_view: web_sys::GpuTextureView,
+    opaque_hud_depth: web_sys::GpuTexture,
+    pub opaque_hud_depth_view: web_sys::GpuTextureView,
+    // written at pick time, never multisampled
+    ids: web_sys::GpuTexture,
+    pub ids_view: web_sys::GpuTextureView,
+    surface: web_sys::GpuTexture,
+    pub surface_view: web_sys::GpuTextureView,
+    depth: web_sys::GpuTexture,
+    pub depth_view: web_sys::GpuTextureView,
+}
+
+impl PickerTransparentTextures {
+    /// Creates the textures at the size of the current render textures.
+    pub fn new(
+        gpu: &AwsmRendererWebGpu,
+        formats: &RenderTextureFormats,
+        render_texture_views: &RenderTextureViews,
+        msaa_sample_count: Option<u32>,
+    ) -> Result<Self> {
+        let width = render_texture_views.width;
+        let height = render_texture_views.height;
+
+        let create_texture = |format: TextureFormat,
+                              usage: TextureUsage,
+                              sample_count: Option<u32>,
+                              label: &'static str|
+         -> Result<(web_sys::GpuTexture, web_sys::GpuTextureView)> {
+            let mut descriptor = TextureDescriptor::new(
+                format,
+                Extent3d::new(width, Some(height), Some(1)),
+                usage.with_texture_binding(),
+            )
+            .with_label(label);
+
+            if let Some(sample_count) = sample_count {
+                descriptor = descriptor.with_sample_count(sample_count);
+            }
+
+            let texture = gpu
+                .create_texture(&descriptor.into())
+                .map_err(AwsmRenderTextureError::CreateTexture)?;
+            let view = texture.create_view().map_err(|e| {
+                AwsmRenderTextureError::CreateTextureView(format!("{label}: {e:?}"))
+            })?;
+
+            Ok((texture, view))
+        };
+
+        let (opaque_depth, opaque_depth_view) = create_texture(
+            formats.depth,
+            TextureUsage::new().with_copy_dst(),
+            msaa_sample_count,
+            "Picker Opaque Depth",
+        )?;
+        let (opaque_hud_depth, opaque_hud_depth_view) = create_texture(
+            formats.depth,
+            TextureUsage::new().with_copy_dst(),
+            msaa_sample_count,
+            "Picker Opaque Hud Depth",
+        )?;
+        let (ids, ids_view) = create_texture(
+            formats.pick_ids,
+            TextureUsage::new().with_render_attachment(),
+            None,
+            "Picker Ids",
+        )?;
+        let (surface, surface_view) = create_texture(
+            formats.pick_surface,
+            TextureUsage::new().with_render_attachment(),
+            None,
+            "Picker Surface",
+        )?;
+        let (depth, depth_view) = create_texture(
+            formats.depth,
+            TextureUsage::new().with_render_attachment(),
+            None,
+            "Picker Depth",
+        )?;
+
+        Ok(Self {
+            width,
+            height,
+            msaa_sample_count,
+            opaque_depth,
+            opaque_depth_view,
+            opaque_hud_depth,
+            opaque_hud_depth_view,
+            ids,
+            ids_view,
+            surface,
+            surface_view,
+            depth,
+            depth_view,
+        })
+    }
+
+    /// Returns true if these textures can be used with the current render textures.
+    pub fn matches(
+        &self,
+        render_texture_views: &RenderTextureViews,
+        msaa_sample_count: Option<u32>,
+    ) -> bool {
+        self.width == render_texture_views.width
+            && self.height == render_texture_views.height
+            && self.msaa_sample_count == msaa_sample_count
+    }
+
+    /// Copies the depth buffers out before the transparent passes write into them.
+    pub fn copy_opaque_depth(&self, ctx: &RenderContext) -> Result<()> {
+        let size = Extent3d::new(self.width, Some(self.height), Some(1));
+
+        ctx.command_encoder.copy_texture_to_texture(
+            &TexelCopyTextureInfo::new(&ctx.render_texture_views.depth_texture).into(),
+            &TexelCopyTextureInfo::new(&self.opaque_depth).into(),
+            &size.clone().into(),
+        )?;
+
+        ctx.command_encoder.copy_texture_to_texture(
+            &TexelCopyTextureInfo::new(&ctx.render_texture_views.hud_depth_texture).into(),
+            &TexelCopyTextureInfo::new(&self.opaque_hud_depth).into(),
+            &size.into(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Frees the GPU textures.
+    pub fn destroy(&self) {
+        self.opaque_depth.destroy();
+        self.opaque_hud_depth.destroy();
+        self.ids.destroy();
+        self.surface.destroy();
+        self.depth.destroy();
+    }
+}
+
+impl AwsmRenderer {
+    // Draws the transparent meshes into the picked pixel of the pick textures, world first and
+    // then the HUD over it
+    pub(super) fn encode_transparent_pick(
+        &self,
+        command_encoder: &CommandEncoder,
+        textures: &PickerTransparentTextures,
+        x: i32,
+        y: i32,
+    ) -> Result<()> {
+        // a scissor outside the textures is a validation error, and there's nothing to pick there
+        if x < 0 || y < 0 || x as u32 >= textures.width || y as u32 >= textures.height {
+            return Ok(());
+        }
+
+        let pipelines = &self.render_passes.material_transparent.pipelines;
+        let (main_bind_group, mesh_material_bind_group, lights_bind_group, texture_bind_group) =
+            self.render_passes
+                .material_transparent
+                .bind_groups
+                .get_bind_groups()?;
+
+        for is_hud in [false, true] {
+            // the HUD draws over the world's picks, but is depth-tested in its own space
+            let color_load_op = if is_hud { LoadOp::Load } else { LoadOp::Clear };
+
+            let render_pass = command_encoder.begin_render_pass(
+                &RenderPassDescriptor {
+                    label: Some(if is_hud {
+                        "Picker Transparent Pass (HUD)"
+                    } else {
+                        "Picker Transparent Pass"
+                    }),
+                    color_attachments: vec![
+                        ColorAttachment::new(&textures.ids_view, color_load_op, StoreOp::Store),
+                        ColorAttachment::new(&textures.surface_view, color_load_op, StoreOp::Store),
+                    ],
+                    depth_stencil_attachment: Some(
+                        DepthStencilAttachment::new(&textures.depth_view)
+                            .with_depth_load_op(LoadOp::Clear)
+                            .with_depth_clear_value(1.0)
+                            .with_depth_store_op(StoreOp::Store),
+                    ),
+                    ..Default::default()
+                }
+                .into(),
+            )?;
+
+            render_pass.set_scissor_rect(x as u32, y as u32, 1, 1);
+
+            render_pass.set_bind_group(0u32, main_bind_group, None)?;
+            render_pass.set_bind_group(1u32, lights_bind_group, None)?;
+            render_pass.set_bind_group(2u32, texture_bind_group, None)?;
+
+            // opaque meshes are already in the visibility buffer, no sorting needed with depth testing
+            for (mesh_key, mesh) in self.meshes.iter().filter(|(_, mesh)| {
+                !mesh.hidden
+                    && mesh.hud == is_hud
+                    && self.materials.is_transparency_pass(mesh.material_key)
+            }) {
+                let Some(render_pipeline_key) = pipelines.get_pick_render_pipeline_key(mesh_key)
+                else {
+                    continue;
+                };
+
+                render_pass.set_pipeline(self.pipelines.render.get(render_pipeline_key)?);
+                mesh.push_material_transparent_pass_commands(
+                    &self.meshes,
+                    &self.instances,
+                    mesh_key,
+                    &render_pass,
+                    mesh_material_bind_group,
+                )?;
+            }
+
+            render_pass.end();
+        }
+
+        Ok(())
+    }
+}
@@ -3,11 +3,16 @@
 use crate::{error::Result, AwsmRenderer};
 
 /// Post-processing settings for the renderer.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PostProcessing {
     pub tonemapping: ToneMapping,
     pub bloom: bool,
     pub dof: bool,
+    /// Screen-space ambient occlusion, `None` to disable.
+    ///
+    /// Computed from the geometry pass depth and normals before opaque shading,
+    /// and only darkens the indirect (IBL) diffuse lighting.
+    pub ssao: Option<Ssao>,
 }
 
 /// Tonemapping operator selection.
@@ -18,12 +23,59 @@ pub enum ToneMapping {
     Aces,
 }
 
+/// Maximum number of hemisphere samples per pixel for SSAO.
+pub const SSAO_MAX_SAMPLE_COUNT: u32 = 64;
+/// Byte size of the SSAO settings uniform (see `ssao_wgsl/helpers.wgsl`).
+pub const SSAO_SETTINGS_BYTE_SIZE: usize = 16;
+
+/// Screen-space ambient occlusion settings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ssao {
+    /// Sampling radius in world units.
+    pub radius: f32,
+    /// Scales the occlusion, 1.0 is physically plausible, higher values exaggerate it.
+    pub intensity: f32,
+    /// Hemisphere samples per pixel, clamped to `1..=SSAO_MAX_SAMPLE_COUNT`.
+    pub sample_count: u32,
+    /// Depth-aware blur to smooth out the sampling noise.
+    pub blur: bool,
+}
+
+impl Default for Ssao {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            intensity: 1.0,
+            sample_count: 16,
+            blur: true,
+        }
+    }
+}
+
+impl Ssao {
+    /// Packs the settings into the uniform layout read by the SSAO shaders.
+    pub fn uniform_data(&self) -> [u8; SSAO_SETTINGS_BYTE_SIZE] {
+        let mut data = [0u8; SSAO_SETTINGS_BYTE_SIZE];
+        data[0..4].copy_from_slice(&self.radius.max(0.0).to_ne_bytes());
+        data[4..8].copy_from_slice(&self.intensity.max(0.0).to_ne_bytes());
+        data[8..12].copy_from_slice(
+            &self
+                .sample_count
+                .clamp(1, SSAO_MAX_SAMPLE_COUNT)
+                .to_ne_bytes(),
+        );
+        data[12..16].copy_from_slice(&u32::from(self.blur).to_ne_bytes());
+        data
+    }
+}
+
 impl Default for PostProcessing {
     fn default() -> Self {
         Self {
             tonemapping: ToneMapping::KhronosNeutralPbr,
             bloom: false,
             dof: false,
+            ssao: None,
         }
     }
 }
@@ -31,8 +83,32 @@ impl Default for PostProcessing {
 impl AwsmRenderer {
     /// Applies post-processing configuration and rebuilds pipelines as needed.
     pub async fn set_post_processing(&mut self, pp: PostProcessing) -> Result<()> {
+        let ssao_disabled = self.post_processing.ssao.is_some() && pp.ssao.is_none();
         self.post_processing = pp;
 
+        if let Some(ssao) = &self.post_processing.ssao {
+            self.render_passes
+                .ssao
+                .bind_groups
+                .write_settings(&self.gpu, ssao)?;
+        } else if ssao_disabled {
+            // the opaque pass always reads the occlusion, so don't leave the last result behind
+            self.render_textures.clear_ambient_occlusion(&self.gpu)?;
+        }
+
+        self.render_passes
+            .ssao
+            .pipelines
+            .set_render_pipeline_keys(
+                &self.anti_aliasing,
+                &self.post_processing,
+                &self.gpu,
+                &mut self.shaders,
+                &mut self.pipelines,
+                &self.pipeline_layouts,
+            )
+            .await?;
+
         self.render_passes
             .effects
             .pipelines
@@ -62,3 +138,6 @@ impl AwsmRenderer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,54 @@
+use super::*;
+
+fn read_f32(data: &[u8], offset: usize) -> f32 {
+    f32::from_ne_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[test]
+fn ssao_uniform_layout() {
+    let ssao = Ssao {
+        radius: 0.75,
+        intensity: 2.0,
+        sample_count: 24,
+        blur: true,
+    };
+    let data = ssao.uniform_data();
+
+    assert_eq!(read_f32(&data, 0), 0.75);
+    assert_eq!(read_f32(&data, 4), 2.0);
+    assert_eq!(read_u32(&data, 8), 24);
+    assert_eq!(read_u32(&data, 12), 1);
+
+    let data = Ssao {
+        blur: false,
+        ..ssao
+    }
+    .uniform_data();
+    assert_eq!(read_u32(&data, 12), 0);
+}
+
+#[test]
+fn ssao_uniform_clamps_settings() {
+    let data = Ssao {
+        radius: -1.0,
+        intensity: -1.0,
+        sample_count: 0,
+        blur: false,
+    }
+    .uniform_data();
+
+    assert_eq!(read_f32(&data, 0), 0.0);
+    assert_eq!(read_f32(&data, 4), 0.0);
+    assert_eq!(read_u32(&data, 8), 1);
+
+    let data = Ssao {
+        sample_count: SSAO_MAX_SAMPLE_COUNT + 1,
+        ..Ssao::default()
+    }
+    .uniform_data();
+    assert_eq!(read_u32(&data, 8), SSAO_MAX_SAMPLE_COUNT);
+}
@@ -0,0 +1,419 @@
+//! CPU ray casting against scene geometry.
+//!
+//! Queries answer right away, without waiting a frame for a GPU pick. A [`Bvh`] over the
+//! meshes' world AABBs narrows down the candidates, then their triangles are tested exactly,
+//! from the CPU copy of the geometry.
+
+mod bvh;
+
+pub use bvh::Bvh;
+
+use glam::{Mat4, Vec2, Vec3};
+
+use crate::{
+    bounds::Aabb,
+    error::Result,
+    meshes::{mesh::Mesh, MeshKey},
+    AwsmRenderer,
+};
+
+/// A ray with a normalized direction, so distances along it are world units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Creates a ray, normalizing the direction.
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize_or_zero(),
+        }
+    }
+
+    /// Creates the ray through a pixel, from the near plane into the screen.
+    ///
+    /// Pixel coordinates have the origin at the top-left, like [`AwsmRenderer::pick`].
+    pub fn from_screen(inv_view_projection: Mat4, x: f32, y: f32, width: u32, height: u32) -> Self {
+        // y flipped from pixel space to NDC
+        let ndc = Vec2::new(
+            (x / width.max(1) as f32) * 2.0 - 1.0,
+            1.0 - (y / height.max(1) as f32) * 2.0,
+        );
+
+        let near = inv_view_projection.project_point3(ndc.extend(0.0));
+        let far = inv_view_projection.project_point3(ndc.extend(1.0));
+
+        Self::new(near, far - near)
+    }
+
+    /// Returns the point at a distance along the ray.
+    pub fn at(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+
+    /// Returns the distance at which the ray enters the AABB, zero if it starts inside.
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        self.intersect_aabb_inv(aabb, Vec3::ONE / self.direction)
+    }
+
+    // Slab test with a precomputed inverse direction, axis-parallel rays give infinities
+    // which the min/max sort out
+    fn intersect_aabb_inv(&self, aabb: &Aabb, inv_direction: Vec3) -> Option<f32> {
+        let t1 = (aabb.min - self.origin) * inv_direction;
+        let t2 = (aabb.max - self.origin) * inv_direction;
+
+        // NaN (origin on a slab plane of a parallel ray) is ignored by min/max
+        let entry = t1.min(t2).max_element().max(0.0);
+        let exit = t1.max(t2).min_element();
+
+        (entry <= exit).then_some(entry)
+    }
+
+    /// Returns the distance and barycentrics of where the ray hits a triangle, from either side.
+    ///
+    /// Barycentrics weigh the triangle's corners in order (Möller–Trumbore).
+    pub fn intersect_triangle(&self, triangle: [Vec3; 3]) -> Option<(f32, Vec3)> {
+        let [a, b, c] = triangle;
+        let ab = b - a;
+        let ac = c - a;
+
+        let p = self.direction.cross(ac);
+        let det = ab.dot(p);
+        // parallel to the triangle, or degenerate, relative to their sizes
+        let scale = self.direction.length() * ab.cross(ac).length();
+        if det.abs() <= scale * 1e-6 {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+
+        let ao = self.origin - a;
+        let u = ao.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = ao.cross(ab);
+        let v = self.direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let distance = ac.dot(q) * inv_det;
+        (distance >= 0.0).then_some((distance, Vec3::new(1.0 - u - v, u, v)))
+    }
+
+    // The same ray in another space, keeping the direction unnormalized so distances still
+    // match this ray's
+    fn transformed(&self, matrix: Mat4) -> Self {
+        Self {
+            origin: matrix.transform_point3(self.origin),
+            direction: matrix.transform_vector3(self.direction),
+        }
+    }
+}
+
+/// The nearest triangle hit on one mesh.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangleHit {
+    pub distance: f32,
+    pub triangle_index: u32,
+    pub barycentrics: Vec3,
+    /// World-space face normal, facing back along the ray.
+    pub normal: Vec3,
+}
+
+/// Finds the nearest triangle the ray hits within `max_distance`.
+///
+/// Triangles are `(triangle index, object-space positions)`, placed in the world by `world`.
+/// The ray is moved into object space rather than the triangles into the world.
+pub fn intersect_triangles(
+    ray: &Ray,
+    world: Mat4,
+    max_distance: f32,
+    triangles: impl IntoIterator<Item = (u32, [Vec3; 3])>,
+) -> Option<TriangleHit> {
+    let inv_world = world.inverse();
+    // flattened on some axis, nothing to hit
+    if !inv_world.is_finite() {
+        return None;
+    }
+
+    let local_ray = ray.transformed(inv_world);
+
+    let mut nearest: Option<(f32, u32, Vec3, [Vec3; 3])> = None;
+
+    for (triangle_index, triangle) in triangles {
+        let max_distance = nearest.map_or(max_distance, |(distance, ..)| distance);
+
+        if let Some((distance, barycentrics)) = local_ray.intersect_triangle(triangle) {
+            if distance <= max_distance {
+                nearest = Some((distance, triangle_index, barycentrics, triangle));
+            }
+        }
+    }
+
+    let (distance, triangle_index, barycentrics, [a, b, c]) = nearest?;
+
+    // normals go through the inverse transpose to stay perpendicular under non-uniform scale
+    let normal = inv_world
+        .transpose()
+        .transform_vector3((b - a).cross(c - a))
+        .normalize_or_zero();
+    let normal = if normal.dot(ray.direction) > 0.0 {
+        -normal
+    } else {
+        normal
+    };
+
+    Some(TriangleHit {
+        distance,
+        triangle_index,
+        barycentrics,
+        normal,
+    })
+}
+
+/// Finds the nearest hit among the items of a BVH.
+///
+/// `intersect` tests one item against the ray within the given max distance, e.g. with
+/// [`intersect_triangles`]. Items are visited roughly nearest first, so far ones are mostly
+/// skipped once something is hit.
+pub fn raycast_bvh<T: Copy, H>(
+    bvh: &Bvh<T>,
+    ray: &Ray,
+    max_distance: f32,
+    mut intersect: impl FnMut(T, f32) -> Option<(f32, H)>,
+) -> Option<(T, f32, H)> {
+    let mut nearest = None;
+
+    bvh.traverse_ray(ray, max_distance, |item, max_distance| {
+        let (distance, hit) = intersect(item, max_distance)?;
+        nearest = Some((item, distance, hit));
+        Some(distance)
+    });
+
+    nearest
+}
+
+/// Which meshes a ray cast considers, and how far.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaycastOptions {
+    pub max_distance: f32,
+    /// Test skinned meshes in their current pose rather than the bind pose.
+    ///
+    /// Costs a joint matrix blend per vertex tested. Skinned meshes are then tested without
+    /// the BVH, since their bounds are the bind pose's.
+    pub skinning: bool,
+    pub include_hidden: bool,
+    pub include_hud: bool,
+}
+
+impl Default for RaycastOptions {
+    fn default() -> Self {
+        Self {
+            max_distance: f32::INFINITY,
+            skinning: false,
+            include_hidden: false,
+            include_hud: false,
+        }
+    }
+}
+
+impl RaycastOptions {
+    fn considers(&self, mesh: &Mesh) -> bool {
+        (self.include_hidden || !mesh.hidden) && (self.include_hud || !mesh.hud)
+    }
+}
+
+/// A ray cast hit on a mesh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaycastHit {
+    pub mesh_key: MeshKey,
+    /// Distance along the ray, in world units.
+    pub distance: f32,
+    /// World-space position of the hit.
+    pub position: Vec3,
+    /// World-space face normal, facing back along the ray.
+    pub normal: Vec3,
+    /// Weights of the triangle's corners at the hit.
+    pub barycentrics: Vec3,
+    pub triangle_index: u32,
+    /// The instance that was hit, for instanced meshes.
+    pub instance_index: Option<usize>,
+}
+
+impl AwsmRenderer {
+    /// Casts a ray against the scene and returns the nearest hit.
+    ///
+    /// Builds a [`Bvh`] over the current world bounds. To cast many rays against the same
+    /// scene, build it once with [`Self::build_raycast_bvh`] and use [`Self::raycast_with_bvh`].
+    ///
+    /// Positions are the undeformed ones (morph targets aren't applied), skinning is opt-in
+    /// through [`RaycastOptions::skinning`].
+    pub fn raycast(&self, ray: &Ray, options: &RaycastOptions) -> Result<Option<RaycastHit>> {
+        self.raycast_with_bvh(&self.build_raycast_bvh(options), ray, options)
+    }
+
+    /// Builds a [`Bvh`] over the world bounds of the meshes a ray cast with these options
+    /// considers.
+    ///
+    /// It's a snapshot, rebuild it after meshes move.
+    pub fn build_raycast_bvh(&self, options: &RaycastOptions) -> Bvh<MeshKey> {
+        Bvh::new(
+            self.meshes
+                .iter()
+                .filter(|(mesh_key, mesh)| {
+                    options.considers(mesh) && !self.raycast_skinned(*mesh_key, options)
+                })
+                .filter_map(|(mesh_key, mesh)| Some((mesh_key, mesh.world_aabb.clone()?))),
+        )
+    }
+
+    /// Casts a ray against the meshes of a prebuilt [`Bvh`], see [`Self::raycast`].
+    pub fn raycast_with_bvh(
+        &self,
+        bvh: &Bvh<MeshKey>,
+        ray: &Ray,
+        options: &RaycastOptions,
+    ) -> Result<Option<RaycastHit>> {
+        let mut error = None;
+
+        let mut intersect = |mesh_key: MeshKey, max_distance: f32| match self.raycast_mesh(
+            mesh_key,
+            ray,
+            max_distance,
+            options,
+        ) {
+            Ok(hit) => hit,
+            Err(err) => {
+                error.get_or_insert(err);
+                None
+            }
+        };
+
+        let mut nearest = raycast_bvh(bvh, ray, options.max_distance, &mut intersect);
+
+        // their bounds don't follow the pose, so they're all tested
+        if options.skinning {
+            for (mesh_key, mesh) in self.meshes.iter() {
+                if !options.considers(mesh) || !self.raycast_skinned(mesh_key, options) {
+                    continue;
+                }
+
+                let max_distance = nearest
+                    .as_ref()
+                    .map_or(options.max_distance, |(_, distance, _)| *distance);
+
+                if let Some((distance, hit)) = intersect(mesh_key, max_distance) {
+                    nearest = Some((mesh_key, distance, hit));
+                }
+            }
+        }
+
+        if let Some(err) = error {
+            return Err(err);
+        }
+
+        Ok(
+            nearest.map(|(mesh_key, distance, (hit, instance_index))| RaycastHit {
+                mesh_key,
+                distance,
+                position: ray.at(distance),
+                normal: hit.normal,
+                barycentrics: hit.barycentrics,
+                triangle_index: hit.triangle_index,
+                instance_index,
+            }),
+        )
+    }
+
+    fn raycast_skinned(&self, mesh_key: MeshKey, options: &RaycastOptions) -> bool {
+        options.skinning
+            && self
+                .meshes
+                .resource(mesh_key)
+                .is_ok_and(|resource| resource.skin_key.is_some())
+    }
+
+    // Nearest hit on one mesh, across its instances
+    fn raycast_mesh(
+        &self,
+        mesh_key: MeshKey,
+        ray: &Ray,
+        max_distance: f32,
+        options: &RaycastOptions,
+    ) -> Result<Option<(f32, (TriangleHit, Option<usize>))>> {
+        let mesh = self.meshes.get(mesh_key)?;
+        let resource = self.meshes.resource(mesh_key)?;
+        let mesh_world = *self.transforms.get_world(mesh.transform_key)?;
+
+        let skin_key = resource.skin_key.filter(|_| options.skinning);
+
+        // Read the triangles once, they're shared by every instance
+        let mut triangles = Vec::new();
+        self.meshes
+            .for_each_triangle(mesh_key, |triangle_index, positions, vertex_indices| {
+                let positions = match skin_key {
+                    Some(skin_key) => {
+                        let mut skinned = positions;
+                        for (position, vertex_index) in skinned.iter_mut().zip(vertex_indices) {
+                            if let Some(skin_matrix) =
+                                self.meshes.skins.vertex_skin_matrix(skin_key, vertex_index)
+                            {
+                                *position = skin_matrix.transform_point3(*position);
+                            }
+                        }
+                        skinned
+                    }
+                    None => positions,
+                };
+                triangles.push((triangle_index, positions));
+            })?;
+
+        let instance_transforms = if mesh.instanced {
+            self.instances.transform_list(mesh.transform_key)
+        } else {
+            None
+        };
+
+        let hit = match instance_transforms {
+            Some(instance_transforms) => {
+                let mut nearest: Option<(TriangleHit, Option<usize>)> = None;
+
+                for (index, instance_transform) in instance_transforms.iter().enumerate() {
+                    let world = mesh_world * instance_transform.to_matrix();
+                    let max_distance = nearest.map_or(max_distance, |(hit, _)| hit.distance);
+
+                    // the mesh's bounds are the union of all instances, check this one's
+                    let instance_missed = skin_key.is_none()
+                        && resource.aabb.as_ref().is_some_and(|aabb| {
+                            !ray.intersect_aabb(&aabb.transformed(&world))
+                                .is_some_and(|entry| entry <= max_distance)
+                        });
+                    if instance_missed {
+                        continue;
+                    }
+
+                    if let Some(hit) =
+                        intersect_triangles(ray, world, max_distance, triangles.iter().copied())
+                    {
+                        nearest = Some((hit, Some(index)));
+                    }
+                }
+
+                nearest
+            }
+            None => {
+                intersect_triangles(ray, mesh_world, max_distance, triangles).map(|hit| (hit, None))
+            }
+        };
+
+        Ok(hit.map(|hit| (hit.0.distance, hit)))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,179 @@
+//! Bounding volume hierarchy over world-space AABBs.
+
+use glam::Vec3;
+
+use crate::bounds::Aabb;
+
+use super::Ray;
+
+// small leaves, the triangle tests behind each item are what's expensive
+const MAX_LEAF_ITEMS: usize = 4;
+
+/// A bounding volume hierarchy over items with world-space bounds.
+///
+/// Built once from a snapshot of the bounds, rebuild it when they change.
+#[derive(Debug, Clone)]
+pub struct Bvh<T> {
+    nodes: Vec<BvhNode>,
+    items: Vec<(T, Aabb)>,
+}
+
+#[derive(Debug, Clone)]
+struct BvhNode {
+    aabb: Aabb,
+    kind: BvhNodeKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BvhNodeKind {
+    // a range of `items`
+    Leaf { start: usize, end: usize },
+    // indices into `nodes`
+    Branch { left: usize, right: usize },
+}
+
+impl<T: Copy> Bvh<T> {
+    /// Builds a hierarchy by splitting on the longest axis at the median centroid.
+    pub fn new(items: impl IntoIterator<Item = (T, Aabb)>) -> Self {
+        let mut bvh = Self {
+            nodes: Vec::new(),
+            items: items.into_iter().collect(),
+        };
+
+        if !bvh.items.is_empty() {
+            bvh.build(0, bvh.items.len());
+        }
+
+        bvh
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // Builds the node for items[start..end] and returns its index
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let mut aabb = self.items[start].1.clone();
+        for (_, item_aabb) in &self.items[start + 1..end] {
+            aabb.extend(item_aabb);
+        }
+
+        let index = self.nodes.len();
+
+        if end - start <= MAX_LEAF_ITEMS {
+            self.nodes.push(BvhNode {
+                aabb,
+                kind: BvhNodeKind::Leaf { start, end },
+            });
+            return index;
+        }
+
+        let size = aabb.size();
+        let axis = if size.x >= size.y && size.x >= size.z {
+            0
+        } else if size.y >= size.z {
+            1
+        } else {
+            2
+        };
+
+        let centroid = |aabb: &Aabb| aabb.center()[axis];
+        let mid = start + (end - start) / 2;
+        self.items[start..end].select_nth_unstable_by(mid - start, |(_, a), (_, b)| {
+            centroid(a).total_cmp(&centroid(b))
+        });
+
+        // placeholder until the children exist
+        self.nodes.push(BvhNode {
+            aabb,
+            kind: BvhNodeKind::Leaf { start, end },
+        });
+
+        let left = self.build(start, mid);
+        let right = self.build(mid, end);
+        self.nodes[index].kind = BvhNodeKind::Branch { left, right };
+
+        index
+    }
+
+    /// Visits the items whose bounds the ray enters within `max_distance`, roughly nearest first.
+    ///
+    /// `visit` gets the item and the current max distance, and returns the distance of anything
+    /// it hit, which shrinks the max distance so further-away items are skipped.
+    pub fn traverse_ray(
+        &self,
+        ray: &Ray,
+        mut max_distance: f32,
+        mut visit: impl FnMut(T, f32) -> Option<f32>,
+    ) {
+        if self.nodes.is_empty() {
+            return;
+        }
+
+        let inv_direction = Vec3::ONE / ray.direction;
+
+        let Some(root_entry) = ray.intersect_aabb_inv(&self.nodes[0].aabb, inv_direction) else {
+            return;
+        };
+
+        let mut stack = vec![(0, root_entry)];
+
+        while let Some((node_index, entry)) = stack.pop() {
+            // something nearer was hit since this was pushed
+            if entry > max_distance {
+                continue;
+            }
+
+            match self.nodes[node_index].kind {
+                BvhNodeKind::Leaf { start, end } => {
+                    let mut entered: Vec<(f32, T)> = self.items[start..end]
+                        .iter()
+                        .filter_map(|(item, aabb)| {
+                            ray.intersect_aabb_inv(aabb, inv_direction)
+                                .filter(|entry| *entry <= max_distance)
+                                .map(|entry| (entry, *item))
+                        })
+                        .collect();
+                    entered.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+                    for (entry, item) in entered {
+                        if entry > max_distance {
+                            break;
+                        }
+
+                        if let Some(distance) = visit(item, max_distance) {
+                            max_distance = max_distance.min(distance);
+                        }
+                    }
+                }
+                BvhNodeKind::Branch { left, right } => {
+                    let child = |index: usize| {
+                        ray.intersect_aabb_inv(&self.nodes[index].aabb, inv_direction)
+                            .filter(|entry| *entry <= max_distance)
+                            .map(|entry| (index, entry))
+                    };
+
+                    // the nearer child is popped first
+                    match (child(left), child(right)) {
+                        (Some(a), Some(b)) => {
+                            let (near, far) = if a.1 <= b.1 { (a, b) } else { (b, a) };
+                            stack.push(far);
+                            stack.push(near);
+                        }
+                        (Some(only), None) | (None, Some(only)) => stack.push(only),
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
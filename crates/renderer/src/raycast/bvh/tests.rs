@@ -0,0 +1,77 @@
+use super::*;
+
+fn unit_box_at(center: Vec3) -> Aabb {
+    Aabb::new(center - Vec3::splat(0.5), center + Vec3::splat(0.5))
+}
+
+// a row of boxes along -z, far enough apart to split into several nodes
+fn row(count: usize) -> Bvh<usize> {
+    Bvh::new((0..count).map(|index| {
+        (
+            index,
+            unit_box_at(Vec3::new(0.0, 0.0, -(index as f32) * 3.0)),
+        )
+    }))
+}
+
+#[test]
+fn empty_bvh_visits_nothing() {
+    let bvh = Bvh::<usize>::new([]);
+    assert!(bvh.is_empty());
+
+    let ray = Ray::new(Vec3::ZERO, -Vec3::Z);
+    bvh.traverse_ray(&ray, f32::INFINITY, |_, _| panic!("nothing to visit"));
+}
+
+#[test]
+fn traversal_only_visits_what_the_ray_enters() {
+    let mut items: Vec<_> = (0..20)
+        .map(|index| (index, unit_box_at(Vec3::new(index as f32 * 2.0, 0.0, 0.0))))
+        .collect();
+    // one box off to the side of the ray
+    items.push((100, unit_box_at(Vec3::new(10.0, 5.0, 0.0))));
+    let bvh = Bvh::new(items);
+    assert_eq!(bvh.len(), 21);
+
+    // straight down onto box 5
+    let ray = Ray::new(Vec3::new(10.0, 0.0, 10.0), -Vec3::Z);
+    let mut visited = Vec::new();
+    bvh.traverse_ray(&ray, f32::INFINITY, |item, _| {
+        visited.push(item);
+        None
+    });
+
+    assert_eq!(visited, vec![5]);
+}
+
+#[test]
+fn traversal_prunes_behind_hits() {
+    let bvh = row(32);
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 10.0), -Vec3::Z);
+
+    // without hits, every box along the ray is visited
+    let mut visited = Vec::new();
+    bvh.traverse_ray(&ray, f32::INFINITY, |item, _| {
+        visited.push(item);
+        None
+    });
+    visited.sort();
+    assert_eq!(visited, (0..32).collect::<Vec<_>>());
+
+    // hitting the nearest box first skips everything behind it
+    let mut visited = Vec::new();
+    bvh.traverse_ray(&ray, f32::INFINITY, |item, _| {
+        visited.push(item);
+        Some(9.5 + item as f32 * 3.0)
+    });
+    assert_eq!(visited, vec![0]);
+
+    // and the max distance limits the reach
+    let mut visited = Vec::new();
+    bvh.traverse_ray(&ray, 16.0, |item, _| {
+        visited.push(item);
+        None
+    });
+    visited.sort();
+    assert_eq!(visited, vec![0, 1, 2]);
+}
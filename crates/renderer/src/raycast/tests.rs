@@ -0,0 +1,147 @@
+use super::*;
+
+const EPSILON: f32 = 1e-4;
+
+fn triangle() -> [Vec3; 3] {
+    [
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    ]
+}
+
+#[test]
+fn ray_enters_aabb() {
+    let aabb = Aabb::new(Vec3::splat(-1.0), Vec3::splat(1.0));
+
+    let toward = Ray::new(Vec3::new(0.0, 0.0, 5.0), -Vec3::Z);
+    assert!((toward.intersect_aabb(&aabb).unwrap() - 4.0).abs() < EPSILON);
+
+    let away = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::Z);
+    assert_eq!(away.intersect_aabb(&aabb), None);
+
+    let beside = Ray::new(Vec3::new(2.0, 0.0, 5.0), -Vec3::Z);
+    assert_eq!(beside.intersect_aabb(&aabb), None);
+
+    // starting inside enters right away
+    let inside = Ray::new(Vec3::ZERO, Vec3::new(1.0, 1.0, 0.0));
+    assert_eq!(inside.intersect_aabb(&aabb), Some(0.0));
+}
+
+#[test]
+fn ray_hits_triangle_with_barycentrics() {
+    let ray = Ray::new(Vec3::new(0.25, 0.5, 2.0), -Vec3::Z);
+    let (distance, barycentrics) = ray.intersect_triangle(triangle()).unwrap();
+
+    assert!((distance - 2.0).abs() < EPSILON);
+    assert!(barycentrics.distance(Vec3::new(0.25, 0.25, 0.5)) < EPSILON);
+
+    // the weights reconstruct the hit point
+    let [a, b, c] = triangle();
+    let point = a * barycentrics.x + b * barycentrics.y + c * barycentrics.z;
+    assert!(point.distance(ray.at(distance)) < EPSILON);
+
+    // back faces are hit too
+    let from_behind = Ray::new(Vec3::new(0.25, 0.25, -2.0), Vec3::Z);
+    assert!(from_behind.intersect_triangle(triangle()).is_some());
+}
+
+#[test]
+fn ray_misses_triangle() {
+    // outside the edges
+    let outside = Ray::new(Vec3::new(0.75, 0.75, 2.0), -Vec3::Z);
+    assert_eq!(outside.intersect_triangle(triangle()), None);
+
+    // behind the origin
+    let behind = Ray::new(Vec3::new(0.25, 0.25, 2.0), Vec3::Z);
+    assert_eq!(behind.intersect_triangle(triangle()), None);
+
+    // parallel to the plane
+    let parallel = Ray::new(Vec3::new(-1.0, 0.25, 0.0), Vec3::X);
+    assert_eq!(parallel.intersect_triangle(triangle()), None);
+}
+
+#[test]
+fn intersect_triangles_in_world_space() {
+    // scaled up 2x on x and moved back, so the triangle spans x 0..2 at z -3
+    let world = Mat4::from_translation(Vec3::new(0.0, 0.0, -3.0))
+        * Mat4::from_scale(Vec3::new(2.0, 1.0, 1.0));
+    let ray = Ray::new(Vec3::new(1.5, 0.25, 0.0), -Vec3::Z);
+
+    let hit = intersect_triangles(&ray, world, f32::INFINITY, [(7, triangle())]).unwrap();
+    assert_eq!(hit.triangle_index, 7);
+    // distances are world units, not object space ones
+    assert!((hit.distance - 3.0).abs() < EPSILON);
+    // the face normal faces back along the ray
+    assert!(hit.normal.distance(Vec3::Z) < EPSILON);
+
+    // out of reach
+    assert!(intersect_triangles(&ray, world, 2.0, [(7, triangle())]).is_none());
+
+    // flattened meshes can't be hit
+    let flat = Mat4::from_scale(Vec3::new(1.0, 1.0, 0.0));
+    assert!(intersect_triangles(&ray, flat, f32::INFINITY, [(7, triangle())]).is_none());
+}
+
+#[test]
+fn intersect_triangles_returns_the_nearest() {
+    let near = triangle().map(|p| p + Vec3::Z);
+    let far = triangle();
+    let ray = Ray::new(Vec3::new(0.25, 0.25, 5.0), -Vec3::Z);
+
+    let hit =
+        intersect_triangles(&ray, Mat4::IDENTITY, f32::INFINITY, [(0, far), (1, near)]).unwrap();
+    assert_eq!(hit.triangle_index, 1);
+    assert!((hit.distance - 4.0).abs() < EPSILON);
+}
+
+#[test]
+fn raycast_bvh_finds_the_nearest_instance() {
+    // (mesh, instance world transforms), like an instanced mesh
+    let meshes = [
+        vec![
+            Mat4::from_translation(Vec3::new(0.0, 0.0, -10.0)),
+            Mat4::from_translation(Vec3::new(0.0, 0.0, -4.0)),
+        ],
+        vec![Mat4::from_translation(Vec3::new(5.0, 0.0, -2.0))],
+        vec![Mat4::from_translation(Vec3::new(0.0, 0.0, -7.0))],
+    ];
+    let local_aabb = Aabb::new(Vec3::ZERO, Vec3::new(1.0, 1.0, 0.0));
+
+    let bvh = Bvh::new(meshes.iter().enumerate().flat_map(|(mesh, instances)| {
+        instances
+            .iter()
+            .enumerate()
+            .map(move |(instance, world)| ((mesh, instance), local_aabb.transformed(world)))
+    }));
+
+    let ray = Ray::new(Vec3::new(0.25, 0.25, 0.0), -Vec3::Z);
+    let (item, distance, hit) = raycast_bvh(&bvh, &ray, f32::INFINITY, |(mesh, instance), max| {
+        let hit = intersect_triangles(&ray, meshes[mesh][instance], max, [(0, triangle())])?;
+        Some((hit.distance, hit))
+    })
+    .unwrap();
+
+    assert_eq!(item, (0, 1));
+    assert!((distance - 4.0).abs() < EPSILON);
+    assert_eq!(hit.triangle_index, 0);
+
+    // nothing within reach
+    assert!(raycast_bvh(&bvh, &ray, 3.0, |_, _| Some((0.0, ()))).is_none());
+}
+
+#[test]
+fn screen_ray_goes_through_the_pixel() {
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::Y);
+    let projection = Mat4::perspective_rh(60.0_f32.to_radians(), 2.0, 0.1, 100.0);
+    let inv_view_projection = (projection * view).inverse();
+
+    // the center of the screen looks straight at the target
+    let center = Ray::from_screen(inv_view_projection, 100.0, 50.0, 200, 100);
+    assert!(center.direction.distance(-Vec3::Z) < EPSILON);
+    assert!((center.origin.z - 4.9).abs() < 1e-3);
+
+    // the top-left corner looks up and to the left
+    let corner = Ray::from_screen(inv_view_projection, 0.0, 0.0, 200, 100);
+    assert!(corner.direction.x < 0.0 && corner.direction.y > 0.0);
+}
@@ -2,13 +2,14 @@
 
 use awsm_renderer_core::command::{
     color::Color,
+    copy_texture::{Origin3d, TexelCopyTextureInfo},
     render_pass::{
         ColorAttachment, DepthStencilAttachment, RenderPassDescriptor, RenderPassEncoder,
     },
     CommandEncoder, LoadOp, StoreOp,
 };
 use awsm_renderer_core::renderer::AwsmRendererWebGpu;
-use awsm_renderer_core::texture::blit::blit_tex;
+use awsm_renderer_core::texture::{blit::blit_tex, mipmap::encode_layer_mipmaps, Extent3d};
 
 use crate::anti_alias::AntiAliasing;
 use crate::bind_groups::{BindGroupCreate, BindGroupRecreateContext, BindGroups};
@@ -21,6 +22,10 @@ use crate::post_process::PostProcessing;
 use crate::render_passes::RenderPasses;
 use crate::render_textures::{RenderTextureViews, RenderTextures};
 use crate::transforms::Transforms;
+use crate::views::{
+    plan_render_view_frames, AwsmRenderViewError, RenderView, RenderViewKey, RenderViewOutput,
+    RenderViewState,
+};
 use crate::{AwsmRenderer, AwsmRendererLogging};
 
 /// Optional callbacks around render passes.
@@ -51,6 +56,27 @@ impl AwsmRenderer {
     // or just call .update_all() right before .render() for convenience
     /// Executes a full render with optional hooks.
     pub fn render(&mut self, hooks: Option<&RenderHooks>) -> Result<()> {
+        self.render_views(&[RenderView::main()], hooks)
+    }
+
+    /// Renders several views in one frame, in list order.
+    ///
+    /// Scene data is written to the GPU once, then each view is rendered and submitted with
+    /// its own camera, render textures and post-processing. Canvas views draw over what
+    /// earlier views drew, and an offscreen view shows up in materials the same frame if it's
+    /// listed before the views that see it, otherwise a frame later.
+    ///
+    /// `pre_render` and `post_render` run once, the other hooks run for every view
+    /// (see [`RenderContext::view`]).
+    ///
+    /// Shadow maps are rendered once, with the first view drawn, and every later view samples
+    /// those. Directional cascades are fitted to the main camera (as of the previous frame),
+    /// not to each view's camera, so views looking elsewhere may fall outside them.
+    pub fn render_views(
+        &mut self,
+        views: &[RenderView],
+        hooks: Option<&RenderHooks>,
+    ) -> Result<()> {
         if let Some(hook) = hooks.and_then(|h| h.pre_render.as_ref()) {
             {
                 let _maybe_span_guard = if self.logging.render_timings {
@@ -74,8 +100,13 @@ impl AwsmRenderer {
             .write_gpu(&self.logging, &self.gpu, &mut self.bind_groups)?;
         self.materials
             .write_gpu(&self.logging, &self.gpu, &mut self.bind_groups)?;
-        self.lights
-            .write_gpu(&self.logging, &self.gpu, &mut self.bind_groups)?;
+        self.lights.write_gpu(
+            &self.logging,
+            &self.gpu,
+            &self.transforms,
+            self.camera.last_matrices.as_ref(),
+            &mut self.bind_groups,
+        )?;
         self.instances.write_gpu(&self.logging, &self.gpu)?;
         self.meshes
             .skins
@@ -93,36 +124,166 @@ impl AwsmRenderer {
         )?;
         self.meshes
             .write_gpu(&self.logging, &self.gpu, &mut self.bind_groups)?;
-        self.camera
-            .write_gpu(&self.logging, &self.gpu, &self.bind_groups)?;
 
-        let render_texture_views = self
-            .render_textures
-            .views(&self.gpu, self.anti_aliasing.clone())?;
+        let canvas_size = self.gpu.current_context_texture_size()?;
+
+        let targets = views
+            .iter()
+            .map(|view| view.key.map(|key| self.views.target(key)).transpose())
+            .collect::<std::result::Result<Vec<_>, AwsmRenderViewError>>()?;
+        let plan = plan_render_view_frames(&targets, canvas_size);
+
+        // one buffer big enough for every view, rather than reallocating between them
+        let (tiles_width, tiles_height) = plan.tiles_size;
+        self.lights
+            .tiles
+            .resize(&self.gpu, tiles_width, tiles_height, &mut self.bind_groups)?;
+
+        for (view, frame) in views.iter().zip(plan.frames) {
+            let Some(frame) = frame else {
+                continue;
+            };
+
+            let output = match view.key.map(|key| self.views.get(key)).transpose()? {
+                Some(RenderViewState {
+                    texture: Some(texture),
+                    ..
+                }) if frame.offscreen => RenderViewOutput::Texture(texture.view.clone()),
+                _ => RenderViewOutput::Canvas {
+                    viewport: frame.viewport,
+                    clear: frame.clear_canvas,
+                },
+            };
+
+            if let Some(key) = view.key {
+                self.swap_render_view(key)?;
+            }
+
+            let result = self.render_view(
+                view,
+                frame.size,
+                output,
+                frame.render_shadows,
+                views.len() > 1,
+                hooks,
+            );
+
+            if let Some(key) = view.key {
+                self.swap_render_view(key)?;
+            }
 
-        if render_texture_views.size_changed {
-            self.bind_groups
-                .mark_create(BindGroupCreate::TextureViewRecreate);
+            result?;
         }
 
-        self.bind_groups.recreate(
-            BindGroupRecreateContext {
-                gpu: &self.gpu,
-                render_texture_views: &render_texture_views,
-                textures: &self.textures,
-                materials: &self.materials,
-                bind_group_layouts: &mut self.bind_group_layouts,
-                meshes: &self.meshes,
-                camera: &self.camera,
-                environment: &self.environment,
-                lights: &self.lights,
-                transforms: &self.transforms,
-                anti_aliasing: &self.anti_aliasing,
-            },
-            &mut self.render_passes,
-            &mut self.picker,
+        if self.views.bound.is_some() {
+            // between frames (picking, readbacks) everything should see the main view
+            if let Some(render_texture_views) = self.render_textures.current_views() {
+                self.bind_groups
+                    .mark_create(BindGroupCreate::TextureViewRecreate);
+                self.recreate_bind_groups(&render_texture_views)?;
+                self.views.bound = None;
+            }
+        }
+
+        if plan.restore_main_camera {
+            // the main view's camera state is already swapped back, but not in the GPU buffer
+            self.camera.write_gpu(
+                &self.logging,
+                &self.gpu,
+                &self.bind_groups,
+                &self.anti_aliasing,
+                self.render_textures.frame_count(),
+            )?;
+        }
+
+        if views.len() > 1 {
+            // the SSAO settings buffer is shared, the main view expects its own in there
+            if let Some(ssao) = &self.post_processing.ssao {
+                self.render_passes
+                    .ssao
+                    .bind_groups
+                    .write_settings(&self.gpu, ssao)?;
+            }
+        }
+
+        if self.anti_aliasing.taa {
+            // after every read this frame, so the next one sees these as the previous values
+            let command_encoder = self.gpu.create_command_encoder(Some("Previous Frame Copy"));
+            self.transforms.copy_to_previous(&command_encoder)?;
+            self.meshes.skins.copy_to_previous(&command_encoder)?;
+            self.meshes
+                .morphs
+                .geometry
+                .copy_to_previous(&command_encoder)?;
+            self.gpu.submit_commands(&command_encoder.finish());
+        }
+
+        if let Some(hook) = hooks.and_then(|h| h.post_render.as_ref()) {
+            {
+                let _maybe_span_guard = if self.logging.render_timings {
+                    Some(tracing::span!(tracing::Level::INFO, "PostRender Hook").entered())
+                } else {
+                    None
+                };
+                hook(self)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Renders one view with its state already swapped in, and submits it
+    fn render_view(
+        &mut self,
+        view: &RenderView,
+        size: (u32, u32),
+        output: RenderViewOutput,
+        render_shadows: bool,
+        shared_frame: bool,
+        hooks: Option<&RenderHooks>,
+    ) -> Result<()> {
+        let _maybe_span_guard = if self.logging.render_timings {
+            Some(tracing::span!(tracing::Level::INFO, "View").entered())
+        } else {
+            None
+        };
+
+        if let Some(camera) = &view.camera {
+            self.camera
+                .update(camera.clone(), size.0 as f32, size.1 as f32)?;
+        }
+
+        self.camera.write_gpu(
+            &self.logging,
+            &self.gpu,
+            &self.bind_groups,
+            &self.anti_aliasing,
+            self.render_textures.frame_count(),
         )?;
 
+        if shared_frame {
+            if let Some(ssao) = &self.post_processing.ssao {
+                self.render_passes
+                    .ssao
+                    .bind_groups
+                    .write_settings(&self.gpu, ssao)?;
+            }
+        }
+
+        let render_texture_views =
+            self.render_textures
+                .views_sized(&self.gpu, self.anti_aliasing.clone(), size)?;
+
+        if render_texture_views.size_changed || self.views.bound != view.key {
+            // picking only ever reads the main view
+            self.bind_groups.mark_create(match view.key {
+                None => BindGroupCreate::TextureViewRecreate,
+                Some(_) => BindGroupCreate::RenderViewChange,
+            });
+            self.views.bound = view.key;
+        }
+
+        self.recreate_bind_groups(&render_texture_views)?;
+
         let ctx = RenderContext {
             gpu: &self.gpu,
             command_encoder: self.gpu.create_command_encoder(Some("Rendering")),
@@ -139,6 +300,8 @@ impl AwsmRenderer {
             anti_aliasing: &self.anti_aliasing,
             post_processing: &self.post_processing,
             clear_color: &self._clear_color,
+            view: view.key,
+            output,
         };
 
         let renderables = self.collect_renderables(&ctx)?;
@@ -154,6 +317,18 @@ impl AwsmRenderer {
             }
         }
 
+        if render_shadows {
+            let _maybe_span_guard = if self.logging.render_timings {
+                Some(tracing::span!(tracing::Level::INFO, "Shadow RenderPass").entered())
+            } else {
+                None
+            };
+
+            self.render_passes
+                .shadow
+                .render(&ctx, &self.lights.shadows)?;
+        }
+
         {
             let _maybe_span_guard = if self.logging.render_timings {
                 Some(tracing::span!(tracing::Level::INFO, "Geometry RenderPass").entered())
@@ -189,6 +364,16 @@ impl AwsmRenderer {
             }
         }
 
+        if view.key.is_none() {
+            let _maybe_span_guard = if self.logging.render_timings {
+                Some(tracing::span!(tracing::Level::INFO, "Picker Opaque Depth Copy").entered())
+            } else {
+                None
+            };
+
+            self.picker.copy_opaque_depth(&ctx)?;
+        }
+
         {
             let _maybe_span_guard = if self.logging.render_timings {
                 Some(tracing::span!(tracing::Level::INFO, "Light Culling RenderPass").entered())
@@ -199,6 +384,16 @@ impl AwsmRenderer {
             self.render_passes.light_culling.render(&ctx)?;
         }
 
+        {
+            let _maybe_span_guard = if self.logging.render_timings {
+                Some(tracing::span!(tracing::Level::INFO, "SSAO RenderPass").entered())
+            } else {
+                None
+            };
+
+            self.render_passes.ssao.render(&ctx)?;
+        }
+
         {
             let _maybe_span_guard = if self.logging.render_timings {
                 Some(tracing::span!(tracing::Level::INFO, "Clear opaque").entered())
@@ -336,6 +531,16 @@ impl AwsmRenderer {
             )?;
         }
 
+        {
+            let _maybe_span_guard = if self.logging.render_timings {
+                Some(tracing::span!(tracing::Level::INFO, "TAA RenderPass").entered())
+            } else {
+                None
+            };
+
+            self.render_passes.taa.render(&ctx)?;
+        }
+
         {
             let _maybe_span_guard = if self.logging.render_timings {
                 Some(tracing::span!(tracing::Level::INFO, "Effects RenderPass").entered())
@@ -367,18 +572,83 @@ impl AwsmRenderer {
             }
         }
 
+        if let Some(key) = view.key {
+            let _maybe_span_guard = if self.logging.render_timings {
+                Some(tracing::span!(tracing::Level::INFO, "Render View Texture Copy").entered())
+            } else {
+                None
+            };
+
+            self.copy_render_view_texture(key, &ctx.command_encoder)?;
+        }
+
         self.gpu.submit_commands(&ctx.command_encoder.finish());
 
-        if let Some(hook) = hooks.and_then(|h| h.post_render.as_ref()) {
-            {
-                let _maybe_span_guard = if self.logging.render_timings {
-                    Some(tracing::span!(tracing::Level::INFO, "PostRender Hook").entered())
-                } else {
-                    None
-                };
-                hook(self)?;
-            }
+        Ok(())
+    }
+
+    fn recreate_bind_groups(&mut self, render_texture_views: &RenderTextureViews) -> Result<()> {
+        self.bind_groups.recreate(
+            BindGroupRecreateContext {
+                gpu: &self.gpu,
+                render_texture_views,
+                textures: &self.textures,
+                materials: &self.materials,
+                bind_group_layouts: &mut self.bind_group_layouts,
+                meshes: &self.meshes,
+                camera: &self.camera,
+                environment: &self.environment,
+                lights: &self.lights,
+                transforms: &self.transforms,
+                anti_aliasing: &self.anti_aliasing,
+            },
+            &mut self.render_passes,
+            &mut self.picker,
+        )
+    }
+
+    // Copies an offscreen view into its pooled texture and refreshes the mips
+    fn copy_render_view_texture(
+        &self,
+        key: RenderViewKey,
+        command_encoder: &CommandEncoder,
+    ) -> Result<()> {
+        let Some(texture) = &self.views.get(key)?.texture else {
+            return Ok(());
+        };
+
+        let entry = self.textures.get_entry(texture.texture_key)?;
+        let Some(array) = self.textures.pool.array_by_index(entry.array_index) else {
+            return Ok(());
+        };
+
+        // a pending upload replaces the pool texture anyway, and it may not have this layer yet
+        if array.gpu_dirty {
+            return Ok(());
         }
+
+        let Some(pool_texture) = &array.gpu_texture else {
+            return Ok(());
+        };
+
+        command_encoder.copy_texture_to_texture(
+            &TexelCopyTextureInfo::new(&texture.texture).into(),
+            &TexelCopyTextureInfo::new(pool_texture)
+                .with_mip_level(0)
+                .with_origin(Origin3d::new().with_z(entry.layer_index as u32))
+                .into(),
+            &Extent3d::new(array.width, Some(array.height), Some(1)).into(),
+        )?;
+
+        encode_layer_mipmaps(
+            &self.gpu,
+            command_encoder,
+            pool_texture,
+            entry.color.mipmap_kind,
+            entry.layer_index as u32,
+            array.mipmap_levels(),
+        )?;
+
         Ok(())
     }
 }
@@ -400,6 +670,10 @@ pub struct RenderContext<'a> {
     pub anti_aliasing: &'a AntiAliasing,
     pub post_processing: &'a PostProcessing,
     pub clear_color: &'a Color,
+    /// The view being rendered, `None` for the main view.
+    pub view: Option<RenderViewKey>,
+    /// Where the display pass writes this view.
+    pub output: RenderViewOutput,
 }
 
 impl<'a> RenderContext<'a> {
@@ -407,36 +681,48 @@ impl<'a> RenderContext<'a> {
     ///
     /// This pass loads the existing visibility attachments and world depth, allowing custom hooks
     /// to append opaque geometry before light culling/material-opaque shading.
+    ///
+    /// When TAA is enabled, the motion vector texture is a fifth attachment, so pipelines used
+    /// here must write it too.
     pub fn begin_world_geometry_extension_pass(
         &'a self,
         label: Option<&'a str>,
     ) -> Result<RenderPassEncoder> {
+        let mut color_attachments = vec![
+            ColorAttachment::new(
+                &self.render_texture_views.visibility_data,
+                LoadOp::Load,
+                StoreOp::Store,
+            ),
+            ColorAttachment::new(
+                &self.render_texture_views.barycentric,
+                LoadOp::Load,
+                StoreOp::Store,
+            ),
+            ColorAttachment::new(
+                &self.render_texture_views.normal_tangent,
+                LoadOp::Load,
+                StoreOp::Store,
+            ),
+            ColorAttachment::new(
+                &self.render_texture_views.barycentric_derivatives,
+                LoadOp::Load,
+                StoreOp::Store,
+            ),
+        ];
+        if let Some(taa) = &self.render_texture_views.taa {
+            color_attachments.push(ColorAttachment::new(
+                &taa.motion_vector,
+                LoadOp::Load,
+                StoreOp::Store,
+            ));
+        }
+
         self.command_encoder
             .begin_render_pass(
                 &RenderPassDescriptor {
                     label,
-                    color_attachments: vec![
-                        ColorAttachment::new(
-                            &self.render_texture_views.visibility_data,
-                            LoadOp::Load,
-                            StoreOp::Store,
-                        ),
-                        ColorAttachment::new(
-                            &self.render_texture_views.barycentric,
-                            LoadOp::Load,
-                            StoreOp::Store,
-                        ),
-                        ColorAttachment::new(
-                            &self.render_texture_views.normal_tangent,
-                            LoadOp::Load,
-                            StoreOp::Store,
-                        ),
-                        ColorAttachment::new(
-                            &self.render_texture_views.barycentric_derivatives,
-                            LoadOp::Load,
-                            StoreOp::Store,
-                        ),
-                    ],
+                    color_attachments,
                     depth_stencil_attachment: Some(
                         DepthStencilAttachment::new(&self.render_texture_views.depth)
                             .with_depth_load_op(LoadOp::Load)
@@ -520,27 +806,57 @@ impl<'a> RenderContext<'a> {
             .map_err(Into::into)
     }
 
-    /// Begins a pass that loads the already-rendered swapchain image.
+    /// Begins a pass that loads the already-rendered display output.
     ///
     /// This is intended for `RenderHooks::last_pass` overlays, where you want to draw on top of
-    /// the display output without clearing it.
+    /// the display output without clearing it. For canvas viewports the pass is limited to the
+    /// view's viewport.
     pub fn begin_display_overlay_pass(
         &'a self,
         label: Option<&'a str>,
     ) -> Result<RenderPassEncoder> {
-        self.command_encoder
-            .begin_render_pass(
-                &RenderPassDescriptor {
-                    label,
-                    color_attachments: vec![ColorAttachment::new(
-                        &self.gpu.current_context_texture_view()?,
-                        LoadOp::Load,
-                        StoreOp::Store,
-                    )],
-                    ..Default::default()
-                }
-                .into(),
-            )
-            .map_err(Into::into)
+        let render_pass = self.command_encoder.begin_render_pass(
+            &RenderPassDescriptor {
+                label,
+                color_attachments: vec![ColorAttachment::new(
+                    &self.output_texture_view()?,
+                    LoadOp::Load,
+                    StoreOp::Store,
+                )],
+                ..Default::default()
+            }
+            .into(),
+        )?;
+
+        self.set_output_viewport(&render_pass);
+
+        Ok(render_pass)
+    }
+
+    /// Returns the texture the display pass writes: the canvas, or an offscreen view's texture.
+    pub fn output_texture_view(&self) -> Result<web_sys::GpuTextureView> {
+        match &self.output {
+            RenderViewOutput::Canvas { .. } => Ok(self.gpu.current_context_texture_view()?),
+            RenderViewOutput::Texture(view) => Ok(view.clone()),
+        }
+    }
+
+    /// Limits a pass on the display output to this view's part of the canvas, if it has one.
+    pub fn set_output_viewport(&self, render_pass: &RenderPassEncoder) {
+        if let RenderViewOutput::Canvas {
+            viewport: Some(viewport),
+            ..
+        } = &self.output
+        {
+            render_pass.set_viewport(
+                viewport.x as f32,
+                viewport.y as f32,
+                viewport.width as f32,
+                viewport.height as f32,
+                0.0,
+                1.0,
+            );
+            render_pass.set_scissor_rect(viewport.x, viewport.y, viewport.width, viewport.height);
+        }
     }
 }
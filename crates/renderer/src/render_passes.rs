@@ -8,7 +8,10 @@ pub mod material_opaque;
 pub mod material_transparent;
 pub mod shader_cache_key;
 pub mod shader_template;
+pub mod shadow;
 pub mod shared;
+pub mod ssao;
+pub mod taa;
 
 use awsm_renderer_core::renderer::AwsmRendererWebGpu;
 
@@ -23,6 +26,8 @@ use crate::{
         light_culling::render_pass::LightCullingRenderPass,
         material_opaque::render_pass::MaterialOpaqueRenderPass,
         material_transparent::render_pass::MaterialTransparentRenderPass,
+        shadow::render_pass::ShadowRenderPass, ssao::render_pass::SsaoRenderPass,
+        taa::render_pass::TaaRenderPass,
     },
     render_textures::RenderTextureFormats,
     shaders::Shaders,
@@ -32,9 +37,12 @@ use crate::{
 /// Collection of render passes used by the renderer.
 pub struct RenderPasses {
     pub geometry: GeometryRenderPass,
+    pub shadow: ShadowRenderPass,
     pub light_culling: LightCullingRenderPass,
+    pub ssao: SsaoRenderPass,
     pub material_opaque: MaterialOpaqueRenderPass,
     pub material_transparent: MaterialTransparentRenderPass,
+    pub taa: TaaRenderPass,
     pub effects: EffectsRenderPass,
     pub display: DisplayRenderPass,
 }
@@ -42,11 +50,17 @@ pub struct RenderPasses {
 impl RenderPasses {
     /// Creates all render passes for the renderer.
     pub async fn new<'a>(ctx: &mut RenderPassInitContext<'a>) -> Result<Self> {
+        let geometry = GeometryRenderPass::new(ctx).await?;
+        let shadow = ShadowRenderPass::new(ctx, &geometry.bind_groups).await?;
+
         Ok(Self {
-            geometry: GeometryRenderPass::new(ctx).await?,
+            geometry,
+            shadow,
             light_culling: LightCullingRenderPass::new(ctx).await?,
+            ssao: SsaoRenderPass::new(ctx).await?,
             material_opaque: MaterialOpaqueRenderPass::new(ctx).await?,
             material_transparent: MaterialTransparentRenderPass::new(ctx).await?,
+            taa: TaaRenderPass::new(ctx).await?,
             effects: EffectsRenderPass::new(ctx).await?,
             display: DisplayRenderPass::new(ctx).await?,
         })
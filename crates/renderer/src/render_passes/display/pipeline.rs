@@ -3,6 +3,7 @@
 use awsm_renderer_core::{
     pipeline::{fragment::ColorTargetState, primitive::PrimitiveState},
     renderer::AwsmRendererWebGpu,
+    texture::TextureFormat,
 };
 
 use crate::{
@@ -22,9 +23,13 @@ use crate::{
 };
 
 /// Pipeline layout and render pipeline for the display pass.
+#[derive(Clone)]
 pub struct DisplayPipelines {
     pub pipeline_layout_key: PipelineLayoutKey,
     pub render_pipeline_key: Option<RenderPipelineKey>,
+    // None for the canvas, otherwise an offscreen texture of this format
+    // which is kept linear, since materials sample it like any other pooled texture
+    pub output_format: Option<TextureFormat>,
 }
 
 impl DisplayPipelines {
@@ -45,6 +50,7 @@ impl DisplayPipelines {
         Ok(Self {
             pipeline_layout_key,
             render_pipeline_key: None,
+            output_format: None,
         })
     }
 
@@ -60,12 +66,16 @@ impl DisplayPipelines {
     ) -> Result<()> {
         let shader_cache_key = ShaderCacheKeyDisplay {
             tonemapping: post_processing.tonemapping,
+            srgb_output: self.output_format.is_none(),
         };
         let shader_key = shaders.get_key(gpu, shader_cache_key).await?;
 
         let render_pipeline_cache_key =
             RenderPipelineCacheKey::new(shader_key, self.pipeline_layout_key)
-                .with_push_fragment_target(ColorTargetState::new(gpu.current_context_format()))
+                .with_push_fragment_target(ColorTargetState::new(
+                    self.output_format
+                        .unwrap_or_else(|| gpu.current_context_format()),
+                ))
                 .with_primitive(
                     PrimitiveState::new()
                         .with_topology(web_sys::GpuPrimitiveTopology::TriangleList)
@@ -14,6 +14,7 @@ use crate::{
         display::{bind_group::DisplayBindGroups, pipeline::DisplayPipelines},
         RenderPassInitContext,
     },
+    views::RenderViewOutput,
 };
 
 /// Display pass bind groups and pipelines.
@@ -35,13 +36,21 @@ impl DisplayRenderPass {
     }
 
     /// Executes the display render pass.
+    ///
+    /// Writes the view's output: the canvas (or its viewport), or an offscreen texture.
     pub fn render(&self, ctx: &RenderContext) -> Result<()> {
+        // later canvas views draw over the earlier ones
+        let load_op = match &ctx.output {
+            RenderViewOutput::Canvas { clear: false, .. } => LoadOp::Load,
+            _ => LoadOp::Clear,
+        };
+
         let render_pass = ctx.command_encoder.begin_render_pass(
             &RenderPassDescriptor {
                 label: Some("Display Render Pass"),
                 color_attachments: vec![ColorAttachment::new(
-                    &ctx.gpu.current_context_texture_view()?,
-                    LoadOp::Clear,
+                    &ctx.output_texture_view()?,
+                    load_op,
                     StoreOp::Store,
                 )
                 .with_clear_color(ctx.clear_color)],
@@ -50,6 +59,8 @@ impl DisplayRenderPass {
             .into(),
         )?;
 
+        ctx.set_output_viewport(&render_pass);
+
         render_pass.set_bind_group(0, self.bind_groups.get_bind_group()?, None)?;
 
         if let Some(pipeline_key) = self.pipelines.render_pipeline_key {
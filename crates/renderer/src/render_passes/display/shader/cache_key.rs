@@ -9,6 +9,8 @@ use crate::{
 #[derive(Hash, Debug, Clone, PartialEq, Eq)]
 pub struct ShaderCacheKeyDisplay {
     pub tonemapping: ToneMapping,
+    /// Encode to sRGB for the canvas, offscreen view textures stay linear.
+    pub srgb_output: bool,
 }
 
 impl From<ShaderCacheKeyDisplay> for ShaderCacheKey {
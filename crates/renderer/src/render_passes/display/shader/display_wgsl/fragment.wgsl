@@ -8,11 +8,13 @@
 
 struct FragmentInput {
     @builtin(position) full_screen_quad_position: vec4<f32>,
+    // 0..1 across the viewport, the position is in attachment pixels which a viewport offsets
+    @location(0) uv: vec2<f32>,
 }
 
 @fragment
 fn frag_main(in: FragmentInput) -> @location(0) vec4<f32> {
-    let coords = vec2<i32>(in.full_screen_quad_position.xy);
+    let coords = vec2<i32>(in.uv * vec2<f32>(textureDimensions(composite_texture)));
 
     var color: vec4<f32> = textureLoad(composite_texture, coords, 0);
 
@@ -26,5 +28,9 @@ fn frag_main(in: FragmentInput) -> @location(0) vec4<f32> {
             let rgb = color.rgb;
     {% endmatch %}
 
-    return vec4<f32>(linear_to_srgb(rgb), color.a);
+    {% if srgb_output %}
+        return vec4<f32>(linear_to_srgb(rgb), color.a);
+    {% else %}
+        return vec4<f32>(rgb, color.a);
+    {% endif %}
 }
@@ -74,11 +74,17 @@ impl EffectsBindGroups {
 
     /// Recreates bind groups for the current render textures.
     pub fn recreate(&mut self, ctx: &BindGroupRecreateContext<'_>) -> Result<()> {
+        // TAA runs before any of the effects
+        let input = match &ctx.render_texture_views.taa {
+            Some(taa) => &taa.resolve,
+            None => &ctx.render_texture_views.composite,
+        };
+
         let mut entries = Vec::new();
 
         entries.push(BindGroupEntry::new(
             entries.len() as u32,
-            BindGroupResource::TextureView(Cow::Borrowed(&ctx.render_texture_views.composite)),
+            BindGroupResource::TextureView(Cow::Borrowed(input)),
         ));
         entries.push(BindGroupEntry::new(
             entries.len() as u32,
@@ -114,7 +120,7 @@ impl EffectsBindGroups {
 
         entries.push(BindGroupEntry::new(
             entries.len() as u32,
-            BindGroupResource::TextureView(Cow::Borrowed(&ctx.render_texture_views.composite)),
+            BindGroupResource::TextureView(Cow::Borrowed(input)),
         ));
         entries.push(BindGroupEntry::new(
             entries.len() as u32,
@@ -156,7 +162,7 @@ fn bind_group_layout_cache_key(
 ) -> BindGroupLayoutCacheKey {
     BindGroupLayoutCacheKey {
         entries: vec![
-            // Composite texture
+            // Composite texture (or TAA resolve)
             BindGroupLayoutCacheKeyEntry {
                 resource: BindGroupLayoutResource::Texture(
                     TextureBindingLayout::new()
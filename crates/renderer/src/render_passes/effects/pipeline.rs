@@ -27,6 +27,7 @@ use crate::{
 pub const BLOOM_BLUR_PASSES: u32 = 3;
 
 /// Compute pipelines for post-processing effects.
+#[derive(Clone)]
 pub struct EffectsPipelines {
     multisampled_pipeline_layout_key: PipelineLayoutKey,
     singlesampled_pipeline_layout_key: PipelineLayoutKey,
@@ -126,6 +126,16 @@ impl GeometryBindGroupTransforms {
                     visibility_fragment: true,
                     visibility_compute: false,
                 },
+                // Previous transform (motion vectors)
+                BindGroupLayoutCacheKeyEntry {
+                    resource: BindGroupLayoutResource::Buffer(
+                        BufferBindingLayout::new()
+                            .with_binding_type(BufferBindingType::ReadOnlyStorage),
+                    ),
+                    visibility_vertex: true,
+                    visibility_fragment: false,
+                    visibility_compute: false,
+                },
             ],
         };
 
@@ -144,10 +154,18 @@ impl GeometryBindGroupTransforms {
         let descriptor = BindGroupDescriptor::new(
             ctx.bind_group_layouts.get(self.bind_group_layout_key)?,
             Some("Geometry Transforms"),
-            vec![BindGroupEntry::new(
-                0,
-                BindGroupResource::Buffer(BufferBinding::new(&ctx.transforms.gpu_buffer)),
-            )],
+            vec![
+                BindGroupEntry::new(
+                    0,
+                    BindGroupResource::Buffer(BufferBinding::new(&ctx.transforms.gpu_buffer)),
+                ),
+                BindGroupEntry::new(
+                    1,
+                    BindGroupResource::Buffer(BufferBinding::new(
+                        &ctx.transforms.previous_gpu_buffer,
+                    )),
+                ),
+            ],
         );
 
         let bind_group = ctx.gpu.create_bind_group(&descriptor.into());
@@ -279,6 +297,26 @@ impl GeometryBindGroupAnimation {
                     visibility_fragment: true,
                     visibility_compute: false,
                 },
+                // Previous morph weights (motion vectors)
+                BindGroupLayoutCacheKeyEntry {
+                    resource: BindGroupLayoutResource::Buffer(
+                        BufferBindingLayout::new()
+                            .with_binding_type(BufferBindingType::ReadOnlyStorage),
+                    ),
+                    visibility_vertex: true,
+                    visibility_fragment: false,
+                    visibility_compute: false,
+                },
+                // Previous skin matrices (motion vectors)
+                BindGroupLayoutCacheKeyEntry {
+                    resource: BindGroupLayoutResource::Buffer(
+                        BufferBindingLayout::new()
+                            .with_binding_type(BufferBindingType::ReadOnlyStorage),
+                    ),
+                    visibility_vertex: true,
+                    visibility_fragment: false,
+                    visibility_compute: false,
+                },
             ],
         };
 
@@ -322,6 +360,18 @@ impl GeometryBindGroupAnimation {
                         &ctx.meshes.skins.joint_index_weights_gpu_buffer,
                     )),
                 ),
+                BindGroupEntry::new(
+                    4,
+                    BindGroupResource::Buffer(BufferBinding::new(
+                        &ctx.meshes.morphs.geometry.previous_gpu_buffer_weights,
+                    )),
+                ),
+                BindGroupEntry::new(
+                    5,
+                    BindGroupResource::Buffer(BufferBinding::new(
+                        &ctx.meshes.skins.previous_matrices_gpu_buffer,
+                    )),
+                ),
             ],
         );
 
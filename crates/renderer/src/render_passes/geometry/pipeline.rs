@@ -25,7 +25,7 @@ use crate::render_passes::geometry::shader::cache_key::ShaderCacheKeyGeometry;
 use crate::render_passes::{geometry::bind_group::GeometryBindGroups, RenderPassInitContext};
 use crate::shaders::{ShaderKey, Shaders};
 
-static VERTEX_BUFFER_LAYOUT: LazyLock<VertexBufferLayout> = LazyLock::new(|| {
+pub(crate) static VERTEX_BUFFER_LAYOUT: LazyLock<VertexBufferLayout> = LazyLock::new(|| {
     VertexBufferLayout {
         // this is the stride across all of the attributes
         // position (12) + triangle_index (4) + barycentric (8) + normal (12) + tangent (16) + original_vertex_index (4) = 56 bytes
@@ -72,28 +72,29 @@ static VERTEX_BUFFER_LAYOUT: LazyLock<VertexBufferLayout> = LazyLock::new(|| {
     }
 });
 
-static VERTEX_BUFFER_LAYOUT_INSTANCING: LazyLock<VertexBufferLayout> = LazyLock::new(|| {
-    let mut vertex_buffer_layout_instancing = VertexBufferLayout {
-        // this is the stride across all of the attributes
-        array_stride: MeshBufferVertexInfo::INSTANCING_BYTE_SIZE as u64,
-        step_mode: Some(VertexStepMode::Instance),
-        attributes: Vec::new(),
-    };
+pub(crate) static VERTEX_BUFFER_LAYOUT_INSTANCING: LazyLock<VertexBufferLayout> =
+    LazyLock::new(|| {
+        let mut vertex_buffer_layout_instancing = VertexBufferLayout {
+            // this is the stride across all of the attributes
+            array_stride: MeshBufferVertexInfo::INSTANCING_BYTE_SIZE as u64,
+            step_mode: Some(VertexStepMode::Instance),
+            attributes: Vec::new(),
+        };
 
-    let start_location = VERTEX_BUFFER_LAYOUT.attributes.len() as u32;
+        let start_location = VERTEX_BUFFER_LAYOUT.attributes.len() as u32;
 
-    for i in 0..4 {
-        vertex_buffer_layout_instancing
-            .attributes
-            .push(VertexAttribute {
-                format: VertexFormat::Float32x4,
-                offset: i * 16,
-                shader_location: start_location + i as u32,
-            });
-    }
+        for i in 0..4 {
+            vertex_buffer_layout_instancing
+                .attributes
+                .push(VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: i * 16,
+                    shader_location: start_location + i as u32,
+                });
+        }
 
-    vertex_buffer_layout_instancing
-});
+        vertex_buffer_layout_instancing
+    });
 
 /// Pipeline layout and render pipelines for the geometry pass.
 pub struct GeometryPipelines {
@@ -134,9 +135,15 @@ impl GeometryPipelines {
         &self,
         opts: GeometryRenderPipelineKeyOpts<'_>,
     ) -> Result<RenderPipelineKey> {
-        let level = match opts.anti_aliasing.has_msaa_checked()? {
-            true => &self.render_pipeline_keys.msaa_4_anti_alias,
-            false => &self.render_pipeline_keys.no_anti_alias,
+        let keys = &self.render_pipeline_keys;
+        let level = match (
+            opts.anti_aliasing.has_msaa_checked()?,
+            opts.anti_aliasing.taa,
+        ) {
+            (true, false) => &keys.msaa_4_anti_alias,
+            (false, false) => &keys.no_anti_alias,
+            (true, true) => &keys.taa_msaa_4_anti_alias,
+            (false, true) => &keys.taa_no_anti_alias,
         };
 
         let level = match opts.instancing {
@@ -164,10 +171,13 @@ pub struct GeometryRenderPipelineKeyOpts<'a> {
     pub cull_mode: CullMode,
 }
 
-/// Collection of geometry pipeline keys keyed by MSAA and instancing options.
+/// Collection of geometry pipeline keys keyed by MSAA, TAA and instancing options.
 pub struct GeometryRenderPipelineKeys {
     pub no_anti_alias: GeometryRenderPipelineKeysLevel1,
     pub msaa_4_anti_alias: GeometryRenderPipelineKeysLevel1,
+    // TAA variants also write motion vectors
+    pub taa_no_anti_alias: GeometryRenderPipelineKeysLevel1,
+    pub taa_msaa_4_anti_alias: GeometryRenderPipelineKeysLevel1,
 }
 
 impl GeometryRenderPipelineKeys {
@@ -177,12 +187,32 @@ impl GeometryRenderPipelineKeys {
         pipeline_layout_key: PipelineLayoutKey,
     ) -> Result<Self> {
         Ok(Self {
-            no_anti_alias: GeometryRenderPipelineKeysLevel1::new(ctx, pipeline_layout_key, None)
-                .await?,
+            no_anti_alias: GeometryRenderPipelineKeysLevel1::new(
+                ctx,
+                pipeline_layout_key,
+                None,
+                false,
+            )
+            .await?,
             msaa_4_anti_alias: GeometryRenderPipelineKeysLevel1::new(
                 ctx,
                 pipeline_layout_key,
                 Some(4),
+                false,
+            )
+            .await?,
+            taa_no_anti_alias: GeometryRenderPipelineKeysLevel1::new(
+                ctx,
+                pipeline_layout_key,
+                None,
+                true,
+            )
+            .await?,
+            taa_msaa_4_anti_alias: GeometryRenderPipelineKeysLevel1::new(
+                ctx,
+                pipeline_layout_key,
+                Some(4),
+                true,
             )
             .await?,
         })
@@ -201,12 +231,14 @@ impl GeometryRenderPipelineKeysLevel1 {
         ctx: &mut RenderPassInitContext<'_>,
         pipeline_layout_key: PipelineLayoutKey,
         msaa_samples: Option<u32>,
+        motion_vectors: bool,
     ) -> Result<Self> {
         Ok(Self {
             no_instancing: GeometryRenderPipelineKeysLevel2::new(
                 ctx,
                 pipeline_layout_key,
                 msaa_samples,
+                motion_vectors,
                 false,
             )
             .await?,
@@ -214,6 +246,7 @@ impl GeometryRenderPipelineKeysLevel1 {
                 ctx,
                 pipeline_layout_key,
                 msaa_samples,
+                motion_vectors,
                 true,
             )
             .await?,
@@ -234,6 +267,7 @@ impl GeometryRenderPipelineKeysLevel2 {
         ctx: &mut RenderPassInitContext<'_>,
         pipeline_layout_key: PipelineLayoutKey,
         msaa_samples: Option<u32>,
+        motion_vectors: bool,
         instancing: bool,
     ) -> Result<Self> {
         Ok(Self {
@@ -241,6 +275,7 @@ impl GeometryRenderPipelineKeysLevel2 {
                 ctx,
                 pipeline_layout_key,
                 msaa_samples,
+                motion_vectors,
                 instancing,
                 CullMode::None,
             )
@@ -249,6 +284,7 @@ impl GeometryRenderPipelineKeysLevel2 {
                 ctx,
                 pipeline_layout_key,
                 msaa_samples,
+                motion_vectors,
                 instancing,
                 CullMode::Back,
             )
@@ -257,6 +293,7 @@ impl GeometryRenderPipelineKeysLevel2 {
                 ctx,
                 pipeline_layout_key,
                 msaa_samples,
+                motion_vectors,
                 instancing,
                 CullMode::Front,
             )
@@ -276,6 +313,7 @@ impl GeometryRenderPipelineKeysLevel3 {
         ctx: &mut RenderPassInitContext<'_>,
         pipeline_layout_key: PipelineLayoutKey,
         msaa_samples: Option<u32>,
+        motion_vectors: bool,
         instancing: bool,
         cull_mode: CullMode,
     ) -> Result<Self> {
@@ -286,6 +324,7 @@ impl GeometryRenderPipelineKeysLevel3 {
                 ShaderCacheKeyGeometry {
                     instancing_transforms: instancing,
                     msaa_samples,
+                    motion_vectors,
                 },
             )
             .await?;
@@ -295,12 +334,17 @@ impl GeometryRenderPipelineKeysLevel3 {
             vertex_buffer_layouts.push(VERTEX_BUFFER_LAYOUT_INSTANCING.clone());
         }
 
-        let color_targets = &[
+        let mut color_targets = vec![
             ColorTargetState::new(ctx.render_texture_formats.visiblity_data),
             ColorTargetState::new(ctx.render_texture_formats.barycentric),
             ColorTargetState::new(ctx.render_texture_formats.normal_tangent),
             ColorTargetState::new(ctx.render_texture_formats.barycentric_derivatives),
         ];
+        if motion_vectors {
+            color_targets.push(ColorTargetState::new(
+                ctx.render_texture_formats.motion_vector,
+            ));
+        }
 
         Ok(Self {
             render_pipeline_key: render_pipeline_key(
@@ -312,7 +356,7 @@ impl GeometryRenderPipelineKeysLevel3 {
                 pipeline_layout_key,
                 shader_key,
                 vertex_buffer_layouts.clone(),
-                color_targets,
+                &color_targets,
                 msaa_samples,
                 cull_mode,
             )
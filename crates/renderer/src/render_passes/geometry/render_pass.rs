@@ -54,7 +54,7 @@ impl GeometryRenderPass {
         renderables: &[Renderable],
         is_hud: bool,
     ) -> Result<()> {
-        let color_attachments = if is_hud {
+        let mut color_attachments = if is_hud {
             vec![
                 ColorAttachment::new(
                     &ctx.render_texture_views.visibility_data,
@@ -104,6 +104,15 @@ impl GeometryRenderPass {
             ]
         };
 
+        if let Some(taa) = &ctx.render_texture_views.taa {
+            // HUD motion replaces the world's where it's drawn
+            color_attachments.push(ColorAttachment::new(
+                &taa.motion_vector,
+                if is_hud { LoadOp::Load } else { LoadOp::Clear },
+                StoreOp::Store,
+            ));
+        }
+
         let depth_stencil_attachment = DepthStencilAttachment::new(if is_hud {
             &ctx.render_texture_views.hud_depth
         } else {
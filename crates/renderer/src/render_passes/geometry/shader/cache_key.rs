@@ -7,6 +7,8 @@ use crate::{render_passes::shader_cache_key::ShaderCacheKeyRenderPass, shaders::
 pub struct ShaderCacheKeyGeometry {
     pub instancing_transforms: bool,
     pub msaa_samples: Option<u32>,
+    // for TAA
+    pub motion_vectors: bool,
 }
 
 impl From<ShaderCacheKeyGeometry> for ShaderCacheKey {
@@ -1,5 +1,8 @@
 @group(0) @binding(0) var<uniform> camera_raw: CameraRaw;
 @group(1) @binding(0) var<storage, read> model_transforms : array<mat4x4<f32>>;
+{% if motion_vectors %}
+    @group(1) @binding(1) var<storage, read> previous_model_transforms : array<mat4x4<f32>>;
+{% endif %}
 @group(2) @binding(0) var<uniform> geometry_mesh_meta: GeometryMeshMeta;
 @group(3) @binding(0) var<storage, read> geometry_morph_weights: array<f32>;
 @group(3) @binding(1) var<storage, read> geometry_morph_values: array<f32>;
@@ -8,3 +11,8 @@
 // We interleave indices with weights and get our index back losslessly via bitcast
 // Layout: vertex 0: [joints_0, joints_1, ...], vertex 1: [joints_0, joints_1, ...], etc.
 @group(3) @binding(3) var<storage, read> skin_joint_index_weights: array<f32>;
+{% if motion_vectors %}
+    // Last frame's values, for motion vectors
+    @group(3) @binding(4) var<storage, read> previous_geometry_morph_weights: array<f32>;
+    @group(3) @binding(5) var<storage, read> previous_skin_joint_matrices: array<mat4x4<f32>>;
+{% endif %}
@@ -6,6 +6,10 @@ struct FragmentInput {
     @location(1) barycentric: vec2<f32>,  // Full barycentric coordinates
     @location(2) world_normal: vec3<f32>,     // Transformed world-space normal
     @location(3) world_tangent: vec4<f32>,    // Transformed world-space tangent (w = handedness)
+    {% if motion_vectors %}
+    @location(4) current_clip: vec4<f32>,
+    @location(5) previous_clip: vec4<f32>,
+    {% endif %}
 }
 
 struct FragmentOutput {
@@ -17,6 +21,10 @@ struct FragmentOutput {
     @location(2) normal_tangent: vec4<f32>,
     // RGBA16float
     @location(3) barycentric_derivatives: vec4<f32>,
+    {% if motion_vectors %}
+    // RG16float
+    @location(4) motion_vector: vec2<f32>,
+    {% endif %}
 }
 
 @fragment
@@ -50,5 +58,9 @@ fn fs_main(input: FragmentInput) -> FragmentOutput {
 
     out.barycentric_derivatives = vec4<f32>(ddx.x, ddy.x, ddx.y, ddy.y);
 
+    {% if motion_vectors %}
+        out.motion_vector = motion_vector(input.current_clip, input.previous_clip, camera_raw.jitter.xy);
+    {% endif %}
+
     return out;
 }
@@ -0,0 +1,76 @@
+//***** MOTION VECTORS *****
+
+// Same as apply_vertex, but only the position, and with last frame's morph weights, joint matrices
+// and transforms (the morph values and skin weights don't change over time)
+// Not unrolled, this only runs when TAA is enabled
+fn previous_world_position(input: ApplyVertexInput) -> vec4<f32> {
+    var position = input.position;
+
+    let target_count = geometry_mesh_meta.morph_geometry_target_len;
+    if target_count != 0 {
+        // see apply_position_morphs for the layout
+        let floats_per_target = 10u;
+        let base_weights_offset = (geometry_mesh_meta.morph_geometry_weights_offset / 4) + 1u;
+        let base_values_offset = (geometry_mesh_meta.morph_geometry_values_offset / 4) + input.vertex_index * target_count * floats_per_target;
+
+        for (var target_index = 0u; target_index < target_count; target_index = target_index + 1u) {
+            let weight = previous_geometry_morph_weights[base_weights_offset + target_index];
+            let value_offset = base_values_offset + (target_index * floats_per_target);
+            position += weight * vec3<f32>(
+                geometry_morph_values[value_offset],
+                geometry_morph_values[value_offset + 1u],
+                geometry_morph_values[value_offset + 2u]
+            );
+        }
+    }
+
+    let skin_sets_count = geometry_mesh_meta.skin_sets_len;
+    if skin_sets_count != 0 {
+        // see apply_position_skin for the layout
+        let base_offset = (geometry_mesh_meta.skin_index_weights_offset / 4) + input.vertex_index * skin_sets_count * floats_per_set;
+        let matrix_offset = geometry_mesh_meta.skin_matrices_offset / 64;
+
+        var skin_matrix = mat4x4<f32>(vec4<f32>(0.0), vec4<f32>(0.0), vec4<f32>(0.0), vec4<f32>(0.0));
+        for (var skin_set_index = 0u; skin_set_index < skin_sets_count; skin_set_index = skin_set_index + 1u) {
+            let buffer_offset = base_offset + (skin_set_index * floats_per_set);
+            for (var joint = 0u; joint < 4u; joint = joint + 1u) {
+                let joint_index = bitcast<u32>(skin_joint_index_weights[buffer_offset + joint * 2u]);
+                let joint_weight = skin_joint_index_weights[buffer_offset + joint * 2u + 1u];
+                skin_matrix = skin_matrix + joint_weight * previous_skin_joint_matrices[joint_index + matrix_offset];
+            }
+        }
+
+        position = (skin_matrix * vec4<f32>(position, 1.0)).xyz;
+    }
+
+    {% if instancing_transforms %}
+        let instance_transform = mat4x4<f32>(
+            input.instance_transform_row_0,
+            input.instance_transform_row_1,
+            input.instance_transform_row_2,
+            input.instance_transform_row_3,
+        );
+
+        let model_transform = previous_model_transforms[geometry_mesh_meta.transform_offset / 64] * instance_transform;
+    {% else %}
+        let model_transform = previous_model_transforms[geometry_mesh_meta.transform_offset / 64];
+    {% endif %}
+
+    return model_transform * vec4<f32>(position, 1.0);
+}
+
+// Screen-space motion from this frame back to the previous one, in UV units
+// i.e. the history for a pixel at `uv` is found at `uv + motion_vector`
+fn motion_vector(current_clip: vec4<f32>, previous_clip: vec4<f32>, jitter: vec2<f32>) -> vec2<f32> {
+    // behind the camera last frame (or no history at all), nothing to reproject
+    if previous_clip.w <= 0.0 {
+        return vec2<f32>(0.0);
+    }
+
+    // the jitter isn't motion, compare unjittered positions
+    let current_ndc = current_clip.xy / current_clip.w - jitter;
+    let previous_ndc = previous_clip.xy / previous_clip.w;
+
+    // NDC y is up, UV y is down
+    return (previous_ndc - current_ndc) * vec2<f32>(0.5, -0.5);
+}
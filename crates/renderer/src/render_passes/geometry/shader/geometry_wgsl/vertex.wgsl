@@ -4,6 +4,9 @@
 {% include "shared_wgsl/vertex/morph.wgsl" %}
 {% include "shared_wgsl/vertex/skin.wgsl" %}
 {% include "shared_wgsl/vertex/apply_vertex.wgsl" %}
+{% if motion_vectors %}
+    {% include "geometry_wgsl/motion_vectors.wgsl" %}
+{% endif %}
 
 
 //***** MAIN *****
@@ -30,6 +33,10 @@ struct VertexOutput {
     @location(1) barycentric: vec2<f32>,  // Full barycentric coordinates
     @location(2) world_normal: vec3<f32>,     // Transformed world-space normal
     @location(3) world_tangent: vec4<f32>,    // Transformed world-space tangent (w = handedness)
+    {% if motion_vectors %}
+    @location(4) current_clip: vec4<f32>,     // Same as clip_position, but not converted to pixels
+    @location(5) previous_clip: vec4<f32>,    // Where this vertex was last frame (unjittered)
+    {% endif %}
 }
 
 @vertex
@@ -52,6 +59,21 @@ fn vert_main(input: VertexInput) -> VertexOutput {
     ), camera);
 
     out.clip_position = applied.clip_position;
+    {% if motion_vectors %}
+        out.current_clip = applied.clip_position;
+        out.previous_clip = camera.prev_view_proj * previous_world_position(ApplyVertexInput(
+            input.original_vertex_index,
+            input.position,
+            input.normal,
+            input.tangent,
+            {% if instancing_transforms %}
+                input.instance_transform_row_0,
+                input.instance_transform_row_1,
+                input.instance_transform_row_2,
+                input.instance_transform_row_3,
+            {% endif %}
+        ));
+    {% endif %}
     out.world_normal = applied.world_normal;
     out.world_tangent = applied.world_tangent;
 
@@ -18,12 +18,16 @@ pub struct ShaderTemplateGeometry {
 /// Bind group template for the geometry pass.
 #[derive(Template, Debug)]
 #[template(path = "geometry_wgsl/bind_groups.wgsl", whitespace = "minimize")]
-pub struct ShaderTemplateGeometryBindGroups {}
+pub struct ShaderTemplateGeometryBindGroups {
+    motion_vectors: bool,
+}
 
 impl ShaderTemplateGeometryBindGroups {
     /// Creates a bind group template from the cache key.
-    pub fn new(_cache_key: &ShaderCacheKeyGeometry) -> Self {
-        Self {}
+    pub fn new(cache_key: &ShaderCacheKeyGeometry) -> Self {
+        Self {
+            motion_vectors: cache_key.motion_vectors,
+        }
     }
 }
 
@@ -34,6 +38,7 @@ pub struct ShaderTemplateGeometryVertex {
     max_morph_unroll: u32,
     max_skin_unroll: u32,
     instancing_transforms: bool,
+    motion_vectors: bool,
 }
 
 impl ShaderTemplateGeometryVertex {
@@ -43,6 +48,7 @@ impl ShaderTemplateGeometryVertex {
             max_morph_unroll: 2,
             max_skin_unroll: 2,
             instancing_transforms: cache_key.instancing_transforms,
+            motion_vectors: cache_key.motion_vectors,
         }
     }
 }
@@ -50,12 +56,16 @@ impl ShaderTemplateGeometryVertex {
 /// Fragment shader template for the geometry pass.
 #[derive(Template, Debug)]
 #[template(path = "geometry_wgsl/fragment.wgsl", whitespace = "minimize")]
-pub struct ShaderTemplateGeometryFragment {}
+pub struct ShaderTemplateGeometryFragment {
+    motion_vectors: bool,
+}
 
 impl ShaderTemplateGeometryFragment {
     /// Creates a fragment shader template from the cache key.
-    pub fn new(_cache_key: &ShaderCacheKeyGeometry) -> Self {
-        Self {}
+    pub fn new(cache_key: &ShaderCacheKeyGeometry) -> Self {
+        Self {
+            motion_vectors: cache_key.motion_vectors,
+        }
     }
 }
 
@@ -1,21 +1,45 @@
 //! Light culling bind group setup.
 
+use std::borrow::Cow;
+
+use awsm_renderer_core::{
+    bind_groups::{
+        BindGroupDescriptor, BindGroupEntry, BindGroupLayoutResource, BindGroupResource,
+        BufferBindingLayout, BufferBindingType, TextureBindingLayout,
+    },
+    buffers::BufferBinding,
+    texture::{TextureSampleType, TextureViewDimension},
+};
+
+use crate::bind_group_layout::{
+    BindGroupLayoutCacheKey, BindGroupLayoutCacheKeyEntry, BindGroupLayoutKey,
+};
 use crate::bind_groups::{AwsmBindGroupError, BindGroupRecreateContext};
 use crate::error::Result;
 use crate::render_passes::RenderPassInitContext;
 
 /// Bind group layout and cached bind group for light culling.
 pub struct LightCullingBindGroups {
-    //pub bind_group_layout_key: BindGroupLayoutKey,
+    pub multisampled_bind_group_layout_key: BindGroupLayoutKey,
+    pub singlesampled_bind_group_layout_key: BindGroupLayoutKey,
     // this is set via `recreate` mechanism
     _bind_group: Option<web_sys::GpuBindGroup>,
 }
 
 impl LightCullingBindGroups {
     /// Creates bind group layout state for light culling.
-    pub async fn new(_ctx: &mut RenderPassInitContext<'_>) -> Result<Self> {
+    pub async fn new(ctx: &mut RenderPassInitContext<'_>) -> Result<Self> {
+        let singlesampled_bind_group_layout_key = ctx
+            .bind_group_layouts
+            .get_key(ctx.gpu, bind_group_layout_cache_key(false))?;
+
+        let multisampled_bind_group_layout_key = ctx
+            .bind_group_layouts
+            .get_key(ctx.gpu, bind_group_layout_cache_key(true))?;
+
         Ok(Self {
-            //bind_group_layout_key,
+            multisampled_bind_group_layout_key,
+            singlesampled_bind_group_layout_key,
             _bind_group: None,
         })
     }
@@ -30,9 +54,99 @@ impl LightCullingBindGroups {
     }
 
     /// Recreates the light culling bind group.
-    pub fn recreate(&mut self, _ctx: &BindGroupRecreateContext<'_>) -> Result<()> {
-        //self._bind_group = Some(ctx.gpu.create_bind_group(&descriptor.into()));
+    pub fn recreate(&mut self, ctx: &BindGroupRecreateContext<'_>) -> Result<()> {
+        let mut entries = Vec::new();
+
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&ctx.camera.gpu_buffer)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(&ctx.render_texture_views.depth)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&ctx.lights.gpu_info_buffer)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&ctx.lights.gpu_punctual_buffer)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&ctx.lights.tiles.gpu_buffer)),
+        ));
+
+        let descriptor = BindGroupDescriptor::new(
+            ctx.bind_group_layouts
+                .get(if ctx.anti_aliasing.has_msaa_checked()? {
+                    self.multisampled_bind_group_layout_key
+                } else {
+                    self.singlesampled_bind_group_layout_key
+                })?,
+            Some("Light Culling"),
+            entries,
+        );
+
+        self._bind_group = Some(ctx.gpu.create_bind_group(&descriptor.into()));
 
         Ok(())
     }
 }
+
+fn bind_group_layout_cache_key(multisampled_geometry: bool) -> BindGroupLayoutCacheKey {
+    BindGroupLayoutCacheKey {
+        entries: vec![
+            // Camera uniform for unprojecting tile bounds and moving lights into view space
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new().with_binding_type(BufferBindingType::Uniform),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Depth texture
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Texture(
+                    TextureBindingLayout::new()
+                        .with_view_dimension(TextureViewDimension::N2d)
+                        .with_sample_type(TextureSampleType::Depth)
+                        .with_multisampled(multisampled_geometry),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Lights info
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new().with_binding_type(BufferBindingType::Uniform),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Punctual lights
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new()
+                        .with_binding_type(BufferBindingType::ReadOnlyStorage),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Light tile indices (output)
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new().with_binding_type(BufferBindingType::Storage),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+        ],
+    }
+}
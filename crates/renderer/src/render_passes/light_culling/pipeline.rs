@@ -1,19 +1,85 @@
 //! Light culling pipeline setup.
 
+use crate::anti_alias::AntiAliasing;
 use crate::error::Result;
+use crate::pipeline_layouts::{PipelineLayoutCacheKey, PipelineLayoutKey};
+use crate::pipelines::compute_pipeline::{ComputePipelineCacheKey, ComputePipelineKey};
 use crate::render_passes::{
-    light_culling::bind_group::LightCullingBindGroups, RenderPassInitContext,
+    light_culling::{
+        bind_group::LightCullingBindGroups, shader::cache_key::ShaderCacheKeyLightCulling,
+    },
+    RenderPassInitContext,
 };
 
 /// Pipeline state for light culling.
-pub struct LightCullingPipelines {}
+pub struct LightCullingPipelines {
+    msaa_4_compute_pipeline_key: ComputePipelineKey,
+    singlesampled_compute_pipeline_key: ComputePipelineKey,
+}
 
 impl LightCullingPipelines {
     /// Creates light culling pipeline state.
     pub async fn new(
-        _ctx: &mut RenderPassInitContext<'_>,
-        _bind_groups: &LightCullingBindGroups,
+        ctx: &mut RenderPassInitContext<'_>,
+        bind_groups: &LightCullingBindGroups,
     ) -> Result<Self> {
-        Ok(Self {})
+        let multisampled_pipeline_layout_key = ctx.pipeline_layouts.get_key(
+            ctx.gpu,
+            ctx.bind_group_layouts,
+            PipelineLayoutCacheKey::new(vec![bind_groups.multisampled_bind_group_layout_key]),
+        )?;
+
+        let singlesampled_pipeline_layout_key = ctx.pipeline_layouts.get_key(
+            ctx.gpu,
+            ctx.bind_group_layouts,
+            PipelineLayoutCacheKey::new(vec![bind_groups.singlesampled_bind_group_layout_key]),
+        )?;
+
+        let msaa_4_compute_pipeline_key =
+            Self::create_pipeline(ctx, Some(4), multisampled_pipeline_layout_key).await?;
+
+        let singlesampled_compute_pipeline_key =
+            Self::create_pipeline(ctx, None, singlesampled_pipeline_layout_key).await?;
+
+        Ok(Self {
+            msaa_4_compute_pipeline_key,
+            singlesampled_compute_pipeline_key,
+        })
+    }
+
+    async fn create_pipeline(
+        ctx: &mut RenderPassInitContext<'_>,
+        msaa_sample_count: Option<u32>,
+        pipeline_layout_key: PipelineLayoutKey,
+    ) -> Result<ComputePipelineKey> {
+        let shader_cache_key = ShaderCacheKeyLightCulling { msaa_sample_count };
+
+        let shader_key = ctx.shaders.get_key(ctx.gpu, shader_cache_key).await?;
+
+        let compute_pipeline_cache_key =
+            ComputePipelineCacheKey::new(shader_key, pipeline_layout_key);
+
+        Ok(ctx
+            .pipelines
+            .compute
+            .get_key(
+                ctx.gpu,
+                ctx.shaders,
+                ctx.pipeline_layouts,
+                compute_pipeline_cache_key,
+            )
+            .await?)
+    }
+
+    /// Returns the light culling pipeline key for the current MSAA state.
+    pub fn get_compute_pipeline_key(
+        &self,
+        anti_aliasing: &AntiAliasing,
+    ) -> Option<ComputePipelineKey> {
+        match anti_aliasing.msaa_sample_count {
+            Some(4) => Some(self.msaa_4_compute_pipeline_key),
+            None => Some(self.singlesampled_compute_pipeline_key),
+            _ => None,
+        }
     }
 }
@@ -1,7 +1,10 @@
 //! Light culling render pass execution.
 
+use awsm_renderer_core::command::compute_pass::ComputePassDescriptor;
+
 use crate::{
-    error::Result,
+    error::{AwsmError, Result},
+    lights::culling::LightTiles,
     render::RenderContext,
     render_passes::{
         light_culling::{bind_group::LightCullingBindGroups, pipeline::LightCullingPipelines},
@@ -26,9 +29,34 @@ impl LightCullingRenderPass {
         })
     }
 
-    /// Executes the light culling pass.
-    pub fn render(&self, _ctx: &RenderContext) -> Result<()> {
-        // TODO!
+    /// Executes the light culling pass, one workgroup per light tile.
+    ///
+    /// Unlike the optional passes this can't be skipped: the shading passes read the tile
+    /// light lists, so an unsupported MSAA count is an error rather than stale tiles.
+    pub fn render(&self, ctx: &RenderContext) -> Result<()> {
+        let pipeline_key = self
+            .pipelines
+            .get_compute_pipeline_key(ctx.anti_aliasing)
+            .ok_or_else(|| {
+                AwsmError::UnsupportedMsaaCount(
+                    ctx.anti_aliasing.msaa_sample_count.unwrap_or_default(),
+                )
+            })?;
+
+        let compute_pass = ctx.command_encoder.begin_compute_pass(Some(
+            &ComputePassDescriptor::new(Some("Light Culling Pass")).into(),
+        ));
+
+        compute_pass.set_bind_group(0u32, self.bind_groups.get_bind_group()?, None)?;
+        compute_pass.set_pipeline(ctx.pipelines.compute.get(pipeline_key)?);
+
+        let (tiles_x, tiles_y) = LightTiles::tile_count(
+            ctx.render_texture_views.width,
+            ctx.render_texture_views.height,
+        );
+        compute_pass.dispatch_workgroups(tiles_x, Some(tiles_y), Some(1));
+
+        compute_pass.end();
 
         Ok(())
     }
@@ -4,7 +4,9 @@ use crate::{render_passes::shader_cache_key::ShaderCacheKeyRenderPass, shaders::
 
 /// Cache key for light culling shaders.
 #[derive(Hash, Debug, Clone, PartialEq, Eq)]
-pub struct ShaderCacheKeyLightCulling {}
+pub struct ShaderCacheKeyLightCulling {
+    pub msaa_sample_count: Option<u32>,
+}
 
 impl From<ShaderCacheKeyLightCulling> for ShaderCacheKey {
     fn from(key: ShaderCacheKeyLightCulling) -> Self {
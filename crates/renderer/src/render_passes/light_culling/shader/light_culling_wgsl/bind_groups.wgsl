@@ -0,0 +1,11 @@
+@group(0) @binding(0) var<uniform> camera_raw: CameraRaw;
+{% if multisampled_geometry %}
+    @group(0) @binding(1) var depth_tex: texture_depth_multisampled_2d;
+{% else %}
+    @group(0) @binding(1) var depth_tex: texture_depth_2d;
+{% endif %}
+// x = n_lights, see LightsInfoPacked
+@group(0) @binding(2) var<uniform> lights_info: vec4<u32>;
+// 4 vec4s per light, see LightPacked
+@group(0) @binding(3) var<storage, read> lights: array<vec4<f32>>;
+@group(0) @binding(4) var<storage, read_write> light_tile_indices: array<u32>;
@@ -0,0 +1,145 @@
+/*************** START camera.wgsl ******************/
+{% include "shared_wgsl/camera.wgsl" %}
+/*************** END camera.wgsl ******************/
+
+/*************** START light_tiles.wgsl ******************/
+{% include "shared_wgsl/lighting/light_tiles.wgsl" %}
+/*************** END light_tiles.wgsl ******************/
+
+// See lights/culling.rs for the CPU reference of this algorithm
+
+// depth is always in [0, 1], so the bit patterns order the same way as the floats
+var<workgroup> tile_min_depth_bits: atomic<u32>;
+var<workgroup> tile_max_depth_bits: atomic<u32>;
+var<workgroup> tile_opaque_count: atomic<u32>;
+var<workgroup> tile_transparent_count: atomic<u32>;
+
+@compute @workgroup_size(16, 16)
+fn main(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(workgroup_id) wid: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let screen_dims = textureDimensions(depth_tex);
+
+    if (local_index == 0u) {
+        atomicStore(&tile_min_depth_bits, bitcast<u32>(1.0f));
+        atomicStore(&tile_max_depth_bits, 0u);
+        atomicStore(&tile_opaque_count, 0u);
+        atomicStore(&tile_transparent_count, 0u);
+    }
+
+    workgroupBarrier();
+
+    // Reduce the depth of every pixel (and every sample) in the tile, skipping cleared depth
+    if (gid.x < screen_dims.x && gid.y < screen_dims.y) {
+        let coords = vec2<i32>(gid.xy);
+        {% if multisampled_geometry %}
+            for (var s = 0; s < {{ msaa_sample_count }}; s++) {
+                accumulate_depth(textureLoad(depth_tex, coords, s));
+            }
+        {% else %}
+            accumulate_depth(textureLoad(depth_tex, coords, 0));
+        {% endif %}
+    }
+
+    workgroupBarrier();
+
+    let min_depth = bitcast<f32>(atomicLoad(&tile_min_depth_bits));
+    let max_depth = bitcast<f32>(atomicLoad(&tile_max_depth_bits));
+    let has_geometry = min_depth <= max_depth;
+
+    let camera = camera_from_raw(camera_raw);
+    let screen_dims_f32 = vec2<f32>(screen_dims);
+
+    let opaque_bounds = tile_view_bounds(camera, wid.xy, screen_dims_f32, min_depth, max_depth);
+    let transparent_bounds = tile_view_bounds(
+        camera,
+        wid.xy,
+        screen_dims_f32,
+        0.0,
+        select(1.0, max_depth, has_geometry)
+    );
+
+    let list_offset = light_tile_list_offset(wid.xy, screen_dims.x, LIGHT_TILE_LIST_OPAQUE);
+    let transparent_list_offset = list_offset + LIGHT_TILE_LIST_STRIDE;
+
+    // Each thread tests a strided subset of the lights
+    let n_lights = lights_info.x;
+    for (var i = local_index; i < n_lights; i += LIGHT_TILE_SIZE * LIGHT_TILE_SIZE) {
+        let pos_range = lights[i * 4u];
+        let kind = u32(lights[i * 4u + 3u].x);
+        let view_position = (camera.view * vec4<f32>(pos_range.xyz, 1.0)).xyz;
+
+        if (has_geometry && light_intersects_view_bounds(kind, view_position, pos_range.w, opaque_bounds)) {
+            let slot = atomicAdd(&tile_opaque_count, 1u);
+            if (slot < LIGHT_TILE_MAX_LIGHTS) {
+                light_tile_indices[list_offset + 1u + slot] = i;
+            }
+        }
+
+        if (light_intersects_view_bounds(kind, view_position, pos_range.w, transparent_bounds)) {
+            let slot = atomicAdd(&tile_transparent_count, 1u);
+            if (slot < LIGHT_TILE_MAX_LIGHTS) {
+                light_tile_indices[transparent_list_offset + 1u + slot] = i;
+            }
+        }
+    }
+
+    workgroupBarrier();
+
+    if (local_index == 0u) {
+        light_tile_indices[list_offset] = min(atomicLoad(&tile_opaque_count), LIGHT_TILE_MAX_LIGHTS);
+        light_tile_indices[transparent_list_offset] = min(atomicLoad(&tile_transparent_count), LIGHT_TILE_MAX_LIGHTS);
+    }
+}
+
+fn accumulate_depth(depth: f32) {
+    if (depth < 1.0) {
+        let bits = bitcast<u32>(depth);
+        atomicMin(&tile_min_depth_bits, bits);
+        atomicMax(&tile_max_depth_bits, bits);
+    }
+}
+
+struct ViewBounds {
+    min: vec3<f32>,
+    max: vec3<f32>,
+};
+
+// View-space box around the tile between two NDC depths
+fn tile_view_bounds(camera: Camera, tile: vec2<u32>, screen_dims: vec2<f32>, min_depth: f32, max_depth: f32) -> ViewBounds {
+    let px_min = vec2<f32>(tile * LIGHT_TILE_SIZE);
+    let px_max = min(vec2<f32>((tile + vec2<u32>(1u)) * LIGHT_TILE_SIZE), screen_dims);
+
+    let ndc_min = vec2<f32>(px_min.x / screen_dims.x * 2.0 - 1.0, 1.0 - px_max.y / screen_dims.y * 2.0);
+    let ndc_max = vec2<f32>(px_max.x / screen_dims.x * 2.0 - 1.0, 1.0 - px_min.y / screen_dims.y * 2.0);
+
+    var bounds = ViewBounds(vec3<f32>(3.402823e38), vec3<f32>(-3.402823e38));
+
+    for (var corner = 0u; corner < 8u; corner++) {
+        let ndc = vec3<f32>(
+            select(ndc_min.x, ndc_max.x, (corner & 1u) != 0u),
+            select(ndc_min.y, ndc_max.y, (corner & 2u) != 0u),
+            select(min_depth, max_depth, (corner & 4u) != 0u),
+        );
+        let p = camera.inv_proj * vec4<f32>(ndc, 1.0);
+        let view_pos = p.xyz / p.w;
+        bounds.min = min(bounds.min, view_pos);
+        bounds.max = max(bounds.max, view_pos);
+    }
+
+    return bounds;
+}
+
+// kind: 1 = directional, 2 = point, 3 = spot (spot is tested as a sphere)
+fn light_intersects_view_bounds(kind: u32, view_position: vec3<f32>, range: f32, bounds: ViewBounds) -> bool {
+    if (kind == 1u || range <= 0.0) {
+        return true;
+    }
+
+    let closest = clamp(view_position, bounds.min, bounds.max);
+    let d = closest - view_position;
+
+    return dot(d, d) <= range * range;
+}
@@ -16,24 +16,34 @@ pub struct ShaderTemplateLightCulling {
 /// Bind group template for the light culling pass.
 #[derive(Template, Debug)]
 #[template(path = "light_culling_wgsl/bind_groups.wgsl", whitespace = "minimize")]
-pub struct ShaderTemplateLightCullingBindGroups {}
+pub struct ShaderTemplateLightCullingBindGroups {
+    pub multisampled_geometry: bool,
+}
 
 impl ShaderTemplateLightCullingBindGroups {
     /// Creates a bind group template from the cache key.
-    pub fn new(_cache_key: &ShaderCacheKeyLightCulling) -> Self {
-        Self {}
+    pub fn new(cache_key: &ShaderCacheKeyLightCulling) -> Self {
+        Self {
+            multisampled_geometry: cache_key.msaa_sample_count.is_some(),
+        }
     }
 }
 
 /// Compute shader template for the light culling pass.
 #[derive(Template, Debug)]
 #[template(path = "light_culling_wgsl/compute.wgsl", whitespace = "minimize")]
-pub struct ShaderTemplateLightCullingCompute {}
+pub struct ShaderTemplateLightCullingCompute {
+    pub multisampled_geometry: bool,
+    pub msaa_sample_count: u32, // 0 if no MSAA
+}
 
 impl ShaderTemplateLightCullingCompute {
     /// Creates a compute shader template from the cache key.
-    pub fn new(_cache_key: &ShaderCacheKeyLightCulling) -> Self {
-        Self {}
+    pub fn new(cache_key: &ShaderCacheKeyLightCulling) -> Self {
+        Self {
+            multisampled_geometry: cache_key.msaa_sample_count.is_some(),
+            msaa_sample_count: cache_key.msaa_sample_count.unwrap_or_default(),
+        }
     }
 }
 
@@ -64,6 +64,45 @@ impl MaterialOpaqueBindGroups {
                 visibility_fragment: false,
                 visibility_compute: true,
             },
+            // light tile indices
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new()
+                        .with_binding_type(BufferBindingType::ReadOnlyStorage),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // shadows info (uniform, the pass is at its storage buffer limit)
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new().with_binding_type(BufferBindingType::Uniform),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // shadow maps
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Texture(
+                    TextureBindingLayout::new()
+                        .with_view_dimension(TextureViewDimension::N2dArray)
+                        .with_sample_type(TextureSampleType::Depth),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // shadow sampler
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Sampler(
+                    SamplerBindingLayout::new().with_binding_type(SamplerBindingType::Comparison),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
         ];
 
         let lights_bind_group_layout_key = ctx.bind_group_layouts.get_key(
@@ -283,6 +322,13 @@ impl MaterialOpaqueBindGroups {
             entries.len() as u32,
             BindGroupResource::TextureView(Cow::Borrowed(&ctx.render_texture_views.opaque)),
         ));
+        // Screen-space ambient occlusion (zeroed when SSAO is disabled)
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(
+                &ctx.render_texture_views.ambient_occlusion,
+            )),
+        ));
 
         let descriptor = BindGroupDescriptor::new(
             ctx.bind_group_layouts
@@ -315,6 +361,27 @@ impl MaterialOpaqueBindGroups {
             BindGroupResource::Buffer(BufferBinding::new(&ctx.lights.gpu_punctual_buffer)),
         ));
 
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&ctx.lights.tiles.gpu_buffer)),
+        ));
+
+        // Shadows
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&ctx.lights.shadows.gpu_info_buffer)),
+        ));
+
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(&ctx.lights.shadows.texture_view)),
+        ));
+
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Sampler(&ctx.lights.shadows.sampler),
+        ));
+
         let descriptor = BindGroupDescriptor::new(
             ctx.bind_group_layouts
                 .get(self.lights_bind_group_layout_key)?,
@@ -589,6 +656,17 @@ async fn create_main_bind_group_layout_key(
             visibility_fragment: false,
             visibility_compute: true,
         },
+        // Screen-space ambient occlusion
+        BindGroupLayoutCacheKeyEntry {
+            resource: BindGroupLayoutResource::Texture(
+                TextureBindingLayout::new()
+                    .with_view_dimension(TextureViewDimension::N2d)
+                    .with_sample_type(TextureSampleType::UnfilterableFloat),
+            ),
+            visibility_vertex: false,
+            visibility_fragment: false,
+            visibility_compute: true,
+        },
     ];
 
     Ok(ctx
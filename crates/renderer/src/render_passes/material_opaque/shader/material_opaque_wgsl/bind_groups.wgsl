@@ -29,9 +29,14 @@
 @group(0) @binding(20) var brdf_lut_tex: texture_2d<f32>;
 @group(0) @binding(21) var brdf_lut_sampler: sampler;
 @group(0) @binding(22) var opaque_tex: texture_storage_2d<rgba16float, write>;
+@group(0) @binding(23) var ambient_occlusion_tex: texture_2d<f32>;
 
 @group(1) @binding(0) var<uniform> lights_info: LightsInfoPacked;
 @group(1) @binding(1) var<storage, read> lights: array<LightPacked>;
+@group(1) @binding(2) var<storage, read> light_tile_indices: array<u32>;
+@group(1) @binding(3) var<uniform> shadows: ShadowsPacked;
+@group(1) @binding(4) var shadow_maps: texture_depth_2d_array;
+@group(1) @binding(5) var shadow_sampler: sampler_comparison;
 
 {% for i in 0..texture_pool_arrays_len %}
     @group(2) @binding({{ i }}u) var pool_tex_{{ i }}: texture_2d_array<f32>;
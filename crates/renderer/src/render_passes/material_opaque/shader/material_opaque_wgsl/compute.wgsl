@@ -209,6 +209,7 @@ fn main(
     let world_normal = tbn.N;
 
     let lights_info = get_lights_info();
+    let light_tile = get_light_tile(gid.xy, screen_dims.x, LIGHT_TILE_LIST_OPAQUE);
 
     // Compute material color and apply lighting based on shader type
     var color: vec3<f32>;
@@ -261,6 +262,7 @@ fn main(
                     uv_sets_index,
                     tbn,
                     bary_derivs,
+                    coords,
                 );
             {% when MipmapMode::None %}
                 let material_color = compute_material_color(
@@ -273,6 +275,7 @@ fn main(
                     vertex_attribute_stride,
                     uv_sets_index,
                     tbn,
+                    coords,
                 );
         {% endmatch %}
 
@@ -287,7 +290,14 @@ fn main(
             material_color,
             standard_coordinates.surface_to_camera,
             standard_coordinates.world_position,
-            lights_info
+            lights_info,
+            light_tile,
+            shadow_receiver(
+                material_mesh_meta.receive_shadows == 1u,
+                world_normal,
+                standard_coordinates.world_position,
+                camera
+            )
         );
         base_alpha = material_color.base.a;
 
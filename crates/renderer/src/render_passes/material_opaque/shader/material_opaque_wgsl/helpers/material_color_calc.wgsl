@@ -9,6 +9,9 @@ struct PbrMaterialGradients {
     specular: UvDerivs,
     specular_color: UvDerivs,
     transmission: UvDerivs,
+    // KHR_materials_diffuse_transmission
+    diffuse_transmission: UvDerivs,
+    diffuse_transmission_color: UvDerivs,
     volume_thickness: UvDerivs,
     // KHR_materials_clearcoat
     clearcoat: UvDerivs,
@@ -17,6 +20,11 @@ struct PbrMaterialGradients {
     // KHR_materials_sheen
     sheen_color: UvDerivs,
     sheen_roughness: UvDerivs,
+    // KHR_materials_anisotropy
+    anisotropy: UvDerivs,
+    // KHR_materials_iridescence
+    iridescence: UvDerivs,
+    iridescence_thickness: UvDerivs,
 }
 {% endif %}
 
@@ -38,9 +46,12 @@ fn pbr_get_material_color{{ mipmap.suffix() }}(
     let ior = pbr_material_load_ior(material.ior_index);
     let specular = pbr_material_load_specular(material.specular_index);
     let transmission = pbr_material_load_transmission(material.transmission_index);
+    let diffuse_transmission = pbr_material_load_diffuse_transmission(material.diffuse_transmission_index);
     let volume = pbr_material_load_volume(material.volume_index);
     let clearcoat = pbr_material_load_clearcoat(material.clearcoat_index);
     let sheen = pbr_material_load_sheen(material.sheen_index);
+    let anisotropy = pbr_material_load_anisotropy(material.anisotropy_index);
+    let iridescence = pbr_material_load_iridescence(material.iridescence_index);
 
     var base = _pbr_material_base_color{{ mipmap.suffix() }}(
         material,
@@ -160,6 +171,33 @@ fn pbr_get_material_color{{ mipmap.suffix() }}(
         {% if mipmap.is_gradient() %}gradients.transmission,{% endif %}
     );
 
+    // Diffuse transmission sampling
+    let diffuse_transmission_factor = _pbr_diffuse_transmission{{ mipmap.suffix() }}(
+        diffuse_transmission,
+        texture_uv(
+            attribute_data_offset,
+            triangle_indices,
+            barycentric,
+            diffuse_transmission.tex_info,
+            vertex_attribute_stride,
+            uv_sets_index,
+        ),
+        {% if mipmap.is_gradient() %}gradients.diffuse_transmission,{% endif %}
+    );
+
+    let diffuse_transmission_color = _pbr_diffuse_transmission_color{{ mipmap.suffix() }}(
+        diffuse_transmission,
+        texture_uv(
+            attribute_data_offset,
+            triangle_indices,
+            barycentric,
+            diffuse_transmission.color_tex_info,
+            vertex_attribute_stride,
+            uv_sets_index,
+        ),
+        {% if mipmap.is_gradient() %}gradients.diffuse_transmission_color,{% endif %}
+    );
+
     let volume_thickness = _pbr_volume_thickness{{ mipmap.suffix() }}(
         volume,
         texture_uv(
@@ -241,6 +279,48 @@ fn pbr_get_material_color{{ mipmap.suffix() }}(
         {% if mipmap.is_gradient() %}gradients.sheen_roughness,{% endif %}
     );
 
+    // Anisotropy sampling
+    let anisotropy_value = _pbr_anisotropy{{ mipmap.suffix() }}(
+        anisotropy,
+        texture_uv(
+            attribute_data_offset,
+            triangle_indices,
+            barycentric,
+            anisotropy.tex_info,
+            vertex_attribute_stride,
+            uv_sets_index,
+        ),
+        {% if mipmap.is_gradient() %}gradients.anisotropy,{% endif %}
+        geometry_tbn,
+    );
+
+    // Iridescence sampling
+    let iridescence_factor = _pbr_iridescence{{ mipmap.suffix() }}(
+        iridescence,
+        texture_uv(
+            attribute_data_offset,
+            triangle_indices,
+            barycentric,
+            iridescence.tex_info,
+            vertex_attribute_stride,
+            uv_sets_index,
+        ),
+        {% if mipmap.is_gradient() %}gradients.iridescence,{% endif %}
+    );
+
+    let iridescence_thickness = _pbr_iridescence_thickness{{ mipmap.suffix() }}(
+        iridescence,
+        texture_uv(
+            attribute_data_offset,
+            triangle_indices,
+            barycentric,
+            iridescence.thickness_tex_info,
+            vertex_attribute_stride,
+            uv_sets_index,
+        ),
+        {% if mipmap.is_gradient() %}gradients.iridescence_thickness,{% endif %}
+    );
+
     return PbrMaterialColor(
         base,
         metallic_roughness,
@@ -251,9 +331,14 @@ fn pbr_get_material_color{{ mipmap.suffix() }}(
         specular_color_factor,
         ior,
         transmission_factor,
+        // Diffuse transmission
+        diffuse_transmission_factor,
+        diffuse_transmission_color,
         volume_thickness,
         volume.attenuation_distance,
         volume.attenuation_color,
+        // Dispersion
+        pbr_material_load_dispersion(material.dispersion_index),
         // Clearcoat
         clearcoat_factor,
         clearcoat_roughness_factor,
@@ -261,6 +346,15 @@ fn pbr_get_material_color{{ mipmap.suffix() }}(
         // Sheen
         sheen_color_factor,
         sheen_roughness_factor,
+        // Anisotropy
+        anisotropy_value.w,
+        anisotropy_value.xyz,
+        // Iridescence
+        iridescence_factor,
+        iridescence.ior,
+        iridescence_thickness,
+        // Screen-space AO is filled in by the opaque pass
+        1.0,
     );
 }
 
@@ -393,6 +487,40 @@ fn _pbr_transmission{{ mipmap.suffix() }}(
     return factor;
 }
 
+// ============================================================================
+// Diffuse transmission (KHR_materials_diffuse_transmission)
+// ============================================================================
+
+// Diffuse transmission factor (A channel)
+fn _pbr_diffuse_transmission{{ mipmap.suffix() }}(
+    diffuse_transmission: PbrDiffuseTransmission,
+    attribute_uv: vec2<f32>,
+    {% if mipmap.is_gradient() %}uv_derivs: UvDerivs,{% endif %}
+) -> f32 {
+    // Early exit: nothing passes through if factor is 0
+    if (diffuse_transmission.factor == 0.0) {
+        return 0.0;
+    }
+    var factor = diffuse_transmission.factor;
+    if diffuse_transmission.tex_info.exists {
+        factor *= {{ mipmap.sample_fn() }}(diffuse_transmission.tex_info, attribute_uv{% if mipmap.is_gradient() %}, uv_derivs{% endif %}).a;
+    }
+    return factor;
+}
+
+// Diffuse transmission color (RGB)
+fn _pbr_diffuse_transmission_color{{ mipmap.suffix() }}(
+    diffuse_transmission: PbrDiffuseTransmission,
+    attribute_uv: vec2<f32>,
+    {% if mipmap.is_gradient() %}uv_derivs: UvDerivs,{% endif %}
+) -> vec3<f32> {
+    var color = diffuse_transmission.color_factor;
+    if (diffuse_transmission.factor != 0.0 && diffuse_transmission.color_tex_info.exists) {
+        color *= {{ mipmap.sample_fn() }}(diffuse_transmission.color_tex_info, attribute_uv{% if mipmap.is_gradient() %}, uv_derivs{% endif %}).rgb;
+    }
+    return color;
+}
+
 // Volume thickness
 fn _pbr_volume_thickness{{ mipmap.suffix() }}(
     volume: PbrVolume,
@@ -500,6 +628,69 @@ fn _pbr_sheen_roughness{{ mipmap.suffix() }}(
     return roughness;
 }
 
+// ============================================================================
+// Anisotropy (KHR_materials_anisotropy)
+// ============================================================================
+
+// World-space anisotropy direction (xyz) and strength (w)
+// Direction in the RG channels, strength in B
+fn _pbr_anisotropy{{ mipmap.suffix() }}(
+    anisotropy: PbrAnisotropy,
+    attribute_uv: vec2<f32>,
+    {% if mipmap.is_gradient() %}uv_derivs: UvDerivs,{% endif %}
+    geometry_tbn: TBN,
+) -> vec4<f32> {
+    // Early exit: isotropic
+    if (anisotropy.strength == 0.0) {
+        return vec4<f32>(geometry_tbn.T, 0.0);
+    }
+    var tex_sample = vec3<f32>(1.0, 0.5, 1.0);
+    if anisotropy.tex_info.exists {
+        tex_sample = {{ mipmap.sample_fn() }}(anisotropy.tex_info, attribute_uv{% if mipmap.is_gradient() %}, uv_derivs{% endif %}).rgb;
+    }
+    return pbr_anisotropy_direction_strength(
+        anisotropy,
+        tex_sample,
+        geometry_tbn.T,
+        geometry_tbn.B,
+        geometry_tbn.N,
+    );
+}
+
+// ============================================================================
+// Iridescence (KHR_materials_iridescence)
+// ============================================================================
+
+// Iridescence intensity factor (R channel)
+fn _pbr_iridescence{{ mipmap.suffix() }}(
+    iridescence: PbrIridescence,
+    attribute_uv: vec2<f32>,
+    {% if mipmap.is_gradient() %}uv_derivs: UvDerivs,{% endif %}
+) -> f32 {
+    // Early exit: no iridescence if factor is 0
+    if (iridescence.factor == 0.0) {
+        return 0.0;
+    }
+    var factor = iridescence.factor;
+    if iridescence.tex_info.exists {
+        factor *= {{ mipmap.sample_fn() }}(iridescence.tex_info, attribute_uv{% if mipmap.is_gradient() %}, uv_derivs{% endif %}).r;
+    }
+    return factor;
+}
+
+// Iridescence thickness in nanometers, the texture (G channel) blends between min and max
+fn _pbr_iridescence_thickness{{ mipmap.suffix() }}(
+    iridescence: PbrIridescence,
+    attribute_uv: vec2<f32>,
+    {% if mipmap.is_gradient() %}uv_derivs: UvDerivs,{% endif %}
+) -> f32 {
+    if (iridescence.factor == 0.0 || !iridescence.thickness_tex_info.exists) {
+        return iridescence.thickness_max;
+    }
+    let t = {{ mipmap.sample_fn() }}(iridescence.thickness_tex_info, attribute_uv{% if mipmap.is_gradient() %}, uv_derivs{% endif %}).g;
+    return mix(iridescence.thickness_min, iridescence.thickness_max, t);
+}
+
 // ============================================================================
 // Unlit Material Color Computation
 // ============================================================================
@@ -61,6 +61,7 @@ fn msaa_process_sample(
     coords: vec2<i32>,
     screen_dims_f32: vec2<f32>,
     lights_info: LightsInfo,
+    light_tile: LightTile,
     standard_coordinates: StandardCoordinates,
     textures: MsaaSampleTextures,
 ) -> MsaaSampleResult {
@@ -142,6 +143,7 @@ fn msaa_process_sample(
                     sample_uv_sets_idx,
                     sample_tbn,
                     textures.bary_derivs,
+                    coords,
                 );
             {% when MipmapMode::None %}
                 let mat_color = compute_material_color(
@@ -154,6 +156,7 @@ fn msaa_process_sample(
                     sample_stride,
                     sample_uv_sets_idx,
                     sample_tbn,
+                    coords,
                 );
         {% endmatch %}
 
@@ -167,7 +170,14 @@ fn msaa_process_sample(
             mat_color,
             standard_coordinates.surface_to_camera,
             standard_coordinates.world_position,
-            lights_info
+            lights_info,
+            light_tile,
+            shadow_receiver(
+                sample_mesh_meta.receive_shadows == 1u,
+                sample_normal,
+                standard_coordinates.world_position,
+                camera
+            )
         );
         return MsaaSampleResult(color, mat_color.base.a, true);
     }
@@ -183,6 +193,7 @@ fn msaa_resolve_samples(
 ) -> MsaaResolveResult {
     // Use shared standard_coordinates for all samples (matches main branch)
     let standard_coordinates = get_standard_coordinates(coords, screen_dims);
+    let light_tile = get_light_tile(vec2<u32>(coords), screen_dims.x, LIGHT_TILE_LIST_OPAQUE);
 
     // Load and process each sample
     let textures_0 = msaa_load_sample_textures(coords, 0u);
@@ -190,10 +201,10 @@ fn msaa_resolve_samples(
     let textures_2 = msaa_load_sample_textures(coords, 2u);
     let textures_3 = msaa_load_sample_textures(coords, 3u);
 
-    let result_0 = msaa_process_sample(camera, coords, screen_dims_f32, lights_info, standard_coordinates, textures_0);
-    let result_1 = msaa_process_sample(camera, coords, screen_dims_f32, lights_info, standard_coordinates, textures_1);
-    let result_2 = msaa_process_sample(camera, coords, screen_dims_f32, lights_info, standard_coordinates, textures_2);
-    let result_3 = msaa_process_sample(camera, coords, screen_dims_f32, lights_info, standard_coordinates, textures_3);
+    let result_0 = msaa_process_sample(camera, coords, screen_dims_f32, lights_info, light_tile, standard_coordinates, textures_0);
+    let result_1 = msaa_process_sample(camera, coords, screen_dims_f32, lights_info, light_tile, standard_coordinates, textures_1);
+    let result_2 = msaa_process_sample(camera, coords, screen_dims_f32, lights_info, light_tile, standard_coordinates, textures_2);
+    let result_3 = msaa_process_sample(camera, coords, screen_dims_f32, lights_info, light_tile, standard_coordinates, textures_3);
 
     // Accumulate results
     var color_sum = vec3<f32>(0.0);
@@ -223,6 +234,7 @@ fn msaa_resolve_samples(
             uv_sets_index: u32,
             geometry_tbn: TBN,
             bary_derivs: vec4<f32>,
+            coords: vec2<i32>,
         ) -> PbrMaterialColor {
             let gradients = pbr_get_gradients(
                 barycentric,
@@ -236,7 +248,7 @@ fn msaa_resolve_samples(
                 camera.view
             );
 
-            return pbr_get_material_color_grad(
+            var material_color = pbr_get_material_color_grad(
                 triangle_indices,
                 attribute_data_offset,
                 triangle_index,
@@ -247,6 +259,8 @@ fn msaa_resolve_samples(
                 gradients,
                 geometry_tbn,
             );
+            material_color.indirect_occlusion = load_indirect_occlusion(coords);
+            return material_color;
         }
     {% when MipmapMode::None %}
         // Compute material color without mipmapping
@@ -260,8 +274,9 @@ fn msaa_resolve_samples(
             vertex_attribute_stride: u32,
             uv_sets_index: u32,
             geometry_tbn: TBN,
+            coords: vec2<i32>,
         ) -> PbrMaterialColor {
-            return pbr_get_material_color_no_mips(
+            var material_color = pbr_get_material_color_no_mips(
                 triangle_indices,
                 attribute_data_offset,
                 triangle_index,
@@ -271,5 +286,12 @@ fn msaa_resolve_samples(
                 uv_sets_index,
                 geometry_tbn,
             );
+            material_color.indirect_occlusion = load_indirect_occlusion(coords);
+            return material_color;
         }
 {% endmatch %}
+
+// The SSAO pass stores the occlusion amount; it stays zeroed (unoccluded) while SSAO is off
+fn load_indirect_occlusion(coords: vec2<i32>) -> f32 {
+    return 1.0 - textureLoad(ambient_occlusion_tex, coords, 0).r;
+}
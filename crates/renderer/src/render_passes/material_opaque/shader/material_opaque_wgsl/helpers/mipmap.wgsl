@@ -227,9 +227,12 @@ fn pbr_get_gradients(
     // Load extension data on-demand for gradient computation
     let specular = pbr_material_load_specular(material.specular_index);
     let transmission = pbr_material_load_transmission(material.transmission_index);
+    let diffuse_transmission = pbr_material_load_diffuse_transmission(material.diffuse_transmission_index);
     let volume = pbr_material_load_volume(material.volume_index);
     let clearcoat = pbr_material_load_clearcoat(material.clearcoat_index);
     let sheen = pbr_material_load_sheen(material.sheen_index);
+    let anisotropy = pbr_material_load_anisotropy(material.anisotropy_index);
+    let iridescence = pbr_material_load_iridescence(material.iridescence_index);
 
     if (material.base_color_tex_info.exists) {
         out.base_color = get_uv_derivatives(
@@ -335,6 +338,33 @@ fn pbr_get_gradients(
         );
     }
 
+    // Diffuse transmission textures
+    if (diffuse_transmission.tex_info.exists) {
+        out.diffuse_transmission = get_uv_derivatives(
+            barycentric,
+            bary_derivs,
+            triangle_indices,
+            attribute_data_offset, vertex_attribute_stride,
+            uv_sets_index,
+            diffuse_transmission.tex_info,
+            world_normal,
+            view_matrix
+        );
+    }
+
+    if (diffuse_transmission.color_tex_info.exists) {
+        out.diffuse_transmission_color = get_uv_derivatives(
+            barycentric,
+            bary_derivs,
+            triangle_indices,
+            attribute_data_offset, vertex_attribute_stride,
+            uv_sets_index,
+            diffuse_transmission.color_tex_info,
+            world_normal,
+            view_matrix
+        );
+    }
+
     if (volume.thickness_tex_info.exists) {
         out.volume_thickness = get_uv_derivatives(
             barycentric,
@@ -415,6 +445,47 @@ fn pbr_get_gradients(
         );
     }
 
+    // Anisotropy texture
+    if (anisotropy.tex_info.exists) {
+        out.anisotropy = get_uv_derivatives(
+            barycentric,
+            bary_derivs,
+            triangle_indices,
+            attribute_data_offset, vertex_attribute_stride,
+            uv_sets_index,
+            anisotropy.tex_info,
+            world_normal,
+            view_matrix
+        );
+    }
+
+    // Iridescence textures
+    if (iridescence.tex_info.exists) {
+        out.iridescence = get_uv_derivatives(
+            barycentric,
+            bary_derivs,
+            triangle_indices,
+            attribute_data_offset, vertex_attribute_stride,
+            uv_sets_index,
+            iridescence.tex_info,
+            world_normal,
+            view_matrix
+        );
+    }
+
+    if (iridescence.thickness_tex_info.exists) {
+        out.iridescence_thickness = get_uv_derivatives(
+            barycentric,
+            bary_derivs,
+            triangle_indices,
+            attribute_data_offset, vertex_attribute_stride,
+            uv_sets_index,
+            iridescence.thickness_tex_info,
+            world_normal,
+            view_matrix
+        );
+    }
+
     return out;
 }
 
@@ -8,7 +8,7 @@ use awsm_renderer_core::bind_groups::{
     TextureBindingLayout,
 };
 use awsm_renderer_core::buffers::BufferBinding;
-use awsm_renderer_core::texture::TextureViewDimension;
+use awsm_renderer_core::texture::{TextureSampleType, TextureViewDimension};
 use indexmap::IndexSet;
 
 use crate::bind_group_layout::{BindGroupLayoutCacheKey, BindGroupLayoutCacheKeyEntry};
@@ -256,6 +256,47 @@ impl MaterialTransparentBindGroups {
                         visibility_fragment: true,
                         visibility_compute: false,
                     },
+                    // light tile indices
+                    BindGroupLayoutCacheKeyEntry {
+                        resource: BindGroupLayoutResource::Buffer(
+                            BufferBindingLayout::new()
+                                .with_binding_type(BufferBindingType::ReadOnlyStorage),
+                        ),
+                        visibility_vertex: false,
+                        visibility_fragment: true,
+                        visibility_compute: false,
+                    },
+                    // shadows info
+                    BindGroupLayoutCacheKeyEntry {
+                        resource: BindGroupLayoutResource::Buffer(
+                            BufferBindingLayout::new()
+                                .with_binding_type(BufferBindingType::Uniform),
+                        ),
+                        visibility_vertex: false,
+                        visibility_fragment: true,
+                        visibility_compute: false,
+                    },
+                    // shadow maps
+                    BindGroupLayoutCacheKeyEntry {
+                        resource: BindGroupLayoutResource::Texture(
+                            TextureBindingLayout::new()
+                                .with_view_dimension(TextureViewDimension::N2dArray)
+                                .with_sample_type(TextureSampleType::Depth),
+                        ),
+                        visibility_vertex: false,
+                        visibility_fragment: true,
+                        visibility_compute: false,
+                    },
+                    // shadow sampler
+                    BindGroupLayoutCacheKeyEntry {
+                        resource: BindGroupLayoutResource::Sampler(
+                            SamplerBindingLayout::new()
+                                .with_binding_type(SamplerBindingType::Comparison),
+                        ),
+                        visibility_vertex: false,
+                        visibility_fragment: true,
+                        visibility_compute: false,
+                    },
                 ],
             },
         )?;
@@ -503,6 +544,27 @@ impl MaterialTransparentBindGroups {
             BindGroupResource::Buffer(BufferBinding::new(&ctx.lights.gpu_punctual_buffer)),
         ));
 
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&ctx.lights.tiles.gpu_buffer)),
+        ));
+
+        // Shadows
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&ctx.lights.shadows.gpu_info_buffer)),
+        ));
+
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(&ctx.lights.shadows.texture_view)),
+        ));
+
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Sampler(&ctx.lights.shadows.sampler),
+        ));
+
         let descriptor = BindGroupDescriptor::new(
             ctx.bind_group_layouts
                 .get(self.lights_bind_group_layout_key)?,
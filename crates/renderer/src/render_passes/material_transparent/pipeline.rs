@@ -42,6 +42,9 @@ use crate::textures::Textures;
 pub struct MaterialTransparentPipelines {
     pipeline_layout_key: PipelineLayoutKey,
     render_pipeline_keys: SecondaryMap<MeshKey, RenderPipelineKey>,
+    // only populated while transparent picking is enabled
+    pick_alpha_threshold: Option<f32>,
+    pick_render_pipeline_keys: SecondaryMap<MeshKey, RenderPipelineKey>,
 }
 
 impl MaterialTransparentPipelines {
@@ -66,10 +69,30 @@ impl MaterialTransparentPipelines {
         Ok(Self {
             pipeline_layout_key,
             render_pipeline_keys: SecondaryMap::new(),
+            pick_alpha_threshold: None,
+            pick_render_pipeline_keys: SecondaryMap::new(),
         })
     }
 
+    /// Sets the alpha threshold for picking variants, or `None` to stop creating them.
+    ///
+    /// Only affects pipelines created afterwards, see [`Self::set_render_pipeline_key`].
+    pub fn set_pick_alpha_threshold(&mut self, alpha_threshold: Option<f32>) {
+        self.pick_alpha_threshold = alpha_threshold;
+
+        if alpha_threshold.is_none() {
+            self.pick_render_pipeline_keys.clear();
+        }
+    }
+
+    /// Returns the alpha threshold for picking variants, if transparent picking is enabled.
+    pub fn pick_alpha_threshold(&self) -> Option<f32> {
+        self.pick_alpha_threshold
+    }
+
     /// Creates and caches a render pipeline for a mesh.
+    ///
+    /// While transparent picking is enabled, this also creates the mesh's picking variant.
     pub async fn set_render_pipeline_key(
         &mut self,
         gpu: &AwsmRendererWebGpu,
@@ -94,8 +117,58 @@ impl MaterialTransparentPipelines {
             msaa_sample_count: anti_aliasing.msaa_sample_count,
             mipmaps: anti_aliasing.mipmap,
             instancing_transforms: mesh.instanced,
+            pick_alpha_threshold_bits: None,
+        };
+
+        let cull_mode = if mesh.double_sided {
+            CullMode::None
+        } else {
+            CullMode::Back
         };
 
+        match self.pick_alpha_threshold {
+            Some(alpha_threshold) => {
+                let shader_key = shaders
+                    .get_key(
+                        gpu,
+                        ShaderCacheKeyMaterialTransparent {
+                            pick_alpha_threshold_bits: Some(alpha_threshold.to_bits()),
+                            ..shader_cache_key.clone()
+                        },
+                    )
+                    .await?;
+
+                // no blending, the front-most fragment that passes the alpha test wins
+                let color_targets = &[
+                    ColorTargetState::new(render_texture_formats.pick_ids),
+                    ColorTargetState::new(render_texture_formats.pick_surface),
+                ];
+
+                // only ever drawn into the single-sampled pick textures
+                let pick_render_pipeline_key = render_pipeline_key(
+                    gpu,
+                    shaders,
+                    pipelines,
+                    pipeline_layouts,
+                    render_texture_formats.depth,
+                    self.pipeline_layout_key,
+                    shader_key,
+                    vertex_buffer_layouts(mesh, mesh_buffer_info),
+                    color_targets,
+                    None,
+                    cull_mode,
+                    mesh.hud,
+                )
+                .await?;
+
+                self.pick_render_pipeline_keys
+                    .insert(mesh_key, pick_render_pipeline_key);
+            }
+            None => {
+                self.pick_render_pipeline_keys.remove(mesh_key);
+            }
+        }
+
         let shader_key = shaders.get_key(gpu, shader_cache_key).await?;
 
         let color_targets = &[
@@ -122,11 +195,7 @@ impl MaterialTransparentPipelines {
             vertex_buffer_layouts(mesh, mesh_buffer_info),
             color_targets,
             anti_aliasing.msaa_sample_count,
-            if mesh.double_sided {
-                CullMode::None
-            } else {
-                CullMode::Back
-            },
+            cull_mode,
             mesh.hud,
         )
         .await?;
@@ -142,15 +211,24 @@ impl MaterialTransparentPipelines {
         self.render_pipeline_keys.get(mesh_key).cloned()
     }
 
+    /// Returns the cached picking pipeline key for a mesh, if transparent picking is enabled.
+    pub fn get_pick_render_pipeline_key(&self, mesh_key: MeshKey) -> Option<RenderPipelineKey> {
+        self.pick_render_pipeline_keys.get(mesh_key).cloned()
+    }
+
     /// Copies a cached pipeline key from one mesh to another.
     pub fn clone_render_pipeline_key(&mut self, from: MeshKey, to: MeshKey) {
         if let Some(key) = self.render_pipeline_keys.get(from).cloned() {
             self.render_pipeline_keys.insert(to, key);
         }
+        if let Some(key) = self.pick_render_pipeline_keys.get(from).cloned() {
+            self.pick_render_pipeline_keys.insert(to, key);
+        }
     }
 
     /// Removes the cached render pipeline key for a mesh, if present.
     pub fn remove_render_pipeline_key(&mut self, mesh_key: MeshKey) -> Option<RenderPipelineKey> {
+        self.pick_render_pipeline_keys.remove(mesh_key);
         self.render_pipeline_keys.remove(mesh_key)
     }
 }
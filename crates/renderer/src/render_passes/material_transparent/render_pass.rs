@@ -35,8 +35,11 @@ impl MaterialTransparentRenderPass {
         &mut self,
         ctx: &mut RenderPassInitContext<'_>,
     ) -> Result<()> {
+        let pick_alpha_threshold = self.pipelines.pick_alpha_threshold();
         self.bind_groups = self.bind_groups.clone_because_texture_pool_changed(ctx)?;
         self.pipelines = MaterialTransparentPipelines::new(ctx, &self.bind_groups).await?;
+        self.pipelines
+            .set_pick_alpha_threshold(pick_alpha_threshold);
 
         Ok(())
     }
@@ -17,6 +17,10 @@ pub struct ShaderCacheKeyMaterialTransparent {
     pub texture_pool_samplers_len: u32,
     pub msaa_sample_count: Option<u32>,
     pub mipmaps: bool,
+    /// Set for the picking variant, which writes pick IDs instead of color.
+    ///
+    /// Stored as the bits of the `f32` alpha threshold so the key stays hashable.
+    pub pick_alpha_threshold_bits: Option<u32>,
 }
 
 impl From<ShaderCacheKeyMaterialTransparent> for ShaderCacheKey {
@@ -19,6 +19,10 @@
 @group(1) @binding(5) var brdf_lut_sampler: sampler;
 @group(1) @binding(6) var<uniform> lights_info: LightsInfoPacked;
 @group(1) @binding(7) var<storage, read> lights: array<LightPacked>;
+@group(1) @binding(8) var<storage, read> light_tile_indices: array<u32>;
+@group(1) @binding(9) var<uniform> shadows: ShadowsPacked;
+@group(1) @binding(10) var shadow_maps: texture_depth_2d_array;
+@group(1) @binding(11) var shadow_sampler: sampler_comparison;
 
 
 {% for i in 0..texture_pool_arrays_len %}
@@ -12,19 +12,62 @@ struct FragmentInput {
     {% for i in 0..uv_sets %}
         @location({{ in_uv_set_start + i }}) uv_{{ i }}: vec2<f32>,
     {% endfor %}
+
+    {% if picking %}
+    @location({{ in_uv_set_start + uv_sets }}) @interpolate(flat) instance_index: u32,
+    {% endif %}
 }
 
+{% if picking %}
+// make sure these match the picker compute shader
+const PICK_LAYER_TRANSPARENT: u32 = 1u;
+const PICK_LAYER_HUD: u32 = 2u;
+
+struct FragmentOutput {
+    // Rgba32uint: mesh key high, mesh key low, instance index, pick layer (0 = nothing drawn)
+    @location(0) pick_ids: vec4<u32>,
+    // Rgba32float: world-space normal, depth
+    @location(1) pick_surface: vec4<f32>,
+}
+{% else %}
 struct FragmentOutput {
     // Rgba16float
     @location(0) color: vec4<f32>,
 }
+{% endif %}
+
+// Sample transmission background, split per color channel for dispersive volumes
+// (KHR_materials_dispersion): each channel refracts with its own IOR, red least and blue most
+fn sample_transmission_background(
+    frag_pos: vec4<f32>,
+    world_position: vec3<f32>,
+    normal: vec3<f32>,
+    view_dir: vec3<f32>,
+    ior: f32,
+    dispersion: f32,
+    roughness: f32,
+    thickness: f32,
+    camera: Camera,
+) -> vec3<f32> {
+    // Only volumes refract, so thin-walled materials have nothing to split
+    if (dispersion > 0.0 && thickness > 0.0) {
+        let iors = dispersion_iors(ior, dispersion);
+        return vec3<f32>(
+            sample_transmission_background_ior(frag_pos, world_position, normal, view_dir, iors.r, roughness, thickness, camera).r,
+            sample_transmission_background_ior(frag_pos, world_position, normal, view_dir, iors.g, roughness, thickness, camera).g,
+            sample_transmission_background_ior(frag_pos, world_position, normal, view_dir, iors.b, roughness, thickness, camera).b,
+        );
+    }
+
+    return sample_transmission_background_ior(frag_pos, world_position, normal, view_dir, ior, roughness, thickness, camera);
+}
 
 // Sample transmission background from the opaque render with screen-space refraction
 // Falls back to IBL environment when refracted ray goes outside screen bounds
 // Uses the physically-based approach from glTF sample renderer:
 // 1. Compute refracted ray exit point in world space
 // 2. Project to screen space using view/projection matrices
-fn sample_transmission_background(
+fn sample_transmission_background_ior(
     frag_pos: vec4<f32>,
     world_position: vec3<f32>,
     normal: vec3<f32>,
@@ -182,6 +225,48 @@ fn sample_transmission_background(
     return background;
 }
 
+{% if picking %}
+@fragment
+fn fs_main(input: FragmentInput) -> FragmentOutput {
+    var out: FragmentOutput;
+
+    // Same double-sided handling as the color path, so normal mapping sees the same surface
+    var world_normal = input.world_normal;
+    var world_tangent = input.world_tangent;
+    if (!input.front_facing) {
+        world_normal = -world_normal;
+        world_tangent.w = -world_tangent.w;
+    }
+
+    let material_offset = material_mesh_meta.material_offset;
+    let shader_id = material_load_shader_id(material_offset);
+
+    // Only the alpha matters here, there's no need to light anything
+    var base_alpha: f32;
+    if (shader_id == SHADER_ID_UNLIT) {
+        let unlit_material = unlit_get_material(material_offset);
+        base_alpha = unlit_get_material_color(unlit_material, input).base.a;
+    } else {
+        let material = pbr_get_material(material_offset);
+        base_alpha = pbr_get_material_color(material, world_normal, world_tangent, input).base.a;
+    }
+
+    // Nearly invisible fragments leave the pick to whatever is behind them
+    if (base_alpha < f32({{ pick_alpha_threshold }})) {
+        discard;
+    }
+
+    out.pick_ids = vec4<u32>(
+        material_mesh_meta.mesh_key_high,
+        material_mesh_meta.mesh_key_low,
+        input.instance_index,
+        select(PICK_LAYER_TRANSPARENT, PICK_LAYER_HUD, material_mesh_meta.is_hud == 1u),
+    );
+    out.pick_surface = vec4<f32>(normalize(world_normal), input.frag_pos.z);
+
+    return out;
+}
+{% else %}
 @fragment
 fn fs_main(input: FragmentInput) -> FragmentOutput {
     var out: FragmentOutput;
@@ -239,6 +324,25 @@ fn fs_main(input: FragmentInput) -> FragmentOutput {
         // Get lighting info
         let lights_info = get_lights_info();
 
+        // HUD meshes are drawn over the scene regardless of its depth, so they skip the culled tiles
+        var light_tile: LightTile;
+        if (material_mesh_meta.is_hud == 1u) {
+            light_tile = get_light_tile_unculled(lights_info);
+        } else {
+            light_tile = get_light_tile(
+                vec2<u32>(input.frag_pos.xy),
+                textureDimensions(opaque_tex).x,
+                LIGHT_TILE_LIST_TRANSPARENT
+            );
+        }
+
+        let receiver = shadow_receiver(
+            material_mesh_meta.receive_shadows == 1u,
+            world_normal,
+            input.world_position,
+            camera
+        );
+
         // Check if we need screen-space transmission
         let metallic = clamp(material_color.metallic_roughness.x, 0.0, 1.0);
         let effective_transmission = material_color.transmission * (1.0 - metallic);
@@ -254,6 +358,7 @@ fn fs_main(input: FragmentInput) -> FragmentOutput {
                 material_color.normal,
                 -surface_to_camera,  // view direction (towards surface)
                 material_color.ior,
+                material_color.dispersion,
                 roughness,
                 material_color.volume_thickness,
                 camera,
@@ -265,6 +370,8 @@ fn fs_main(input: FragmentInput) -> FragmentOutput {
                 surface_to_camera,
                 input.world_position,
                 lights_info,
+                light_tile,
+                receiver,
                 transmission_background
             );
         } else {
@@ -273,7 +380,9 @@ fn fs_main(input: FragmentInput) -> FragmentOutput {
                 material_color,
                 surface_to_camera,
                 input.world_position,
-                lights_info
+                lights_info,
+                light_tile,
+                receiver
             );
         }
 
@@ -286,3 +395,4 @@ fn fs_main(input: FragmentInput) -> FragmentOutput {
 
     return out;
 }
+{% endif %}
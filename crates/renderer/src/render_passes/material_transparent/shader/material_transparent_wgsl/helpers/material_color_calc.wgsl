@@ -30,9 +30,12 @@ fn pbr_get_material_color(
     let ior = pbr_material_load_ior(material.ior_index);
     let specular = pbr_material_load_specular(material.specular_index);
     let transmission = pbr_material_load_transmission(material.transmission_index);
+    let diffuse_transmission = pbr_material_load_diffuse_transmission(material.diffuse_transmission_index);
     let volume = pbr_material_load_volume(material.volume_index);
     let clearcoat = pbr_material_load_clearcoat(material.clearcoat_index);
     let sheen = pbr_material_load_sheen(material.sheen_index);
+    let anisotropy = pbr_material_load_anisotropy(material.anisotropy_index);
+    let iridescence = pbr_material_load_iridescence(material.iridescence_index);
 
     var base = pbr_material_base_color(material, fragment_input);
 
@@ -58,6 +61,8 @@ fn pbr_get_material_color(
     let specular_factor = pbr_specular(specular, fragment_input);
     let specular_color_factor = pbr_specular_color(specular, fragment_input);
     let transmission_factor = pbr_transmission(transmission, fragment_input);
+    let diffuse_transmission_factor = pbr_diffuse_transmission(diffuse_transmission, fragment_input);
+    let diffuse_transmission_color = pbr_diffuse_transmission_color(diffuse_transmission, fragment_input);
     let volume_thickness = pbr_volume_thickness(volume, fragment_input);
 
     // Clearcoat
@@ -69,6 +74,13 @@ fn pbr_get_material_color(
     let sheen_color_factor = pbr_sheen_color(sheen, fragment_input);
     let sheen_roughness_factor = pbr_sheen_roughness(sheen, fragment_input);
 
+    // Anisotropy
+    let anisotropy_value = pbr_anisotropy(anisotropy, world_normal, world_tangent, fragment_input);
+
+    // Iridescence
+    let iridescence_factor = pbr_iridescence(iridescence, fragment_input);
+    let iridescence_thickness = pbr_iridescence_thickness(iridescence, fragment_input);
+
     return PbrMaterialColor(
         base,
         metallic_roughness,
@@ -79,9 +91,14 @@ fn pbr_get_material_color(
         specular_color_factor,
         ior,
         transmission_factor,
+        // Diffuse transmission
+        diffuse_transmission_factor,
+        diffuse_transmission_color,
         volume_thickness,
         volume.attenuation_distance,
         volume.attenuation_color,
+        // Dispersion
+        pbr_material_load_dispersion(material.dispersion_index),
         // Clearcoat
         clearcoat_factor,
         clearcoat_roughness_factor,
@@ -89,6 +106,15 @@ fn pbr_get_material_color(
         // Sheen
         sheen_color_factor,
         sheen_roughness_factor,
+        // Anisotropy
+        anisotropy_value.w,
+        anisotropy_value.xyz,
+        // Iridescence
+        iridescence_factor,
+        iridescence.ior,
+        iridescence_thickness,
+        // Screen-space AO is filled in by the opaque pass
+        1.0,
     );
 }
 
@@ -223,6 +249,40 @@ fn pbr_transmission(
     return factor;
 }
 
+// ============================================================================
+// Diffuse transmission (KHR_materials_diffuse_transmission)
+// ============================================================================
+
+// Sample diffuse transmission texture (A channel) and apply factor
+fn pbr_diffuse_transmission(
+    diffuse_transmission: PbrDiffuseTransmission,
+    fragment_input: FragmentInput
+) -> f32 {
+    // Early exit: nothing passes through if factor is 0
+    if (diffuse_transmission.factor == 0.0) {
+        return 0.0;
+    }
+    var factor = diffuse_transmission.factor;
+    if diffuse_transmission.tex_info.exists {
+        let uv = texture_uv(diffuse_transmission.tex_info, fragment_input);
+        factor *= texture_pool_sample(diffuse_transmission.tex_info, uv).a;
+    }
+    return factor;
+}
+
+// Sample diffuse transmission color texture (RGB) and apply color factor
+fn pbr_diffuse_transmission_color(
+    diffuse_transmission: PbrDiffuseTransmission,
+    fragment_input: FragmentInput
+) -> vec3<f32> {
+    var color = diffuse_transmission.color_factor;
+    if (diffuse_transmission.factor != 0.0 && diffuse_transmission.color_tex_info.exists) {
+        let uv = texture_uv(diffuse_transmission.color_tex_info, fragment_input);
+        color *= texture_pool_sample(diffuse_transmission.color_tex_info, uv).rgb;
+    }
+    return color;
+}
+
 // Sample volume thickness texture (G channel) and apply factor
 fn pbr_volume_thickness(
     volume: PbrVolume,
@@ -336,6 +396,70 @@ fn pbr_sheen_roughness(
     return roughness;
 }
 
+// ============================================================================
+// Anisotropy (KHR_materials_anisotropy)
+// ============================================================================
+
+// Sample anisotropy texture (direction in RG, strength in B) and return
+// the world-space direction (xyz) and strength (w)
+fn pbr_anisotropy(
+    anisotropy: PbrAnisotropy,
+    world_normal: vec3<f32>,
+    world_tangent: vec4<f32>,
+    fragment_input: FragmentInput
+) -> vec4<f32> {
+    let N = normalize(world_normal);
+    let T = orthonormal_tangent_from_vertex(N, world_tangent.xyz);
+
+    // Early exit: isotropic
+    if (anisotropy.strength == 0.0) {
+        return vec4<f32>(T, 0.0);
+    }
+
+    var tex_sample = vec3<f32>(1.0, 0.5, 1.0);
+    if anisotropy.tex_info.exists {
+        let uv = texture_uv(anisotropy.tex_info, fragment_input);
+        tex_sample = texture_pool_sample(anisotropy.tex_info, uv).rgb;
+    }
+
+    let B = cross(N, T) * world_tangent.w;
+    return pbr_anisotropy_direction_strength(anisotropy, tex_sample, T, B, N);
+}
+
+// ============================================================================
+// Iridescence (KHR_materials_iridescence)
+// ============================================================================
+
+// Sample iridescence texture (R channel) and apply factor
+fn pbr_iridescence(
+    iridescence: PbrIridescence,
+    fragment_input: FragmentInput
+) -> f32 {
+    // Early exit: no iridescence if factor is 0
+    if (iridescence.factor == 0.0) {
+        return 0.0;
+    }
+    var factor = iridescence.factor;
+    if iridescence.tex_info.exists {
+        let uv = texture_uv(iridescence.tex_info, fragment_input);
+        factor *= texture_pool_sample(iridescence.tex_info, uv).r;
+    }
+    return factor;
+}
+
+// Sample iridescence thickness texture (G channel) and blend between min and max, in nanometers
+fn pbr_iridescence_thickness(
+    iridescence: PbrIridescence,
+    fragment_input: FragmentInput
+) -> f32 {
+    if (iridescence.factor == 0.0 || !iridescence.thickness_tex_info.exists) {
+        return iridescence.thickness_max;
+    }
+    let uv = texture_uv(iridescence.thickness_tex_info, fragment_input);
+    let t = texture_pool_sample(iridescence.thickness_tex_info, uv).g;
+    return mix(iridescence.thickness_min, iridescence.thickness_max, t);
+}
+
 // ============================================================================
 // Unlit Material Color Computation
 // ============================================================================
@@ -1,6 +1,9 @@
 //***** MAIN *****
 struct VertexInput {
     @builtin(vertex_index) vertex_index: u32,
+    {% if picking %}
+    @builtin(instance_index) instance_index: u32,
+    {% endif %}
     @location(0) position: vec3<f32>,      // Model-space position
     @location(1) normal: vec3<f32>,        // Model-space normal
     @location(2) tangent: vec4<f32>,       // Model-space tangent (w = handedness)
@@ -34,6 +37,11 @@ struct VertexOutput {
     {% for i in 0..uv_sets %}
         @location({{ out_uv_set_start + i }}) uv_{{ i }}: vec2<f32>,
     {% endfor %}
+
+    {% if picking %}
+    // which instance was drawn, so picks can report it
+    @location({{ out_uv_set_start + uv_sets }}) @interpolate(flat) instance_index: u32,
+    {% endif %}
 }
 
 @vertex
@@ -68,5 +76,9 @@ fn vert_main(input: VertexInput) -> VertexOutput {
         out.uv_{{ i }} = input.uv_{{ i }};
     {% endfor %}
 
+    {% if picking %}
+        out.instance_index = input.instance_index;
+    {% endif %}
+
     return out;
 }
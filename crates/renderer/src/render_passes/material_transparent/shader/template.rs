@@ -103,6 +103,7 @@ pub struct ShaderTemplateTransparentMaterialVertex {
     pub in_color_set_start: u32,
     pub out_uv_set_start: u32,
     pub out_color_set_start: u32,
+    pub picking: bool,
 }
 
 impl ShaderTemplateTransparentMaterialVertex {
@@ -131,6 +132,7 @@ impl ShaderTemplateTransparentMaterialVertex {
             in_color_set_start,
             out_uv_set_start,
             out_color_set_start,
+            picking: cache_key.pick_alpha_threshold_bits.is_some(),
         }
     }
 }
@@ -150,6 +152,9 @@ pub struct ShaderTemplateTransparentMaterialFragment {
     pub texture_pool_samplers_len: u32,
     pub transmission_blur_rings: u32, // more rings = higher quality = more expensive
     pub debug: ShaderTemplateMaterialTransparentDebug,
+    pub picking: bool,
+    // fragments below this alpha don't write a pick ID, only meaningful when picking
+    pub pick_alpha_threshold: f32,
 }
 
 impl ShaderTemplateTransparentMaterialFragment {
@@ -169,6 +174,11 @@ impl ShaderTemplateTransparentMaterialFragment {
             texture_pool_samplers_len: cache_key.texture_pool_samplers_len,
             transmission_blur_rings: 3,
             debug: ShaderTemplateMaterialTransparentDebug::new(),
+            picking: cache_key.pick_alpha_threshold_bits.is_some(),
+            pick_alpha_threshold: cache_key
+                .pick_alpha_threshold_bits
+                .map(f32::from_bits)
+                .unwrap_or_default(),
         }
     }
 
@@ -258,6 +268,10 @@ impl ShaderTemplateMaterialTransparent {
     #[cfg(debug_assertions)]
     /// Returns an optional debug label for shader compilation.
     pub fn debug_label(&self) -> Option<&str> {
-        Some("Material Transparent")
+        if self.fragment.picking {
+            Some("Material Transparent (Picking)")
+        } else {
+            Some("Material Transparent")
+        }
     }
 }
@@ -9,16 +9,22 @@ use crate::render_passes::{
         ShaderCacheKeyMaterialOpaque, ShaderCacheKeyMaterialOpaqueEmpty,
     },
     material_transparent::shader::cache_key::ShaderCacheKeyMaterialTransparent,
+    shadow::shader::cache_key::ShaderCacheKeyShadow,
+    ssao::shader::cache_key::ShaderCacheKeySsao,
+    taa::shader::cache_key::ShaderCacheKeyTaa,
 };
 
 /// Cache key variants for render-pass shader templates.
 #[derive(Hash, Debug, Clone, PartialEq, Eq)]
 pub enum ShaderCacheKeyRenderPass {
     Geometry(ShaderCacheKeyGeometry),
+    Shadow(ShaderCacheKeyShadow),
     LightCulling(ShaderCacheKeyLightCulling),
+    Ssao(ShaderCacheKeySsao),
     MaterialOpaque(ShaderCacheKeyMaterialOpaque),
     MaterialOpaqueEmpty(ShaderCacheKeyMaterialOpaqueEmpty),
     MaterialTransparent(ShaderCacheKeyMaterialTransparent),
+    Taa(ShaderCacheKeyTaa),
     Effects(ShaderCacheKeyEffects),
     Display(ShaderCacheKeyDisplay),
 }
@@ -11,6 +11,9 @@ use crate::{
         },
         material_transparent::shader::template::ShaderTemplateMaterialTransparent,
         shader_cache_key::ShaderCacheKeyRenderPass,
+        shadow::shader::template::ShaderTemplateShadow,
+        ssao::shader::template::ShaderTemplateSsao,
+        taa::shader::template::ShaderTemplateTaa,
     },
     shaders::AwsmShaderError,
 };
@@ -18,10 +21,13 @@ use crate::{
 /// Render-pass shader template variants.
 pub enum ShaderTemplateRenderPass {
     Geometry(ShaderTemplateGeometry),
+    Shadow(ShaderTemplateShadow),
     LightCulling(ShaderTemplateLightCulling),
+    Ssao(ShaderTemplateSsao),
     MaterialOpaque(ShaderTemplateMaterialOpaque),
     MaterialOpaqueEmpty(ShaderTemplateMaterialOpaqueEmpty),
     MaterialTransparent(ShaderTemplateMaterialTransparent),
+    Taa(ShaderTemplateTaa),
     Effects(ShaderTemplateEffects),
     Display(ShaderTemplateDisplay),
 }
@@ -34,9 +40,15 @@ impl TryFrom<&ShaderCacheKeyRenderPass> for ShaderTemplateRenderPass {
             ShaderCacheKeyRenderPass::Geometry(cache_key) => {
                 Ok(ShaderTemplateRenderPass::Geometry(cache_key.try_into()?))
             }
+            ShaderCacheKeyRenderPass::Shadow(cache_key) => {
+                Ok(ShaderTemplateRenderPass::Shadow(cache_key.try_into()?))
+            }
             ShaderCacheKeyRenderPass::LightCulling(cache_key) => Ok(
                 ShaderTemplateRenderPass::LightCulling(cache_key.try_into()?),
             ),
+            ShaderCacheKeyRenderPass::Ssao(cache_key) => {
+                Ok(ShaderTemplateRenderPass::Ssao(cache_key.try_into()?))
+            }
             ShaderCacheKeyRenderPass::MaterialOpaque(cache_key) => Ok(
                 ShaderTemplateRenderPass::MaterialOpaque(cache_key.try_into()?),
             ),
@@ -46,6 +58,9 @@ impl TryFrom<&ShaderCacheKeyRenderPass> for ShaderTemplateRenderPass {
             ShaderCacheKeyRenderPass::MaterialTransparent(cache_key) => Ok(
                 ShaderTemplateRenderPass::MaterialTransparent(cache_key.try_into()?),
             ),
+            ShaderCacheKeyRenderPass::Taa(cache_key) => {
+                Ok(ShaderTemplateRenderPass::Taa(cache_key.try_into()?))
+            }
             ShaderCacheKeyRenderPass::Effects(cache_key) => {
                 Ok(ShaderTemplateRenderPass::Effects(cache_key.try_into()?))
             }
@@ -61,10 +76,13 @@ impl ShaderTemplateRenderPass {
     pub fn into_source(self) -> std::result::Result<String, AwsmShaderError> {
         match self {
             ShaderTemplateRenderPass::Geometry(tmpl) => tmpl.into_source(),
+            ShaderTemplateRenderPass::Shadow(tmpl) => tmpl.into_source(),
             ShaderTemplateRenderPass::LightCulling(tmpl) => tmpl.into_source(),
+            ShaderTemplateRenderPass::Ssao(tmpl) => tmpl.into_source(),
             ShaderTemplateRenderPass::MaterialOpaque(tmpl) => tmpl.into_source(),
             ShaderTemplateRenderPass::MaterialOpaqueEmpty(tmpl) => tmpl.into_source(),
             ShaderTemplateRenderPass::MaterialTransparent(tmpl) => tmpl.into_source(),
+            ShaderTemplateRenderPass::Taa(tmpl) => tmpl.into_source(),
             ShaderTemplateRenderPass::Effects(tmpl) => tmpl.into_source(),
             ShaderTemplateRenderPass::Display(tmpl) => tmpl.into_source(),
         }
@@ -75,10 +93,13 @@ impl ShaderTemplateRenderPass {
     pub fn debug_label(&self) -> Option<&str> {
         match self {
             ShaderTemplateRenderPass::Geometry(tmpl) => tmpl.debug_label(),
+            ShaderTemplateRenderPass::Shadow(tmpl) => tmpl.debug_label(),
             ShaderTemplateRenderPass::LightCulling(tmpl) => tmpl.debug_label(),
+            ShaderTemplateRenderPass::Ssao(tmpl) => tmpl.debug_label(),
             ShaderTemplateRenderPass::MaterialOpaque(tmpl) => tmpl.debug_label(),
             ShaderTemplateRenderPass::MaterialOpaqueEmpty(tmpl) => tmpl.debug_label(),
             ShaderTemplateRenderPass::MaterialTransparent(tmpl) => tmpl.debug_label(),
+            ShaderTemplateRenderPass::Taa(tmpl) => tmpl.debug_label(),
             ShaderTemplateRenderPass::Effects(tmpl) => tmpl.debug_label(),
             ShaderTemplateRenderPass::Display(tmpl) => tmpl.debug_label(),
         }
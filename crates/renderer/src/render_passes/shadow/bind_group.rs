@@ -0,0 +1,81 @@
+//! Shadow pass bind group setup.
+
+use awsm_renderer_core::{
+    bind_groups::{
+        BindGroupDescriptor, BindGroupEntry, BindGroupLayoutResource, BindGroupResource,
+        BufferBindingLayout, BufferBindingType,
+    },
+    buffers::BufferBinding,
+};
+
+use crate::error::Result;
+use crate::{
+    bind_group_layout::{
+        BindGroupLayoutCacheKey, BindGroupLayoutCacheKeyEntry, BindGroupLayoutKey,
+    },
+    bind_groups::{AwsmBindGroupError, BindGroupRecreateContext},
+    lights::shadows::SHADOW_VIEW_BYTE_ALIGNMENT,
+    render_passes::RenderPassInitContext,
+};
+
+/// Bind group for the shadow view, the other groups are shared with the geometry pass.
+pub struct ShadowBindGroups {
+    pub bind_group_layout_key: BindGroupLayoutKey,
+    // this is set via `recreate` mechanism
+    _bind_group: Option<web_sys::GpuBindGroup>,
+}
+
+impl ShadowBindGroups {
+    /// Creates the shadow view bind group layout.
+    pub async fn new(ctx: &mut RenderPassInitContext<'_>) -> Result<Self> {
+        let bind_group_layout_cache_key = BindGroupLayoutCacheKey {
+            entries: vec![BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new()
+                        .with_binding_type(BufferBindingType::Uniform)
+                        .with_dynamic_offset(true),
+                ),
+                visibility_vertex: true,
+                visibility_fragment: false,
+                visibility_compute: false,
+            }],
+        };
+
+        let bind_group_layout_key = ctx
+            .bind_group_layouts
+            .get_key(ctx.gpu, bind_group_layout_cache_key)?;
+
+        Ok(Self {
+            bind_group_layout_key,
+            _bind_group: None,
+        })
+    }
+
+    /// Recreates the shadow view bind group.
+    pub fn recreate(&mut self, ctx: &BindGroupRecreateContext<'_>) -> Result<()> {
+        let descriptor = BindGroupDescriptor::new(
+            ctx.bind_group_layouts.get(self.bind_group_layout_key)?,
+            Some("Shadow View"),
+            vec![BindGroupEntry::new(
+                0,
+                BindGroupResource::Buffer(
+                    BufferBinding::new(&ctx.lights.shadows.gpu_views_buffer)
+                        .with_size(SHADOW_VIEW_BYTE_ALIGNMENT),
+                ),
+            )],
+        );
+
+        self._bind_group = Some(ctx.gpu.create_bind_group(&descriptor.into()));
+
+        Ok(())
+    }
+
+    /// Returns the active shadow view bind group.
+    pub fn get_bind_group(
+        &self,
+    ) -> std::result::Result<&web_sys::GpuBindGroup, AwsmBindGroupError> {
+        self._bind_group
+            .as_ref()
+            .ok_or_else(|| AwsmBindGroupError::NotFound("Shadow view".to_string()))
+    }
+}
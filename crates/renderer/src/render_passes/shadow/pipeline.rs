@@ -0,0 +1,162 @@
+//! Shadow pass pipeline setup.
+
+use awsm_renderer_core::compare::CompareFunction;
+use awsm_renderer_core::pipeline::depth_stencil::DepthStencilState;
+use awsm_renderer_core::pipeline::primitive::{
+    CullMode, FrontFace, PrimitiveState, PrimitiveTopology,
+};
+
+use crate::error::{AwsmError, Result};
+use crate::lights::shadows::SHADOW_MAP_FORMAT;
+use crate::pipeline_layouts::{PipelineLayoutCacheKey, PipelineLayoutKey};
+use crate::pipelines::render_pipeline::{RenderPipelineCacheKey, RenderPipelineKey};
+use crate::render_passes::geometry::bind_group::GeometryBindGroups;
+use crate::render_passes::geometry::pipeline::{
+    VERTEX_BUFFER_LAYOUT, VERTEX_BUFFER_LAYOUT_INSTANCING,
+};
+use crate::render_passes::shadow::bind_group::ShadowBindGroups;
+use crate::render_passes::shadow::shader::cache_key::ShaderCacheKeyShadow;
+use crate::render_passes::RenderPassInitContext;
+
+// slope-scaled bias for the rasterized depth, the per-light constant bias is applied
+// when sampling so it can be tweaked without rebuilding pipelines
+const SHADOW_DEPTH_BIAS_SLOPE_SCALE: f32 = 1.5;
+
+/// Pipeline layout and render pipelines for the shadow pass.
+pub struct ShadowPipelines {
+    pub pipeline_layout_key: PipelineLayoutKey,
+    render_pipeline_keys: ShadowRenderPipelineKeys,
+}
+
+impl ShadowPipelines {
+    /// Creates shadow pipeline layouts and cached keys.
+    ///
+    /// Everything but the view is bound with the geometry pass bind groups.
+    pub async fn new(
+        ctx: &mut RenderPassInitContext<'_>,
+        bind_groups: &ShadowBindGroups,
+        geometry_bind_groups: &GeometryBindGroups,
+    ) -> Result<Self> {
+        let pipeline_layout_cache_key = PipelineLayoutCacheKey::new(vec![
+            bind_groups.bind_group_layout_key,
+            geometry_bind_groups.transforms.bind_group_layout_key,
+            geometry_bind_groups.meta.bind_group_layout_key,
+            geometry_bind_groups.animation.bind_group_layout_key,
+        ]);
+
+        let pipeline_layout_key = ctx.pipeline_layouts.get_key(
+            ctx.gpu,
+            ctx.bind_group_layouts,
+            pipeline_layout_cache_key,
+        )?;
+
+        let render_pipeline_keys = ShadowRenderPipelineKeys {
+            no_instancing: ShadowRenderPipelineKeysLevel1::new(ctx, pipeline_layout_key, false)
+                .await?,
+            instancing: ShadowRenderPipelineKeysLevel1::new(ctx, pipeline_layout_key, true).await?,
+        };
+
+        Ok(Self {
+            pipeline_layout_key,
+            render_pipeline_keys,
+        })
+    }
+
+    /// Returns the render pipeline key for the requested options.
+    pub fn get_render_pipeline_key(
+        &self,
+        instancing: bool,
+        cull_mode: CullMode,
+    ) -> Result<RenderPipelineKey> {
+        let level = match instancing {
+            true => &self.render_pipeline_keys.instancing,
+            false => &self.render_pipeline_keys.no_instancing,
+        };
+
+        match cull_mode {
+            CullMode::None => Ok(level.no_cull),
+            CullMode::Back => Ok(level.back_cull),
+            CullMode::Front => Ok(level.front_cull),
+            _ => Err(AwsmError::UnsupportedCullMode(cull_mode)),
+        }
+    }
+}
+
+/// Shadow pipeline keys keyed by instancing.
+pub struct ShadowRenderPipelineKeys {
+    pub no_instancing: ShadowRenderPipelineKeysLevel1,
+    pub instancing: ShadowRenderPipelineKeysLevel1,
+}
+
+/// Shadow pipeline keys keyed by cull mode.
+pub struct ShadowRenderPipelineKeysLevel1 {
+    pub no_cull: RenderPipelineKey,
+    pub back_cull: RenderPipelineKey,
+    pub front_cull: RenderPipelineKey,
+}
+
+impl ShadowRenderPipelineKeysLevel1 {
+    /// Creates shadow pipeline keys for all cull modes.
+    pub async fn new(
+        ctx: &mut RenderPassInitContext<'_>,
+        pipeline_layout_key: PipelineLayoutKey,
+        instancing: bool,
+    ) -> Result<Self> {
+        Ok(Self {
+            no_cull: render_pipeline_key(ctx, pipeline_layout_key, instancing, CullMode::None)
+                .await?,
+            back_cull: render_pipeline_key(ctx, pipeline_layout_key, instancing, CullMode::Back)
+                .await?,
+            front_cull: render_pipeline_key(ctx, pipeline_layout_key, instancing, CullMode::Front)
+                .await?,
+        })
+    }
+}
+
+async fn render_pipeline_key(
+    ctx: &mut RenderPassInitContext<'_>,
+    pipeline_layout_key: PipelineLayoutKey,
+    instancing: bool,
+    cull_mode: CullMode,
+) -> Result<RenderPipelineKey> {
+    let shader_key = ctx
+        .shaders
+        .get_key(
+            ctx.gpu,
+            ShaderCacheKeyShadow {
+                instancing_transforms: instancing,
+            },
+        )
+        .await?;
+
+    let primitive_state = PrimitiveState::new()
+        .with_topology(PrimitiveTopology::TriangleList)
+        .with_front_face(FrontFace::Ccw)
+        .with_cull_mode(cull_mode);
+
+    let depth_stencil = DepthStencilState::new(SHADOW_MAP_FORMAT)
+        .with_depth_write_enabled(true)
+        .with_depth_compare(CompareFunction::LessEqual)
+        .with_depth_bias_slope_scale(SHADOW_DEPTH_BIAS_SLOPE_SCALE);
+
+    let mut pipeline_cache_key = RenderPipelineCacheKey::new(shader_key, pipeline_layout_key)
+        .with_primitive(primitive_state)
+        .with_depth_stencil(depth_stencil)
+        .with_push_vertex_buffer_layout(VERTEX_BUFFER_LAYOUT.clone());
+
+    if instancing {
+        pipeline_cache_key = pipeline_cache_key
+            .with_push_vertex_buffer_layout(VERTEX_BUFFER_LAYOUT_INSTANCING.clone());
+    }
+
+    Ok(ctx
+        .pipelines
+        .render
+        .get_key(
+            ctx.gpu,
+            ctx.shaders,
+            ctx.pipeline_layouts,
+            pipeline_cache_key,
+        )
+        .await?)
+}
@@ -0,0 +1,140 @@
+//! Shadow render pass execution.
+
+use awsm_renderer_core::command::{
+    render_pass::{DepthStencilAttachment, RenderPassDescriptor},
+    LoadOp, StoreOp,
+};
+
+use crate::{
+    debug::{debug_unique_string, DEBUG_ID_RENDERABLE},
+    error::Result,
+    frustum::Frustum,
+    lights::shadows::{Shadows, SHADOW_VIEW_BYTE_ALIGNMENT},
+    render::RenderContext,
+    render_passes::{
+        geometry::bind_group::GeometryBindGroups,
+        shadow::{bind_group::ShadowBindGroups, pipeline::ShadowPipelines},
+        RenderPassInitContext,
+    },
+};
+
+/// Shadow pass bind groups and pipelines.
+pub struct ShadowRenderPass {
+    pub bind_groups: ShadowBindGroups,
+    pub pipelines: ShadowPipelines,
+}
+
+impl ShadowRenderPass {
+    /// Creates the shadow render pass resources.
+    ///
+    /// Must be created after the geometry pass, whose bind group layouts it shares.
+    pub async fn new(
+        ctx: &mut RenderPassInitContext<'_>,
+        geometry_bind_groups: &GeometryBindGroups,
+    ) -> Result<Self> {
+        let bind_groups = ShadowBindGroups::new(ctx).await?;
+        let pipelines = ShadowPipelines::new(ctx, &bind_groups, geometry_bind_groups).await?;
+
+        Ok(Self {
+            bind_groups,
+            pipelines,
+        })
+    }
+
+    /// Renders depth for every shadow map layer.
+    ///
+    /// Casters are all visible, non-HUD meshes with `cast_shadows` set. Meshes in the
+    /// transparency pass don't cast shadows, and alpha-masked materials cast solid shadows.
+    pub fn render(&self, ctx: &RenderContext, shadows: &Shadows) -> Result<()> {
+        let geometry_bind_groups = &ctx.render_passes.geometry.bind_groups;
+
+        for (view_index, (view_projection, layer_view)) in shadows
+            .view_projections()
+            .iter()
+            .zip(shadows.layer_views.iter())
+            .enumerate()
+        {
+            let render_pass = ctx.command_encoder.begin_render_pass(
+                &RenderPassDescriptor {
+                    label: Some("Shadow Render Pass"),
+                    depth_stencil_attachment: Some(
+                        DepthStencilAttachment::new(layer_view)
+                            .with_depth_load_op(LoadOp::Clear)
+                            .with_depth_store_op(StoreOp::Store)
+                            .with_depth_clear_value(1.0),
+                    ),
+                    ..Default::default()
+                }
+                .into(),
+            )?;
+
+            render_pass.set_bind_group(
+                0,
+                self.bind_groups.get_bind_group()?,
+                Some(&[(view_index * SHADOW_VIEW_BYTE_ALIGNMENT) as u32]),
+            )?;
+            render_pass.set_bind_group(
+                1,
+                geometry_bind_groups.transforms.get_bind_group()?,
+                None,
+            )?;
+            render_pass.set_bind_group(
+                3,
+                geometry_bind_groups.animation.get_bind_group()?,
+                None,
+            )?;
+
+            let frustum = Frustum::from_view_projection(*view_projection);
+
+            let mut last_render_pipeline_key = None;
+
+            for (mesh_key, mesh) in ctx.meshes.iter() {
+                if mesh.hidden
+                    || mesh.hud
+                    || !mesh.cast_shadows
+                    || ctx.materials.is_transparency_pass(mesh.material_key)
+                {
+                    continue;
+                }
+
+                if let Some(world_aabb) = &mesh.world_aabb {
+                    if !frustum.intersects_aabb(world_aabb) {
+                        continue;
+                    }
+                }
+
+                match self
+                    .pipelines
+                    .get_render_pipeline_key(mesh.instanced, mesh.cull_mode())
+                {
+                    Ok(render_pipeline_key) => {
+                        if last_render_pipeline_key != Some(render_pipeline_key) {
+                            render_pass
+                                .set_pipeline(ctx.pipelines.render.get(render_pipeline_key)?);
+                            last_render_pipeline_key = Some(render_pipeline_key);
+                        }
+
+                        mesh.push_geometry_pass_commands(
+                            ctx,
+                            mesh_key,
+                            &render_pass,
+                            geometry_bind_groups,
+                        )?;
+                    }
+                    Err(err) => {
+                        debug_unique_string(DEBUG_ID_RENDERABLE, &err.to_string(), || {
+                            tracing::warn!(
+                                "Skipping mesh in Shadow Pass due to missing pipeline: {:?}",
+                                mesh_key
+                            )
+                        });
+                    }
+                }
+            }
+
+            render_pass.end();
+        }
+
+        Ok(())
+    }
+}
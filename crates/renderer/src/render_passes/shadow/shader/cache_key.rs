@@ -0,0 +1,15 @@
+//! Shader cache key for the shadow pass.
+
+use crate::{render_passes::shader_cache_key::ShaderCacheKeyRenderPass, shaders::ShaderCacheKey};
+
+/// Cache key for shadow pass shaders.
+#[derive(Hash, Debug, Clone, PartialEq, Eq)]
+pub struct ShaderCacheKeyShadow {
+    pub instancing_transforms: bool,
+}
+
+impl From<ShaderCacheKeyShadow> for ShaderCacheKey {
+    fn from(key: ShaderCacheKeyShadow) -> Self {
+        ShaderCacheKey::RenderPass(ShaderCacheKeyRenderPass::Shadow(key))
+    }
+}
@@ -0,0 +1,12 @@
+@group(0) @binding(0) var<uniform> shadow_view: ShadowView;
+@group(1) @binding(0) var<storage, read> model_transforms : array<mat4x4<f32>>;
+@group(2) @binding(0) var<uniform> geometry_mesh_meta: GeometryMeshMeta;
+@group(3) @binding(0) var<storage, read> geometry_morph_weights: array<f32>;
+@group(3) @binding(1) var<storage, read> geometry_morph_values: array<f32>;
+@group(3) @binding(2) var<storage, read> skin_joint_matrices: array<mat4x4<f32>>;
+@group(3) @binding(3) var<storage, read> skin_joint_index_weights: array<f32>;
+
+// One shadow map layer, selected with a dynamic offset
+struct ShadowView {
+    view_proj: mat4x4<f32>,
+};
@@ -0,0 +1,50 @@
+{% include "shared_wgsl/vertex/geometry_mesh_meta.wgsl" %}
+{% include "shared_wgsl/camera.wgsl" %}
+{% include "shared_wgsl/vertex/transform.wgsl" %}
+{% include "shared_wgsl/vertex/morph.wgsl" %}
+{% include "shared_wgsl/vertex/skin.wgsl" %}
+{% include "shared_wgsl/vertex/apply_vertex.wgsl" %}
+
+
+//***** MAIN *****
+// Same vertex layout as the geometry pass, so the same mesh buffers can be drawn
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) triangle_index: u32,
+    @location(2) barycentric: vec2<f32>,
+    @location(3) normal: vec3<f32>,
+    @location(4) tangent: vec4<f32>,
+    @location(5) original_vertex_index: u32,
+    {% if instancing_transforms %}
+    @location(6) instance_transform_row_0: vec4<f32>,
+    @location(7) instance_transform_row_1: vec4<f32>,
+    @location(8) instance_transform_row_2: vec4<f32>,
+    @location(9) instance_transform_row_3: vec4<f32>,
+    {% endif %}
+};
+
+@vertex
+fn vert_main(input: VertexInput) -> @builtin(position) vec4<f32> {
+    // apply_vertex only needs the view-projection
+    var camera: Camera;
+    camera.view_proj = shadow_view.view_proj;
+
+    let applied = apply_vertex(ApplyVertexInput(
+        input.original_vertex_index,
+        input.position,
+        input.normal,
+        input.tangent,
+        {% if instancing_transforms %}
+            input.instance_transform_row_0,
+            input.instance_transform_row_1,
+            input.instance_transform_row_2,
+            input.instance_transform_row_3,
+        {% endif %}
+    ), camera);
+
+    return applied.clip_position;
+}
+
+// Depth only, there are no color targets
+@fragment
+fn frag_main() {}
@@ -0,0 +1,75 @@
+//! Shader templates for the shadow pass.
+
+use askama::Template;
+
+use crate::{
+    render_passes::shadow::shader::cache_key::ShaderCacheKeyShadow,
+    shaders::{AwsmShaderError, Result},
+};
+
+/// Shadow pass shader template components.
+#[derive(Debug)]
+pub struct ShaderTemplateShadow {
+    pub bind_groups: ShaderTemplateShadowBindGroups,
+    pub vertex: ShaderTemplateShadowVertex,
+}
+
+/// Bind group template for the shadow pass.
+#[derive(Template, Debug)]
+#[template(path = "shadow_wgsl/bind_groups.wgsl", whitespace = "minimize")]
+pub struct ShaderTemplateShadowBindGroups {}
+
+impl ShaderTemplateShadowBindGroups {
+    /// Creates a bind group template from the cache key.
+    pub fn new(_cache_key: &ShaderCacheKeyShadow) -> Self {
+        Self {}
+    }
+}
+
+/// Vertex shader template for the shadow pass (with an empty fragment stage).
+#[derive(Template, Debug)]
+#[template(path = "shadow_wgsl/vertex.wgsl", whitespace = "minimize")]
+pub struct ShaderTemplateShadowVertex {
+    max_morph_unroll: u32,
+    max_skin_unroll: u32,
+    instancing_transforms: bool,
+}
+
+impl ShaderTemplateShadowVertex {
+    /// Creates a vertex shader template from the cache key.
+    pub fn new(cache_key: &ShaderCacheKeyShadow) -> Self {
+        Self {
+            max_morph_unroll: 2,
+            max_skin_unroll: 2,
+            instancing_transforms: cache_key.instancing_transforms,
+        }
+    }
+}
+
+impl TryFrom<&ShaderCacheKeyShadow> for ShaderTemplateShadow {
+    type Error = AwsmShaderError;
+
+    fn try_from(value: &ShaderCacheKeyShadow) -> Result<Self> {
+        Ok(Self {
+            bind_groups: ShaderTemplateShadowBindGroups::new(value),
+            vertex: ShaderTemplateShadowVertex::new(value),
+        })
+    }
+}
+
+impl ShaderTemplateShadow {
+    /// Renders the shadow shader template into WGSL.
+    pub fn into_source(self) -> Result<String> {
+        let bind_groups_source = self.bind_groups.render()?;
+        let vertex_source = self.vertex.render()?;
+        let source = format!("{}\n{}", bind_groups_source, vertex_source);
+
+        Ok(source)
+    }
+
+    #[cfg(debug_assertions)]
+    /// Returns an optional debug label for shader compilation.
+    pub fn debug_label(&self) -> Option<&str> {
+        Some("Shadow")
+    }
+}
@@ -14,6 +14,8 @@ struct CameraRaw {
     frustum_rays: array<vec4<f32>, 4>,
     viewport: vec4<f32>, // in pixels, x,y,width,height
     dof_params: vec4<f32>, // x=focus_distance, y=aperture (f-stop), zw=unused
+    prev_view_proj: mat4x4<f32>, // previous frame, unjittered
+    jitter: vec4<f32>, // xy = TAA jitter in NDC (already applied to proj), zw=unused
 };
 
 // Friendly camera structure (no padding, easier to work with)
@@ -32,6 +34,8 @@ struct Camera {
     viewport_size: vec2<f32>, // width,height
     focus_distance: f32, // DoF focus distance in world units
     aperture: f32, // DoF aperture f-stop (lower = more blur)
+    prev_view_proj: mat4x4<f32>, // previous frame's view_proj, unjittered (for motion vectors)
+    jitter: vec2<f32>, // TAA jitter in NDC, zero when TAA is off
 };
 
 // Convert from raw uniform to friendly structure
@@ -50,5 +54,7 @@ fn camera_from_raw(raw: CameraRaw) -> Camera {
     camera.viewport_size = vec2<f32>(raw.viewport.z, raw.viewport.w);
     camera.focus_distance = raw.dof_params.x;
     camera.aperture = raw.dof_params.y;
+    camera.prev_view_proj = raw.prev_view_proj;
+    camera.jitter = raw.jitter.xy;
     return camera;
 }
@@ -3,7 +3,9 @@
 // Implements Cook-Torrance specular BRDF with split-sum IBL approximation
 // Safe for HDR workflows (no final saturate - tone mapping applied elsewhere)
 // Supports: KHR_materials_ior, KHR_materials_transmission, KHR_materials_volume,
-//           KHR_materials_clearcoat, KHR_materials_sheen
+//           KHR_materials_dispersion, KHR_materials_diffuse_transmission,
+//           KHR_materials_clearcoat, KHR_materials_sheen, KHR_materials_anisotropy,
+//           KHR_materials_iridescence
 // -------------------------------------------------------------
 
 // -------------------------------------------------------------
@@ -46,6 +48,14 @@ fn refract_direction(incident: vec3<f32>, normal: vec3<f32>, eta: f32) -> vec3<f
     return eta * incident + (eta * cos_i - cos_t) * normal;
 }
 
+// Per-channel IOR for KHR_materials_dispersion
+// The spread is derived from the Abbe number (dispersion = 20 / V_d), around the base IOR for green
+fn dispersion_iors(ior: f32, dispersion: f32) -> vec3<f32> {
+    let ior_val = effective_ior(ior);
+    let half_spread = (ior_val - 1.0) * 0.025 * dispersion;
+    return vec3<f32>(ior_val - half_spread, ior_val, ior_val + half_spread);
+}
+
 // -------------------------------------------------------------
 // Volume Attenuation (Beer's Law)
 // -------------------------------------------------------------
@@ -139,6 +149,215 @@ fn geometry_smith(n: vec3<f32>, v: vec3<f32>, l: vec3<f32>, alpha: f32) -> f32 {
     return geometry_schlick_ggx(n_dot_v, alpha) * geometry_schlick_ggx(n_dot_l, alpha);
 }
 
+// -------------------------------------------------------------
+// Anisotropic GGX (KHR_materials_anisotropy)
+// -------------------------------------------------------------
+
+// Tangent frame for anisotropy, re-orthogonalized against the shading normal
+// (the direction comes from the geometry tangent, the normal may be normal-mapped)
+struct AnisotropyFrame {
+    t: vec3<f32>,
+    b: vec3<f32>,
+}
+
+fn anisotropy_frame(n: vec3<f32>, direction: vec3<f32>) -> AnisotropyFrame {
+    var t = direction - n * dot(n, direction);
+    let len_sq = dot(t, t);
+    if (len_sq > 1e-8) {
+        t = t * inverseSqrt(len_sq);
+    } else {
+        // direction is along the normal, any perpendicular will do
+        let axis = select(vec3<f32>(0.0, 0.0, 1.0), vec3<f32>(0.0, 1.0, 0.0), abs(n.z) > 0.999);
+        t = normalize(cross(axis, n));
+    }
+    return AnisotropyFrame(t, cross(n, t));
+}
+
+// Roughness along the anisotropy direction, the bitangent keeps the base roughness
+fn anisotropy_alpha_t(alpha: f32, anisotropy: f32) -> f32 {
+    return mix(alpha, 1.0, anisotropy * anisotropy);
+}
+
+// Anisotropic GGX normal distribution (Burley 2012)
+fn distribution_ggx_anisotropic(n_dot_h: f32, t_dot_h: f32, b_dot_h: f32, alpha_t: f32, alpha_b: f32) -> f32 {
+    let a2 = max(alpha_t * alpha_b, 1e-6);
+    let f = vec3<f32>(alpha_b * t_dot_h, alpha_t * b_dot_h, a2 * n_dot_h);
+    let w2 = a2 / max(dot(f, f), EPSILON);
+    return a2 * w2 * w2 / PI;
+}
+
+// Height-correlated anisotropic Smith visibility (Heitz 2014)
+// Includes the 1 / (4 * N·L * N·V) term, so it replaces G / (4 * N·L * N·V)
+fn visibility_ggx_anisotropic(
+    n_dot_l: f32,
+    n_dot_v: f32,
+    t_dot_v: f32,
+    b_dot_v: f32,
+    t_dot_l: f32,
+    b_dot_l: f32,
+    alpha_t: f32,
+    alpha_b: f32,
+) -> f32 {
+    let ggx_v = n_dot_l * length(vec3<f32>(alpha_t * t_dot_v, alpha_b * b_dot_v, n_dot_v));
+    let ggx_l = n_dot_v * length(vec3<f32>(alpha_t * t_dot_l, alpha_b * b_dot_l, n_dot_l));
+    return saturate(0.5 / max(ggx_v + ggx_l, EPSILON));
+}
+
+// Bends the reflection normal towards the anisotropy bitangent,
+// which stretches IBL reflections the same way the direct highlight is stretched
+fn anisotropy_bent_normal(
+    n: vec3<f32>,
+    v: vec3<f32>,
+    roughness: f32,
+    anisotropy: f32,
+    anisotropy_bitangent: vec3<f32>,
+) -> vec3<f32> {
+    let anisotropic_tangent = cross(anisotropy_bitangent, v);
+    let anisotropic_normal = cross(anisotropic_tangent, anisotropy_bitangent);
+    let bend = 1.0 - anisotropy * (1.0 - roughness);
+    let bend4 = bend * bend * bend * bend;
+    return safe_normalize(mix(anisotropic_normal, n, bend4));
+}
+
+// -------------------------------------------------------------
+// Thin-film iridescence (KHR_materials_iridescence)
+// Belcour & Barla 2017, as given in the extension spec
+// -------------------------------------------------------------
+
+// CIE XYZ to linear sRGB (Rec. 709 primaries)
+const XYZ_TO_REC709: mat3x3<f32> = mat3x3<f32>(
+    vec3<f32>(3.2404542, -0.9692660, 0.0556434),
+    vec3<f32>(-1.5371385, 1.8760108, -0.2040259),
+    vec3<f32>(-0.4985314, 0.0415560, 1.0572252),
+);
+
+fn iridescence_f0_to_ior(f0: vec3<f32>) -> vec3<f32> {
+    let sqrt_f0 = sqrt(f0);
+    return (vec3<f32>(1.0) + sqrt_f0) / (vec3<f32>(1.0) - sqrt_f0);
+}
+
+fn iridescence_ior_to_f0(transmitted_ior: vec3<f32>, incident_ior: f32) -> vec3<f32> {
+    let r = (transmitted_ior - vec3<f32>(incident_ior)) / (transmitted_ior + vec3<f32>(incident_ior));
+    return r * r;
+}
+
+// Inverse of Schlick's approximation (with f90 = 1), recovers F0 from a Fresnel value
+fn iridescence_schlick_to_f0(f: vec3<f32>, cos_theta: f32) -> vec3<f32> {
+    let x = saturate(1.0 - cos_theta);
+    let x2 = x * x;
+    let x5 = clamp(x * x2 * x2, 0.0, 0.9999);
+    return (f - vec3<f32>(x5)) / (1.0 - x5);
+}
+
+// Spectral sensitivity of the optical path difference (in nm), fitted Gaussians in XYZ
+fn iridescence_sensitivity(opd: f32, shift: vec3<f32>) -> vec3<f32> {
+    let phase = 2.0 * PI * opd * 1.0e-9;
+    let val = vec3<f32>(5.4856e-13, 4.4201e-13, 5.2481e-13);
+    let pos = vec3<f32>(1.6810e+06, 1.7953e+06, 2.2084e+06);
+    let variance = vec3<f32>(4.3278e+09, 9.3046e+09, 6.6121e+09);
+
+    var xyz = val * sqrt(2.0 * PI * variance) * cos(pos * phase + shift) * exp(-(phase * phase) * variance);
+    xyz.x += 9.7470e-14 * sqrt(2.0 * PI * 4.5282e+09) * cos(2.2399e+06 * phase + shift.x) * exp(-4.5282e+09 * phase * phase);
+    xyz /= 1.0685e-7;
+
+    return XYZ_TO_REC709 * xyz;
+}
+
+// Fresnel of a thin film (ior film_ior, thickness in nm) on top of a base layer with base_f0
+fn iridescence_fresnel(outside_ior: f32, film_ior: f32, cos_theta1: f32, thickness: f32, base_f0: vec3<f32>) -> vec3<f32> {
+    // The film fades into the outside medium as it gets very thin
+    let iridescence_ior = mix(outside_ior, film_ior, smoothstep(0.0, 0.03, thickness));
+
+    // Angle in the film (Snell's law)
+    let ior_ratio = outside_ior / iridescence_ior;
+    let sin_theta2_sq = ior_ratio * ior_ratio * (1.0 - cos_theta1 * cos_theta1);
+    let cos_theta2_sq = 1.0 - sin_theta2_sq;
+    if (cos_theta2_sq < 0.0) {
+        // Total internal reflection
+        return vec3<f32>(1.0);
+    }
+    let cos_theta2 = sqrt(cos_theta2_sq);
+
+    // First interface (outside -> film)
+    let r0 = iridescence_ior_to_f0(vec3<f32>(iridescence_ior), outside_ior).x;
+    let r12 = fresnel_schlick(cos_theta1, vec3<f32>(r0)).x;
+    let t121 = 1.0 - r12;
+    let phi12 = select(0.0, PI, iridescence_ior < outside_ior);
+    let phi21 = PI - phi12;
+
+    // Second interface (film -> base), clamped so metals don't produce an infinite IOR
+    let base_ior = iridescence_f0_to_ior(clamp(base_f0, vec3<f32>(0.0), vec3<f32>(0.9999)));
+    let r1 = iridescence_ior_to_f0(base_ior, iridescence_ior);
+    let r23 = fresnel_schlick(cos_theta2, r1);
+    let phi23 = select(vec3<f32>(0.0), vec3<f32>(PI), base_ior < vec3<f32>(iridescence_ior));
+
+    // Phase shift
+    let opd = 2.0 * iridescence_ior * thickness * cos_theta2;
+    let phi = vec3<f32>(phi21) + phi23;
+
+    // Compound terms
+    let r123 = clamp(r12 * r23, vec3<f32>(1e-5), vec3<f32>(0.9999));
+    let r123_sqrt = sqrt(r123);
+    let rs = (t121 * t121) * r23 / (vec3<f32>(1.0) - r123);
+
+    // Reflectance for m = 0 (DC term)
+    var result = r12 + rs;
+
+    // Reflectance for m > 0 (pairs of diracs)
+    var cm = rs - t121;
+    for (var m = 1; m <= 2; m++) {
+        cm *= r123_sqrt;
+        let sm = 2.0 * iridescence_sensitivity(f32(m) * opd, f32(m) * phi);
+        result += cm * sm;
+    }
+
+    // Out of gamut colors can go negative
+    return max(result, vec3<f32>(0.0));
+}
+
+// Thin-film Fresnel at the view angle, seen from air
+fn iridescence_view_fresnel(color: PbrMaterialColor, n_dot_v: f32, F0: vec3<f32>) -> vec3<f32> {
+    return iridescence_fresnel(1.0, color.iridescence_ior, n_dot_v, color.iridescence_thickness, F0);
+}
+
+// Strength of the thin film, zero when there's no thickness to interfere with
+fn iridescence_strength(color: PbrMaterialColor) -> f32 {
+    return select(0.0, color.iridescence, color.iridescence_thickness > 0.0);
+}
+
+// -------------------------------------------------------------
+// Diffuse transmission (KHR_materials_diffuse_transmission)
+// Thin surfaces (leaves, paper, cloth) let diffuse light through from the back side
+// -------------------------------------------------------------
+
+// Strength of the transmitted lobe, metals don't transmit
+fn diffuse_transmission_strength(color: PbrMaterialColor) -> f32 {
+    return color.diffuse_transmission * (1.0 - clamp(color.metallic_roughness.x, 0.0, 1.0));
+}
+
+// Cosine term of the diffuse lobe, mixing front-lit reflection with back-lit transmission
+// (the spec's mix(diffuse_brdf, diffuse_btdf, factor), the btdf tinted by the transmission color)
+fn diffuse_transmission_cosine(color: PbrMaterialColor, n_dot_l: f32, back_n_dot_l: f32) -> vec3<f32> {
+    let strength = diffuse_transmission_strength(color);
+    return mix(vec3<f32>(n_dot_l), color.diffuse_transmission_color * back_n_dot_l, strength);
+}
+
+// Diffuse irradiance, with the back side's irradiance coming through for diffuse transmission
+fn diffuse_transmission_irradiance(
+    color: PbrMaterialColor,
+    n: vec3<f32>,
+    irradiance_tex: texture_cube<f32>,
+    irradiance_sampler: sampler
+) -> vec3<f32> {
+    let irradiance = sampleIrradiance(n, irradiance_tex, irradiance_sampler);
+    let strength = diffuse_transmission_strength(color);
+    if (strength <= 0.0) {
+        return irradiance;
+    }
+    let back_irradiance = sampleIrradiance(-n, irradiance_tex, irradiance_sampler);
+    return mix(irradiance, back_irradiance * color.diffuse_transmission_color, strength);
+}
+
 // -------------------------------------------------------------
 // Clearcoat BRDF (KHR_materials_clearcoat)
 // -------------------------------------------------------------
@@ -337,28 +556,56 @@ fn brdf_direct(color: PbrMaterialColor, light_brdf: LightBrdf, surface_to_camera
     // Cook-Torrance specular BRDF: DFG / (4 * N·L * N·V)
     // When V and L are antiparallel, H is undefined. Treat that as zero specular
     // and use view-Fresnel for diffuse energy conservation.
-    let F = select(
+    var F = select(
         fresnel_schlick_f90(n_dot_v, F0, f90),
         fresnel_schlick_f90(v_dot_h, F0, f90),
         has_half
     );
-    let D = distribution_ggx(n_dot_h, alpha);
-    let G = geometry_smith(n, v, l, alpha);
+
+    // Thin-film interference replaces the Fresnel term where the film covers the surface
+    let iridescence = iridescence_strength(color);
+    if (iridescence > 0.0) {
+        F = mix(F, iridescence_view_fresnel(color, n_dot_v, F0), iridescence);
+    }
+    var specular_dv = 0.0;
+    if (color.anisotropy_strength > 0.0) {
+        // Anisotropic lobe: stretched along the anisotropy direction
+        let frame = anisotropy_frame(n, color.anisotropy_direction);
+        let alpha_t = anisotropy_alpha_t(alpha, color.anisotropy_strength);
+        let D = distribution_ggx_anisotropic(n_dot_h, dot(frame.t, h), dot(frame.b, h), alpha_t, alpha);
+        let V = visibility_ggx_anisotropic(
+            n_dot_l,
+            n_dot_v,
+            dot(frame.t, v),
+            dot(frame.b, v),
+            dot(frame.t, l),
+            dot(frame.b, l),
+            alpha_t,
+            alpha,
+        );
+        specular_dv = D * V;
+    } else {
+        let D = distribution_ggx(n_dot_h, alpha);
+        let G = geometry_smith(n, v, l, alpha);
+        specular_dv = (D * G) / max(4.0 * n_dot_l * n_dot_v, EPSILON);
+    }
     let specular = select(
         vec3<f32>(0.0),
-        F * (D * G) / max(4.0 * n_dot_l * n_dot_v, EPSILON),
+        F * specular_dv,
         has_half
     );
 
     // Lambertian diffuse (energy-conserving: scaled by (1-F_max) and non-metallic portion)
-    // Note: transmission modifies diffuse in brdf_ibl, but for direct lighting we keep
-    // standard diffuse since punctual lights don't transmit through surfaces
+    // Note: specular transmission modifies diffuse in brdf_ibl, but for direct lighting we keep
+    // standard diffuse since punctual lights don't refract through surfaces
     let F_max = max(max(F.r, F.g), F.b);
     let k_d = (1.0 - F_max) * (1.0 - metallic);
-    let diffuse = k_d * base_color * (1.0 / PI);
+    // Diffuse transmission: part of the lobe is lit from the back side instead, already cosine-weighted
+    let diffuse_cosine = diffuse_transmission_cosine(color, n_dot_l, max(dot(-n, l), 0.0));
+    let diffuse = k_d * base_color * (1.0 / PI) * diffuse_cosine;
 
     // Base layer contribution
-    var result = (diffuse + specular) * light_brdf.radiance * n_dot_l * color.occlusion;
+    var result = (diffuse + specular * n_dot_l) * light_brdf.radiance * color.occlusion;
 
     // Sheen contribution (cloth-like rim highlight)
     let sheen = sheen_brdf_direct(color.sheen_color, color.sheen_roughness, n, v, l);
@@ -420,7 +667,17 @@ fn brdf_ibl_with_transmission(
     let f90 = mix(color.specular, 1.0, metallic);
 
     // Fresnel at view direction
-    let F_view = fresnel_schlick_f90(n_dot_v, F0, f90);
+    var F_view = fresnel_schlick_f90(n_dot_v, F0, f90);
+
+    // Thin-film interference: the film's Fresnel stands in for the base one,
+    // and its equivalent F0 drives the split-sum specular below
+    var F0_specular = F0;
+    let iridescence = iridescence_strength(color);
+    if (iridescence > 0.0) {
+        let F_iridescence = iridescence_view_fresnel(color, n_dot_v, F0);
+        F_view = mix(F_view, F_iridescence, iridescence);
+        F0_specular = mix(F0, iridescence_schlick_to_f0(F_iridescence, n_dot_v), iridescence);
+    }
     let F_view_max = max(max(F_view.r, F_view.g), F_view.b);
 
     // Effective transmission: metals don't transmit
@@ -431,8 +688,9 @@ fn brdf_ibl_with_transmission(
 
     if (effective_transmission > 0.0) {
         // Diffuse IBL contribution
-        let irradiance = sampleIrradiance(n, ibl_irradiance_tex, ibl_irradiance_sampler);
-        let diffuse_brdf = base_color * (1.0 / PI) * irradiance;
+        let irradiance = diffuse_transmission_irradiance(color, n, ibl_irradiance_tex, ibl_irradiance_sampler);
+        // Screen-space AO darkens only this diffuse term, not the transmitted background
+        let diffuse_brdf = base_color * (1.0 / PI) * irradiance * color.indirect_occlusion;
 
         // Transmission BTDF contribution
         // Apply volume attenuation if thickness > 0
@@ -456,9 +714,9 @@ fn brdf_ibl_with_transmission(
         // Per spec: base = mix(diffuse_brdf, specular_btdf * baseColor, transmission)
         base_layer = mix(diffuse_brdf, transmission_btdf, effective_transmission);
     } else {
-        // No transmission - standard diffuse
-        let irradiance = sampleIrradiance(n, ibl_irradiance_tex, ibl_irradiance_sampler);
-        base_layer = base_color * (1.0 / PI) * irradiance;
+        // No transmission - standard diffuse (with diffuse transmission, if any)
+        let irradiance = diffuse_transmission_irradiance(color, n, ibl_irradiance_tex, ibl_irradiance_sampler);
+        base_layer = base_color * (1.0 / PI) * irradiance * color.indirect_occlusion;
     }
 
     // Apply diffuse/transmission energy conservation
@@ -466,11 +724,16 @@ fn brdf_ibl_with_transmission(
     let base_contribution = k_d * base_layer * color.occlusion;
 
     // Specular IBL: prefiltered environment * (F0 * scale + f90 * bias) from BRDF LUT
-    let R = reflect(-v, n);
+    var reflection_normal = n;
+    if (color.anisotropy_strength > 0.0) {
+        let frame = anisotropy_frame(n, color.anisotropy_direction);
+        reflection_normal = anisotropy_bent_normal(n, v, roughness, color.anisotropy_strength, frame.b);
+    }
+    let R = reflect(-v, reflection_normal);
     let prefiltered = samplePrefilteredEnv(R, roughness, ibl_filtered_env_tex, ibl_filtered_env_sampler, ibl_info);
     let brdf_lut = sampleBRDFLUT(n_dot_v, roughness, brdf_lut_tex, brdf_lut_sampler);
     // Apply occlusion to specular with reduced strength to avoid over-darkening reflections
-    let specular = prefiltered * (F0 * brdf_lut.x + vec3<f32>(f90) * brdf_lut.y) * mix(1.0, color.occlusion, 0.5);
+    let specular = prefiltered * (F0_specular * brdf_lut.x + vec3<f32>(f90) * brdf_lut.y) * mix(1.0, color.occlusion, 0.5);
 
     // Sheen contribution for IBL (approximate using diffuse irradiance)
     let sheen_scaling = sheen_albedo_scaling(color.sheen_color, color.sheen_roughness, n_dot_v);
@@ -513,6 +776,41 @@ fn brdf_ibl_with_transmission(
     return result;
 }
 
+// Environment seen through a transmissive surface, refracted with the given IOR when it's a volume
+fn ibl_transmission_sample(
+    color: PbrMaterialColor,
+    n: vec3<f32>,
+    v: vec3<f32>,
+    ior: f32,
+    roughness: f32,
+    ibl_filtered_env_tex: texture_cube<f32>,
+    ibl_filtered_env_sampler: sampler,
+    ibl_info: IblInfo
+) -> vec3<f32> {
+    // Determine sample direction for transmission
+    var sample_dir = -v;  // Default: straight through (thin-walled)
+
+    // If volumetric (thickness > 0), apply refraction
+    let ior_val = effective_ior(ior);
+    if (color.volume_thickness > 0.0 && ior_val != 1.0) {
+        let refracted = refract_direction(v, n, 1.0 / ior_val);
+        // Use dot product instead of length to avoid sqrt (checking for non-zero)
+        if (dot(refracted, refracted) > 1e-6) {
+            sample_dir = refracted;
+        }
+        // else: TIR occurred, keep straight-through direction
+    }
+
+    // Sample environment with roughness-based blur
+    return samplePrefilteredEnv(
+        sample_dir,
+        roughness,
+        ibl_filtered_env_tex,
+        ibl_filtered_env_sampler,
+        ibl_info
+    );
+}
+
 // Standard IBL without explicit transmission background (uses IBL for transmission)
 fn brdf_ibl(
     color: PbrMaterialColor,
@@ -536,28 +834,17 @@ fn brdf_ibl(
         let v = safe_normalize(surface_to_camera);
         let roughness = max(clamp(color.metallic_roughness.y, 0.0, 1.0), 0.04);
 
-        // Determine sample direction for transmission
-        var sample_dir = -v;  // Default: straight through (thin-walled)
-
-        // If volumetric (thickness > 0), apply refraction
-        let ior_val = effective_ior(color.ior);
-        if (color.volume_thickness > 0.0 && ior_val != 1.0) {
-            let refracted = refract_direction(v, n, 1.0 / ior_val);
-            // Use dot product instead of length to avoid sqrt (checking for non-zero)
-            if (dot(refracted, refracted) > 1e-6) {
-                sample_dir = refracted;
-            }
-            // else: TIR occurred, keep straight-through direction
+        if (color.dispersion > 0.0 && color.volume_thickness > 0.0) {
+            // Dispersion: each channel refracts with its own IOR
+            let iors = dispersion_iors(color.ior, color.dispersion);
+            transmission_background = vec3<f32>(
+                ibl_transmission_sample(color, n, v, iors.r, roughness, ibl_filtered_env_tex, ibl_filtered_env_sampler, ibl_info).r,
+                ibl_transmission_sample(color, n, v, iors.g, roughness, ibl_filtered_env_tex, ibl_filtered_env_sampler, ibl_info).g,
+                ibl_transmission_sample(color, n, v, iors.b, roughness, ibl_filtered_env_tex, ibl_filtered_env_sampler, ibl_info).b,
+            );
+        } else {
+            transmission_background = ibl_transmission_sample(color, n, v, color.ior, roughness, ibl_filtered_env_tex, ibl_filtered_env_sampler, ibl_info);
         }
-
-        // Sample environment with roughness-based blur
-        transmission_background = samplePrefilteredEnv(
-            sample_dir,
-            roughness,
-            ibl_filtered_env_tex,
-            ibl_filtered_env_sampler,
-            ibl_info
-        );
     }
 
     return brdf_ibl_with_transmission(
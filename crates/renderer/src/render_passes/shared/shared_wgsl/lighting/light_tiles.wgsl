@@ -0,0 +1,19 @@
+// must match lights/culling.rs
+const LIGHT_TILE_SIZE: u32 = 16u;
+const LIGHT_TILE_MAX_LIGHTS: u32 = 63u;
+// one list is [count, index_0, index_1, ...]
+const LIGHT_TILE_LIST_STRIDE: u32 = 64u;
+// each tile holds the opaque list followed by the transparent list
+const LIGHT_TILE_STRIDE: u32 = 128u;
+const LIGHT_TILE_LIST_OPAQUE: u32 = 0u;
+const LIGHT_TILE_LIST_TRANSPARENT: u32 = 1u;
+
+fn light_tile_count_x(screen_width: u32) -> u32 {
+    return max((screen_width + LIGHT_TILE_SIZE - 1u) / LIGHT_TILE_SIZE, 1u);
+}
+
+// offset (in u32 words) of a tile list within `light_tile_indices`
+fn light_tile_list_offset(tile: vec2<u32>, screen_width: u32, list: u32) -> u32 {
+    let tile_index = tile.y * light_tile_count_x(screen_width) + tile.x;
+    return tile_index * LIGHT_TILE_STRIDE + list * LIGHT_TILE_LIST_STRIDE;
+}
@@ -1,3 +1,7 @@
+/*************** START light_tiles.wgsl ******************/
+{% include "shared_wgsl/lighting/light_tiles.wgsl" %}
+/*************** END light_tiles.wgsl ******************/
+
 struct LightsInfoPacked {
     data: vec4<u32>,
 }
@@ -19,7 +23,7 @@ struct LightPacked {
   dir_inner: vec4<f32>,
   // color.rgb + intensity
   color_intensity: vec4<f32>,
-  // kind (as uint) + outer_cone + 2 pads (or extra params)
+  // kind (as uint) + outer_cone + shadow slot + 1 pad
   kind_outer_pad: vec4<f32>,
 };
 
@@ -32,8 +36,14 @@ struct Light {
     direction: vec3<f32>,
     inner_cone: f32,
     outer_cone: f32,
+    // shadow slot + 1, 0 for no shadow
+    shadow: u32,
 };
 
+/*************** START shadows.wgsl ******************/
+{% include "shared_wgsl/lighting/shadows.wgsl" %}
+/*************** END shadows.wgsl ******************/
+
 fn get_lights_info() -> LightsInfo {
     // expects `lights_info` is global LightsInfoPacked
     return LightsInfo(
@@ -56,10 +66,40 @@ fn get_light(i: u32) -> Light {
         p.pos_range.w,
         p.dir_inner.xyz,
         p.dir_inner.w,
-        p.kind_outer_pad.y
+        p.kind_outer_pad.y,
+        u32(p.kind_outer_pad.z)
+    );
+}
+
+// The lights to loop over for a pixel, either a culled tile list or all lights
+struct LightTile {
+    offset: u32,
+    count: u32,
+    indexed: bool,
+};
+
+fn get_light_tile(pixel: vec2<u32>, screen_width: u32, list: u32) -> LightTile {
+    // expects `light_tile_indices` is global array<u32>, written by the light culling pass
+    let offset = light_tile_list_offset(pixel / LIGHT_TILE_SIZE, screen_width, list);
+    return LightTile(
+        offset + 1u,
+        min(light_tile_indices[offset], LIGHT_TILE_MAX_LIGHTS),
+        true
     );
 }
 
+// for surfaces the culling pass knows nothing about (e.g. HUD)
+fn get_light_tile_unculled(lights_info: LightsInfo) -> LightTile {
+    return LightTile(0u, lights_info.n_lights, false);
+}
+
+fn get_light_tile_light_index(tile: LightTile, i: u32) -> u32 {
+    if (tile.indexed) {
+        return light_tile_indices[tile.offset + i];
+    }
+    return i;
+}
+
 struct LightBrdf {
     normal: vec3<f32>,
     n_dot_l: f32,
@@ -123,6 +163,8 @@ fn apply_lighting(
     surface_to_camera: vec3<f32>,
     world_position: vec3<f32>,
     lights_info: LightsInfo,
+    light_tile: LightTile,
+    shadow_receiver: ShadowReceiver,
 ) -> vec3<f32> {
     var color = vec3<f32>(0.0);
 
@@ -142,8 +184,10 @@ fn apply_lighting(
     {% endif %}
 
     {% if has_lighting_punctual() %}
-        for(var i = 0u; i < lights_info.n_lights; i = i + 1u) {
-            let light_brdf = light_to_brdf(get_light(i), material_color.normal, world_position);
+        for(var i = 0u; i < light_tile.count; i = i + 1u) {
+            let light = get_light(get_light_tile_light_index(light_tile, i));
+            var light_brdf = light_to_brdf(light, material_color.normal, world_position);
+            light_brdf.radiance *= light_shadow(light, light_brdf.light_dir, world_position, shadow_receiver);
             color += brdf_direct(material_color, light_brdf, surface_to_camera);
         }
     {% endif %}
@@ -157,6 +201,8 @@ fn apply_lighting_with_transmission(
     surface_to_camera: vec3<f32>,
     world_position: vec3<f32>,
     lights_info: LightsInfo,
+    light_tile: LightTile,
+    shadow_receiver: ShadowReceiver,
     transmission_background: vec3<f32>,
 ) -> vec3<f32> {
     var color = vec3<f32>(0.0);
@@ -178,8 +224,10 @@ fn apply_lighting_with_transmission(
     {% endif %}
 
     {% if has_lighting_punctual() %}
-        for(var i = 0u; i < lights_info.n_lights; i = i + 1u) {
-            let light_brdf = light_to_brdf(get_light(i), material_color.normal, world_position);
+        for(var i = 0u; i < light_tile.count; i = i + 1u) {
+            let light = get_light(get_light_tile_light_index(light_tile, i));
+            var light_brdf = light_to_brdf(light, material_color.normal, world_position);
+            light_brdf.radiance *= light_shadow(light, light_brdf.light_dir, world_position, shadow_receiver);
             color += brdf_direct(material_color, light_brdf, surface_to_camera);
         }
     {% endif %}
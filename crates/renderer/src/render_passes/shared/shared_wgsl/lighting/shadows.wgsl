@@ -0,0 +1,109 @@
+// must match lights/shadows.rs
+const SHADOW_MAX_LIGHTS: u32 = 8u;
+const SHADOW_MAX_VIEWS: u32 = 16u;
+
+struct ShadowLightPacked {
+    // bias, normal_bias, pcf radius (texels), texel size (uv)
+    params: vec4<f32>,
+    // first view, view count, light kind, padding
+    views: vec4<u32>,
+    // view-space far distance of each cascade (directional lights only)
+    cascade_splits: vec4<f32>,
+};
+
+struct ShadowsPacked {
+    // light count, view count, resolution, padding
+    info: vec4<u32>,
+    lights: array<ShadowLightPacked, SHADOW_MAX_LIGHTS>,
+    view_projs: array<mat4x4<f32>, SHADOW_MAX_VIEWS>,
+};
+
+// The surface being shaded, as far as shadows are concerned
+struct ShadowReceiver {
+    enabled: bool,
+    normal: vec3<f32>,
+    // distance along the camera forward axis, for cascade selection
+    view_depth: f32,
+};
+
+fn shadow_receiver(enabled: bool, normal: vec3<f32>, world_position: vec3<f32>, camera: Camera) -> ShadowReceiver {
+    let view_position = camera.view * vec4<f32>(world_position, 1.0);
+    return ShadowReceiver(enabled, normal, -view_position.z);
+}
+
+// Returns how much of the light reaches the surface, 1.0 is fully lit
+fn light_shadow(light: Light, light_dir: vec3<f32>, world_position: vec3<f32>, receiver: ShadowReceiver) -> f32 {
+    // expects `shadows`, `shadow_maps` and `shadow_sampler` globals
+    if (!receiver.enabled || light.shadow == 0u || light.shadow > shadows.info.x) {
+        return 1.0;
+    }
+
+    let shadow = shadows.lights[light.shadow - 1u];
+    let first_view = shadow.views.x;
+    let view_count = shadow.views.y;
+    var view = first_view;
+
+    switch (shadow.views.z) {
+        case 1u: { // Directional, pick the cascade
+            var cascade = view_count;
+            for (var i = 0u; i < view_count; i = i + 1u) {
+                if (receiver.view_depth <= shadow.cascade_splits[i]) {
+                    cascade = i;
+                    break;
+                }
+            }
+            if (cascade == view_count) {
+                // beyond the last cascade
+                return 1.0;
+            }
+            view = first_view + cascade;
+        }
+        case 2u: { // Point, pick the cube face by major axis
+            let d = world_position - light.position;
+            let a = abs(d);
+            var face = 0u;
+            if (a.x >= a.y && a.x >= a.z) {
+                face = select(1u, 0u, d.x > 0.0);
+            } else if (a.y >= a.z) {
+                face = select(3u, 2u, d.y > 0.0);
+            } else {
+                face = select(5u, 4u, d.z > 0.0);
+            }
+            view = first_view + face;
+        }
+        default: { // Spot, single view
+        }
+    }
+
+    // push the lookup off the surface, more so at grazing angles
+    // always towards the lit side, lights behind the surface still reach it through diffuse transmission
+    let offset_normal = select(receiver.normal, -receiver.normal, dot(receiver.normal, light_dir) < 0.0);
+    let n_dot_l = saturate(dot(offset_normal, light_dir));
+    let offset_position = world_position + offset_normal * (shadow.params.y * (1.0 - n_dot_l));
+
+    let clip = shadows.view_projs[view] * vec4<f32>(offset_position, 1.0);
+    if (clip.w <= 0.0) {
+        return 1.0;
+    }
+    let ndc = clip.xyz / clip.w;
+    if (abs(ndc.x) > 1.0 || abs(ndc.y) > 1.0 || ndc.z < 0.0 || ndc.z > 1.0) {
+        return 1.0;
+    }
+
+    let uv = vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+    let ref_depth = ndc.z - shadow.params.x;
+    let radius = i32(shadow.params.z);
+    let texel = shadow.params.w;
+
+    var lit = 0.0;
+    var taps = 0.0;
+    for (var y = -radius; y <= radius; y = y + 1) {
+        for (var x = -radius; x <= radius; x = x + 1) {
+            let offset = vec2<f32>(f32(x), f32(y)) * texel;
+            lit += textureSampleCompareLevel(shadow_maps, shadow_sampler, uv + offset, i32(view), ref_depth);
+            taps += 1.0;
+        }
+    }
+
+    return lit / taps;
+}
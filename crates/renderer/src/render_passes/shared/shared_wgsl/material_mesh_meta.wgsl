@@ -21,7 +21,7 @@ struct MaterialMeshMeta {
     color_set_count: u32,
     visibility_geometry_data_offset: u32,
     is_hud: u32,
-    padding_1: u32,
+    receive_shadows: u32,
     padding_2: u32,
     padding_3: u32,
     padding_4: array<vec4<u32>, 11>,
@@ -238,6 +238,9 @@ fn pbr_debug_emissive(debug: u32) -> bool {
 fn pbr_debug_specular(debug: u32) -> bool {
     return (debug & (1u << 5u)) != 0u;
 }
+fn pbr_debug_anisotropy(debug: u32) -> bool {
+    return (debug & (1u << 6u)) != 0u;
+}
 
 
 fn pbr_material_load_vertex_color_info(index: u32) -> VertexColorInfo {
@@ -434,9 +437,36 @@ fn pbr_material_load_anisotropy(index: u32) -> PbrAnisotropy {
     return PbrAnisotropy(tex, strength, rotation);
 }
 
-// iridescence: you have the WGSL struct, but note:
-// your Rust currently does NOT write iridescence payload yet (and never sets feature_indices.iridescence).
-// This loader is here for when you add it.
+// World-space anisotropy direction (xyz) and strength (w)
+// tex_sample is the anisotropy texture's rgb: direction in rg (encoded [0,1]), strength in b
+// (pass vec3(1.0, 0.5, 1.0) when there's no texture, i.e. along the tangent at full strength)
+fn pbr_anisotropy_direction_strength(
+    anisotropy: PbrAnisotropy,
+    tex_sample: vec3<f32>,
+    tangent: vec3<f32>,
+    bitangent: vec3<f32>,
+    normal: vec3<f32>,
+) -> vec4<f32> {
+    let strength = saturate(anisotropy.strength * tex_sample.b);
+    if (strength <= 0.0) {
+        return vec4<f32>(tangent, 0.0);
+    }
+
+    // Rotation is counter-clockwise in tangent space, from the tangent towards the bitangent
+    let c = cos(anisotropy.rotation);
+    let s = sin(anisotropy.rotation);
+    let encoded = tex_sample.rg * 2.0 - 1.0;
+    let direction = vec2<f32>(
+        c * encoded.x - s * encoded.y,
+        s * encoded.x + c * encoded.y,
+    );
+
+    let tbn = mat3x3<f32>(tangent, bitangent, normal);
+    return vec4<f32>(safe_normalize(tbn * vec3<f32>(direction, 0.0)), strength);
+}
+
+// iridescence packed by Rust as:
+//   tex(5) + factor + ior + thickness_tex(5) + thickness_min + thickness_max
 struct PbrIridescence {
     tex_info: TextureInfo,
     factor: f32,
@@ -458,8 +488,6 @@ fn pbr_material_load_iridescence(index: u32) -> PbrIridescence {
         );
     }
 
-    // Expected layout (when you implement Rust packing):
-    // tex(5) + factor + ior + thickness_tex(5) + thickness_min + thickness_max
     let tex = material_load_texture_info(index + 0u);
     let factor = material_load_f32(index + 5u);
     let ior = material_load_f32(index + 6u);
@@ -13,10 +13,15 @@ struct PbrMaterialColor {
     ior: f32,
     // KHR_materials_transmission
     transmission: f32,
+    // KHR_materials_diffuse_transmission
+    diffuse_transmission: f32,             // Share of diffuse light passing through (0.0 = none)
+    diffuse_transmission_color: vec3<f32>, // Tint of the light passing through
     // KHR_materials_volume
     volume_thickness: f32,
     volume_attenuation_distance: f32,
     volume_attenuation_color: vec3<f32>,
+    // KHR_materials_dispersion
+    dispersion: f32,             // Chromatic spread of refraction (0.0 = none), only for volumes
     // KHR_materials_clearcoat
     clearcoat: f32,              // Clearcoat layer intensity (0.0 = none, 1.0 = full)
     clearcoat_roughness: f32,    // Roughness of clearcoat layer
@@ -24,6 +29,15 @@ struct PbrMaterialColor {
     // KHR_materials_sheen
     sheen_color: vec3<f32>,      // Sheen color at grazing angles
     sheen_roughness: f32,        // Sheen roughness (affects sheen lobe width)
+    // KHR_materials_anisotropy
+    anisotropy_strength: f32,          // 0.0 = isotropic
+    anisotropy_direction: vec3<f32>,   // World-space direction highlights are stretched along
+    // KHR_materials_iridescence
+    iridescence: f32,              // Thin-film intensity (0.0 = none)
+    iridescence_ior: f32,          // IOR of the thin film
+    iridescence_thickness: f32,    // Thin-film thickness in nanometers
+    // Screen-space ambient occlusion, applied to the IBL diffuse term only (1.0 = unoccluded)
+    indirect_occlusion: f32,
 };
 
 fn pbr_debug_material_color(material: PbrMaterial, color: PbrMaterialColor) -> vec3<f32> {
@@ -49,6 +63,10 @@ fn pbr_debug_material_color(material: PbrMaterial, color: PbrMaterialColor) -> v
         // Show specular color modulated by specular strength
         return color.specular_color * color.specular;
     }
+    if(pbr_debug_anisotropy(material.debug_bitmask)) {
+        // Remap direction from [-1,1] to [0,1], black where isotropic
+        return (color.anisotropy_direction * 0.5 + 0.5) * color.anisotropy_strength;
+    }
 
     // This function was only called behind a gate, so we should never reach here.
     // return magenta to signal error
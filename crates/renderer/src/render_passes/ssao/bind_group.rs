@@ -0,0 +1,248 @@
+//! SSAO pass bind group setup.
+
+use std::borrow::Cow;
+
+use crate::{
+    bind_group_layout::{
+        BindGroupLayoutCacheKey, BindGroupLayoutCacheKeyEntry, BindGroupLayoutKey,
+    },
+    bind_groups::{AwsmBindGroupError, BindGroupRecreateContext},
+    error::Result,
+    post_process::{Ssao, SSAO_SETTINGS_BYTE_SIZE},
+    render_passes::{ssao::shader::cache_key::SsaoPhase, RenderPassInitContext},
+    render_textures::RenderTextureFormats,
+};
+use awsm_renderer_core::{
+    bind_groups::{
+        BindGroupDescriptor, BindGroupEntry, BindGroupLayoutResource, BindGroupResource,
+        BufferBindingLayout, BufferBindingType, StorageTextureAccess, StorageTextureBindingLayout,
+        TextureBindingLayout,
+    },
+    buffers::{BufferBinding, BufferDescriptor, BufferUsage},
+    renderer::AwsmRendererWebGpu,
+    texture::{TextureSampleType, TextureViewDimension},
+};
+
+/// Bind group layouts, settings uniform and cached bind groups for the SSAO pass.
+pub struct SsaoBindGroups {
+    pub multisampled_occlusion_bind_group_layout_key: BindGroupLayoutKey,
+    pub singlesampled_occlusion_bind_group_layout_key: BindGroupLayoutKey,
+    pub multisampled_blur_bind_group_layout_key: BindGroupLayoutKey,
+    pub singlesampled_blur_bind_group_layout_key: BindGroupLayoutKey,
+    pub settings_buffer: web_sys::GpuBuffer,
+    // these are set via `recreate` mechanism
+    _occlusion_bind_group: Option<web_sys::GpuBindGroup>,
+    _blur_bind_group: Option<web_sys::GpuBindGroup>,
+}
+
+impl SsaoBindGroups {
+    /// Creates bind group layouts and the settings buffer for the SSAO pass.
+    pub async fn new(ctx: &mut RenderPassInitContext<'_>) -> Result<Self> {
+        let multisampled_occlusion_bind_group_layout_key = ctx.bind_group_layouts.get_key(
+            ctx.gpu,
+            bind_group_layout_cache_key(ctx.render_texture_formats, true, SsaoPhase::Occlusion),
+        )?;
+
+        let singlesampled_occlusion_bind_group_layout_key = ctx.bind_group_layouts.get_key(
+            ctx.gpu,
+            bind_group_layout_cache_key(ctx.render_texture_formats, false, SsaoPhase::Occlusion),
+        )?;
+
+        let multisampled_blur_bind_group_layout_key = ctx.bind_group_layouts.get_key(
+            ctx.gpu,
+            bind_group_layout_cache_key(ctx.render_texture_formats, true, SsaoPhase::Blur),
+        )?;
+
+        let singlesampled_blur_bind_group_layout_key = ctx.bind_group_layouts.get_key(
+            ctx.gpu,
+            bind_group_layout_cache_key(ctx.render_texture_formats, false, SsaoPhase::Blur),
+        )?;
+
+        let settings_buffer = ctx.gpu.create_buffer(
+            &BufferDescriptor::new(
+                Some("SSAO Settings"),
+                SSAO_SETTINGS_BYTE_SIZE,
+                BufferUsage::new().with_uniform().with_copy_dst(),
+            )
+            .into(),
+        )?;
+
+        Ok(Self {
+            multisampled_occlusion_bind_group_layout_key,
+            singlesampled_occlusion_bind_group_layout_key,
+            multisampled_blur_bind_group_layout_key,
+            singlesampled_blur_bind_group_layout_key,
+            settings_buffer,
+            _occlusion_bind_group: None,
+            _blur_bind_group: None,
+        })
+    }
+
+    /// Uploads the SSAO settings.
+    pub fn write_settings(&self, gpu: &AwsmRendererWebGpu, ssao: &Ssao) -> Result<()> {
+        gpu.write_buffer(
+            &self.settings_buffer,
+            None,
+            ssao.uniform_data().as_slice(),
+            None,
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Returns the bind group for the given phase.
+    pub fn get_bind_group(
+        &self,
+        phase: SsaoPhase,
+    ) -> std::result::Result<&web_sys::GpuBindGroup, AwsmBindGroupError> {
+        match phase {
+            SsaoPhase::Occlusion => self
+                ._occlusion_bind_group
+                .as_ref()
+                .ok_or_else(|| AwsmBindGroupError::NotFound("SSAO".to_string())),
+            SsaoPhase::Blur => self
+                ._blur_bind_group
+                .as_ref()
+                .ok_or_else(|| AwsmBindGroupError::NotFound("SSAO Blur".to_string())),
+        }
+    }
+
+    /// Recreates the bind groups for the current render textures.
+    pub fn recreate(&mut self, ctx: &BindGroupRecreateContext<'_>) -> Result<()> {
+        let multisampled_geometry = ctx.anti_aliasing.has_msaa_checked()?;
+
+        self._occlusion_bind_group = Some(self.create_bind_group(
+            ctx,
+            if multisampled_geometry {
+                self.multisampled_occlusion_bind_group_layout_key
+            } else {
+                self.singlesampled_occlusion_bind_group_layout_key
+            },
+            &ctx.render_texture_views.normal_tangent,
+            &ctx.render_texture_views.ambient_occlusion_raw,
+            "SSAO",
+        )?);
+
+        self._blur_bind_group = Some(self.create_bind_group(
+            ctx,
+            if multisampled_geometry {
+                self.multisampled_blur_bind_group_layout_key
+            } else {
+                self.singlesampled_blur_bind_group_layout_key
+            },
+            &ctx.render_texture_views.ambient_occlusion_raw,
+            &ctx.render_texture_views.ambient_occlusion,
+            "SSAO Blur",
+        )?);
+
+        Ok(())
+    }
+
+    // both phases share a layout shape, only the second texture differs
+    fn create_bind_group(
+        &self,
+        ctx: &BindGroupRecreateContext<'_>,
+        layout_key: BindGroupLayoutKey,
+        input: &web_sys::GpuTextureView,
+        output: &web_sys::GpuTextureView,
+        label: &str,
+    ) -> Result<web_sys::GpuBindGroup> {
+        let mut entries = Vec::new();
+
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(&ctx.render_texture_views.depth)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(input)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&ctx.camera.gpu_buffer)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&self.settings_buffer)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(output)),
+        ));
+
+        let descriptor = BindGroupDescriptor::new(
+            ctx.bind_group_layouts.get(layout_key)?,
+            Some(label),
+            entries,
+        );
+
+        Ok(ctx.gpu.create_bind_group(&descriptor.into()))
+    }
+}
+
+fn bind_group_layout_cache_key(
+    render_texture_formats: &RenderTextureFormats,
+    multisampled_geometry: bool,
+    phase: SsaoPhase,
+) -> BindGroupLayoutCacheKey {
+    BindGroupLayoutCacheKey {
+        entries: vec![
+            // Depth texture
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Texture(
+                    TextureBindingLayout::new()
+                        .with_view_dimension(TextureViewDimension::N2d)
+                        .with_sample_type(TextureSampleType::Depth)
+                        .with_multisampled(multisampled_geometry),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Occlusion: normal tangent texture, Blur: raw occlusion texture
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Texture(
+                    TextureBindingLayout::new()
+                        .with_view_dimension(TextureViewDimension::N2d)
+                        .with_sample_type(TextureSampleType::UnfilterableFloat)
+                        .with_multisampled(match phase {
+                            SsaoPhase::Occlusion => multisampled_geometry,
+                            SsaoPhase::Blur => false,
+                        }),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Camera uniform, for reconstructing view-space positions
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new().with_binding_type(BufferBindingType::Uniform),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Settings uniform
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new().with_binding_type(BufferBindingType::Uniform),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Occlusion output
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::StorageTexture(
+                    StorageTextureBindingLayout::new(render_texture_formats.ambient_occlusion)
+                        .with_view_dimension(TextureViewDimension::N2d)
+                        .with_access(StorageTextureAccess::WriteOnly),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+        ],
+    }
+}
@@ -0,0 +1,165 @@
+//! SSAO pass pipeline setup.
+
+use awsm_renderer_core::renderer::AwsmRendererWebGpu;
+
+use crate::{
+    anti_alias::AntiAliasing,
+    error::Result,
+    pipeline_layouts::{PipelineLayoutCacheKey, PipelineLayoutKey, PipelineLayouts},
+    pipelines::{
+        compute_pipeline::{ComputePipelineCacheKey, ComputePipelineKey},
+        Pipelines,
+    },
+    post_process::PostProcessing,
+    render_passes::{
+        ssao::{
+            bind_group::SsaoBindGroups,
+            shader::cache_key::{ShaderCacheKeySsao, SsaoPhase},
+        },
+        RenderPassInitContext,
+    },
+    shaders::Shaders,
+};
+
+/// Compute pipelines for the SSAO occlusion and blur.
+#[derive(Clone)]
+pub struct SsaoPipelines {
+    multisampled_occlusion_pipeline_layout_key: PipelineLayoutKey,
+    singlesampled_occlusion_pipeline_layout_key: PipelineLayoutKey,
+    multisampled_blur_pipeline_layout_key: PipelineLayoutKey,
+    singlesampled_blur_pipeline_layout_key: PipelineLayoutKey,
+
+    // None when SSAO is disabled
+    occlusion_pipeline: Option<ComputePipelineKey>,
+    blur_pipeline: Option<ComputePipelineKey>,
+}
+
+impl SsaoPipelines {
+    /// Creates pipeline layout state for the SSAO pass.
+    pub async fn new(
+        ctx: &mut RenderPassInitContext<'_>,
+        bind_groups: &SsaoBindGroups,
+    ) -> Result<Self> {
+        let multisampled_occlusion_pipeline_layout_key = ctx.pipeline_layouts.get_key(
+            ctx.gpu,
+            ctx.bind_group_layouts,
+            PipelineLayoutCacheKey::new(vec![
+                bind_groups.multisampled_occlusion_bind_group_layout_key,
+            ]),
+        )?;
+
+        let singlesampled_occlusion_pipeline_layout_key = ctx.pipeline_layouts.get_key(
+            ctx.gpu,
+            ctx.bind_group_layouts,
+            PipelineLayoutCacheKey::new(vec![
+                bind_groups.singlesampled_occlusion_bind_group_layout_key,
+            ]),
+        )?;
+
+        let multisampled_blur_pipeline_layout_key = ctx.pipeline_layouts.get_key(
+            ctx.gpu,
+            ctx.bind_group_layouts,
+            PipelineLayoutCacheKey::new(vec![bind_groups.multisampled_blur_bind_group_layout_key]),
+        )?;
+
+        let singlesampled_blur_pipeline_layout_key = ctx.pipeline_layouts.get_key(
+            ctx.gpu,
+            ctx.bind_group_layouts,
+            PipelineLayoutCacheKey::new(vec![bind_groups.singlesampled_blur_bind_group_layout_key]),
+        )?;
+
+        Ok(Self {
+            multisampled_occlusion_pipeline_layout_key,
+            singlesampled_occlusion_pipeline_layout_key,
+            multisampled_blur_pipeline_layout_key,
+            singlesampled_blur_pipeline_layout_key,
+            occlusion_pipeline: None,
+            blur_pipeline: None,
+        })
+    }
+
+    /// Returns the pipeline for the given phase, if SSAO is enabled.
+    pub fn get_pipeline(&self, phase: SsaoPhase) -> Option<ComputePipelineKey> {
+        match phase {
+            SsaoPhase::Occlusion => self.occlusion_pipeline,
+            SsaoPhase::Blur => self.blur_pipeline,
+        }
+    }
+
+    /// Updates pipelines for the current anti-aliasing and post-processing settings.
+    pub async fn set_render_pipeline_keys(
+        &mut self,
+        anti_aliasing: &AntiAliasing,
+        post_processing: &PostProcessing,
+        gpu: &AwsmRendererWebGpu,
+        shaders: &mut Shaders,
+        pipelines: &mut Pipelines,
+        pipeline_layouts: &PipelineLayouts,
+    ) -> Result<()> {
+        if post_processing.ssao.is_none() {
+            self.occlusion_pipeline = None;
+            self.blur_pipeline = None;
+            return Ok(());
+        }
+
+        let multisampled_geometry = anti_aliasing.has_msaa_checked()?;
+
+        self.occlusion_pipeline = Some(
+            self.create_pipeline(
+                gpu,
+                shaders,
+                pipelines,
+                pipeline_layouts,
+                multisampled_geometry,
+                SsaoPhase::Occlusion,
+            )
+            .await?,
+        );
+
+        // the blur pipeline also runs with the blur off, as a plain copy
+        self.blur_pipeline = Some(
+            self.create_pipeline(
+                gpu,
+                shaders,
+                pipelines,
+                pipeline_layouts,
+                multisampled_geometry,
+                SsaoPhase::Blur,
+            )
+            .await?,
+        );
+
+        Ok(())
+    }
+
+    async fn create_pipeline(
+        &self,
+        gpu: &AwsmRendererWebGpu,
+        shaders: &mut Shaders,
+        pipelines: &mut Pipelines,
+        pipeline_layouts: &PipelineLayouts,
+        multisampled_geometry: bool,
+        phase: SsaoPhase,
+    ) -> Result<ComputePipelineKey> {
+        let shader_cache_key = ShaderCacheKeySsao {
+            multisampled_geometry,
+            phase,
+        };
+        let shader_key = shaders.get_key(gpu, shader_cache_key).await?;
+
+        let pipeline_layout_key = match (phase, multisampled_geometry) {
+            (SsaoPhase::Occlusion, true) => self.multisampled_occlusion_pipeline_layout_key,
+            (SsaoPhase::Occlusion, false) => self.singlesampled_occlusion_pipeline_layout_key,
+            (SsaoPhase::Blur, true) => self.multisampled_blur_pipeline_layout_key,
+            (SsaoPhase::Blur, false) => self.singlesampled_blur_pipeline_layout_key,
+        };
+
+        let compute_pipeline_cache_key =
+            ComputePipelineCacheKey::new(shader_key, pipeline_layout_key);
+
+        Ok(pipelines
+            .compute
+            .get_key(gpu, shaders, pipeline_layouts, compute_pipeline_cache_key)
+            .await?)
+    }
+}
@@ -0,0 +1,65 @@
+//! SSAO render pass execution.
+
+use awsm_renderer_core::command::compute_pass::ComputePassDescriptor;
+
+use crate::{
+    error::Result,
+    render::RenderContext,
+    render_passes::{
+        ssao::{
+            bind_group::SsaoBindGroups, pipeline::SsaoPipelines,
+            shader::cache_key::SsaoPhase,
+        },
+        RenderPassInitContext,
+    },
+};
+
+/// SSAO pass bind groups and pipelines.
+pub struct SsaoRenderPass {
+    pub bind_groups: SsaoBindGroups,
+    pub pipelines: SsaoPipelines,
+}
+
+impl SsaoRenderPass {
+    /// Creates the SSAO render pass resources.
+    pub async fn new(ctx: &mut RenderPassInitContext<'_>) -> Result<Self> {
+        let bind_groups = SsaoBindGroups::new(ctx).await?;
+        let pipelines = SsaoPipelines::new(ctx, &bind_groups).await?;
+
+        Ok(Self {
+            bind_groups,
+            pipelines,
+        })
+    }
+
+    /// Computes the ambient occlusion from the geometry pass depth and normals, then blurs it.
+    ///
+    /// Does nothing when SSAO is disabled.
+    pub fn render(&self, ctx: &RenderContext) -> Result<()> {
+        for phase in [SsaoPhase::Occlusion, SsaoPhase::Blur] {
+            let Some(pipeline_key) = self.pipelines.get_pipeline(phase) else {
+                return Ok(());
+            };
+
+            let compute_pass = ctx.command_encoder.begin_compute_pass(Some(
+                &ComputePassDescriptor::new(Some(match phase {
+                    SsaoPhase::Occlusion => "SSAO Pass",
+                    SsaoPhase::Blur => "SSAO Blur Pass",
+                }))
+                .into(),
+            ));
+
+            compute_pass.set_bind_group(0, self.bind_groups.get_bind_group(phase)?, None)?;
+            compute_pass.set_pipeline(ctx.pipelines.compute.get(pipeline_key)?);
+            compute_pass.dispatch_workgroups(
+                ctx.render_texture_views.width.div_ceil(8),
+                Some(ctx.render_texture_views.height.div_ceil(8)),
+                Some(1),
+            );
+
+            compute_pass.end();
+        }
+
+        Ok(())
+    }
+}
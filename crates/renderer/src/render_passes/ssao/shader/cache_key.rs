@@ -0,0 +1,25 @@
+//! Shader cache key definitions for the SSAO pass.
+
+use crate::{render_passes::shader_cache_key::ShaderCacheKeyRenderPass, shaders::ShaderCacheKey};
+
+/// Phase of the SSAO pass
+#[derive(Hash, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SsaoPhase {
+    /// Samples the depth buffer around each pixel, writes the raw occlusion
+    Occlusion,
+    /// Depth-aware blur of the raw occlusion (or a plain copy when the blur is off)
+    Blur,
+}
+
+/// Cache key for SSAO pass shaders.
+#[derive(Hash, Debug, Clone, PartialEq, Eq)]
+pub struct ShaderCacheKeySsao {
+    pub multisampled_geometry: bool,
+    pub phase: SsaoPhase,
+}
+
+impl From<ShaderCacheKeySsao> for ShaderCacheKey {
+    fn from(key: ShaderCacheKeySsao) -> Self {
+        ShaderCacheKey::RenderPass(ShaderCacheKeyRenderPass::Ssao(key))
+    }
+}
@@ -0,0 +1,17 @@
+{% if multisampled_geometry %}
+    @group(0) @binding(0) var depth_tex: texture_depth_multisampled_2d;
+{% else %}
+    @group(0) @binding(0) var depth_tex: texture_depth_2d;
+{% endif %}
+{% if blur %}
+    @group(0) @binding(1) var occlusion_raw_tex: texture_2d<f32>;
+{% else %}
+    {% if multisampled_geometry %}
+        @group(0) @binding(1) var normal_tangent_tex: texture_multisampled_2d<f32>;
+    {% else %}
+        @group(0) @binding(1) var normal_tangent_tex: texture_2d<f32>;
+    {% endif %}
+{% endif %}
+@group(0) @binding(2) var<uniform> camera_raw: CameraRaw;
+@group(0) @binding(3) var<uniform> settings: SsaoSettings;
+@group(0) @binding(4) var occlusion_tex: texture_storage_2d<r32float, write>;
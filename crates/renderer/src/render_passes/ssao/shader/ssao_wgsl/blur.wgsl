@@ -0,0 +1,50 @@
+const BLUR_RADIUS: i32 = 2;
+// How quickly neighbors lose weight as their depth differs from the center (relative to its depth),
+// which keeps the occlusion from bleeding across silhouettes
+const BLUR_DEPTH_SHARPNESS: f32 = 50.0;
+
+@compute @workgroup_size(8, 8)
+fn main(
+    @builtin(global_invocation_id) gid: vec3<u32>
+) {
+    let coords = vec2<i32>(gid.xy);
+    let screen_dims = textureDimensions(occlusion_tex);
+    let screen_dims_i32 = vec2<i32>(i32(screen_dims.x), i32(screen_dims.y));
+
+    // Bounds check
+    if (coords.x >= screen_dims_i32.x || coords.y >= screen_dims_i32.y) {
+        return;
+    }
+
+    let center_occlusion = textureLoad(occlusion_raw_tex, coords, 0).r;
+    let center_depth = textureLoad(depth_tex, coords, 0);
+
+    if (settings.blur == 0u || center_depth >= 1.0) {
+        textureStore(occlusion_tex, coords, vec4<f32>(center_occlusion, 0.0, 0.0, 0.0));
+        return;
+    }
+
+    let camera = camera_from_raw(camera_raw);
+    let screen_dims_f32 = vec2<f32>(f32(screen_dims.x), f32(screen_dims.y));
+    let center_z = view_position(coords, center_depth, screen_dims_f32, camera).z;
+
+    var occlusion = 0.0;
+    var total_weight = 0.0;
+    for (var y = -BLUR_RADIUS; y <= BLUR_RADIUS; y++) {
+        for (var x = -BLUR_RADIUS; x <= BLUR_RADIUS; x++) {
+            let sample_coords = clamp(coords + vec2<i32>(x, y), vec2<i32>(0), screen_dims_i32 - 1);
+            let depth = textureLoad(depth_tex, sample_coords, 0);
+            if (depth >= 1.0) {
+                continue;
+            }
+
+            let z = view_position(sample_coords, depth, screen_dims_f32, camera).z;
+            let weight = exp(-abs(z - center_z) / max(abs(center_z), EPSILON) * BLUR_DEPTH_SHARPNESS);
+            occlusion += textureLoad(occlusion_raw_tex, sample_coords, 0).r * weight;
+            total_weight += weight;
+        }
+    }
+
+    // the center always contributes with a weight of 1
+    textureStore(occlusion_tex, coords, vec4<f32>(occlusion / total_weight, 0.0, 0.0, 0.0));
+}
@@ -0,0 +1,21 @@
+/*************** START camera.wgsl ******************/
+{% include "shared_wgsl/camera.wgsl" %}
+/*************** END camera.wgsl ******************/
+
+/*************** START math.wgsl ******************/
+{% include "shared_wgsl/math.wgsl" %}
+/*************** END math.wgsl ******************/
+
+/*************** START helpers.wgsl ******************/
+{% include "ssao_wgsl/helpers.wgsl" %}
+/*************** END helpers.wgsl ******************/
+
+{% if blur %}
+/*************** START blur.wgsl ******************/
+{% include "ssao_wgsl/blur.wgsl" %}
+/*************** END blur.wgsl ******************/
+{% else %}
+/*************** START occlusion.wgsl ******************/
+{% include "ssao_wgsl/occlusion.wgsl" %}
+/*************** END occlusion.wgsl ******************/
+{% endif %}
@@ -0,0 +1,15 @@
+// Matches Ssao::uniform_data
+struct SsaoSettings {
+    radius: f32,
+    intensity: f32,
+    sample_count: u32,
+    blur: u32,
+};
+
+// View-space position of a pixel from its depth (sample 0 when multisampled)
+fn view_position(coords: vec2<i32>, depth: f32, screen_dims: vec2<f32>, camera: Camera) -> vec3<f32> {
+    let uv = (vec2<f32>(coords) + 0.5) / screen_dims;
+    let ndc = vec2<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0);
+    let position = camera.inv_proj * vec4<f32>(ndc, depth, 1.0);
+    return position.xyz / position.w;
+}
@@ -0,0 +1,88 @@
+const SSAO_MAX_SAMPLE_COUNT: u32 = 64u;
+// Samples must be this far (relative to the radius) in front of the scene to count,
+// so flat surfaces don't occlude themselves
+const DEPTH_BIAS: f32 = 0.025;
+const GOLDEN_ANGLE: f32 = 2.39996323;
+
+@compute @workgroup_size(8, 8)
+fn main(
+    @builtin(global_invocation_id) gid: vec3<u32>
+) {
+    let coords = vec2<i32>(gid.xy);
+    let screen_dims = textureDimensions(occlusion_tex);
+    let screen_dims_i32 = vec2<i32>(i32(screen_dims.x), i32(screen_dims.y));
+
+    // Bounds check
+    if (coords.x >= screen_dims_i32.x || coords.y >= screen_dims_i32.y) {
+        return;
+    }
+
+    let depth = textureLoad(depth_tex, coords, 0);
+    if (depth >= 1.0) {
+        // nothing was drawn here
+        textureStore(occlusion_tex, coords, vec4<f32>(0.0));
+        return;
+    }
+
+    let camera = camera_from_raw(camera_raw);
+    let screen_dims_f32 = vec2<f32>(f32(screen_dims.x), f32(screen_dims.y));
+    let position = view_position(coords, depth, screen_dims_f32, camera);
+    let world_normal = decode_octahedral(textureLoad(normal_tangent_tex, coords, 0).xy);
+    let normal = safe_normalize((camera.view * vec4<f32>(world_normal, 0.0)).xyz);
+    let tb = canonical_tb(normal);
+
+    // Per-pixel rotation of the sample pattern, varied over frames so TAA can average it out.
+    // The remaining noise is what the blur is for
+    let noise = interleaved_gradient_noise(vec2<f32>(coords) + 5.588238 * f32(camera.frame_count % 64u));
+
+    let sample_count = clamp(settings.sample_count, 1u, SSAO_MAX_SAMPLE_COUNT);
+    let sample_count_f32 = f32(sample_count);
+    let bias = settings.radius * DEPTH_BIAS;
+
+    var occlusion = 0.0;
+    for (var i = 0u; i < sample_count; i++) {
+        let fi = f32(i) + 0.5;
+
+        // cosine-weighted spiral over the hemisphere around the normal
+        let sin_theta = sqrt(fi / sample_count_f32);
+        let cos_theta = sqrt(1.0 - fi / sample_count_f32);
+        let phi = fi * GOLDEN_ANGLE + noise * TAU;
+        let direction = (tb.t * cos(phi) + tb.b * sin(phi)) * sin_theta + normal * cos_theta;
+
+        // more samples close to the surface, where the contact shadows are
+        let distance = fract(noise + fi * 0.618034);
+        let sample_position = position + direction * settings.radius * mix(0.1, 1.0, distance * distance);
+
+        let clip = camera.proj * vec4<f32>(sample_position, 1.0);
+        if (clip.w <= 0.0) {
+            continue;
+        }
+        let sample_ndc = clip.xy / clip.w;
+        let sample_uv = vec2<f32>(sample_ndc.x * 0.5 + 0.5, 0.5 - sample_ndc.y * 0.5);
+        if (any(sample_uv < vec2<f32>(0.0)) || any(sample_uv >= vec2<f32>(1.0))) {
+            continue;
+        }
+
+        let sample_coords = clamp(vec2<i32>(sample_uv * screen_dims_f32), vec2<i32>(0), screen_dims_i32 - 1);
+        let scene_depth = textureLoad(depth_tex, sample_coords, 0);
+        if (scene_depth >= 1.0) {
+            continue;
+        }
+        let scene_position = view_position(sample_coords, scene_depth, screen_dims_f32, camera);
+
+        // view space looks down -Z, so the scene is in front of the sample when its Z is larger.
+        // Occluders much further away than the radius fade out, so silhouettes don't get halos
+        if (scene_position.z >= sample_position.z + bias) {
+            occlusion += smoothstep(0.0, 1.0, settings.radius / max(abs(position.z - scene_position.z), EPSILON));
+        }
+    }
+
+    occlusion = saturate(occlusion / sample_count_f32 * settings.intensity);
+
+    textureStore(occlusion_tex, coords, vec4<f32>(occlusion, 0.0, 0.0, 0.0));
+}
+
+// Jimenez 2014, "Next Generation Post Processing in Call of Duty: Advanced Warfare"
+fn interleaved_gradient_noise(position: vec2<f32>) -> f32 {
+    return fract(52.9829189 * fract(dot(position, vec2<f32>(0.06711056, 0.00583715))));
+}
@@ -0,0 +1,79 @@
+//! Shader templates for the SSAO pass.
+
+use askama::Template;
+
+use crate::{
+    render_passes::ssao::shader::cache_key::{ShaderCacheKeySsao, SsaoPhase},
+    shaders::{AwsmShaderError, Result},
+};
+
+/// SSAO pass shader template components.
+#[derive(Debug)]
+pub struct ShaderTemplateSsao {
+    pub bind_groups: ShaderTemplateSsaoBindGroups,
+    pub compute: ShaderTemplateSsaoCompute,
+}
+
+/// Bind group template for the SSAO pass.
+#[derive(Template, Debug)]
+#[template(path = "ssao_wgsl/bind_groups.wgsl", whitespace = "minimize")]
+pub struct ShaderTemplateSsaoBindGroups {
+    pub multisampled_geometry: bool,
+    pub blur: bool,
+}
+
+impl ShaderTemplateSsaoBindGroups {
+    /// Creates a bind group template from the cache key.
+    pub fn new(cache_key: &ShaderCacheKeySsao) -> Self {
+        Self {
+            multisampled_geometry: cache_key.multisampled_geometry,
+            blur: cache_key.phase == SsaoPhase::Blur,
+        }
+    }
+}
+
+/// Compute shader template for the SSAO pass.
+#[derive(Template, Debug)]
+#[template(path = "ssao_wgsl/compute.wgsl", whitespace = "minimize")]
+pub struct ShaderTemplateSsaoCompute {
+    pub blur: bool,
+}
+
+impl ShaderTemplateSsaoCompute {
+    /// Creates a compute shader template from the cache key.
+    pub fn new(cache_key: &ShaderCacheKeySsao) -> Self {
+        Self {
+            blur: cache_key.phase == SsaoPhase::Blur,
+        }
+    }
+}
+
+impl TryFrom<&ShaderCacheKeySsao> for ShaderTemplateSsao {
+    type Error = AwsmShaderError;
+
+    fn try_from(value: &ShaderCacheKeySsao) -> Result<Self> {
+        Ok(Self {
+            bind_groups: ShaderTemplateSsaoBindGroups::new(value),
+            compute: ShaderTemplateSsaoCompute::new(value),
+        })
+    }
+}
+
+impl ShaderTemplateSsao {
+    /// Renders the SSAO shader template into WGSL.
+    pub fn into_source(self) -> Result<String> {
+        let bind_groups_source = self.bind_groups.render()?;
+        let compute_source = self.compute.render()?;
+        Ok(format!("{}\n{}", bind_groups_source, compute_source))
+    }
+
+    #[cfg(debug_assertions)]
+    /// Returns an optional debug label for shader compilation.
+    pub fn debug_label(&self) -> Option<&str> {
+        Some(if self.compute.blur {
+            "SSAO Blur"
+        } else {
+            "SSAO"
+        })
+    }
+}
@@ -0,0 +1,191 @@
+//! TAA pass bind group setup.
+
+use std::borrow::Cow;
+
+use crate::{
+    bind_group_layout::{
+        BindGroupLayoutCacheKey, BindGroupLayoutCacheKeyEntry, BindGroupLayoutKey,
+    },
+    bind_groups::{AwsmBindGroupError, BindGroupRecreateContext},
+    error::Result,
+    render_passes::RenderPassInitContext,
+    render_textures::RenderTextureFormats,
+};
+use awsm_renderer_core::{
+    bind_groups::{
+        BindGroupDescriptor, BindGroupEntry, BindGroupLayoutResource, BindGroupResource,
+        BufferBindingLayout, BufferBindingType, StorageTextureAccess, StorageTextureBindingLayout,
+        TextureBindingLayout,
+    },
+    buffers::BufferBinding,
+    texture::{TextureSampleType, TextureViewDimension},
+};
+
+/// Bind group layouts and cached bind group for the TAA pass.
+#[derive(Default)]
+pub struct TaaBindGroups {
+    pub multisampled_bind_group_layout_key: BindGroupLayoutKey,
+    pub singlesampled_bind_group_layout_key: BindGroupLayoutKey,
+    // this is set via `recreate` mechanism, only when TAA is enabled
+    _bind_group: Option<web_sys::GpuBindGroup>,
+}
+
+impl TaaBindGroups {
+    /// Creates bind group layouts for the TAA pass.
+    pub async fn new(ctx: &mut RenderPassInitContext<'_>) -> Result<Self> {
+        let singlesampled_bind_group_layout_cache_key =
+            bind_group_layout_cache_key(ctx.render_texture_formats, false);
+
+        let multisampled_bind_group_layout_cache_key =
+            bind_group_layout_cache_key(ctx.render_texture_formats, true);
+
+        let singlesampled_bind_group_layout_key = ctx
+            .bind_group_layouts
+            .get_key(ctx.gpu, singlesampled_bind_group_layout_cache_key)?;
+
+        let multisampled_bind_group_layout_key = ctx
+            .bind_group_layouts
+            .get_key(ctx.gpu, multisampled_bind_group_layout_cache_key)?;
+
+        Ok(Self {
+            multisampled_bind_group_layout_key,
+            singlesampled_bind_group_layout_key,
+            _bind_group: None,
+        })
+    }
+
+    /// Returns the TAA bind group.
+    pub fn get_bind_group(
+        &self,
+    ) -> std::result::Result<&web_sys::GpuBindGroup, AwsmBindGroupError> {
+        self._bind_group
+            .as_ref()
+            .ok_or_else(|| AwsmBindGroupError::NotFound("TAA".to_string()))
+    }
+
+    /// Recreates the bind group for the current render textures.
+    pub fn recreate(&mut self, ctx: &BindGroupRecreateContext<'_>) -> Result<()> {
+        let Some(taa) = ctx.render_texture_views.taa.as_ref() else {
+            self._bind_group = None;
+            return Ok(());
+        };
+
+        let mut entries = Vec::new();
+
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(&ctx.render_texture_views.composite)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::Buffer(BufferBinding::new(&ctx.camera.gpu_buffer)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(&ctx.render_texture_views.depth)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(&taa.motion_vector)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(&taa.history)),
+        ));
+        entries.push(BindGroupEntry::new(
+            entries.len() as u32,
+            BindGroupResource::TextureView(Cow::Borrowed(&taa.resolve)),
+        ));
+
+        let descriptor = BindGroupDescriptor::new(
+            ctx.bind_group_layouts
+                .get(if ctx.anti_aliasing.has_msaa_checked()? {
+                    self.multisampled_bind_group_layout_key
+                } else {
+                    self.singlesampled_bind_group_layout_key
+                })?,
+            Some("TAA"),
+            entries,
+        );
+
+        self._bind_group = Some(ctx.gpu.create_bind_group(&descriptor.into()));
+
+        Ok(())
+    }
+}
+
+fn bind_group_layout_cache_key(
+    render_texture_formats: &RenderTextureFormats,
+    multisampled_geometry: bool,
+) -> BindGroupLayoutCacheKey {
+    BindGroupLayoutCacheKey {
+        entries: vec![
+            // Composite texture
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Texture(
+                    TextureBindingLayout::new()
+                        .with_view_dimension(TextureViewDimension::N2d)
+                        .with_sample_type(TextureSampleType::UnfilterableFloat),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Camera uniform, for jitter and reprojecting the background
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Buffer(
+                    BufferBindingLayout::new().with_binding_type(BufferBindingType::Uniform),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Depth texture
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Texture(
+                    TextureBindingLayout::new()
+                        .with_view_dimension(TextureViewDimension::N2d)
+                        .with_sample_type(TextureSampleType::Depth)
+                        .with_multisampled(multisampled_geometry),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Motion vector texture
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Texture(
+                    TextureBindingLayout::new()
+                        .with_view_dimension(TextureViewDimension::N2d)
+                        .with_sample_type(TextureSampleType::UnfilterableFloat)
+                        .with_multisampled(multisampled_geometry),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // History texture
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::Texture(
+                    TextureBindingLayout::new()
+                        .with_view_dimension(TextureViewDimension::N2d)
+                        .with_sample_type(TextureSampleType::UnfilterableFloat),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+            // Resolve texture
+            BindGroupLayoutCacheKeyEntry {
+                resource: BindGroupLayoutResource::StorageTexture(
+                    StorageTextureBindingLayout::new(render_texture_formats.color)
+                        .with_view_dimension(TextureViewDimension::N2d)
+                        .with_access(StorageTextureAccess::WriteOnly),
+                ),
+                visibility_vertex: false,
+                visibility_fragment: false,
+                visibility_compute: true,
+            },
+        ],
+    }
+}
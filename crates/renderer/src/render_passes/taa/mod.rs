@@ -0,0 +1,4 @@
+pub mod bind_group;
+pub mod pipeline;
+pub mod render_pass;
+pub mod shader;
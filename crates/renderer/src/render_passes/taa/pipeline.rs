@@ -0,0 +1,144 @@
+//! TAA pass pipeline setup.
+
+use awsm_renderer_core::renderer::AwsmRendererWebGpu;
+
+use crate::{
+    anti_alias::AntiAliasing,
+    error::Result,
+    pipeline_layouts::{PipelineLayoutCacheKey, PipelineLayoutKey, PipelineLayouts},
+    pipelines::{
+        compute_pipeline::{ComputePipelineCacheKey, ComputePipelineKey},
+        Pipelines,
+    },
+    render_passes::{
+        taa::{bind_group::TaaBindGroups, shader::cache_key::ShaderCacheKeyTaa},
+        RenderPassInitContext,
+    },
+    shaders::Shaders,
+};
+
+/// Compute pipelines for the TAA resolve.
+pub struct TaaPipelines {
+    multisampled_pipeline_layout_key: PipelineLayoutKey,
+    singlesampled_pipeline_layout_key: PipelineLayoutKey,
+
+    // None when TAA is disabled
+    with_history_pipeline: Option<ComputePipelineKey>,
+    // first frame, or after a reset
+    without_history_pipeline: Option<ComputePipelineKey>,
+}
+
+impl TaaPipelines {
+    /// Creates pipeline layout state for the TAA pass.
+    pub async fn new(
+        ctx: &mut RenderPassInitContext<'_>,
+        bind_groups: &TaaBindGroups,
+    ) -> Result<Self> {
+        let singlesampled_pipeline_layout_cache_key =
+            PipelineLayoutCacheKey::new(vec![bind_groups.singlesampled_bind_group_layout_key]);
+        let multisampled_pipeline_layout_cache_key =
+            PipelineLayoutCacheKey::new(vec![bind_groups.multisampled_bind_group_layout_key]);
+
+        let singlesampled_pipeline_layout_key = ctx.pipeline_layouts.get_key(
+            ctx.gpu,
+            ctx.bind_group_layouts,
+            singlesampled_pipeline_layout_cache_key,
+        )?;
+
+        let multisampled_pipeline_layout_key = ctx.pipeline_layouts.get_key(
+            ctx.gpu,
+            ctx.bind_group_layouts,
+            multisampled_pipeline_layout_cache_key,
+        )?;
+
+        Ok(Self {
+            multisampled_pipeline_layout_key,
+            singlesampled_pipeline_layout_key,
+            with_history_pipeline: None,
+            without_history_pipeline: None,
+        })
+    }
+
+    /// Returns the pipeline for the given history state, if TAA is enabled.
+    pub fn get_pipeline(&self, history_valid: bool) -> Option<ComputePipelineKey> {
+        if history_valid {
+            self.with_history_pipeline
+        } else {
+            self.without_history_pipeline
+        }
+    }
+
+    /// Updates pipelines for the current anti-aliasing settings.
+    pub async fn set_render_pipeline_keys(
+        &mut self,
+        anti_aliasing: &AntiAliasing,
+        gpu: &AwsmRendererWebGpu,
+        shaders: &mut Shaders,
+        pipelines: &mut Pipelines,
+        pipeline_layouts: &PipelineLayouts,
+    ) -> Result<()> {
+        if !anti_aliasing.taa {
+            self.with_history_pipeline = None;
+            self.without_history_pipeline = None;
+            return Ok(());
+        }
+
+        let multisampled_geometry = anti_aliasing.has_msaa_checked()?;
+
+        self.with_history_pipeline = Some(
+            self.create_pipeline(
+                gpu,
+                shaders,
+                pipelines,
+                pipeline_layouts,
+                multisampled_geometry,
+                true,
+            )
+            .await?,
+        );
+
+        self.without_history_pipeline = Some(
+            self.create_pipeline(
+                gpu,
+                shaders,
+                pipelines,
+                pipeline_layouts,
+                multisampled_geometry,
+                false,
+            )
+            .await?,
+        );
+
+        Ok(())
+    }
+
+    async fn create_pipeline(
+        &self,
+        gpu: &AwsmRendererWebGpu,
+        shaders: &mut Shaders,
+        pipelines: &mut Pipelines,
+        pipeline_layouts: &PipelineLayouts,
+        multisampled_geometry: bool,
+        history_valid: bool,
+    ) -> Result<ComputePipelineKey> {
+        let shader_cache_key = ShaderCacheKeyTaa {
+            multisampled_geometry,
+            history_valid,
+        };
+        let shader_key = shaders.get_key(gpu, shader_cache_key).await?;
+
+        let compute_pipeline_cache_key = ComputePipelineCacheKey::new(
+            shader_key,
+            if multisampled_geometry {
+                self.multisampled_pipeline_layout_key
+            } else {
+                self.singlesampled_pipeline_layout_key
+            },
+        );
+
+        Ok(pipelines
+            .compute
+            .get_key(gpu, shaders, pipeline_layouts, compute_pipeline_cache_key)
+            .await?)
+    }
+}
@@ -0,0 +1,75 @@
+//! TAA render pass execution.
+
+use awsm_renderer_core::{
+    command::{compute_pass::ComputePassDescriptor, copy_texture::TexelCopyTextureInfo},
+    texture::Extent3d,
+};
+
+use crate::{
+    error::Result,
+    render::RenderContext,
+    render_passes::{
+        taa::{bind_group::TaaBindGroups, pipeline::TaaPipelines},
+        RenderPassInitContext,
+    },
+};
+
+/// TAA pass bind groups and pipelines.
+pub struct TaaRenderPass {
+    pub bind_groups: TaaBindGroups,
+    pub pipelines: TaaPipelines,
+}
+
+impl TaaRenderPass {
+    /// Creates the TAA render pass resources.
+    pub async fn new(ctx: &mut RenderPassInitContext<'_>) -> Result<Self> {
+        let bind_groups = TaaBindGroups::new(ctx).await?;
+        let pipelines = TaaPipelines::new(ctx, &bind_groups).await?;
+
+        Ok(Self {
+            bind_groups,
+            pipelines,
+        })
+    }
+
+    /// Resolves the composite texture against the history, then keeps the result as the next
+    /// frame's history.
+    ///
+    /// Does nothing when TAA is disabled.
+    pub fn render(&self, ctx: &RenderContext) -> Result<()> {
+        let Some(taa) = ctx.render_texture_views.taa.as_ref() else {
+            return Ok(());
+        };
+
+        let Some(pipeline_key) = self.pipelines.get_pipeline(taa.history_valid) else {
+            return Ok(());
+        };
+
+        let compute_pass = ctx
+            .command_encoder
+            .begin_compute_pass(Some(&ComputePassDescriptor::new(Some("TAA Pass")).into()));
+
+        compute_pass.set_bind_group(0, self.bind_groups.get_bind_group()?, None)?;
+        compute_pass.set_pipeline(ctx.pipelines.compute.get(pipeline_key)?);
+        compute_pass.dispatch_workgroups(
+            ctx.render_texture_views.width.div_ceil(8),
+            Some(ctx.render_texture_views.height.div_ceil(8)),
+            Some(1),
+        );
+
+        compute_pass.end();
+
+        ctx.command_encoder.copy_texture_to_texture(
+            &TexelCopyTextureInfo::new(&taa.resolve_texture).into(),
+            &TexelCopyTextureInfo::new(&taa.history_texture).into(),
+            &Extent3d::new(
+                ctx.render_texture_views.width,
+                Some(ctx.render_texture_views.height),
+                Some(1),
+            )
+            .into(),
+        )?;
+
+        Ok(())
+    }
+}
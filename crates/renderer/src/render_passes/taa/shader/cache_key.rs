@@ -0,0 +1,17 @@
+//! Shader cache key definitions for the TAA pass.
+
+use crate::{render_passes::shader_cache_key::ShaderCacheKeyRenderPass, shaders::ShaderCacheKey};
+
+/// Cache key for TAA pass shaders.
+#[derive(Hash, Debug, Clone, PartialEq, Eq)]
+pub struct ShaderCacheKeyTaa {
+    pub multisampled_geometry: bool,
+    // false on the first frame and after a reset, the current frame is passed through as-is
+    pub history_valid: bool,
+}
+
+impl From<ShaderCacheKeyTaa> for ShaderCacheKey {
+    fn from(key: ShaderCacheKeyTaa) -> Self {
+        ShaderCacheKey::RenderPass(ShaderCacheKeyRenderPass::Taa(key))
+    }
+}
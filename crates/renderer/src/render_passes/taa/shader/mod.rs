@@ -0,0 +1,2 @@
+pub mod cache_key;
+pub mod template;
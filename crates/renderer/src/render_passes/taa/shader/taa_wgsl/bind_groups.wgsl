@@ -0,0 +1,11 @@
+@group(0) @binding(0) var composite_tex: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> camera_raw: CameraRaw;
+{% if multisampled_geometry %}
+    @group(0) @binding(2) var depth_tex: texture_depth_multisampled_2d;
+    @group(0) @binding(3) var motion_vector_tex: texture_multisampled_2d<f32>;
+{% else %}
+    @group(0) @binding(2) var depth_tex: texture_depth_2d;
+    @group(0) @binding(3) var motion_vector_tex: texture_2d<f32>;
+{% endif %}
+@group(0) @binding(4) var history_tex: texture_2d<f32>;
+@group(0) @binding(5) var resolve_tex: texture_storage_2d<rgba16float, write>;
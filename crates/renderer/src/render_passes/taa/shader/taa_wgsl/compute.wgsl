@@ -0,0 +1,168 @@
+/*************** START camera.wgsl ******************/
+{% include "shared_wgsl/camera.wgsl" %}
+/*************** END camera.wgsl ******************/
+
+// Weight of the current frame when blending with the history
+const CURRENT_WEIGHT: f32 = 0.1;
+// How far (in standard deviations) the history may be from the neighborhood mean
+const VARIANCE_CLIP_GAMMA: f32 = 1.0;
+
+@compute @workgroup_size(8, 8)
+fn main(
+    @builtin(global_invocation_id) gid: vec3<u32>
+) {
+    let coords = vec2<i32>(gid.xy);
+    let screen_dims = textureDimensions(composite_tex);
+    let screen_dims_i32 = vec2<i32>(i32(screen_dims.x), i32(screen_dims.y));
+
+    // Bounds check
+    if (coords.x >= screen_dims_i32.x || coords.y >= screen_dims_i32.y) {
+        return;
+    }
+
+    let current = textureLoad(composite_tex, coords, 0).rgb;
+
+    {% if history_valid %}
+        let camera = camera_from_raw(camera_raw);
+        let screen_dims_f32 = vec2<f32>(f32(screen_dims.x), f32(screen_dims.y));
+        let uv = (vec2<f32>(coords) + 0.5) / screen_dims_f32;
+
+        // Neighborhood statistics in a tonemapped YCoCg space, so bright outliers don't dominate
+        // and the clipping box is tighter around the chroma
+        var m1 = vec3<f32>(0.0);
+        var m2 = vec3<f32>(0.0);
+        var neighborhood_min = vec3<f32>(1e9);
+        var neighborhood_max = vec3<f32>(-1e9);
+        // the motion of the closest surface around the pixel, so edges of moving objects
+        // are reprojected with them instead of with the background
+        var closest_depth = 2.0;
+        var closest_coords = coords;
+
+        for (var y = -1; y <= 1; y++) {
+            for (var x = -1; x <= 1; x++) {
+                let sample_coords = clamp(coords + vec2<i32>(x, y), vec2<i32>(0), screen_dims_i32 - 1);
+                let color = rgb_to_ycocg(tonemap(textureLoad(composite_tex, sample_coords, 0).rgb));
+                m1 += color;
+                m2 += color * color;
+                neighborhood_min = min(neighborhood_min, color);
+                neighborhood_max = max(neighborhood_max, color);
+
+                let depth = textureLoad(depth_tex, sample_coords, 0);
+                if (depth < closest_depth) {
+                    closest_depth = depth;
+                    closest_coords = sample_coords;
+                }
+            }
+        }
+
+        var motion: vec2<f32>;
+        if (closest_depth >= 1.0) {
+            // nothing was drawn here, only the camera moved
+            motion = background_motion(uv, camera);
+        } else {
+            motion = textureLoad(motion_vector_tex, closest_coords, 0).xy;
+        }
+
+        let history_uv = uv + motion;
+        if (any(history_uv < vec2<f32>(0.0)) || any(history_uv > vec2<f32>(1.0))) {
+            // disoccluded at the edge of the screen, nothing to reproject
+            textureStore(resolve_tex, coords, vec4<f32>(current, 1.0));
+            return;
+        }
+
+        let mean = m1 / 9.0;
+        let sigma = sqrt(max(m2 / 9.0 - mean * mean, vec3<f32>(0.0)));
+        let clip_min = max(neighborhood_min, mean - VARIANCE_CLIP_GAMMA * sigma);
+        let clip_max = min(neighborhood_max, mean + VARIANCE_CLIP_GAMMA * sigma);
+
+        let history = rgb_to_ycocg(tonemap(sample_history(history_uv, screen_dims_f32)));
+        let clipped_history = clip_to_box(history, clip_min, clip_max);
+
+        let blended = mix(clipped_history, rgb_to_ycocg(tonemap(current)), CURRENT_WEIGHT);
+        var result = tonemap_invert(ycocg_to_rgb(blended));
+
+        // never let a bad history sample stick around
+        if (any(result != result)) {
+            result = current;
+        }
+
+        textureStore(resolve_tex, coords, vec4<f32>(result, 1.0));
+    {% else %}
+        textureStore(resolve_tex, coords, vec4<f32>(current, 1.0));
+    {% endif %}
+}
+
+{% if history_valid %}
+// Motion of a background pixel, from the camera alone (see motion_vector in the geometry pass)
+fn background_motion(uv: vec2<f32>, camera: Camera) -> vec2<f32> {
+    let ndc = vec2<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0);
+    // a point on the far plane, may be at infinity (w = 0), which still projects fine
+    let world = camera.inv_view_proj * vec4<f32>(ndc, 1.0, 1.0);
+    let previous_clip = camera.prev_view_proj * world;
+    if (previous_clip.w <= 0.0) {
+        return vec2<f32>(0.0);
+    }
+
+    let previous_ndc = previous_clip.xy / previous_clip.w;
+    let current_ndc = ndc - camera.jitter;
+
+    return (previous_ndc - current_ndc) * vec2<f32>(0.5, -0.5);
+}
+
+// Manual bilinear, the history is an unfilterable float texture
+fn sample_history(uv: vec2<f32>, screen_dims: vec2<f32>) -> vec3<f32> {
+    let position = uv * screen_dims - 0.5;
+    let base = floor(position);
+    let f = position - base;
+    let max_coords = vec2<i32>(screen_dims) - 1;
+    let c00 = clamp(vec2<i32>(base), vec2<i32>(0), max_coords);
+    let c11 = clamp(vec2<i32>(base) + 1, vec2<i32>(0), max_coords);
+
+    let s00 = textureLoad(history_tex, c00, 0).rgb;
+    let s10 = textureLoad(history_tex, vec2<i32>(c11.x, c00.y), 0).rgb;
+    let s01 = textureLoad(history_tex, vec2<i32>(c00.x, c11.y), 0).rgb;
+    let s11 = textureLoad(history_tex, c11, 0).rgb;
+
+    return mix(mix(s00, s10, f.x), mix(s01, s11, f.x), f.y);
+}
+
+// Clips the history towards the center of the box until it's inside
+// (rather than clamping each channel, which shifts the hue)
+fn clip_to_box(history: vec3<f32>, box_min: vec3<f32>, box_max: vec3<f32>) -> vec3<f32> {
+    let center = (box_max + box_min) * 0.5;
+    let extents = max((box_max - box_min) * 0.5, vec3<f32>(1e-5));
+    let offset = history - center;
+    let units = abs(offset / extents);
+    let max_unit = max(units.x, max(units.y, units.z));
+
+    if (max_unit > 1.0) {
+        return center + offset / max_unit;
+    }
+
+    return history;
+}
+
+// Reversible tonemap, so the blend weights aren't dominated by very bright pixels
+fn tonemap(color: vec3<f32>) -> vec3<f32> {
+    return color / (1.0 + max(color.r, max(color.g, color.b)));
+}
+
+fn tonemap_invert(color: vec3<f32>) -> vec3<f32> {
+    return color / max(1.0 - max(color.r, max(color.g, color.b)), 1e-5);
+}
+
+fn rgb_to_ycocg(color: vec3<f32>) -> vec3<f32> {
+    return vec3<f32>(
+        0.25 * color.r + 0.5 * color.g + 0.25 * color.b,
+        0.5 * color.r - 0.5 * color.b,
+        -0.25 * color.r + 0.5 * color.g - 0.25 * color.b
+    );
+}
+
+fn ycocg_to_rgb(color: vec3<f32>) -> vec3<f32> {
+    let y = color.x;
+    let co = color.y;
+    let cg = color.z;
+    return vec3<f32>(y + co - cg, y + cg, y - co - cg);
+}
+{% endif %}
@@ -0,0 +1,73 @@
+//! Shader templates for the TAA pass.
+
+use askama::Template;
+
+use crate::{
+    render_passes::taa::shader::cache_key::ShaderCacheKeyTaa,
+    shaders::{AwsmShaderError, Result},
+};
+
+/// TAA pass shader template components.
+#[derive(Debug)]
+pub struct ShaderTemplateTaa {
+    pub bind_groups: ShaderTemplateTaaBindGroups,
+    pub compute: ShaderTemplateTaaCompute,
+}
+
+/// Bind group template for the TAA pass.
+#[derive(Template, Debug)]
+#[template(path = "taa_wgsl/bind_groups.wgsl", whitespace = "minimize")]
+pub struct ShaderTemplateTaaBindGroups {
+    pub multisampled_geometry: bool,
+}
+
+impl ShaderTemplateTaaBindGroups {
+    /// Creates a bind group template from the cache key.
+    pub fn new(cache_key: &ShaderCacheKeyTaa) -> Self {
+        Self {
+            multisampled_geometry: cache_key.multisampled_geometry,
+        }
+    }
+}
+
+/// Compute shader template for the TAA pass.
+#[derive(Template, Debug)]
+#[template(path = "taa_wgsl/compute.wgsl", whitespace = "minimize")]
+pub struct ShaderTemplateTaaCompute {
+    pub history_valid: bool,
+}
+
+impl ShaderTemplateTaaCompute {
+    /// Creates a compute shader template from the cache key.
+    pub fn new(cache_key: &ShaderCacheKeyTaa) -> Self {
+        Self {
+            history_valid: cache_key.history_valid,
+        }
+    }
+}
+
+impl TryFrom<&ShaderCacheKeyTaa> for ShaderTemplateTaa {
+    type Error = AwsmShaderError;
+
+    fn try_from(value: &ShaderCacheKeyTaa) -> Result<Self> {
+        Ok(Self {
+            bind_groups: ShaderTemplateTaaBindGroups::new(value),
+            compute: ShaderTemplateTaaCompute::new(value),
+        })
+    }
+}
+
+impl ShaderTemplateTaa {
+    /// Renders the TAA shader template into WGSL.
+    pub fn into_source(self) -> Result<String> {
+        let bind_groups_source = self.bind_groups.render()?;
+        let compute_source = self.compute.render()?;
+        Ok(format!("{}\n{}", bind_groups_source, compute_source))
+    }
+
+    #[cfg(debug_assertions)]
+    /// Returns an optional debug label for shader compilation.
+    pub fn debug_label(&self) -> Option<&str> {
+        Some("TAA")
+    }
+}
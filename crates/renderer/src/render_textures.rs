@@ -21,6 +21,8 @@ pub struct RenderTextures {
     pub transparent_to_composite_blit_pipeline_no_anti_alias: BlitPipeline,
     frame_count: u32,
     inner: Option<RenderTexturesInner>,
+    // whether the TAA history holds a previous frame that can be reprojected
+    taa_history_valid: bool,
 }
 
 /// Formats used for render textures.
@@ -31,6 +33,11 @@ pub struct RenderTextureFormats {
     pub barycentric: TextureFormat,
     pub normal_tangent: TextureFormat, // Packed: octahedral normal + tangent angle + handedness
     pub barycentric_derivatives: TextureFormat,
+    // Only written when TAA is enabled, screen-space motion in UV units
+    pub motion_vector: TextureFormat,
+
+    // Output from SSAO pass, the amount of occlusion (0.0 = none)
+    pub ambient_occlusion: TextureFormat,
 
     // Output from coloring passes (opaque + transparent)
     pub color: TextureFormat,
@@ -39,7 +46,11 @@ pub struct RenderTextureFormats {
 
     // For depth testing and transparency
     pub depth: TextureFormat,
-    // note - output from the composite pass will be whatever the gpu texture format is
+
+    // Output from the transparent picking pass, only used when transparent picking is enabled
+    pub pick_ids: TextureFormat, // mesh key + instance index + layer
+    pub pick_surface: TextureFormat, // world-space normal + depth
+                                 // note - output from the composite pass will be whatever the gpu texture format is
 }
 
 impl RenderTextureFormats {
@@ -50,8 +61,12 @@ impl RenderTextureFormats {
             barycentric: TextureFormat::Rg16float,
             normal_tangent: TextureFormat::Rgba16float,
             barycentric_derivatives: TextureFormat::Rgba16float,
-            color: TextureFormat::Rgba16float, // HDR format for bloom/tonemapping
-            depth: TextureFormat::Depth32float, // More precision for thin/close surfaces
+            motion_vector: TextureFormat::Rg16float,
+            ambient_occlusion: TextureFormat::R32float, // single-channel float that can be a storage texture
+            color: TextureFormat::Rgba16float,          // HDR format for bloom/tonemapping
+            depth: TextureFormat::Depth32float,         // More precision for thin/close surfaces
+            pick_ids: TextureFormat::Rgba32uint,
+            pick_surface: TextureFormat::Rgba32float,
         }
     }
 }
@@ -78,6 +93,7 @@ impl RenderTextures {
             formats,
             frame_count: 0,
             inner: None,
+            taa_history_valid: false,
             opaque_to_transparent_blit_pipeline_msaa_4,
             opaque_to_transparent_blit_pipeline_no_anti_alias,
             transparent_to_composite_blit_pipeline_no_anti_alias,
@@ -99,6 +115,11 @@ impl RenderTextures {
         self.frame_count() % 2 == 0
     }
 
+    /// Drops the TAA history, so the next frame is resolved without reprojection.
+    pub fn reset_taa_history(&mut self) {
+        self.taa_history_valid = false;
+    }
+
     /// Returns render texture views, recreating if size or AA changed.
     pub fn views(
         &mut self,
@@ -109,6 +130,18 @@ impl RenderTextures {
             .current_context_texture_size()
             .map_err(AwsmRenderTextureError::CurrentScreenSize)?;
 
+        self.views_sized(gpu, anti_aliasing, current_size)
+    }
+
+    /// Like [`Self::views`], for a size other than the canvas (viewports and offscreen views).
+    pub fn views_sized(
+        &mut self,
+        gpu: &AwsmRendererWebGpu,
+        anti_aliasing: AntiAliasing,
+        current_size: (u32, u32),
+    ) -> Result<RenderTextureViews> {
+        let taa = anti_aliasing.taa;
+
         let size_changed = match self.inner.as_ref() {
             Some(inner) => (inner.width, inner.height) != current_size,
             None => true,
@@ -135,17 +168,44 @@ impl RenderTextures {
                 anti_aliasing,
             )?;
             self.inner = Some(inner);
+            self.taa_history_valid = false;
         }
 
+        let taa_history_valid = self.taa_history_valid;
+        // the frame rendered with these views fills the history
+        self.taa_history_valid = taa;
+
         Ok(RenderTextureViews::new(
             self.inner.as_ref().unwrap(),
             self.ping_pong(),
             current_size.0,
             current_size.1,
             size_changed,
+            taa_history_valid,
         ))
     }
 
+    /// Returns views of the current textures without recreating them or touching the TAA
+    /// history, `None` before they were first created.
+    pub fn current_views(&self) -> Option<RenderTextureViews> {
+        self.inner.as_ref().map(|inner| {
+            RenderTextureViews::new(
+                inner,
+                self.ping_pong(),
+                inner.width,
+                inner.height,
+                false,
+                self.taa_history_valid,
+            )
+        })
+    }
+
+    /// Exchanges the current textures with another view's, see [`RenderTextureTarget`].
+    pub(crate) fn swap_target(&mut self, target: &mut RenderTextureTarget) {
+        std::mem::swap(&mut self.inner, &mut target.inner);
+        std::mem::swap(&mut self.taa_history_valid, &mut target.taa_history_valid);
+    }
+
     /// Clears the opaque render texture when initialized.
     pub fn clear_opaque(&self, gpu: &AwsmRendererWebGpu) -> Result<()> {
         if let Some(inner) = self.inner.as_ref() {
@@ -157,6 +217,50 @@ impl RenderTextures {
             Ok(())
         }
     }
+
+    /// Resets the ambient occlusion texture to "no occlusion".
+    ///
+    /// Only needed when SSAO is turned off, a fresh texture starts out cleared.
+    pub fn clear_ambient_occlusion(&self, gpu: &AwsmRendererWebGpu) -> Result<()> {
+        if let Some(inner) = self.inner.as_ref() {
+            // rare enough that there's no point keeping a clearer (and its buffer) around
+            TextureClearer::new(
+                gpu,
+                self.formats.ambient_occlusion,
+                inner.width,
+                inner.height,
+            )
+            .map_err(AwsmRenderTextureError::CreateTextureClearer)?
+            .clear(gpu, &inner.ambient_occlusion)
+            .map_err(AwsmRenderTextureError::TextureClearerClear)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The render textures of one extra view, swapped into [`RenderTextures`] while it renders.
+///
+/// Each view keeps its own textures (and TAA history) at its own size, so switching views
+/// only recreates bind groups, not textures.
+#[derive(Default)]
+pub struct RenderTextureTarget {
+    inner: Option<RenderTexturesInner>,
+    taa_history_valid: bool,
+}
+
+impl RenderTextureTarget {
+    /// Destroys the textures, if they were ever created.
+    pub fn destroy(self) {
+        if let Some(inner) = self.inner {
+            inner.destroy();
+        }
+    }
+
+    /// Drops the TAA history, so the next frame is resolved without reprojection.
+    pub fn reset_taa_history(&mut self) {
+        self.taa_history_valid = false;
+    }
 }
 
 /// Collection of texture views used by render passes.
@@ -167,6 +271,10 @@ pub struct RenderTextureViews {
     pub normal_tangent: web_sys::GpuTextureView,
     pub barycentric_derivatives: web_sys::GpuTextureView,
 
+    // Output from SSAO pass (before and after the blur), input to opaque pass
+    pub ambient_occlusion_raw: web_sys::GpuTextureView,
+    pub ambient_occlusion: web_sys::GpuTextureView,
+
     // Output from opaque pass
     pub opaque: web_sys::GpuTextureView,
     pub opaque_to_transparent_blit_bind_group_msaa_4: web_sys::GpuBindGroup,
@@ -183,8 +291,14 @@ pub struct RenderTextureViews {
     pub effects: web_sys::GpuTextureView,
     pub bloom: web_sys::GpuTextureView,
 
+    // Only when TAA is enabled
+    pub taa: Option<RenderTextureViewsTaa>,
+
     pub depth: web_sys::GpuTextureView,
     pub hud_depth: web_sys::GpuTextureView,
+    // Textures behind the depth views, for copying (transparent picking)
+    pub depth_texture: web_sys::GpuTexture,
+    pub hud_depth_texture: web_sys::GpuTexture,
     pub size_changed: bool,
     pub width: u32,
     pub height: u32,
@@ -200,6 +314,7 @@ impl RenderTextureViews {
         width: u32,
         height: u32,
         size_changed: bool,
+        taa_history_valid: bool,
     ) -> Self {
         let curr_index = if ping_pong { 0 } else { 1 };
         let prev_index = if ping_pong { 1 } else { 0 };
@@ -208,6 +323,8 @@ impl RenderTextureViews {
             barycentric: inner.barycentric_view.clone(),
             normal_tangent: inner.normal_tangent_view.clone(),
             barycentric_derivatives: inner.barycentric_derivatives_view.clone(),
+            ambient_occlusion_raw: inner.ambient_occlusion_raw_view.clone(),
+            ambient_occlusion: inner.ambient_occlusion_view.clone(),
             opaque: inner.opaque_view.clone(),
             opaque_to_transparent_blit_bind_group_msaa_4: inner
                 .opaque_to_transparent_blit_bind_group_msaa_4
@@ -221,9 +338,19 @@ impl RenderTextureViews {
             transparent: inner.transparent_view.clone(),
             depth: inner.depth_view.clone(),
             hud_depth: inner.hud_depth_view.clone(),
+            depth_texture: inner.depth.clone(),
+            hud_depth_texture: inner.hud_depth.clone(),
             effects: inner.effects_view.clone(),
             bloom: inner.bloom_view.clone(),
             composite: inner.composite_view.clone(),
+            taa: inner.taa.as_ref().map(|taa| RenderTextureViewsTaa {
+                motion_vector: taa.motion_vector_view.clone(),
+                resolve: taa.resolve_view.clone(),
+                history: taa.history_view.clone(),
+                resolve_texture: taa.resolve.clone(),
+                history_texture: taa.history.clone(),
+                history_valid: taa_history_valid,
+            }),
             size_changed,
             curr_index,
             prev_index,
@@ -233,6 +360,21 @@ impl RenderTextureViews {
     }
 }
 
+/// Texture views used by the TAA resolve.
+pub struct RenderTextureViewsTaa {
+    // Output from geometry pass, maybe multisampled
+    pub motion_vector: web_sys::GpuTextureView,
+    // Output from TAA pass, input to effects pass
+    pub resolve: web_sys::GpuTextureView,
+    // Last frame's resolve
+    pub history: web_sys::GpuTextureView,
+    // For copying the resolve into the history at the end of the TAA pass
+    pub resolve_texture: web_sys::GpuTexture,
+    pub history_texture: web_sys::GpuTexture,
+    // false on the first frame and after a reset, the resolve then ignores the history
+    pub history_valid: bool,
+}
+
 /// Internal texture storage and GPU objects.
 #[allow(dead_code)]
 pub struct RenderTexturesInner {
@@ -242,14 +384,18 @@ pub struct RenderTexturesInner {
     pub barycentric: web_sys::GpuTexture,
     pub barycentric_view: web_sys::GpuTextureView,
 
-    // pub taa_clip_positions: [web_sys::GpuTexture; 2],
-    // pub taa_clip_position_views: [web_sys::GpuTextureView; 2],
     pub normal_tangent: web_sys::GpuTexture,
     pub normal_tangent_view: web_sys::GpuTextureView,
 
     pub barycentric_derivatives: web_sys::GpuTexture,
     pub barycentric_derivatives_view: web_sys::GpuTextureView,
 
+    pub ambient_occlusion_raw: web_sys::GpuTexture,
+    pub ambient_occlusion_raw_view: web_sys::GpuTextureView,
+
+    pub ambient_occlusion: web_sys::GpuTexture,
+    pub ambient_occlusion_view: web_sys::GpuTextureView,
+
     pub opaque: web_sys::GpuTexture,
     pub opaque_clearer: TextureClearer,
     pub opaque_view: web_sys::GpuTextureView,
@@ -275,6 +421,8 @@ pub struct RenderTexturesInner {
     pub bloom: web_sys::GpuTexture,
     pub bloom_view: web_sys::GpuTextureView,
 
+    pub taa: Option<RenderTexturesTaa>,
+
     pub width: u32,
     pub height: u32,
 
@@ -346,6 +494,50 @@ impl RenderTexturesInner {
             )
             .map_err(AwsmRenderTextureError::CreateTexture)?;
 
+        let taa = if anti_aliasing.taa {
+            Some(RenderTexturesTaa::new(
+                gpu,
+                &render_texture_formats,
+                maybe_multisample_texture(render_texture_formats.motion_vector, "Motion Vector"),
+                width,
+                height,
+            )?)
+        } else {
+            None
+        };
+
+        // NEVER multisampled, used as storage textures
+        // always created since the opaque pass reads the occlusion whether SSAO is on or not
+        // (it's then never written, and stays cleared)
+        let ambient_occlusion_raw = gpu
+            .create_texture(
+                &TextureDescriptor::new(
+                    render_texture_formats.ambient_occlusion,
+                    Extent3d::new(width, Some(height), Some(1)),
+                    TextureUsage::new()
+                        .with_storage_binding()
+                        .with_texture_binding(),
+                )
+                .with_label("Ambient Occlusion Raw")
+                .into(),
+            )
+            .map_err(AwsmRenderTextureError::CreateTexture)?;
+
+        let ambient_occlusion = gpu
+            .create_texture(
+                &TextureDescriptor::new(
+                    render_texture_formats.ambient_occlusion,
+                    Extent3d::new(width, Some(height), Some(1)),
+                    TextureUsage::new()
+                        .with_storage_binding()
+                        .with_texture_binding()
+                        .with_copy_dst(),
+                )
+                .with_label("Ambient Occlusion")
+                .into(),
+            )
+            .map_err(AwsmRenderTextureError::CreateTexture)?;
+
         // NEVER multisampled, used as a storage texture
         let opaque = gpu
             .create_texture(
@@ -384,18 +576,22 @@ impl RenderTexturesInner {
                 .map_err(AwsmRenderTextureError::CreateTexture)?
         };
 
+        // Transparent picking copies both depth buffers out before the transparent passes
+        // write into them, so they're copy sources too
+        let depth_texture = |label: &'static str| -> TextureDescriptor<'static> {
+            let mut descriptor = maybe_multisample_texture(render_texture_formats.depth, label);
+            descriptor.usage = descriptor.usage.with_copy_src();
+            descriptor
+        };
+
         let depth = gpu
-            .create_texture(
-                &maybe_multisample_texture(render_texture_formats.depth, "Depth").into(),
-            )
+            .create_texture(&depth_texture("Depth").into())
             // Keeping the depth buffer bindable allows later passes (e.g. compute shading) to
             // sample it directly for world-position reconstruction.
             .map_err(AwsmRenderTextureError::CreateTexture)?;
 
         let hud_depth = gpu
-            .create_texture(
-                &maybe_multisample_texture(render_texture_formats.depth, "Hud Depth").into(),
-            )
+            .create_texture(&depth_texture("Hud Depth").into())
             .map_err(AwsmRenderTextureError::CreateTexture)?;
 
         // NEVER multisampled, that's the point
@@ -454,15 +650,6 @@ impl RenderTexturesInner {
             AwsmRenderTextureError::CreateTextureView(format!("barycentric: {e:?}"))
         })?;
 
-        // let taa_clip_position_views = [
-        //     taa_clip_positions[0].create_view().map_err(|e| {
-        //         AwsmRenderTextureError::CreateTextureView(format!("taa_clip_positions[0]: {e:?}"))
-        //     })?,
-        //     taa_clip_positions[1].create_view().map_err(|e| {
-        //         AwsmRenderTextureError::CreateTextureView(format!("taa_clip_positions[1]: {e:?}"))
-        //     })?,
-        // ];
-
         let normal_tangent_view = normal_tangent.create_view().map_err(|e| {
             AwsmRenderTextureError::CreateTextureView(format!("normal_tangent: {e:?}"))
         })?;
@@ -471,6 +658,14 @@ impl RenderTexturesInner {
             AwsmRenderTextureError::CreateTextureView(format!("barycentric: {e:?}"))
         })?;
 
+        let ambient_occlusion_raw_view = ambient_occlusion_raw.create_view().map_err(|e| {
+            AwsmRenderTextureError::CreateTextureView(format!("ambient_occlusion_raw: {e:?}"))
+        })?;
+
+        let ambient_occlusion_view = ambient_occlusion.create_view().map_err(|e| {
+            AwsmRenderTextureError::CreateTextureView(format!("ambient_occlusion: {e:?}"))
+        })?;
+
         let opaque_view = opaque
             .create_view()
             .map_err(|e| AwsmRenderTextureError::CreateTextureView(format!("opaque: {e:?}")))?;
@@ -534,6 +729,12 @@ impl RenderTexturesInner {
             barycentric_derivatives,
             barycentric_derivatives_view,
 
+            ambient_occlusion_raw,
+            ambient_occlusion_raw_view,
+
+            ambient_occlusion,
+            ambient_occlusion_view,
+
             opaque,
             opaque_view,
             opaque_clearer: TextureClearer::new(gpu, render_texture_formats.color, width, height)
@@ -560,6 +761,8 @@ impl RenderTexturesInner {
             bloom,
             bloom_view,
 
+            taa,
+
             width,
             height,
 
@@ -571,17 +774,100 @@ impl RenderTexturesInner {
     pub fn destroy(self) {
         self.visibility_data.destroy();
         self.barycentric.destroy();
-        // for texture in self.taa_clip_positions {
-        //     texture.destroy();
-        // }
         self.normal_tangent.destroy();
         self.barycentric_derivatives.destroy();
+        self.ambient_occlusion_raw.destroy();
+        self.ambient_occlusion.destroy();
         self.opaque.destroy();
         self.transparent.destroy();
         self.depth.destroy();
         self.composite.destroy();
         self.effects.destroy();
         self.bloom.destroy();
+        if let Some(taa) = self.taa {
+            taa.destroy();
+        }
+    }
+}
+
+/// Textures only needed for TAA.
+pub struct RenderTexturesTaa {
+    pub motion_vector: web_sys::GpuTexture,
+    pub motion_vector_view: web_sys::GpuTextureView,
+
+    // NEVER multisampled, written by the TAA compute pass
+    pub resolve: web_sys::GpuTexture,
+    pub resolve_view: web_sys::GpuTextureView,
+
+    pub history: web_sys::GpuTexture,
+    pub history_view: web_sys::GpuTextureView,
+}
+
+impl RenderTexturesTaa {
+    fn new(
+        gpu: &AwsmRendererWebGpu,
+        render_texture_formats: &RenderTextureFormats,
+        motion_vector_descriptor: TextureDescriptor<'static>,
+        width: u32,
+        height: u32,
+    ) -> Result<Self> {
+        let motion_vector = gpu
+            .create_texture(&motion_vector_descriptor.into())
+            .map_err(AwsmRenderTextureError::CreateTexture)?;
+
+        let resolve = gpu
+            .create_texture(
+                &TextureDescriptor::new(
+                    render_texture_formats.color,
+                    Extent3d::new(width, Some(height), Some(1)),
+                    TextureUsage::new()
+                        .with_storage_binding()
+                        .with_texture_binding()
+                        .with_copy_src(),
+                )
+                .with_label("TAA Resolve")
+                .into(),
+            )
+            .map_err(AwsmRenderTextureError::CreateTexture)?;
+
+        let history = gpu
+            .create_texture(
+                &TextureDescriptor::new(
+                    render_texture_formats.color,
+                    Extent3d::new(width, Some(height), Some(1)),
+                    TextureUsage::new().with_texture_binding().with_copy_dst(),
+                )
+                .with_label("TAA History")
+                .into(),
+            )
+            .map_err(AwsmRenderTextureError::CreateTexture)?;
+
+        let motion_vector_view = motion_vector.create_view().map_err(|e| {
+            AwsmRenderTextureError::CreateTextureView(format!("motion_vector: {e:?}"))
+        })?;
+
+        let resolve_view = resolve.create_view().map_err(|e| {
+            AwsmRenderTextureError::CreateTextureView(format!("taa_resolve: {e:?}"))
+        })?;
+
+        let history_view = history.create_view().map_err(|e| {
+            AwsmRenderTextureError::CreateTextureView(format!("taa_history: {e:?}"))
+        })?;
+
+        Ok(Self {
+            motion_vector,
+            motion_vector_view,
+            resolve,
+            resolve_view,
+            history,
+            history_view,
+        })
+    }
+
+    fn destroy(self) {
+        self.motion_vector.destroy();
+        self.resolve.destroy();
+        self.history.destroy();
     }
 }
 
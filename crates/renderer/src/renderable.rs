@@ -228,7 +228,8 @@ impl Renderable<'_> {
     ) -> Result<()> {
         match self {
             Self::Mesh { mesh, key, .. } => mesh.push_material_transparent_pass_commands(
-                ctx,
+                ctx.meshes,
+                ctx.instances,
                 *key,
                 render_pass,
                 mesh_material_bind_group,
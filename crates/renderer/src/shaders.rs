@@ -11,7 +11,10 @@ use thiserror::Error;
 use awsm_renderer_core::shaders::ShaderModuleExt;
 
 use crate::{
-    picker::{ShaderCacheKeyPicker, ShaderTemplatePicker},
+    picker::{
+        ShaderCacheKeyPicker, ShaderCacheKeyPickerRegion, ShaderTemplatePicker,
+        ShaderTemplatePickerRegion,
+    },
     render_passes::{
         shader_cache_key::ShaderCacheKeyRenderPass, shader_template::ShaderTemplateRenderPass,
     },
@@ -85,12 +88,14 @@ impl Default for Shaders {
 pub enum ShaderCacheKey {
     RenderPass(ShaderCacheKeyRenderPass),
     Picker(ShaderCacheKeyPicker),
+    PickerRegion(ShaderCacheKeyPickerRegion),
 }
 
 /// Shader template variants for renderer-managed shaders.
 pub enum ShaderTemplate {
     RenderPass(ShaderTemplateRenderPass),
     Picker(ShaderTemplatePicker),
+    PickerRegion(ShaderTemplatePickerRegion),
 }
 
 impl TryFrom<&ShaderCacheKey> for ShaderTemplate {
@@ -102,6 +107,9 @@ impl TryFrom<&ShaderCacheKey> for ShaderTemplate {
                 Ok(ShaderTemplate::RenderPass(cache_key.try_into()?))
             }
             ShaderCacheKey::Picker(cache_key) => Ok(ShaderTemplate::Picker(cache_key.into())),
+            ShaderCacheKey::PickerRegion(cache_key) => {
+                Ok(ShaderTemplate::PickerRegion(cache_key.into()))
+            }
         }
     }
 }
@@ -126,6 +134,7 @@ impl ShaderTemplate {
         match self {
             ShaderTemplate::RenderPass(tmpl) => tmpl.debug_label(),
             ShaderTemplate::Picker(tmpl) => tmpl.debug_label(),
+            ShaderTemplate::PickerRegion(tmpl) => tmpl.debug_label(),
         }
     }
 
@@ -134,6 +143,7 @@ impl ShaderTemplate {
         let source = match self {
             ShaderTemplate::RenderPass(tmpl) => tmpl.into_source()?,
             ShaderTemplate::Picker(tmpl) => tmpl.into_source()?,
+            ShaderTemplate::PickerRegion(tmpl) => tmpl.into_source()?,
         };
         //tracing::info!("{:#?}", tmpl);
         // print_shader_source(&source, true);
@@ -1,11 +1,14 @@
 //! Texture management and GPU uploads.
 
-use std::{collections::HashMap, sync::LazyLock};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::LazyLock,
+};
 
 use awsm_renderer_core::{
     buffers::{BufferDescriptor, BufferUsage},
     compare::CompareFunction,
-    cubemap::{self, CubemapBytesLayout, CubemapFace},
+    cubemap::{self, CubemapBytesLayout, CubemapFace, CubemapImage},
     error::AwsmCoreError,
     image::ImageData,
     renderer::AwsmRendererWebGpu,
@@ -115,6 +118,43 @@ impl AwsmRenderer {
         Ok(())
     }
 
+    /// Removes a pooled texture that no material uses, compacting its texture array.
+    ///
+    /// This re-uploads the pool and recreates the bind groups that depend on it, so prefer
+    /// `collect_garbage` when freeing many textures at once.
+    pub async fn remove_texture(&mut self, key: TextureKey) -> crate::error::Result<bool> {
+        let count = self.materials.texture_ref_count(key);
+        if count > 0 {
+            return Err(AwsmTextureError::TextureInUse { key, count }.into());
+        }
+
+        if !self.textures.remove_image(key) {
+            return Ok(false);
+        }
+
+        // pool entries and sampler indices may have moved
+        self.materials.rewrite_all(&self.textures);
+        self.finalize_gpu_textures().await?;
+
+        Ok(true)
+    }
+
+    /// Removes a cubemap texture that isn't the current skybox or IBL texture.
+    pub fn remove_cubemap_texture(
+        &mut self,
+        texture_key: CubemapTextureKey,
+    ) -> crate::error::Result<bool> {
+        let ibl = &self.lights.ibl;
+        if self.environment.skybox.texture_key == texture_key
+            || ibl.prefiltered_env.texture_key == texture_key
+            || ibl.irradiance.texture_key == texture_key
+        {
+            return Err(AwsmTextureError::CubemapTextureInUse(texture_key).into());
+        }
+
+        Ok(self.textures.remove_cubemap(texture_key))
+    }
+
     /// Updates one face of a cubemap texture in-place from raw bytes.
     pub fn update_cubemap_texture_face(
         &self,
@@ -170,7 +210,10 @@ pub struct Textures {
     pub pool_sampler_set: IndexSet<SamplerKey>,
     pub texture_transform_identity_offset: usize,
     pool_textures: SlotMap<TextureKey, TexturePoolEntryInfo<TextureKey>>,
+    pool_texture_samplers: SecondaryMap<TextureKey, SamplerKey>,
     cubemaps: SlotMap<CubemapTextureKey, web_sys::GpuTexture>,
+    // what a cubemap was created from, to recreate it after a device loss
+    cubemap_sources: SecondaryMap<CubemapTextureKey, CubemapImage>,
     samplers: SlotMap<SamplerKey, web_sys::GpuSampler>,
     sampler_cache: HashMap<SamplerCacheKey, SamplerKey>,
     // We keep a mirror of the sampler address modes so that materials can adjust UVs manually when
@@ -323,7 +366,9 @@ impl Textures {
             pool: TexturePool::new(),
             pool_sampler_set: IndexSet::new(),
             pool_textures: SlotMap::with_key(),
+            pool_texture_samplers: SecondaryMap::new(),
             cubemaps: SlotMap::with_key(),
+            cubemap_sources: SecondaryMap::new(),
             texture_transforms,
             texture_transforms_buffer,
             texture_transforms_gpu_buffer,
@@ -351,10 +396,43 @@ impl Textures {
         })?;
 
         self.pool_sampler_set.insert(sampler_key);
+        self.pool_texture_samplers.insert(key, sampler_key);
 
         Ok(key)
     }
 
+    /// Removes an image from the texture pool. Returns true if it was present.
+    ///
+    /// Other textures may move within the pool, so material data must be rewritten and the
+    /// pool re-uploaded afterwards, see `AwsmRenderer::remove_texture`.
+    pub fn remove_image(&mut self, key: TextureKey) -> bool {
+        if self.pool_textures.remove(key).is_none() {
+            return false;
+        }
+
+        self.pool.remove_image(key);
+        self.pool_texture_samplers.remove(key);
+
+        for (key, entry) in self.pool_textures.iter_mut() {
+            if let Some(moved) = self.pool.entry(key) {
+                *entry = moved;
+            }
+        }
+
+        // keep the order of the samplers that are still in use
+        let used_samplers: HashSet<SamplerKey> =
+            self.pool_texture_samplers.values().copied().collect();
+        self.pool_sampler_set
+            .retain(|sampler_key| used_samplers.contains(sampler_key));
+
+        true
+    }
+
+    /// Returns true if the key refers to a pooled texture.
+    pub fn contains_image(&self, key: TextureKey) -> bool {
+        self.pool_textures.contains_key(key)
+    }
+
     /// Inserts a texture transform and returns its key.
     pub fn insert_texture_transform(
         &mut self,
@@ -396,6 +474,33 @@ impl Textures {
         self.cubemaps.insert(texture)
     }
 
+    /// Inserts a cubemap texture along with the image it was created from, and returns its key.
+    ///
+    /// The image is kept so the texture can be recreated after a device loss, a cubemap inserted
+    /// without one comes back empty. Updates made in place afterwards (e.g.
+    /// [`AwsmRenderer::update_cubemap_texture_face`]) aren't kept.
+    pub fn insert_cubemap_with_source(
+        &mut self,
+        texture: web_sys::GpuTexture,
+        source: CubemapImage,
+    ) -> CubemapTextureKey {
+        let key = self.cubemaps.insert(texture);
+        self.cubemap_sources.insert(key, source);
+        key
+    }
+
+    /// Removes a cubemap texture and destroys it on the GPU. Returns true if it was present.
+    pub fn remove_cubemap(&mut self, key: CubemapTextureKey) -> bool {
+        self.cubemap_sources.remove(key);
+        match self.cubemaps.remove(key) {
+            Some(texture) => {
+                texture.destroy();
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Returns a cubemap texture by key.
     pub fn get_cubemap(&self, key: CubemapTextureKey) -> Result<&web_sys::GpuTexture> {
         self.cubemaps
@@ -403,6 +508,39 @@ impl Textures {
             .ok_or(AwsmTextureError::CubemapTextureNotFound(key))
     }
 
+    // Recreates everything on a new device after a device loss, keeping all keys: samplers
+    // from their cache keys, cubemaps from their sources, and the pool and texture transforms
+    // get uploaded again from their CPU copies on the next write.
+    //
+    // Returns the cubemaps that had no source, which are now empty.
+    pub(crate) async fn restore_gpu(
+        &mut self,
+        gpu: &AwsmRendererWebGpu,
+    ) -> Result<Vec<CubemapTextureKey>> {
+        self.pool.mark_gpu_lost();
+        self.texture_transforms_buffer.mark_gpu_lost();
+        self.texture_transforms_gpu_dirty = true;
+
+        for (cache_key, sampler_key) in &self.sampler_cache {
+            if let Some(sampler) = self.samplers.get_mut(*sampler_key) {
+                *sampler = gpu.create_sampler(Some(&sampler_descriptor(cache_key).into()));
+            }
+        }
+
+        let mut empty_cubemaps = Vec::new();
+        for (key, texture) in self.cubemaps.iter_mut() {
+            *texture = match self.cubemap_sources.get(key) {
+                Some(source) => source.create_texture_and_view(gpu, None).await?.0,
+                None => {
+                    empty_cubemaps.push(key);
+                    cubemap::create_texture_like(gpu, texture, None)?
+                }
+            };
+        }
+
+        Ok(empty_cubemaps)
+    }
+
     async fn write_gpu_texture_pool(
         &mut self,
         logging: &AwsmRendererLogging,
@@ -496,6 +634,23 @@ impl Textures {
         )
     }
 
+    /// Removes a sampler that no pooled texture uses. Returns true if it was present.
+    pub fn remove_sampler(&mut self, key: SamplerKey) -> Result<bool> {
+        if self.pool_sampler_set.contains(&key) {
+            return Err(AwsmTextureError::SamplerInUse(key));
+        }
+
+        if self.samplers.remove(key).is_none() {
+            return Ok(false);
+        }
+
+        self.sampler_cache
+            .retain(|_, sampler_key| *sampler_key != key);
+        self.sampler_address_modes.remove(key);
+
+        Ok(true)
+    }
+
     /// Returns a sampler by key.
     pub fn get_sampler(&self, key: SamplerKey) -> Result<&web_sys::GpuSampler> {
         self.samplers
@@ -515,17 +670,8 @@ impl Textures {
     }
 }
 
-fn create_sampler_key(
-    gpu: &AwsmRendererWebGpu,
-    cache_key: SamplerCacheKey,
-    samplers: &mut SlotMap<SamplerKey, web_sys::GpuSampler>,
-    sampler_cache: &mut HashMap<SamplerCacheKey, SamplerKey>,
-    sampler_address_modes: &mut SecondaryMap<
-        SamplerKey,
-        (Option<AddressMode>, Option<AddressMode>),
-    >,
-) -> Result<SamplerKey> {
-    let descriptor = SamplerDescriptor {
+fn sampler_descriptor(cache_key: &SamplerCacheKey) -> SamplerDescriptor<'static> {
+    SamplerDescriptor {
         label: None,
         address_mode_u: cache_key.address_mode_u,
         address_mode_v: cache_key.address_mode_v,
@@ -537,7 +683,20 @@ fn create_sampler_key(
         mag_filter: cache_key.mag_filter,
         min_filter: cache_key.min_filter,
         mipmap_filter: cache_key.mipmap_filter,
-    };
+    }
+}
+
+fn create_sampler_key(
+    gpu: &AwsmRendererWebGpu,
+    cache_key: SamplerCacheKey,
+    samplers: &mut SlotMap<SamplerKey, web_sys::GpuSampler>,
+    sampler_cache: &mut HashMap<SamplerCacheKey, SamplerKey>,
+    sampler_address_modes: &mut SecondaryMap<
+        SamplerKey,
+        (Option<AddressMode>, Option<AddressMode>),
+    >,
+) -> Result<SamplerKey> {
+    let descriptor = sampler_descriptor(&cache_key);
 
     // tracing::info!("address_mode_u: {address_mode_u:?}, address_mode_v: {address_mode_v:?}, address_mode_w: {address_mode_w:?}, compare: {compare:?}, lod_min_clamp: {lod_min_clamp:?}, lod_max_clamp: {lod_max_clamp:?}, max_anisotropy: {max_anisotropy:?}, mag_filter: {mag_filter:?}, min_filter: {min_filter:?}, mipmap_filter: {mipmap_filter:?}",
     //     address_mode_u = cache_key.address_mode_u,
@@ -605,6 +764,15 @@ pub enum AwsmTextureError {
     #[error("[texture] subemap texture not found: {0:?}")]
     CubemapTextureNotFound(CubemapTextureKey),
 
+    #[error("[texture] texture {key:?} is still used by {count} material texture slot(s)")]
+    TextureInUse { key: TextureKey, count: usize },
+
+    #[error("[texture] cubemap texture is still used by the skybox or IBL: {0:?}")]
+    CubemapTextureInUse(CubemapTextureKey),
+
+    #[error("[texture] sampler is still used by pooled textures: {0:?}")]
+    SamplerInUse(SamplerKey),
+
     #[error("[texture] no clamp sampler found in mega-texture")]
     NoClampSamplerInMegaTexture,
 }
@@ -10,6 +10,7 @@ use std::{
 
 use awsm_renderer_core::{
     buffers::{BufferDescriptor, BufferUsage},
+    command::CommandEncoder,
     error::AwsmCoreError,
     pipeline::primitive::FrontFace,
     renderer::AwsmRendererWebGpu,
@@ -19,7 +20,7 @@ use slotmap::{new_key_type, SecondaryMap, SlotMap};
 use crate::{
     bind_groups::{BindGroupCreate, BindGroups},
     buffer::dynamic_uniform::DynamicUniformBuffer,
-    buffer::helpers::write_buffer_with_dirty_ranges,
+    buffer::helpers::{copy_whole_buffer, write_buffer_with_dirty_ranges},
     meshes::skins::AwsmSkinError,
     AwsmRenderer, AwsmRendererLogging,
 };
@@ -58,10 +59,16 @@ pub struct Transforms {
     normals_buffer: DynamicUniformBuffer<TransformKey>,
     pub(crate) gpu_buffer: web_sys::GpuBuffer,
     pub(crate) normals_gpu_buffer: web_sys::GpuBuffer,
+    // last frame's transforms, for motion vectors
+    pub(crate) previous_gpu_buffer: web_sys::GpuBuffer,
 }
 
-static BUFFER_USAGE: LazyLock<BufferUsage> =
-    LazyLock::new(|| BufferUsage::new().with_storage().with_copy_dst());
+static BUFFER_USAGE: LazyLock<BufferUsage> = LazyLock::new(|| {
+    BufferUsage::new()
+        .with_storage()
+        .with_copy_dst()
+        .with_copy_src()
+});
 
 impl Transforms {
     /// Initial transform slot capacity.
@@ -81,6 +88,14 @@ impl Transforms {
             )
             .into(),
         )?;
+        let previous_gpu_buffer = gpu.create_buffer(
+            &BufferDescriptor::new(
+                Some("Previous Transforms"),
+                Transforms::INITIAL_CAPACITY * Transforms::BYTE_SIZE,
+                *BUFFER_USAGE,
+            )
+            .into(),
+        )?;
         let normals_gpu_buffer = gpu.create_buffer(
             &BufferDescriptor::new(
                 Some("Normal Transform Matrices"),
@@ -125,6 +140,7 @@ impl Transforms {
             normals_buffer,
             gpu_buffer,
             normals_gpu_buffer,
+            previous_gpu_buffer,
         })
     }
 
@@ -155,11 +171,23 @@ impl Transforms {
     }
 
     /// Removes a transform and its buffers.
+    ///
+    /// Its children move up to its parent, keeping their world pose.
     pub fn remove(&mut self, key: TransformKey) {
-        if key == self.root_node {
+        if key == self.root_node || !self.locals.contains_key(key) {
             return;
         }
 
+        let parent = self.parents.get(key).copied();
+        let local_matrix = self.locals[key].to_matrix();
+        for child in self.children.get(key).cloned().unwrap_or_default() {
+            // shear from non-uniform scale can't be kept in a Transform, same as any decomposition
+            let child_local = &mut self.locals[child];
+            *child_local = Transform::from(local_matrix * child_local.to_matrix());
+            self.dirties.insert(child);
+            self.set_parent(child, parent);
+        }
+
         // happens separately so that we can remove the node from the parent's children list
         self.unset_parent(key);
 
@@ -249,6 +277,13 @@ impl Transforms {
         self.dirties.clear();
     }
 
+    // after a device loss, the next write_gpu recreates the buffers from the CPU data
+    pub(crate) fn mark_gpu_lost(&mut self) {
+        self.buffer.mark_gpu_lost();
+        self.normals_buffer.mark_gpu_lost();
+        self.gpu_dirty = true;
+    }
+
     // This *does* write to the gpu, should be called only once per frame
     // just write the entire buffer in one fell swoop
     /// Writes dirty transform data to the GPU.
@@ -270,6 +305,10 @@ impl Transforms {
                 self.gpu_buffer = gpu.create_buffer(
                     &BufferDescriptor::new(Some("Transforms"), new_size, *BUFFER_USAGE).into(),
                 )?;
+                self.previous_gpu_buffer = gpu.create_buffer(
+                    &BufferDescriptor::new(Some("Previous Transforms"), new_size, *BUFFER_USAGE)
+                        .into(),
+                )?;
 
                 bind_groups.mark_create(BindGroupCreate::TransformsResize);
                 transform_resized = true;
@@ -293,6 +332,14 @@ impl Transforms {
             if transform_resized {
                 self.buffer.clear_dirty_ranges();
                 gpu.write_buffer(&self.gpu_buffer, None, self.buffer.raw_slice(), None, None)?;
+                // no history survives the resize, so this frame has no motion
+                gpu.write_buffer(
+                    &self.previous_gpu_buffer,
+                    None,
+                    self.buffer.raw_slice(),
+                    None,
+                    None,
+                )?;
             } else {
                 let transform_ranges = self.buffer.take_dirty_ranges();
                 write_buffer_with_dirty_ranges(
@@ -327,6 +374,12 @@ impl Transforms {
         Ok(())
     }
 
+    /// Records a copy of this frame's transforms, read back as the previous ones next frame.
+    pub(crate) fn copy_to_previous(&self, command_encoder: &CommandEncoder) -> Result<()> {
+        copy_whole_buffer(command_encoder, &self.gpu_buffer, &self.previous_gpu_buffer)?;
+        Ok(())
+    }
+
     /// Takes and clears the list of dirty mesh transforms.
     pub fn take_dirty_meshes(&mut self) -> HashMap<TransformKey, Mat4> {
         self.dirty_meshes
@@ -0,0 +1,568 @@
+//! Extra views rendered alongside the main one: canvas viewports and offscreen textures.
+//!
+//! Each view has its own render textures, camera history and post-processing pipelines,
+//! which are swapped into the renderer while it renders (see `AwsmRenderer::render_views`).
+//! Offscreen views are copied into the texture pool, so materials can sample them like any
+//! other texture, e.g. for in-world monitors.
+
+use awsm_renderer_core::{
+    command::color::Color,
+    error::AwsmCoreError,
+    image::{bitmap, ImageData},
+    sampler::{FilterMode, MipmapFilterMode},
+    texture::{
+        mipmap::{prepare_mipmaps, MipmapTextureKind},
+        texture_pool::TextureColorInfo,
+        Extent3d, TextureDescriptor, TextureFormat, TextureUsage,
+    },
+};
+use slotmap::{new_key_type, SlotMap};
+use thiserror::Error;
+
+use crate::{
+    camera::{CameraMatrices, CameraViewState},
+    post_process::PostProcessing,
+    render_passes::{
+        display::pipeline::DisplayPipelines, effects::pipeline::EffectsPipelines,
+        ssao::pipeline::SsaoPipelines,
+    },
+    render_textures::RenderTextureTarget,
+    textures::{SamplerCacheKey, TextureKey},
+    AwsmRenderer,
+};
+
+/// Format of offscreen view textures, also their format in the texture pool.
+pub const RENDER_VIEW_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba8unorm;
+
+new_key_type! {
+    /// Opaque key for extra render views.
+    pub struct RenderViewKey;
+}
+
+/// A rectangle of the canvas in pixels, from the top-left corner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Viewport {
+    /// Creates a viewport.
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the part of the viewport inside a canvas of the given size, `None` if nothing is.
+    pub fn clamp_to(&self, canvas_width: u32, canvas_height: u32) -> Option<Self> {
+        let width = self.width.min(canvas_width.saturating_sub(self.x));
+        let height = self.height.min(canvas_height.saturating_sub(self.y));
+
+        (width > 0 && height > 0).then_some(Self {
+            x: self.x,
+            y: self.y,
+            width,
+            height,
+        })
+    }
+}
+
+/// Where a view is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderViewTarget {
+    /// A rectangle of the canvas, e.g. one half of a split-screen or a minimap.
+    Canvas(Viewport),
+    /// An offscreen texture, materials can sample it via [`RenderViews::texture_key`].
+    Texture { width: u32, height: u32 },
+}
+
+// how one view is drawn in a frame
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct RenderViewFrame {
+    pub size: (u32, u32),
+    // None for offscreen views and the main view, which covers the whole canvas
+    pub viewport: Option<Viewport>,
+    pub offscreen: bool,
+    // the first canvas view clears it, later ones draw over
+    pub clear_canvas: bool,
+    // shadow maps don't depend on the view, so only the first one drawn renders them
+    pub render_shadows: bool,
+}
+
+// how a list of views is drawn in a frame
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RenderViewPlan {
+    // one per view, `None` for views that are skipped
+    pub frames: Vec<Option<RenderViewFrame>>,
+    // one tile buffer big enough for every view
+    pub tiles_size: (u32, u32),
+    // the camera buffer is shared, if an extra view was drawn last it holds that view's camera
+    pub restore_main_camera: bool,
+}
+
+// `None` targets are the main view, canvas views entirely off the canvas are skipped
+pub(crate) fn plan_render_view_frames(
+    targets: &[Option<RenderViewTarget>],
+    canvas_size: (u32, u32),
+) -> RenderViewPlan {
+    let mut canvas_drawn = false;
+    let mut shadows_rendered = false;
+    let mut tiles_size = (1, 1);
+    let mut restore_main_camera = false;
+
+    let frames = targets
+        .iter()
+        .map(|target| {
+            let (size, viewport, offscreen) = match target {
+                None => (canvas_size, None, false),
+                Some(RenderViewTarget::Canvas(viewport)) => {
+                    let viewport = viewport.clamp_to(canvas_size.0, canvas_size.1)?;
+                    ((viewport.width, viewport.height), Some(viewport), false)
+                }
+                // offscreen views keep their own size, whatever the canvas is
+                Some(RenderViewTarget::Texture { width, height }) => {
+                    ((*width, *height), None, true)
+                }
+            };
+
+            let frame = RenderViewFrame {
+                size,
+                viewport,
+                offscreen,
+                clear_canvas: !offscreen && !canvas_drawn,
+                render_shadows: !shadows_rendered,
+            };
+
+            canvas_drawn |= !offscreen;
+            shadows_rendered = true;
+            restore_main_camera = target.is_some();
+            tiles_size = (tiles_size.0.max(size.0), tiles_size.1.max(size.1));
+
+            Some(frame)
+        })
+        .collect();
+
+    RenderViewPlan {
+        frames,
+        tiles_size,
+        restore_main_camera,
+    }
+}
+
+/// Settings for an extra view.
+#[derive(Clone, Debug)]
+pub struct RenderViewDesc {
+    pub target: RenderViewTarget,
+    pub post_processing: PostProcessing,
+}
+
+impl RenderViewDesc {
+    /// Creates a view description with default post-processing.
+    pub fn new(target: RenderViewTarget) -> Self {
+        Self {
+            target,
+            post_processing: PostProcessing::default(),
+        }
+    }
+
+    /// Sets the post-processing for this view.
+    pub fn with_post_processing(mut self, post_processing: PostProcessing) -> Self {
+        self.post_processing = post_processing;
+        self
+    }
+}
+
+/// One entry in the list of views given to `AwsmRenderer::render_views`.
+#[derive(Clone, Debug)]
+pub struct RenderView {
+    /// `None` for the main view.
+    pub key: Option<RenderViewKey>,
+    /// The camera for this frame, the main view may leave it to `update_camera`.
+    pub camera: Option<CameraMatrices>,
+}
+
+impl RenderView {
+    /// The main view: the whole canvas, with the camera from `update_camera` and the
+    /// renderer's own post-processing. Picking reads what this view rendered.
+    pub fn main() -> Self {
+        Self {
+            key: None,
+            camera: None,
+        }
+    }
+
+    /// An extra view added with `AwsmRenderer::add_render_view`.
+    pub fn new(key: RenderViewKey, camera: CameraMatrices) -> Self {
+        Self {
+            key: Some(key),
+            camera: Some(camera),
+        }
+    }
+}
+
+/// Where the display pass writes the view being rendered.
+#[derive(Clone, Debug)]
+pub enum RenderViewOutput {
+    /// The canvas, `clear` is false once an earlier view drew into it this frame.
+    Canvas {
+        viewport: Option<Viewport>,
+        clear: bool,
+    },
+    /// An offscreen view's texture.
+    Texture(web_sys::GpuTextureView),
+}
+
+/// The extra views, and the state each one swaps into the renderer while it renders.
+#[derive(Default)]
+pub struct RenderViews {
+    views: SlotMap<RenderViewKey, RenderViewState>,
+    // whose render textures the bind groups were last created with, None for the main view
+    pub(crate) bound: Option<RenderViewKey>,
+}
+
+pub(crate) struct RenderViewState {
+    pub target: RenderViewTarget,
+    pub post_processing: PostProcessing,
+    pub render_textures: RenderTextureTarget,
+    pub camera: CameraViewState,
+    pub ssao_pipelines: SsaoPipelines,
+    pub effects_pipelines: EffectsPipelines,
+    pub display_pipelines: DisplayPipelines,
+    pub texture: Option<RenderViewTexture>,
+}
+
+// the display pass can't write the pool (no render attachment usage), so it goes through this
+pub(crate) struct RenderViewTexture {
+    pub texture: web_sys::GpuTexture,
+    pub view: web_sys::GpuTextureView,
+    pub texture_key: TextureKey,
+}
+
+impl RenderViews {
+    /// Returns the keys of all extra views.
+    pub fn keys(&self) -> impl Iterator<Item = RenderViewKey> + '_ {
+        self.views.keys()
+    }
+
+    /// Returns true if the key refers to a view.
+    pub fn contains(&self, key: RenderViewKey) -> bool {
+        self.views.contains_key(key)
+    }
+
+    /// Returns where a view is drawn.
+    pub fn target(&self, key: RenderViewKey) -> Result<RenderViewTarget> {
+        Ok(self.get(key)?.target)
+    }
+
+    /// Returns the post-processing of a view.
+    pub fn post_processing(&self, key: RenderViewKey) -> Result<&PostProcessing> {
+        Ok(&self.get(key)?.post_processing)
+    }
+
+    /// Returns the pooled texture an offscreen view renders into, for use in materials.
+    ///
+    /// `None` for canvas views.
+    pub fn texture_key(&self, key: RenderViewKey) -> Result<Option<TextureKey>> {
+        Ok(self
+            .get(key)?
+            .texture
+            .as_ref()
+            .map(|texture| texture.texture_key))
+    }
+
+    /// Moves a canvas view, e.g. after the canvas was resized.
+    pub fn set_viewport(&mut self, key: RenderViewKey, viewport: Viewport) -> Result<()> {
+        let state = self.get_mut(key)?;
+        match state.target {
+            RenderViewTarget::Canvas(_) => {
+                state.target = RenderViewTarget::Canvas(viewport);
+                Ok(())
+            }
+            RenderViewTarget::Texture { .. } => Err(AwsmRenderViewError::NotCanvas(key)),
+        }
+    }
+
+    pub(crate) fn get(&self, key: RenderViewKey) -> Result<&RenderViewState> {
+        self.views
+            .get(key)
+            .ok_or(AwsmRenderViewError::NotFound(key))
+    }
+
+    pub(crate) fn get_mut(&mut self, key: RenderViewKey) -> Result<&mut RenderViewState> {
+        self.views
+            .get_mut(key)
+            .ok_or(AwsmRenderViewError::NotFound(key))
+    }
+
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut RenderViewState> {
+        self.views.values_mut()
+    }
+}
+
+impl AwsmRenderer {
+    /// Adds a view that can be rendered alongside the main one with `render_views`.
+    ///
+    /// Offscreen views also get a pooled texture (see [`RenderViews::texture_key`]),
+    /// which is black until the view is first rendered.
+    pub async fn add_render_view(
+        &mut self,
+        desc: RenderViewDesc,
+    ) -> crate::error::Result<RenderViewKey> {
+        let RenderViewDesc {
+            target,
+            post_processing,
+        } = desc;
+
+        let texture = match target {
+            RenderViewTarget::Canvas(_) => None,
+            RenderViewTarget::Texture { width, height } => {
+                Some(self.create_render_view_texture(width, height).await?)
+            }
+        };
+
+        let display_pipelines = DisplayPipelines {
+            output_format: texture.as_ref().map(|_| RENDER_VIEW_TEXTURE_FORMAT),
+            ..self.render_passes.display.pipelines.clone()
+        };
+
+        let key = self.views.views.insert(RenderViewState {
+            target,
+            // the pipelines below are built from this one, against the view's own settings
+            post_processing: self.post_processing.clone(),
+            render_textures: RenderTextureTarget::default(),
+            camera: CameraViewState::default(),
+            ssao_pipelines: self.render_passes.ssao.pipelines.clone(),
+            effects_pipelines: self.render_passes.effects.pipelines.clone(),
+            display_pipelines,
+            texture,
+        });
+
+        if let Err(err) = self
+            .set_render_view_post_processing(key, post_processing)
+            .await
+        {
+            self.remove_render_view(key).await?;
+            return Err(err);
+        }
+
+        Ok(key)
+    }
+
+    /// Changes the post-processing of an extra view and rebuilds its pipelines.
+    pub async fn set_render_view_post_processing(
+        &mut self,
+        key: RenderViewKey,
+        post_processing: PostProcessing,
+    ) -> crate::error::Result<()> {
+        self.swap_render_view(key)?;
+        let result = self.set_post_processing(post_processing).await;
+        self.swap_render_view(key)?;
+
+        // the SSAO settings buffer is shared, the main view expects its own in there
+        if let Some(ssao) = &self.post_processing.ssao {
+            self.render_passes
+                .ssao
+                .bind_groups
+                .write_settings(&self.gpu, ssao)?;
+        }
+
+        result
+    }
+
+    /// Removes an extra view. Returns false if it didn't exist.
+    ///
+    /// Fails while materials still use an offscreen view's texture.
+    pub async fn remove_render_view(&mut self, key: RenderViewKey) -> crate::error::Result<bool> {
+        let Ok(state) = self.views.get(key) else {
+            return Ok(false);
+        };
+
+        if let Some(texture_key) = state.texture.as_ref().map(|texture| texture.texture_key) {
+            self.remove_texture(texture_key).await?;
+        }
+
+        if let Some(state) = self.views.views.remove(key) {
+            state.render_textures.destroy();
+            if let Some(texture) = state.texture {
+                texture.texture.destroy();
+            }
+        }
+
+        Ok(true)
+    }
+
+    // Exchanges the renderer's per-view state with an extra view's, calling it again swaps back
+    pub(crate) fn swap_render_view(&mut self, key: RenderViewKey) -> Result<()> {
+        let state = self
+            .views
+            .views
+            .get_mut(key)
+            .ok_or(AwsmRenderViewError::NotFound(key))?;
+
+        std::mem::swap(&mut self.post_processing, &mut state.post_processing);
+        self.render_textures.swap_target(&mut state.render_textures);
+        self.camera.swap_view_state(&mut state.camera);
+        std::mem::swap(
+            &mut self.render_passes.ssao.pipelines,
+            &mut state.ssao_pipelines,
+        );
+        std::mem::swap(
+            &mut self.render_passes.effects.pipelines,
+            &mut state.effects_pipelines,
+        );
+        std::mem::swap(
+            &mut self.render_passes.display.pipelines,
+            &mut state.display_pipelines,
+        );
+
+        Ok(())
+    }
+
+    // After a device loss, rebuilds every view's GPU state on top of the already restored
+    // main view. Offscreen views keep their pooled texture key, it's black until rendered again.
+    pub(crate) async fn restore_render_views_gpu(&mut self) -> crate::error::Result<()> {
+        self.views.bound = None;
+
+        let keys: Vec<RenderViewKey> = self.views.keys().collect();
+        let mut any_texture = false;
+
+        for key in keys {
+            let gpu_texture = match self.views.target(key)? {
+                RenderViewTarget::Canvas(_) => None,
+                RenderViewTarget::Texture { width, height } => {
+                    any_texture = true;
+                    Some(self.create_render_view_gpu_texture(width, height)?)
+                }
+            };
+
+            let ssao_pipelines = self.render_passes.ssao.pipelines.clone();
+            let effects_pipelines = self.render_passes.effects.pipelines.clone();
+            let display_pipelines = self.render_passes.display.pipelines.clone();
+
+            let state = self.views.get_mut(key)?;
+            state.render_textures = RenderTextureTarget::default();
+            state.camera.reset_history();
+            state.ssao_pipelines = ssao_pipelines;
+            state.effects_pipelines = effects_pipelines;
+            state.display_pipelines = DisplayPipelines {
+                output_format: state.display_pipelines.output_format,
+                ..display_pipelines
+            };
+            if let (Some(texture), Some((gpu_texture, view))) = (&mut state.texture, gpu_texture) {
+                texture.texture = gpu_texture;
+                texture.view = view;
+            }
+
+            // rebuilds the pipelines against the view's own settings, same as add_render_view
+            let post_processing = state.post_processing.clone();
+            self.set_render_view_post_processing(key, post_processing)
+                .await?;
+        }
+
+        if any_texture {
+            for is_array in [false, true] {
+                prepare_mipmaps(&self.gpu, RENDER_VIEW_TEXTURE_FORMAT, is_array).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn create_render_view_gpu_texture(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> crate::error::Result<(web_sys::GpuTexture, web_sys::GpuTextureView)> {
+        let texture = self.gpu.create_texture(
+            &TextureDescriptor::new(
+                RENDER_VIEW_TEXTURE_FORMAT,
+                Extent3d::new(width, Some(height), Some(1)),
+                TextureUsage::new().with_render_attachment().with_copy_src(),
+            )
+            .with_label("Render View")
+            .into(),
+        )?;
+
+        let view = texture
+            .create_view()
+            .map_err(AwsmCoreError::create_texture_view)?;
+
+        Ok((texture, view))
+    }
+
+    async fn create_render_view_texture(
+        &mut self,
+        width: u32,
+        height: u32,
+    ) -> crate::error::Result<RenderViewTexture> {
+        let (texture, view) = self.create_render_view_gpu_texture(width, height)?;
+
+        // placeholder until the first render, the pool needs an image to size the layer
+        let image = ImageData::Bitmap {
+            image: bitmap::create_color(Color::BLACK, width, height, None).await?,
+            options: None,
+        };
+
+        let sampler_key = self.textures.get_sampler_key(
+            &self.gpu,
+            SamplerCacheKey {
+                min_filter: Some(FilterMode::Linear),
+                mag_filter: Some(FilterMode::Linear),
+                mipmap_filter: Some(MipmapFilterMode::Linear),
+                max_anisotropy: Some(16),
+                ..Default::default()
+            },
+        )?;
+
+        let texture_key = self.textures.add_image(
+            image,
+            RENDER_VIEW_TEXTURE_FORMAT,
+            sampler_key,
+            TextureColorInfo {
+                mipmap_kind: MipmapTextureKind::Albedo,
+                // the display pass already wrote it linear
+                srgb_to_linear: false,
+                premultiplied_alpha: Some(false),
+            },
+        )?;
+
+        // the mips are regenerated every frame, which can't wait for a compile then
+        // the pool array may or may not end up with more than one layer
+        for is_array in [false, true] {
+            prepare_mipmaps(&self.gpu, RENDER_VIEW_TEXTURE_FORMAT, is_array).await?;
+        }
+
+        if let Err(err) = self.finalize_gpu_textures().await {
+            texture.destroy();
+            return Err(err);
+        }
+
+        Ok(RenderViewTexture {
+            texture,
+            view,
+            texture_key,
+        })
+    }
+}
+
+/// Result type for render view operations.
+type Result<T> = std::result::Result<T, AwsmRenderViewError>;
+
+/// Render view errors.
+#[derive(Error, Debug)]
+pub enum AwsmRenderViewError {
+    #[error("[render_view] not found: {0:?}")]
+    NotFound(RenderViewKey),
+
+    #[error("[render_view] not a canvas view: {0:?}")]
+    NotCanvas(RenderViewKey),
+}
+
+#[cfg(test)]
+mod tests;
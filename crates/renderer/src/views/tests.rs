@@ -0,0 +1,126 @@
+use super::*;
+
+#[test]
+fn viewport_inside_the_canvas_is_unchanged() {
+    let viewport = Viewport::new(10, 20, 100, 50);
+    assert_eq!(viewport.clamp_to(800, 600), Some(viewport));
+}
+
+#[test]
+fn viewport_is_cut_at_the_canvas_edge() {
+    let viewport = Viewport::new(700, 550, 200, 100);
+    assert_eq!(
+        viewport.clamp_to(800, 600),
+        Some(Viewport::new(700, 550, 100, 50))
+    );
+}
+
+#[test]
+fn viewport_off_the_canvas_is_skipped() {
+    assert_eq!(Viewport::new(800, 0, 100, 100).clamp_to(800, 600), None);
+    assert_eq!(Viewport::new(0, 600, 100, 100).clamp_to(800, 600), None);
+    assert_eq!(Viewport::new(0, 0, 0, 100).clamp_to(800, 600), None);
+}
+
+#[test]
+fn main_view_has_no_key_or_camera() {
+    let view = RenderView::main();
+    assert!(view.key.is_none());
+    assert!(view.camera.is_none());
+}
+
+#[test]
+fn texture_view_keeps_its_own_size() {
+    let target = RenderViewTarget::Texture {
+        width: 2048,
+        height: 1024,
+    };
+    let plan = plan_render_view_frames(&[Some(target)], (800, 600));
+
+    let frame = plan.frames[0].unwrap();
+    assert_eq!(frame.size, (2048, 1024));
+    assert_eq!(frame.viewport, None);
+    assert!(frame.offscreen);
+    assert!(!frame.clear_canvas);
+    assert_eq!(plan.tiles_size, (2048, 1024));
+}
+
+#[test]
+fn texture_view_is_drawn_without_a_canvas() {
+    let target = RenderViewTarget::Texture {
+        width: 256,
+        height: 256,
+    };
+    let plan = plan_render_view_frames(&[Some(target)], (0, 0));
+    assert_eq!(plan.frames[0].map(|frame| frame.size), Some((256, 256)));
+}
+
+#[test]
+fn texture_view_first_leaves_the_canvas_clear_to_the_main_view() {
+    let targets = [
+        Some(RenderViewTarget::Texture {
+            width: 256,
+            height: 128,
+        }),
+        None,
+        Some(RenderViewTarget::Canvas(Viewport::new(0, 0, 200, 100))),
+    ];
+    let plan = plan_render_view_frames(&targets, (800, 600));
+    let frames: Vec<RenderViewFrame> = plan.frames.into_iter().flatten().collect();
+
+    assert_eq!(
+        frames
+            .iter()
+            .map(|frame| frame.clear_canvas)
+            .collect::<Vec<_>>(),
+        [false, true, false]
+    );
+    assert_eq!(frames[1].size, (800, 600));
+    assert_eq!(frames[2].viewport, Some(Viewport::new(0, 0, 200, 100)));
+    assert_eq!(plan.tiles_size, (800, 600));
+}
+
+#[test]
+fn shadows_are_rendered_by_the_first_view_drawn() {
+    let targets = [
+        Some(RenderViewTarget::Canvas(Viewport::new(900, 0, 100, 100))),
+        Some(RenderViewTarget::Texture {
+            width: 256,
+            height: 256,
+        }),
+        None,
+    ];
+    let plan = plan_render_view_frames(&targets, (800, 600));
+
+    assert_eq!(plan.frames[0], None);
+    assert_eq!(
+        plan.frames
+            .iter()
+            .flatten()
+            .map(|frame| frame.render_shadows)
+            .collect::<Vec<_>>(),
+        [true, false]
+    );
+}
+
+#[test]
+fn main_camera_is_restored_after_an_extra_view_draws_last() {
+    let minimap = Some(RenderViewTarget::Canvas(Viewport::new(0, 0, 200, 200)));
+    let monitor = Some(RenderViewTarget::Texture {
+        width: 256,
+        height: 256,
+    });
+
+    // picking after the frame reads the camera buffer, which the minimap wrote last
+    assert!(plan_render_view_frames(&[None, minimap], (800, 600)).restore_main_camera);
+    // the main view didn't draw at all, whatever is in there isn't its camera
+    assert!(plan_render_view_frames(&[monitor], (800, 600)).restore_main_camera);
+
+    // the main view drew last, its camera is already there
+    assert!(!plan_render_view_frames(&[monitor, minimap, None], (800, 600)).restore_main_camera);
+    assert!(!plan_render_view_frames(&[None], (800, 600)).restore_main_camera);
+
+    // a skipped view doesn't write anything
+    let off_canvas = Some(RenderViewTarget::Canvas(Viewport::new(900, 0, 100, 100)));
+    assert!(!plan_render_view_frames(&[None, off_canvas], (800, 600)).restore_main_camera);
+}